@@ -1,21 +1,31 @@
 use crate::ui::modals::error::ErrorModal;
 use crate::utils::channel::Channel;
+use geometry::animations::timeline::Timeline;
+use geometry::animations::trail::Trail;
+use geometry::animations::transformation::AnimationTransformation;
+use geometry::animations::{Playback, PlaybackMode};
 use geometry::figures::detail::Detail;
 use geometry::figures::grid::{Grid2D, Grid2DBuilder};
 use geometry::pipeline::Pipeline;
 use geometry::transformations::affine::Affine;
-use geometry::transformations::affine::scaling::AffineScaling;
+use geometry::transformations::affine::reflection::AffineLineReflection;
+use geometry::transformations::affine::scaling::{AffinePivotScaling, AffineScaling};
+use geometry::transformations::affine::shear::AffineShear;
 use geometry::transformations::affine::symmetry::AffinePointSymmetry;
+use geometry::transformations::correspondence::AffineCorrespondence;
 use geometry::transformations::euclidean::offset::EuclideanOffset;
 use geometry::transformations::euclidean::rotation::EuclideanRotation;
 use geometry::transformations::projective::Projective;
+use geometry::transformations::projective::quad::ProjectiveQuad;
 use geometry::units::{Centimeter, Pixel};
 use geometry::viewport::{Viewport, ViewportGeometry, ViewportState, ZeroPointLocation};
+use nalgebra::Matrix3;
 
 #[derive(Debug)]
 pub struct Context {
     pub figures: FiguresState,
     pub transformations: TransformContext,
+    pub animations: AnimationsContext,
     pub viewport: Viewport,
     pub errors_channel: Channel<ErrorModal>,
 }
@@ -25,6 +35,7 @@ impl Default for Context {
         Self {
             figures: FiguresState::default(),
             transformations: TransformContext::default(),
+            animations: AnimationsContext::default(),
 
             viewport: Viewport {
                 // Default settings like panning, zooming, etc.
@@ -55,6 +66,25 @@ impl Context {
             || self.transformations.scale.is_enabled
             || self.transformations.projective.is_enabled;
     }
+
+    /// Affine, scale and projective maps that are enabled, as one row-vector
+    /// matrix in the order they are applied.
+    pub fn global_matrix(&self) -> Matrix3<f64> {
+        let transformations = &self.transformations;
+        let mut matrix = Matrix3::identity();
+
+        if transformations.affine.is_enabled {
+            matrix *= transformations.affine.matrix();
+        }
+        if transformations.scale.is_enabled {
+            matrix *= transformations.scale.matrix();
+        }
+        if transformations.projective.is_enabled {
+            matrix *= transformations.projective.matrix();
+        }
+
+        matrix
+    }
 }
 
 #[derive(Debug)]
@@ -86,7 +116,36 @@ pub struct TransformContext {
     pub offset: EuclideanOffset,
     pub rotation: EuclideanRotation,
     pub affine: Affine,
+    pub affine_pairs: AffineCorrespondence,
     pub scale: AffineScaling,
     pub symmetry: AffinePointSymmetry,
+    pub shear: AffineShear,
+    pub pivot_scaling: AffinePivotScaling,
+    pub reflection: AffineLineReflection,
     pub projective: Projective,
+    pub quad: ProjectiveQuad,
+}
+
+#[derive(Debug)]
+pub struct AnimationsContext {
+    pub timeline: Timeline,
+    /// Grows the detail and the grid into the enabled global maps.
+    pub transformation: AnimationTransformation,
+    /// Turns the detail into its point symmetry before the symmetry is saved.
+    pub symmetry: AnimationTransformation,
+    pub trail: Trail,
+}
+
+impl Default for AnimationsContext {
+    fn default() -> Self {
+        Self {
+            timeline: Timeline::default(),
+            transformation: AnimationTransformation::default(),
+            symmetry: AnimationTransformation::new(Playback::new(
+                2.0,
+                PlaybackMode::Once,
+            )),
+            trail: Trail::default(),
+        }
+    }
 }
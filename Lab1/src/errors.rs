@@ -2,6 +2,7 @@ use crate::config::ConfigError;
 use crate::io::IoError;
 use crate::logs::LogError;
 use crate::ui::GraphicsBackendError;
+use geometry::transformations::correspondence::CorrespondenceError;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -9,6 +10,9 @@ pub enum ProjectError {
     #[error("Configuration. {0}")]
     Config(#[from] ConfigError),
 
+    #[error("Transformation. {0}")]
+    Correspondence(#[from] CorrespondenceError),
+
     #[error("Graphics Backend. {0}")]
     GraphicsBackend(#[from] GraphicsBackendError),
 
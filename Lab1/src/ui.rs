@@ -69,4 +69,5 @@ pub mod workspace;
 pub mod components {
     pub mod canvas;
     pub mod settings;
+    pub mod timeline;
 }
@@ -1,9 +1,16 @@
 use crate::context::Context;
 use egui::{CentralPanel, Color32, Frame, Painter, Response, Sense, Shape};
+use geometry::animations::Animation;
+use geometry::animations::transformation::{transform_lines, transform_point};
 use geometry::primitives::line2d::Line2D;
 use geometry::primitives::point2d::Point2D;
+use geometry::transformations::affine::reflection::AffineLineReflection;
+use geometry::transformations::affine::scaling::AffinePivotScaling;
+use geometry::transformations::affine::shear::AffineShear;
 use geometry::transformations::affine::symmetry::AffinePointSymmetry;
 use geometry::transformations::euclidean::rotation::EuclideanRotation;
+use geometry::transformations::projective::horizon;
+use nalgebra::Matrix3;
 
 #[derive(Debug, Default)]
 pub struct CanvasComponent;
@@ -27,7 +34,7 @@ impl CanvasComponent {
         Self::draw(ui, context, shapes)
     }
 
-    fn create_shapes(_ui: &mut egui::Ui, context: &mut Context) -> Vec<Shape> {
+    fn create_shapes(ui: &mut egui::Ui, context: &mut Context) -> Vec<Shape> {
         let mut lines = vec![];
 
         let mut grid: Vec<Line2D<Point2D>> =
@@ -35,6 +42,12 @@ impl CanvasComponent {
 
         let mut detail = context.figures.detail.lines();
 
+        let animations = &mut context.animations;
+        animations
+            .timeline
+            .tick(ui.input(|i| f64::from(i.stable_dt)));
+        Self::animate_symmetry(context);
+
         context
             .transformations
             .offset
@@ -47,17 +60,72 @@ impl CanvasComponent {
             .transformations
             .symmetry
             .handle(vec![&mut context.figures.detail_pipeline]);
+        context
+            .transformations
+            .shear
+            .handle(vec![&mut context.figures.detail_pipeline]);
+        context
+            .transformations
+            .pivot_scaling
+            .handle(vec![&mut context.figures.detail_pipeline]);
+        context
+            .transformations
+            .reflection
+            .handle(vec![&mut context.figures.detail_pipeline]);
 
         context.figures.detail_pipeline.do_tasks(&mut detail);
 
+        // Symmetry that is being played, saved once it is over
+        let animations = &mut context.animations;
+        let mut matrix = Matrix3::identity();
+        let mut is_animating = animations
+            .symmetry
+            .update(&animations.timeline, &mut matrix);
+        transform_lines(&matrix, &mut detail);
+
         let rotation_point = context.transformations.rotation.leading_point();
-        let symmetry_point = context.transformations.symmetry.leading_point();
+        let shear_point = context.transformations.shear.leading_point();
+        let scaling_point = context.transformations.pivot_scaling.leading_point();
+        let reflection_point = context.transformations.reflection.leading_point();
+        let mirror = context.transformations.reflection.mirror();
+        let symmetry_point = context
+            .transformations
+            .symmetry
+            .leading_point()
+            .or(is_animating.then_some(context.animations.symmetry.center));
 
         // Other transformations that applied, but not saved
-        Self::global_transform_lines(&mut detail, context);
-        Self::global_transform_lines(&mut grid, context);
+        let target = context.global_matrix();
+        let animations = &mut context.animations;
+        animations.transformation.set_target(target);
+        let mut global = Matrix3::identity();
+        let horizon = if animations
+            .transformation
+            .update(&animations.timeline, &mut global)
+        {
+            is_animating = true;
+            transform_lines(&global, &mut detail);
+            transform_lines(&global, &mut grid);
+
+            let projective = &context.transformations.projective;
+            horizon(&global).filter(|_| projective.is_horizon_visible)
+        } else {
+            Self::global_transform_lines(&mut detail, context);
+            Self::global_transform_lines(&mut grid, context);
+
+            // Affine maps keep the line at infinity, only the projective one moves it
+            context.transformations.projective.horizon()
+        };
+        let animations = &mut context.animations;
+        if animations.timeline.needs_repaint(is_animating) {
+            ui.ctx().request_repaint();
+        }
+        animations
+            .trail
+            .record(animations.timeline.time(), is_animating, &detail, &[]);
 
         // Conversion to shapes
+        let grid_count = grid.len();
         lines.extend(grid);
         lines.extend(detail);
 
@@ -66,16 +134,49 @@ impl CanvasComponent {
             .map(|line| line.to_pixels(&context.viewport).to_shape())
             .collect::<Vec<Shape>>();
 
+        // Trail between the grid and the detail
+        let trail = context.animations.trail.shapes(&context.viewport);
+        shapes.splice(grid_count..grid_count, trail);
+
+        // Image of the line at infinity
+        if let Some(line) = horizon {
+            shapes.push(line.to_pixels(&context.viewport).to_shape());
+        }
+
+        // Mirror of the line reflection, under the leading points
+        if let Some(mut line) = mirror {
+            Self::leading_transform_point(&mut line.start, &global, context);
+            Self::leading_transform_point(&mut line.end, &global, context);
+            shapes.push(line.to_pixels(&context.viewport).to_shape());
+        }
         // Rotation point
         if let Some(mut dot) = rotation_point {
-            Self::global_transform_point(&mut dot, context);
+            Self::leading_transform_point(&mut dot, &global, context);
             shapes.push(EuclideanRotation::leading_shape(dot, &context.viewport));
         }
         // Symmetry point
         if let Some(mut dot) = symmetry_point {
-            Self::global_transform_point(&mut dot, context);
+            Self::leading_transform_point(&mut dot, &global, context);
             shapes.push(AffinePointSymmetry::leading_shape(dot, &context.viewport));
         }
+        // Shear point
+        if let Some(mut dot) = shear_point {
+            Self::leading_transform_point(&mut dot, &global, context);
+            shapes.push(AffineShear::leading_shape(dot, &context.viewport));
+        }
+        // Scaling point
+        if let Some(mut dot) = scaling_point {
+            Self::leading_transform_point(&mut dot, &global, context);
+            shapes.push(AffinePivotScaling::leading_shape(dot, &context.viewport));
+        }
+        // Reflection point
+        if let Some(mut dot) = reflection_point {
+            Self::leading_transform_point(&mut dot, &global, context);
+            shapes.push(AffineLineReflection::leading_shape(dot, &context.viewport));
+        }
+
+        // Corners of the projective map
+        shapes.extend(context.transformations.quad.shapes(&context.viewport));
 
         shapes
     }
@@ -84,6 +185,15 @@ impl CanvasComponent {
         let (response, painter) = Self::initialize_painter(ui, context);
         painter.extend(shapes);
 
+        let transformations = &mut context.transformations;
+        if transformations
+            .quad
+            .update(ui, &response, &context.viewport)
+            && let Some(matrix) = transformations.quad.solve()
+        {
+            transformations.projective.set_matrix(matrix);
+        }
+
         response
     }
 
@@ -99,12 +209,52 @@ impl CanvasComponent {
         (response, painter)
     }
 
-    fn global_transform_lines(lines: &mut [Line2D<Point2D>], context: &mut Context) {
+    fn global_transform_lines(lines: &mut Vec<Line2D<Point2D>>, context: &mut Context) {
         context.transformations.affine.handle(lines);
         context.transformations.scale.handle(lines);
         context.transformations.projective.handle(lines);
     }
 
+    /// Point symmetry applied while the animation is on is played first and
+    /// saved to the pipeline only when it is over.
+    fn animate_symmetry(context: &mut Context) {
+        let animations = &mut context.animations;
+        let symmetry = &mut context.transformations.symmetry;
+        let time = animations.timeline.time();
+
+        if animations.transformation.playback.is_enabled && symmetry.is_enabled {
+            let animation = &mut animations.symmetry;
+            animation.center = Point2D {
+                x: symmetry.x,
+                y: symmetry.y,
+            };
+            animation.set_target(symmetry.matrix());
+            animation.start = time;
+            animation.playback.duration = animations.transformation.playback.duration;
+            animation.playback.is_enabled = true;
+            symmetry.reset();
+        } else if animations.symmetry.playback.is_enabled
+            && animations.symmetry.is_finished(time)
+        {
+            let animation = &mut animations.symmetry;
+            animation.playback.is_enabled = false;
+            symmetry.x = animation.center.x;
+            symmetry.y = animation.center.y;
+            symmetry.run();
+        }
+    }
+
+    /// Puts a leading point where the global maps, or their animation, take it.
+    fn leading_transform_point(
+        point: &mut Point2D, animated: &Matrix3<f64>, context: &mut Context,
+    ) {
+        if context.animations.transformation.playback.is_enabled {
+            transform_point(animated, point);
+        } else {
+            Self::global_transform_point(point, context);
+        }
+    }
+
     fn global_transform_point(point: &mut Point2D, context: &mut Context) {
         context.transformations.affine.transform_point(point);
         context.transformations.scale.transform_point(point);
@@ -1,5 +1,6 @@
 use crate::context::Context;
-use egui::{Color32, DragValue, Grid, RichText, ScrollArea, SidePanel};
+use crate::ui::modals::error::ErrorModal;
+use egui::{Color32, DragValue, Grid, RichText, ScrollArea, SidePanel, Slider};
 use geometry::figures::detail::{ArcId, CircleId, DetailElementId, SegmentId};
 use geometry::figures::grid;
 use strum::IntoEnumIterator;
@@ -106,6 +107,18 @@ impl SettingsComponent {
                     ui.add_space(10.0);
 
                     self.projective(ui, context);
+
+                    ui.add_space(10.0);
+                    ui.separator();
+                    ui.add_space(10.0);
+
+                    self.animation(ui, context);
+
+                    ui.add_space(10.0);
+                    ui.separator();
+                    ui.add_space(10.0);
+
+                    self.trail(ui, context);
                 });
             });
     }
@@ -363,6 +376,69 @@ impl SettingsComponent {
 
         ui.add_space(5.0);
 
+        ui.group(|ui| {
+            ui.vertical_centered(|ui| {
+                ui.label("From Three Points");
+            });
+
+            ui.add_space(5.0);
+
+            let pairs = &mut context.transformations.affine_pairs;
+            Grid::new("AFFINE_Correspondence")
+                .num_columns(6)
+                .show(ui, |ui| {
+                    ui.label("1:");
+                    ui.add(DragValue::new(&mut pairs.source[0].x.0).speed(0.1));
+                    ui.add(DragValue::new(&mut pairs.source[0].y.0).speed(0.1));
+                    ui.label("→");
+                    ui.add(DragValue::new(&mut pairs.target[0].x.0).speed(0.1));
+                    ui.add(DragValue::new(&mut pairs.target[0].y.0).speed(0.1));
+                    ui.end_row();
+
+                    ui.label("2:");
+                    ui.add(DragValue::new(&mut pairs.source[1].x.0).speed(0.1));
+                    ui.add(DragValue::new(&mut pairs.source[1].y.0).speed(0.1));
+                    ui.label("→");
+                    ui.add(DragValue::new(&mut pairs.target[1].x.0).speed(0.1));
+                    ui.add(DragValue::new(&mut pairs.target[1].y.0).speed(0.1));
+                    ui.end_row();
+
+                    ui.label("3:");
+                    ui.add(DragValue::new(&mut pairs.source[2].x.0).speed(0.1));
+                    ui.add(DragValue::new(&mut pairs.source[2].y.0).speed(0.1));
+                    ui.label("→");
+                    ui.add(DragValue::new(&mut pairs.target[2].x.0).speed(0.1));
+                    ui.add(DragValue::new(&mut pairs.target[2].y.0).speed(0.1));
+                    ui.end_row();
+                });
+
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                ui.columns(2, |ui| {
+                    ui[0].vertical_centered_justified(|ui| {
+                        if ui.button("Solve").clicked() {
+                            match context.transformations.affine_pairs.solve() {
+                                Ok(affine) => context.transformations.affine = affine,
+                                Err(error) => {
+                                    context
+                                        .errors_channel
+                                        .try_send(ErrorModal::new(error.into()));
+                                },
+                            }
+                        }
+                    });
+                    ui[1].vertical_centered_justified(|ui| {
+                        if ui.button("Reset").clicked() {
+                            context.transformations.affine_pairs.reset();
+                        }
+                    });
+                });
+            });
+        });
+
+        ui.add_space(5.0);
+
         ui.group(|ui| {
             ui.vertical_centered(|ui| {
                 ui.label("Scale");
@@ -442,6 +518,185 @@ impl SettingsComponent {
                 });
             });
         });
+
+        ui.add_space(10.0);
+
+        ui.group(|ui| {
+            ui.vertical_centered(|ui| {
+                ui.label("Shear");
+            });
+
+            ui.add_space(5.0);
+
+            Grid::new("AFFINE_Shear").num_columns(2).show(ui, |ui| {
+                ui.label("X:");
+                ui.add(
+                    DragValue::new(&mut context.transformations.shear.x.0)
+                        .speed(0.1)
+                        .fixed_decimals(2),
+                );
+                ui.end_row();
+
+                ui.label("Y:");
+                ui.add(
+                    DragValue::new(&mut context.transformations.shear.y.0)
+                        .speed(0.1)
+                        .fixed_decimals(2),
+                );
+                ui.end_row();
+
+                ui.label("Along X:");
+                ui.add(
+                    DragValue::new(&mut context.transformations.shear.shear_x)
+                        .speed(0.01)
+                        .fixed_decimals(2),
+                );
+                ui.end_row();
+
+                ui.label("Along Y:");
+                ui.add(
+                    DragValue::new(&mut context.transformations.shear.shear_y)
+                        .speed(0.01)
+                        .fixed_decimals(2),
+                );
+                ui.end_row();
+            });
+
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                ui.columns(2, |ui| {
+                    ui[0].vertical_centered_justified(|ui| {
+                        if ui.button("Apply").clicked() {
+                            context.transformations.shear.run();
+                        }
+                    });
+                    ui[1].vertical_centered_justified(|ui| {
+                        if ui.button("Reset").clicked() {
+                            context.transformations.shear.reset();
+                        }
+                    });
+                });
+            });
+        });
+
+        ui.add_space(10.0);
+
+        ui.group(|ui| {
+            ui.vertical_centered(|ui| {
+                ui.label("Scaling about Point");
+            });
+
+            ui.add_space(5.0);
+
+            Grid::new("AFFINE_Pivot_Scaling")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label("X:");
+                    ui.add(
+                        DragValue::new(&mut context.transformations.pivot_scaling.x.0)
+                            .speed(0.1)
+                            .fixed_decimals(2),
+                    );
+                    ui.end_row();
+
+                    ui.label("Y:");
+                    ui.add(
+                        DragValue::new(&mut context.transformations.pivot_scaling.y.0)
+                            .speed(0.1)
+                            .fixed_decimals(2),
+                    );
+                    ui.end_row();
+
+                    ui.label("Mx:");
+                    ui.add(
+                        DragValue::new(&mut context.transformations.pivot_scaling.mx)
+                            .speed(0.01)
+                            .fixed_decimals(2),
+                    );
+                    ui.end_row();
+
+                    ui.label("My:");
+                    ui.add(
+                        DragValue::new(&mut context.transformations.pivot_scaling.my)
+                            .speed(0.01)
+                            .fixed_decimals(2),
+                    );
+                    ui.end_row();
+                });
+
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                ui.columns(2, |ui| {
+                    ui[0].vertical_centered_justified(|ui| {
+                        if ui.button("Apply").clicked() {
+                            context.transformations.pivot_scaling.run();
+                        }
+                    });
+                    ui[1].vertical_centered_justified(|ui| {
+                        if ui.button("Reset").clicked() {
+                            context.transformations.pivot_scaling.reset();
+                        }
+                    });
+                });
+            });
+        });
+
+        ui.add_space(10.0);
+
+        ui.group(|ui| {
+            ui.vertical_centered(|ui| {
+                ui.label("Line Reflection");
+            });
+
+            ui.add_space(5.0);
+
+            Grid::new("AFFINE_Line_Reflection")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label("X:");
+                    ui.add(
+                        DragValue::new(&mut context.transformations.reflection.x.0)
+                            .speed(0.1)
+                            .fixed_decimals(2),
+                    );
+                    ui.end_row();
+
+                    ui.label("Y:");
+                    ui.add(
+                        DragValue::new(&mut context.transformations.reflection.y.0)
+                            .speed(0.1)
+                            .fixed_decimals(2),
+                    );
+                    ui.end_row();
+
+                    ui.label("Angle:");
+                    ui.add(
+                        DragValue::new(&mut context.transformations.reflection.angle)
+                            .speed(1)
+                            .suffix(" °"),
+                    );
+                    ui.end_row();
+                });
+
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                ui.columns(2, |ui| {
+                    ui[0].vertical_centered_justified(|ui| {
+                        if ui.button("Apply").clicked() {
+                            context.transformations.reflection.run();
+                        }
+                    });
+                    ui[1].vertical_centered_justified(|ui| {
+                        if ui.button("Reset").clicked() {
+                            context.transformations.reflection.reset();
+                        }
+                    });
+                });
+            });
+        });
     }
 
     fn projective(&self, ui: &mut egui::Ui, context: &mut Context) {
@@ -456,86 +711,310 @@ impl SettingsComponent {
                 &mut context.transformations.projective.is_enabled,
                 "Enabled",
             );
+            ui.checkbox(
+                &mut context.transformations.projective.is_horizon_visible,
+                "Show Horizon",
+            );
 
+            let mut is_edited = false;
             Grid::new("PROJECTIVE").num_columns(2).show(ui, |ui| {
                 ui.label("Xx:");
-                ui.add(
-                    DragValue::new(&mut context.transformations.projective.xx)
-                        .speed(0.1)
-                        .fixed_decimals(2),
-                );
+                is_edited |= ui
+                    .add(
+                        DragValue::new(&mut context.transformations.projective.xx)
+                            .speed(0.1)
+                            .fixed_decimals(2),
+                    )
+                    .changed();
                 ui.end_row();
 
                 ui.label("Xy:");
-                ui.add(
-                    DragValue::new(&mut context.transformations.projective.xy)
-                        .speed(0.1)
-                        .fixed_decimals(2),
-                );
+                is_edited |= ui
+                    .add(
+                        DragValue::new(&mut context.transformations.projective.xy)
+                            .speed(0.1)
+                            .fixed_decimals(2),
+                    )
+                    .changed();
                 ui.end_row();
 
                 ui.label("Wx:");
-                ui.add(
-                    DragValue::new(&mut context.transformations.projective.wx)
-                        .speed(0.1)
-                        .fixed_decimals(2),
-                );
+                is_edited |= ui
+                    .add(
+                        DragValue::new(&mut context.transformations.projective.wx)
+                            .speed(0.1)
+                            .fixed_decimals(2),
+                    )
+                    .changed();
                 ui.end_row();
 
                 ui.label("Yx:");
-                ui.add(
-                    DragValue::new(&mut context.transformations.projective.yx)
-                        .speed(0.1)
-                        .fixed_decimals(2),
-                );
+                is_edited |= ui
+                    .add(
+                        DragValue::new(&mut context.transformations.projective.yx)
+                            .speed(0.1)
+                            .fixed_decimals(2),
+                    )
+                    .changed();
                 ui.end_row();
 
                 ui.label("Yy:");
-                ui.add(
-                    DragValue::new(&mut context.transformations.projective.yy)
-                        .speed(0.1)
-                        .fixed_decimals(2),
-                );
+                is_edited |= ui
+                    .add(
+                        DragValue::new(&mut context.transformations.projective.yy)
+                            .speed(0.1)
+                            .fixed_decimals(2),
+                    )
+                    .changed();
                 ui.end_row();
 
                 ui.label("Wy:");
-                ui.add(
-                    DragValue::new(&mut context.transformations.projective.wy)
-                        .speed(0.1)
-                        .fixed_decimals(2),
-                );
+                is_edited |= ui
+                    .add(
+                        DragValue::new(&mut context.transformations.projective.wy)
+                            .speed(0.1)
+                            .fixed_decimals(2),
+                    )
+                    .changed();
                 ui.end_row();
 
                 ui.label("0x:");
+                is_edited |= ui
+                    .add(
+                        DragValue::new(&mut context.transformations.projective.zero_x)
+                            .speed(0.1)
+                            .fixed_decimals(2),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("0y:");
+                is_edited |= ui
+                    .add(
+                        DragValue::new(&mut context.transformations.projective.zero_y)
+                            .speed(0.1)
+                            .fixed_decimals(2),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("W0:");
+                is_edited |= ui
+                    .add(
+                        DragValue::new(&mut context.transformations.projective.w_zero)
+                            .speed(0.1)
+                            .fixed_decimals(2),
+                    )
+                    .changed();
+                ui.end_row();
+            });
+
+            // Edited coefficients take over from the corners
+            if is_edited {
+                context.transformations.projective.homography = None;
+            }
+            if context.transformations.projective.homography.is_some() {
+                ui.label(
+                    RichText::new("Set by the corners, an axis stays at infinity.")
+                        .color(Color32::GRAY),
+                );
+            }
+
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Reset").clicked() {
+                    context.transformations.projective.reset();
+                }
+            });
+        });
+
+        ui.add_space(5.0);
+
+        ui.group(|ui| {
+            ui.vertical_centered(|ui| {
+                ui.label("Corners");
+            });
+
+            ui.add_space(5.0);
+
+            let transformations = &mut context.transformations;
+            if ui
+                .checkbox(&mut transformations.quad.is_enabled, "Drag on Canvas")
+                .changed()
+                && transformations.quad.is_enabled
+                && let Some(matrix) = transformations.quad.solve()
+            {
+                transformations.projective.set_matrix(matrix);
+            }
+
+            ui.label(
+                RichText::new("The gray square is taken onto the quad of the corners.")
+                    .color(Color32::GRAY),
+            );
+            if let Some(error) = &transformations.quad.error {
+                ui.colored_label(Color32::RED, error.to_string());
+            }
+
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Reset Corners").clicked() {
+                    transformations.quad.reset();
+                    if transformations.quad.is_enabled
+                        && let Some(matrix) = transformations.quad.solve()
+                    {
+                        transformations.projective.set_matrix(matrix);
+                    }
+                }
+            });
+        });
+    }
+
+    fn trail(&self, ui: &mut egui::Ui, context: &mut Context) {
+        ui.group(|ui| {
+            ui.vertical_centered_justified(|ui| {
+                ui.label(RichText::new("Trail").color(Color32::WHITE));
+            });
+
+            ui.add_space(5.0);
+
+            let trail = &mut context.animations.trail;
+            Grid::new("TRAIL").num_columns(2).show(ui, |ui| {
+                ui.checkbox(&mut trail.is_ghosts_enabled, "Ghosts:");
                 ui.add(
-                    DragValue::new(&mut context.transformations.projective.zero_x)
-                        .speed(0.1)
-                        .fixed_decimals(2),
+                    DragValue::new(&mut trail.ghosts)
+                        .speed(0.2)
+                        .range(1..=60)
+                        .suffix(" frames"),
                 );
                 ui.end_row();
 
-                ui.label("0y:");
+                ui.label("Every:");
                 ui.add(
-                    DragValue::new(&mut context.transformations.projective.zero_y)
-                        .speed(0.1)
-                        .fixed_decimals(2),
+                    DragValue::new(&mut trail.spacing)
+                        .speed(0.2)
+                        .range(1..=60)
+                        .suffix(" frames"),
                 );
                 ui.end_row();
 
-                ui.label("W0:");
+                ui.label("Fade:");
+                ui.add(Slider::new(&mut trail.opacity, 0.05..=1.0).fixed_decimals(2));
+                ui.end_row();
+
+                ui.checkbox(&mut trail.is_traces_enabled, "Traces:");
                 ui.add(
-                    DragValue::new(&mut context.transformations.projective.w_zero)
-                        .speed(0.1)
-                        .fixed_decimals(2),
+                    DragValue::new(&mut trail.trace_length)
+                        .speed(1)
+                        .range(2..=5000)
+                        .suffix(" frames"),
                 );
                 ui.end_row();
+
+                ui.label("Trace Color:");
+                ui.color_edit_button_srgba(&mut trail.trace_stroke.color);
+                ui.end_row();
             });
 
             ui.vertical_centered_justified(|ui| {
-                if ui.button("Reset").clicked() {
-                    context.transformations.projective.reset();
+                if ui.button("Clear Trail").clicked() {
+                    trail.clear();
+                }
+            });
+        });
+    }
+
+    fn animation(&self, ui: &mut egui::Ui, context: &mut Context) {
+        ui.vertical_centered_justified(|ui| {
+            ui.label(RichText::new("Animated Transformations").color(Color32::WHITE));
+        });
+
+        ui.add_space(5.0);
+
+        ui.group(|ui| {
+            let animations = &mut context.animations;
+            let animation = &mut animations.transformation;
+
+            ui.horizontal(|ui| {
+                ui.label("Status:");
+                if animation.playback.is_enabled {
+                    ui.colored_label(Color32::LIGHT_GREEN, "Running");
+                } else {
+                    ui.colored_label(Color32::RED, "Stopped");
                 }
             });
+
+            ui.label("Grows the enabled maps and the point symmetry from the identity.");
+
+            widgets::playback::show(ui, &mut animation.playback, "TRANSFORMATION");
+
+            ui.add_space(5.0);
+
+            ui.label(format!("Current Matrix (t = {:.2}):", animation.progress()));
+            Grid::new("TRANSFORMATION_MATRIX")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    let matrix = animation.matrix();
+                    for row in 0..3 {
+                        for column in 0..3 {
+                            ui.label(
+                                RichText::new(format!("{:.3}", matrix[(row, column)]))
+                                    .monospace()
+                                    .color(Color32::WHITE),
+                            );
+                        }
+                        ui.end_row();
+                    }
+                });
+
+            if let Some(steps) = animation.steps() {
+                ui.add_space(5.0);
+                Grid::new("TRANSFORMATION_STEPS")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("Translation:");
+                        ui.label(format!(
+                            "({:.2}, {:.2})",
+                            steps.translation_x, steps.translation_y
+                        ));
+                        ui.end_row();
+
+                        ui.label("Rotation:");
+                        ui.label(format!("{:.1}°", steps.rotation.degree()));
+                        ui.end_row();
+
+                        ui.label("Scale:");
+                        ui.label(format!("({:.2}, {:.2})", steps.scale_x, steps.scale_y));
+                        ui.end_row();
+
+                        ui.label("Shear:");
+                        ui.label(format!("{:.2}", steps.shear));
+                        ui.end_row();
+                    });
+            }
+
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                ui.columns(2, |ui| {
+                    ui[0].vertical_centered_justified(|ui| {
+                        let text = if animation.playback.is_enabled {
+                            "Stop"
+                        } else {
+                            "Start"
+                        };
+                        if ui.button(text).clicked() {
+                            animation.playback.toggle();
+                            animations.timeline.rewind();
+                            animations.timeline.is_playing = true;
+                        }
+                    });
+                    ui[1].vertical_centered_justified(|ui| {
+                        if ui.button("Replay").clicked() {
+                            animations.timeline.rewind();
+                            animations.timeline.is_playing = true;
+                        }
+                    });
+                });
+            });
         });
     }
 }
@@ -0,0 +1,10 @@
+use crate::context::Context;
+
+#[derive(Debug, Default)]
+pub struct TimelineComponent;
+
+impl TimelineComponent {
+    pub fn show(&mut self, ui: &mut egui::Ui, context: &mut Context) {
+        widgets::timeline::panel(ui, &mut context.animations.timeline, None);
+    }
+}
@@ -1,6 +1,10 @@
 use crate::ui::modals::error::ErrorModal;
 use crate::utils::channel::Channel;
 use geometry::animations::epicycloid::AnimationEpicycloid;
+use geometry::animations::export::AnimationExport;
+use geometry::animations::keyframes::KeyframeAnimation;
+use geometry::animations::timeline::Timeline;
+use geometry::animations::trail::Trail;
 use geometry::animations::walker::CurveWalker;
 use geometry::figures::epicycloid::Epicycloid;
 use geometry::figures::grid::{Grid2D, Grid2DBuilder};
@@ -77,6 +81,10 @@ pub struct TransformContext {
 
 #[derive(Debug, Default)]
 pub struct AnimationsContext {
+    pub timeline: Timeline,
+    pub export: AnimationExport,
     pub epicycloid: AnimationEpicycloid,
     pub walker: CurveWalker,
+    pub keyframes: KeyframeAnimation,
+    pub trail: Trail,
 }
@@ -1,6 +1,7 @@
 use crate::context::Context;
 use eframe::epaint::Stroke;
 use egui::{CentralPanel, Color32, Frame, Painter, Response, Sense, Shape};
+use geometry::animations::Animation;
 use geometry::primitives::line2d::Line2D;
 use geometry::primitives::point2d::Point2D;
 use geometry::shapes::dot::DotMetadata;
@@ -24,12 +25,33 @@ impl CanvasComponent {
 
     fn pipeline(ui: &mut egui::Ui, context: &mut Context) -> Response {
         let shapes = Self::create_shapes(ui, context);
+        let animations = &mut context.animations;
+        animations.export.capture(
+            ui.ctx(),
+            &shapes,
+            &[],
+            &context.viewport,
+            &mut animations.timeline,
+        );
         Self::draw(ui, context, shapes)
     }
 
     fn create_shapes(ui: &mut egui::Ui, context: &mut Context) -> Vec<Shape> {
         let mut lines = vec![];
 
+        // Animation
+        let animations = &mut context.animations;
+        animations.export.prepare(&mut animations.timeline);
+        animations
+            .timeline
+            .tick(ui.input(|i| f64::from(i.stable_dt)));
+        let mut is_animating = animations
+            .keyframes
+            .update(&animations.timeline, &mut context.figures.epicycloid);
+        is_animating |= animations
+            .epicycloid
+            .update(&animations.timeline, &mut context.figures.epicycloid);
+
         let grid: Vec<Line2D<Point2D>> = context.figures.grid.lines(&context.viewport);
         let mut epicycloid = context.figures.epicycloid.lines();
 
@@ -47,9 +69,27 @@ impl CanvasComponent {
             .epicycloid_pipeline
             .do_tasks(&mut epicycloid);
 
-        context.animations.walker.step(ui, &mut epicycloid);
+        let animations = &mut context.animations;
+        is_animating |= animations.walker.update(&animations.timeline, &epicycloid);
+        if animations.timeline.needs_repaint(is_animating) {
+            ui.ctx().request_repaint();
+        }
+
+        let walker = &animations.walker;
+        let tracked: Vec<Point2D> = walker
+            .is_visible
+            .then(|| walker.point())
+            .into_iter()
+            .collect();
+        animations.trail.record(
+            animations.timeline.time(),
+            is_animating,
+            &epicycloid,
+            &tracked,
+        );
 
         // Conversion to shapes
+        let grid_count = grid.len();
         lines.extend(grid);
         lines.extend(epicycloid);
 
@@ -58,6 +98,10 @@ impl CanvasComponent {
             .map(|line| line.to_pixels(&context.viewport).to_shape())
             .collect::<Vec<Shape>>();
 
+        // Trail between the grid and the figure
+        let trail = context.animations.trail.shapes(&context.viewport);
+        shapes.splice(grid_count..grid_count, trail);
+
         // Rotation point
         if let Some(dot) = context.transformations.rotation.leading_point() {
             shapes.push(EuclideanRotation::leading_shape(dot, &context.viewport));
@@ -74,9 +118,7 @@ impl CanvasComponent {
         let (response, painter) = Self::initialize_painter(ui, context);
         painter.extend(shapes);
 
-        // Animation
-        let epicycloid = &mut context.figures.epicycloid;
-        context.animations.epicycloid.run(ui, epicycloid);
+        let epicycloid = &context.figures.epicycloid;
 
         let mut additional_lines = vec![];
         if let Some(normal) = context.animations.walker.normal(epicycloid) {
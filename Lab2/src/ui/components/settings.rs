@@ -1,6 +1,5 @@
 use crate::context::Context;
-use egui::{Color32, DragValue, Grid, RichText, ScrollArea, SidePanel};
-use geometry::animations::walker;
+use egui::{Color32, DragValue, Grid, RichText, ScrollArea, SidePanel, Slider};
 use geometry::figures::grid;
 
 #[derive(Debug)]
@@ -100,12 +99,24 @@ impl SettingsComponent {
                     ui.separator();
                     ui.add_space(10.0);
 
+                    self.keyframes(ui, context);
+
+                    ui.add_space(10.0);
+                    ui.separator();
+                    ui.add_space(10.0);
+
                     self.curve_walk(ui, context);
 
                     ui.add_space(10.0);
                     ui.separator();
                     ui.add_space(10.0);
 
+                    self.trail(ui, context);
+
+                    ui.add_space(10.0);
+                    ui.separator();
+                    ui.add_space(10.0);
+
                     self.properties(ui, context);
 
                     ui.add_space(10.0);
@@ -329,15 +340,21 @@ impl SettingsComponent {
 
             ui.horizontal(|ui| {
                 ui.label("Status:");
-                if context.animations.epicycloid.is_enabled {
+                if context.animations.epicycloid.playback.is_enabled {
                     ui.colored_label(Color32::LIGHT_GREEN, "Running");
                 } else {
                     ui.colored_label(Color32::RED, "Stopped");
                 }
             });
 
+            widgets::playback::show(
+                ui,
+                &mut context.animations.epicycloid.playback,
+                "EPICYCLOID",
+            );
+
             ui.vertical_centered_justified(|ui| {
-                let text = if context.animations.epicycloid.is_enabled {
+                let text = if context.animations.epicycloid.playback.is_enabled {
                     "Stop"
                 } else {
                     "Start"
@@ -350,6 +367,78 @@ impl SettingsComponent {
         });
     }
 
+    fn keyframes(&self, ui: &mut egui::Ui, context: &mut Context) {
+        ui.group(|ui| {
+            ui.vertical_centered_justified(|ui| {
+                ui.label(RichText::new("Keyframes").color(Color32::WHITE));
+            });
+
+            ui.add_space(5.0);
+
+            let animations = &mut context.animations;
+            widgets::keyframes::show(
+                ui,
+                &mut animations.keyframes,
+                &mut context.figures.epicycloid,
+                &animations.timeline,
+            );
+        });
+    }
+
+    fn trail(&self, ui: &mut egui::Ui, context: &mut Context) {
+        ui.group(|ui| {
+            ui.vertical_centered_justified(|ui| {
+                ui.label(RichText::new("Trail").color(Color32::WHITE));
+            });
+
+            ui.add_space(5.0);
+
+            let trail = &mut context.animations.trail;
+            Grid::new("TRAIL").num_columns(2).show(ui, |ui| {
+                ui.checkbox(&mut trail.is_ghosts_enabled, "Ghosts:");
+                ui.add(
+                    DragValue::new(&mut trail.ghosts)
+                        .speed(0.2)
+                        .range(1..=60)
+                        .suffix(" frames"),
+                );
+                ui.end_row();
+
+                ui.label("Every:");
+                ui.add(
+                    DragValue::new(&mut trail.spacing)
+                        .speed(0.2)
+                        .range(1..=60)
+                        .suffix(" frames"),
+                );
+                ui.end_row();
+
+                ui.label("Fade:");
+                ui.add(Slider::new(&mut trail.opacity, 0.05..=1.0).fixed_decimals(2));
+                ui.end_row();
+
+                ui.checkbox(&mut trail.is_traces_enabled, "Traces:");
+                ui.add(
+                    DragValue::new(&mut trail.trace_length)
+                        .speed(1)
+                        .range(2..=5000)
+                        .suffix(" frames"),
+                );
+                ui.end_row();
+
+                ui.label("Trace Color:");
+                ui.color_edit_button_srgba(&mut trail.trace_stroke.color);
+                ui.end_row();
+            });
+
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Clear Trail").clicked() {
+                    trail.clear();
+                }
+            });
+        });
+    }
+
     fn curve_walk(&self, ui: &mut egui::Ui, context: &mut Context) {
         ui.vertical_centered_justified(|ui| {
             ui.label(RichText::new("Curve Walk").color(Color32::WHITE));
@@ -367,7 +456,7 @@ impl SettingsComponent {
                     ui.label(RichText::new("Hidden").color(Color32::RED));
                 };
 
-                if context.animations.walker.is_enabled {
+                if context.animations.walker.playback.is_enabled {
                     ui.label(RichText::new("Running").color(Color32::LIGHT_GREEN));
                 } else {
                     ui.label(RichText::new("Stopped").color(Color32::RED));
@@ -396,15 +485,13 @@ impl SettingsComponent {
 
                     ui.end_row();
                 }
-
-                ui.label("Speed: ");
-                ui.add(
-                    DragValue::new(&mut context.animations.walker.step)
-                        .speed(1)
-                        .range(walker::STEP_RANGE),
-                );
-                ui.end_row();
             });
+
+            widgets::playback::show(
+                ui,
+                &mut context.animations.walker.playback,
+                "WALKER",
+            );
         });
     }
 
@@ -1,9 +1,14 @@
 use crate::ui::modals::error::ErrorModal;
 use crate::utils::channel::Channel;
 use geometry::animations::contour::AnimationContour;
+use geometry::animations::export::AnimationExport;
+use geometry::animations::morph::MorphShape;
+use geometry::animations::timeline::Timeline;
+use geometry::animations::trail::Trail;
 use geometry::figures::contour::Contour;
 use geometry::figures::grid::{Grid2D, Grid2DBuilder};
 use geometry::pipeline::Pipeline;
+use geometry::primitives::point2d::Point2D;
 use geometry::transformations::euclidean::offset::EuclideanOffset;
 use geometry::transformations::euclidean::rotation::EuclideanRotation;
 use geometry::units::Centimeter;
@@ -77,5 +82,91 @@ pub struct TransformContext {
 
 #[derive(Debug, Default)]
 pub struct AnimationsContext {
+    pub timeline: Timeline,
+    pub export: AnimationExport,
     pub contour: AnimationContour,
+    pub morph: MorphState,
+    pub trail: Trail,
+}
+
+/// Contours the morph can start and end with.
+#[derive(Debug)]
+pub struct MorphState {
+    pub contours: Vec<SavedContour>,
+    /// Index of the source contour.
+    pub source: usize,
+    /// Index of the target contour.
+    pub target: usize,
+}
+
+impl Default for MorphState {
+    fn default() -> Self {
+        Self {
+            contours: SavedContour::presets(),
+            source: 0,
+            target: 1,
+        }
+    }
+}
+
+impl MorphState {
+    pub fn save(&mut self, contour: &Contour) {
+        self.contours.push(SavedContour {
+            name: format!("Saved {}", self.contours.len() + 1),
+            shape: MorphShape::Ferguson {
+                knots: contour.curve.knots.clone(),
+                is_closed: contour.curve.is_closed,
+            },
+        });
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SavedContour {
+    pub name: String,
+    pub shape: MorphShape,
+}
+
+impl SavedContour {
+    /// The shark and the circle of the original animation, and a few polylines.
+    fn presets() -> Vec<Self> {
+        let polygon = |name: &str, radii: &[f64], count: usize| {
+            let points = (0..count)
+                .map(|index| {
+                    let angle = std::f64::consts::TAU * index as f64 / count as f64
+                        + std::f64::consts::FRAC_PI_2;
+                    let radius = radii[index % radii.len()];
+                    Point2D::new(radius * angle.cos(), radius * angle.sin())
+                })
+                .collect();
+
+            Self {
+                name: name.to_string(),
+                shape: MorphShape::Polyline {
+                    points,
+                    is_closed: true,
+                },
+            }
+        };
+
+        vec![
+            Self {
+                name: "Shark".to_string(),
+                shape: MorphShape::Ferguson {
+                    knots: AnimationContour::shark_contour(),
+                    is_closed: true,
+                },
+            },
+            Self {
+                name: "Circle".to_string(),
+                shape: MorphShape::Ferguson {
+                    knots: AnimationContour::circle_contour(16, Centimeter(12.0)),
+                    is_closed: true,
+                },
+            },
+            polygon("Triangle", &[12.0], 3),
+            polygon("Diamond", &[12.0], 4),
+            polygon("Star", &[12.0, 5.0], 10),
+        ]
+    }
 }
@@ -1,5 +1,6 @@
 use crate::context::Context;
 use egui::{CentralPanel, Color32, Frame, Painter, Response, Sense, Shape};
+use geometry::animations::Animation;
 use geometry::primitives::line2d::Line2D;
 use geometry::primitives::point2d::Point2D;
 use geometry::transformations::euclidean::rotation::EuclideanRotation;
@@ -22,6 +23,14 @@ impl CanvasComponent {
 
     fn pipeline(ui: &mut egui::Ui, context: &mut Context) -> Response {
         let shapes = Self::create_shapes(ui, context);
+        let animations = &mut context.animations;
+        animations.export.capture(
+            ui.ctx(),
+            &shapes,
+            &[],
+            &context.viewport,
+            &mut animations.timeline,
+        );
         Self::draw(ui, context, shapes)
     }
 
@@ -50,10 +59,33 @@ impl CanvasComponent {
             pipeline.clear();
         }
 
-        context
-            .animations
+        let animations = &mut context.animations;
+        animations.export.prepare(&mut animations.timeline);
+        animations
+            .timeline
+            .tick(ui.input(|i| f64::from(i.stable_dt)));
+        let is_animating = animations
             .contour
-            .step(ui, &mut context.figures.contour.curve.knots);
+            .update(&animations.timeline, &mut context.figures.contour.curve);
+        if animations.timeline.needs_repaint(is_animating) {
+            ui.ctx().request_repaint();
+        }
+
+        let curve_lines = context.figures.contour.lines();
+        let knots: Vec<Point2D> = context
+            .figures
+            .contour
+            .curve
+            .knots
+            .iter()
+            .map(|knot| knot.control.point.coordinates)
+            .collect();
+        animations.trail.record(
+            animations.timeline.time(),
+            is_animating,
+            &curve_lines,
+            &knots,
+        );
 
         // Conversion to shapes
         lines.extend(grid);
@@ -63,6 +95,9 @@ impl CanvasComponent {
             .map(|line| line.to_pixels(&context.viewport).to_shape())
             .collect::<Vec<Shape>>();
 
+        // Trail under the curve
+        shapes.extend(context.animations.trail.shapes(&context.viewport));
+
         // Adding curve & skeleton
         shapes.extend(curve);
         shapes.extend(skeleton);
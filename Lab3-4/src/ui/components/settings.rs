@@ -1,6 +1,8 @@
 use crate::context::Context;
-use egui::{Color32, DragValue, Grid, RichText, ScrollArea, SidePanel};
+use egui::{Color32, ComboBox, DragValue, Grid, RichText, ScrollArea, SidePanel, Slider};
+use geometry::animations::morph::Interpolation;
 use geometry::figures::grid;
+use strum::IntoEnumIterator;
 
 #[derive(Debug)]
 pub struct SettingsComponent {
@@ -99,6 +101,12 @@ impl SettingsComponent {
                     ui.separator();
                     ui.add_space(10.0);
 
+                    self.trail(ui, context);
+
+                    ui.add_space(10.0);
+                    ui.separator();
+                    ui.add_space(10.0);
+
                     self.euclidean(ui, context);
                 });
             });
@@ -143,6 +151,60 @@ impl SettingsComponent {
         });
     }
 
+    fn trail(&self, ui: &mut egui::Ui, context: &mut Context) {
+        ui.group(|ui| {
+            ui.vertical_centered_justified(|ui| {
+                ui.label(RichText::new("Trail").color(Color32::WHITE));
+            });
+
+            ui.add_space(5.0);
+
+            let trail = &mut context.animations.trail;
+            Grid::new("TRAIL").num_columns(2).show(ui, |ui| {
+                ui.checkbox(&mut trail.is_ghosts_enabled, "Ghosts:");
+                ui.add(
+                    DragValue::new(&mut trail.ghosts)
+                        .speed(0.2)
+                        .range(1..=60)
+                        .suffix(" frames"),
+                );
+                ui.end_row();
+
+                ui.label("Every:");
+                ui.add(
+                    DragValue::new(&mut trail.spacing)
+                        .speed(0.2)
+                        .range(1..=60)
+                        .suffix(" frames"),
+                );
+                ui.end_row();
+
+                ui.label("Fade:");
+                ui.add(Slider::new(&mut trail.opacity, 0.05..=1.0).fixed_decimals(2));
+                ui.end_row();
+
+                ui.checkbox(&mut trail.is_traces_enabled, "Traces:");
+                ui.add(
+                    DragValue::new(&mut trail.trace_length)
+                        .speed(1)
+                        .range(2..=5000)
+                        .suffix(" frames"),
+                );
+                ui.end_row();
+
+                ui.label("Trace Color:");
+                ui.color_edit_button_srgba(&mut trail.trace_stroke.color);
+                ui.end_row();
+            });
+
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Clear Trail").clicked() {
+                    trail.clear();
+                }
+            });
+        });
+    }
+
     fn animation(&self, ui: &mut egui::Ui, context: &mut Context) {
         ui.group(|ui| {
             ui.vertical_centered_justified(|ui| {
@@ -153,25 +215,23 @@ impl SettingsComponent {
 
             ui.horizontal(|ui| {
                 ui.label("Status:");
-                if context.animations.contour.is_enabled {
+                if context.animations.contour.playback.is_enabled {
                     ui.colored_label(Color32::LIGHT_GREEN, "Running");
                 } else {
                     ui.colored_label(Color32::RED, "Stopped");
                 }
             });
 
-            ui.horizontal(|ui| {
-                ui.label("Speed:");
-                ui.add(
-                    DragValue::new(&mut context.animations.contour.speed)
-                        .speed(0.01)
-                        .range(0.0..=1.0)
-                        .fixed_decimals(2),
-                )
-            });
+            self.morph(ui, context);
+
+            widgets::playback::show(
+                ui,
+                &mut context.animations.contour.playback,
+                "CONTOUR",
+            );
 
             ui.vertical_centered_justified(|ui| {
-                let text = if context.animations.contour.is_enabled {
+                let text = if context.animations.contour.playback.is_enabled {
                     "Stop"
                 } else {
                     "Start"
@@ -189,12 +249,82 @@ impl SettingsComponent {
                     context
                         .animations
                         .contour
-                        .reset(&mut context.figures.contour.curve.knots);
+                        .reset(&mut context.figures.contour.curve);
+                }
+            });
+
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Save Current Contour").clicked() {
+                    context.animations.morph.save(&context.figures.contour);
                 }
             });
         });
     }
 
+    fn morph(&self, ui: &mut egui::Ui, context: &mut Context) {
+        let animation = &mut context.animations.contour;
+        let morph = &mut context.animations.morph;
+
+        let (mut source, mut target) = (morph.source, morph.target);
+        let mut samples = animation.samples();
+
+        Grid::new("CONTOUR_MORPH").num_columns(2).show(ui, |ui| {
+            for (label, index) in [("Source:", &mut source), ("Target:", &mut target)] {
+                ui.label(label);
+                ComboBox::from_id_salt(("MORPH", label))
+                    .selected_text(
+                        morph
+                            .contours
+                            .get(*index)
+                            .map_or("None", |contour| contour.name.as_str()),
+                    )
+                    .show_ui(ui, |ui| {
+                        for (position, contour) in morph.contours.iter().enumerate() {
+                            ui.selectable_value(index, position, contour.name.as_str());
+                        }
+                    });
+                ui.end_row();
+            }
+
+            ui.label("Knots:");
+            ui.add(DragValue::new(&mut samples).speed(1).range(8..=256));
+            ui.end_row();
+
+            ui.label("Interpolation:");
+            ComboBox::from_id_salt("MORPH_INTERPOLATION")
+                .selected_text(animation.interpolation.name())
+                .show_ui(ui, |ui| {
+                    for interpolation in Interpolation::iter() {
+                        ui.selectable_value(
+                            &mut animation.interpolation,
+                            interpolation,
+                            interpolation.name(),
+                        );
+                    }
+                });
+            ui.end_row();
+        });
+
+        if let Some(contour) = morph.contours.get(source)
+            && source != morph.source
+        {
+            morph.source = source;
+            animation.set_source(contour.shape.clone());
+            if !animation.playback.is_enabled {
+                animation.reset(&mut context.figures.contour.curve);
+            }
+        }
+        if let Some(contour) = morph.contours.get(target)
+            && target != morph.target
+        {
+            morph.target = target;
+            animation.set_target(contour.shape.clone());
+        }
+        if samples != animation.samples() {
+            animation.set_samples(samples);
+        }
+    }
+
     fn euclidean(&self, ui: &mut egui::Ui, context: &mut Context) {
         ui.vertical_centered_justified(|ui| {
             ui.label(RichText::new("Euclidean Transformations").color(Color32::WHITE));
@@ -1,14 +1,33 @@
 use crate::ui::modals::error::ErrorModal;
 use crate::utils::channel::Channel;
+use geometry::animations::attractor::AnimationAttractor;
+use geometry::animations::export::AnimationExport;
+use geometry::animations::keyframes::KeyframeAnimation;
+use geometry::animations::orientation::AnimationOrientation;
 use geometry::animations::star::AnimationStar;
+use geometry::animations::timeline::Timeline;
+use geometry::animations::trail::Trail;
+use geometry::animations::{Animatable, Parameter};
+use geometry::figures::extrusion::{Extrusion, ExtrusionSource};
 use geometry::figures::grid3d::Grid3D;
 use geometry::figures::star3d::Star3D;
+use geometry::figures::sweep::Sweep;
+use geometry::fractals::attractors::Attractor3D;
 use geometry::pipeline::Pipeline3D;
+use geometry::primitives::line2d::Line2D;
+use geometry::primitives::line3d::Line3D;
+use geometry::primitives::point2d::Point2D;
+use geometry::primitives::point3d::Point3D;
 use geometry::projections::twopoint::TwoPointPerspective;
+use geometry::transformations::affine::reflection3d::AffinePlaneReflection3D;
+use geometry::transformations::affine::scaling3d::AffineScaling3D;
+use geometry::transformations::affine::shear3d::AffineShear3D;
+use geometry::transformations::euclidean::arcball::Arcball;
 use geometry::transformations::euclidean::offset3d::EuclideanOffset3D;
 use geometry::transformations::euclidean::rotation3d::{
     EuclideanRotation3D, Rotation3DOperation,
 };
+use geometry::units::Centimeter;
 use geometry::viewport::{Viewport, ViewportGeometry, ViewportState, ZeroPointLocation};
 
 #[derive(Debug)]
@@ -52,23 +71,124 @@ impl Context {
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct FiguresState {
     pub grid: Grid3D,
+    pub kind: FigureKind,
     pub star: Star3D,
+    pub extrusion: Extrusion,
+    pub extrusion_source: ExtrusionSource,
+    /// Larger side of the extruded outline.
+    pub extrusion_size: Centimeter,
+    pub sweep: Sweep,
+    pub attractor: Attractor3D,
     pub star_pipeline: Pipeline3D,
 }
 
+impl Default for FiguresState {
+    fn default() -> Self {
+        Self {
+            grid: Grid3D::default(),
+            kind: FigureKind::Star,
+            star: Star3D::default(),
+            extrusion: Extrusion::default(),
+            extrusion_source: ExtrusionSource::Detail,
+            extrusion_size: Centimeter(10.0),
+            sweep: Sweep::default(),
+            attractor: Attractor3D::default(),
+            star_pipeline: Pipeline3D::default(),
+        }
+    }
+}
+
+impl FiguresState {
+    pub fn lines(&self) -> Vec<Line3D<Point3D>> {
+        match self.kind {
+            FigureKind::Star => self.star.lines(),
+            FigureKind::Extrusion => self.extrusion.extrude(&self.extrusion_outline()),
+            FigureKind::Sweep => self.sweep.lines(),
+            FigureKind::Attractor => self.attractor.lines(),
+        }
+    }
+
+    pub fn pivot_point(&self) -> Point3D {
+        match self.kind {
+            FigureKind::Star => self.star.pivot_point(),
+            FigureKind::Extrusion => {
+                self.extrusion.pivot_point(&self.extrusion_outline())
+            },
+            FigureKind::Sweep => self.sweep.pivot_point(),
+            FigureKind::Attractor => self.attractor.pivot_point(),
+        }
+    }
+
+    fn extrusion_outline(&self) -> Vec<Line2D<Point2D>> {
+        self.extrusion_source.outline(self.extrusion_size)
+    }
+}
+
+/// Keyframes drive the parameters of the figure that is currently shown.
+impl Animatable for FiguresState {
+    fn parameters(&mut self) -> Vec<Parameter<'_>> {
+        match self.kind {
+            FigureKind::Star => self.star.parameters(),
+            FigureKind::Extrusion => {
+                let mut parameters = self.extrusion.parameters();
+                parameters.push(Parameter::new(
+                    "Outline Size",
+                    &mut self.extrusion_size.0,
+                    1.0..=30.0,
+                ));
+                parameters
+            },
+            FigureKind::Sweep => self.sweep.parameters(),
+            FigureKind::Attractor => self.attractor.parameters(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FigureKind {
+    Star,
+    Extrusion,
+    Sweep,
+    Attractor,
+}
+
+impl FigureKind {
+    pub const ALL: [Self; 4] =
+        [Self::Star, Self::Extrusion, Self::Sweep, Self::Attractor];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Star => "Star",
+            Self::Extrusion => "Extrusion",
+            Self::Sweep => "Sweep",
+            Self::Attractor => "Strange Attractor",
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct TransformContext {
     pub offset: EuclideanOffset3D,
     pub rotation: EuclideanRotation3D,
+    pub scaling: AffineScaling3D,
+    pub shear: AffineShear3D,
+    pub reflection: AffinePlaneReflection3D,
+    pub arcball: Arcball,
 }
 
 #[derive(Debug, Default)]
 pub struct AnimationsContext {
+    pub timeline: Timeline,
+    pub export: AnimationExport,
     pub star: AnimationStar,
     pub rotation: Rotation3DOperation,
+    pub attractor: AnimationAttractor,
+    pub keyframes: KeyframeAnimation,
+    pub orientation: AnimationOrientation,
+    pub trail: Trail,
 }
 
 #[derive(Debug, Default)]
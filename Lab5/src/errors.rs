@@ -2,6 +2,7 @@ use crate::config::ConfigError;
 use crate::io::IoError;
 use crate::logs::LogError;
 use crate::ui::GraphicsBackendError;
+use geometry::animations::export::ExportError;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -9,6 +10,9 @@ pub enum ProjectError {
     #[error("Configuration. {0}")]
     Config(#[from] ConfigError),
 
+    #[error("Export. {0}")]
+    Export(#[from] ExportError),
+
     #[error("Graphics Backend. {0}")]
     GraphicsBackend(#[from] GraphicsBackendError),
 
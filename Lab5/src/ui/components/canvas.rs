@@ -1,9 +1,18 @@
-use crate::context::Context;
+use crate::context::{Context, FigureKind};
 use egui::{CentralPanel, Color32, Frame, Painter, Response, Sense, Shape};
+use geometry::animations::Animation;
 use geometry::primitives::line2d::Line2D;
 use geometry::primitives::line3d::Line3D;
 use geometry::primitives::point2d::Point2D;
-use geometry::primitives::point3d::Point3D;
+use geometry::primitives::point3d::{Point3D, Pointable3D};
+use geometry::transformations::affine::reflection3d::AffinePlaneReflection3D;
+use geometry::transformations::affine::scaling3d::AffineScaling3D;
+use geometry::transformations::affine::shear3d::AffineShear3D;
+use geometry::units::Centimeter;
+use geometry::viewport::Viewport;
+
+/// Side of the square the mirror plane is drawn as.
+const MIRROR_SIDE: f64 = 20.0;
 
 #[derive(Debug, Default)]
 pub struct CanvasComponent;
@@ -16,13 +25,23 @@ impl CanvasComponent {
                 .show(ui, |ui| {
                     ui.input(|i| context.viewport.handle_scroll(i));
                     let response = Self::pipeline(ui, context);
-                    context.viewport.handle_pan(ui, response);
+                    if !context.transformations.arcball.handle(ui, &response) {
+                        context.viewport.handle_pan(ui, response);
+                    }
                 });
         });
     }
 
     fn pipeline(ui: &mut egui::Ui, context: &mut Context) -> Response {
         let shapes = Self::create_shapes(ui, context);
+        let animations = &mut context.animations;
+        animations.export.capture(
+            ui.ctx(),
+            &shapes,
+            &[],
+            &context.viewport,
+            &mut animations.timeline,
+        );
         Self::draw(ui, context, shapes)
     }
 
@@ -37,8 +56,30 @@ impl CanvasComponent {
             .map(|line| line.to_2d(&context.projections.twopoint))
             .collect();
 
-        let mut star: Vec<Line3D<Point3D>> = context.figures.star.lines();
-        let mut pivot = context.figures.star.pivot_point();
+        let animations = &mut context.animations;
+        animations.export.prepare(&mut animations.timeline);
+        animations
+            .timeline
+            .tick(ui.input(|i| f64::from(i.stable_dt)));
+        let mut is_animating = animations
+            .keyframes
+            .update(&animations.timeline, &mut context.figures);
+        is_animating |= animations.attractor.update(
+            &animations.timeline,
+            &mut context.figures.attractor.parameters,
+        );
+        is_animating |= animations.orientation.update(
+            &animations.timeline,
+            &mut context.transformations.arcball.orientation,
+        );
+
+        let mut figure: Vec<Line3D<Point3D>> = context.figures.lines();
+        let mut pivot = context.figures.pivot_point();
+        let mut tips_pivot = pivot;
+        let mut tips = match context.figures.kind {
+            FigureKind::Star => context.figures.star.tips(),
+            _ => vec![],
+        };
 
         context
             .transformations
@@ -48,39 +89,134 @@ impl CanvasComponent {
             .transformations
             .rotation
             .handle(vec![&mut context.figures.star_pipeline]);
-        context.animations.star.run(
-            ui,
-            &mut context.figures.star,
-            &mut context.animations.rotation,
+        context
+            .transformations
+            .scaling
+            .handle(vec![&mut context.figures.star_pipeline]);
+        context
+            .transformations
+            .shear
+            .handle(vec![&mut context.figures.star_pipeline]);
+        context
+            .transformations
+            .reflection
+            .handle(vec![&mut context.figures.star_pipeline]);
+        let animations = &mut context.animations;
+        is_animating |= animations.star.update(
+            &animations.timeline,
+            (&mut context.figures.star, &mut animations.rotation),
         );
+        if animations.timeline.needs_repaint(is_animating) {
+            ui.ctx().request_repaint();
+        }
 
         context
             .figures
             .star_pipeline
-            .do_tasks(&mut star, &mut pivot);
+            .do_tasks(&mut figure, &mut pivot);
+        context
+            .figures
+            .star_pipeline
+            .do_tasks_points(&mut tips, &mut tips_pivot);
 
         // Animation for rotation
-        if context.animations.star.is_enabled {
-            for line in &mut star {
+        if context.animations.star.playback.is_enabled {
+            for line in &mut figure {
                 context.animations.rotation.go(&mut line.start, &mut pivot);
                 context.animations.rotation.go(&mut line.end, &mut pivot);
             }
-            ui.ctx().request_repaint();
+            for tip in &mut tips {
+                context.animations.rotation.go(tip, &mut pivot);
+            }
         }
 
-        let star: Vec<Line2D<Point2D>> = star
+        // Orientation from the arcball
+        let arcball = context.transformations.arcball.operation();
+        for line in &mut figure {
+            arcball.go(&mut line.start, &mut pivot);
+            arcball.go(&mut line.end, &mut pivot);
+        }
+        for tip in &mut tips {
+            arcball.go(tip, &mut pivot);
+        }
+
+        let figure: Vec<Line2D<Point2D>> = figure
             .iter()
             .map(|line| line.to_2d(&context.projections.twopoint))
             .collect();
+        let tips: Vec<Point2D> = tips
+            .iter()
+            .map(|tip| tip.to_2d(&context.projections.twopoint))
+            .collect();
+
+        let animations = &mut context.animations;
+        animations
+            .trail
+            .record(animations.timeline.time(), is_animating, &figure, &tips);
 
         // Conversion to shapes
+        let grid_count = grid.len();
         lines.extend(grid);
-        lines.extend(star);
+        lines.extend(figure);
 
-        lines
+        let mut shapes = lines
             .iter()
             .map(|line| line.to_pixels(&context.viewport).to_shape())
-            .collect::<Vec<Shape>>()
+            .collect::<Vec<Shape>>();
+
+        // Trail between the grid and the figure
+        let trail = context.animations.trail.shapes(&context.viewport);
+        shapes.splice(grid_count..grid_count, trail);
+
+        shapes.extend(Self::leading_shapes(context, pivot));
+
+        shapes
+    }
+
+    /// Pivot of the affine maps being edited and the mirror plane, turned by the
+    /// arcball like the figure.
+    fn leading_shapes(context: &Context, pivot: Point3D) -> Vec<Shape> {
+        let transformations = &context.transformations;
+        let projection = &context.projections.twopoint;
+        let arcball = transformations.arcball.operation();
+        let mut origin = pivot;
+
+        let mut mirror = transformations
+            .reflection
+            .mirror(pivot, Centimeter(MIRROR_SIDE));
+        for line in &mut mirror {
+            arcball.go(&mut line.start, &mut origin);
+            arcball.go(&mut line.end, &mut origin);
+        }
+        let mut shapes = mirror
+            .iter()
+            .map(|line| {
+                line.to_2d(projection)
+                    .to_pixels(&context.viewport)
+                    .to_shape()
+            })
+            .collect::<Vec<Shape>>();
+
+        let mut dot = |point: Option<Point3D>, shape: fn(Point2D, &Viewport) -> Shape| {
+            if let Some(mut point) = point {
+                arcball.go(&mut point, &mut origin);
+                shapes.push(shape(point.to_2d(projection), &context.viewport));
+            }
+        };
+        dot(
+            transformations.scaling.leading_point(pivot),
+            AffineScaling3D::leading_shape,
+        );
+        dot(
+            transformations.shear.leading_point(pivot),
+            AffineShear3D::leading_shape,
+        );
+        dot(
+            transformations.reflection.leading_point(pivot),
+            AffinePlaneReflection3D::leading_shape,
+        );
+
+        shapes
     }
 
     fn draw(ui: &mut egui::Ui, context: &mut Context, shapes: Vec<Shape>) -> Response {
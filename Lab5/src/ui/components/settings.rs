@@ -1,5 +1,9 @@
-use crate::context::Context;
-use egui::{Color32, DragValue, Grid, RichText, ScrollArea, SidePanel};
+use crate::context::{Context, FigureKind};
+use egui::{Color32, ComboBox, DragValue, Grid, RichText, ScrollArea, SidePanel, Slider};
+use geometry::figures::extrusion::ExtrusionSource;
+use geometry::figures::sweep::{FrameMode, SweepPath, SweepProfile};
+use geometry::fractals::attractors::{Attractor3D, Flow3D};
+use strum::IntoEnumIterator;
 
 #[derive(Debug)]
 pub struct SettingsComponent {
@@ -80,7 +84,7 @@ impl SettingsComponent {
                     ui.separator();
                     ui.add_space(10.0);
 
-                    self.star(ui, context);
+                    self.figure(ui, context);
 
                     ui.add_space(10.0);
                     ui.separator();
@@ -98,11 +102,57 @@ impl SettingsComponent {
                     ui.separator();
                     ui.add_space(10.0);
 
+                    self.keyframes(ui, context);
+
+                    ui.add_space(10.0);
+                    ui.separator();
+                    ui.add_space(10.0);
+
+                    self.orientation(ui, context);
+
+                    ui.add_space(10.0);
+                    ui.separator();
+                    ui.add_space(10.0);
+
+                    self.trail(ui, context);
+
+                    ui.add_space(10.0);
+                    ui.separator();
+                    ui.add_space(10.0);
+
                     self.euclidean(ui, context);
+
+                    ui.add_space(10.0);
+                    ui.separator();
+                    ui.add_space(10.0);
+
+                    self.affine(ui, context);
                 });
             });
     }
 
+    fn figure(&self, ui: &mut egui::Ui, context: &mut Context) {
+        ui.horizontal(|ui| {
+            ui.label("Figure:");
+            ComboBox::from_id_salt("FIGURE_KIND")
+                .selected_text(context.figures.kind.name())
+                .show_ui(ui, |ui| {
+                    for kind in FigureKind::ALL {
+                        ui.selectable_value(&mut context.figures.kind, kind, kind.name());
+                    }
+                });
+        });
+
+        ui.add_space(10.0);
+
+        match context.figures.kind {
+            FigureKind::Star => self.star(ui, context),
+            FigureKind::Extrusion => self.extrusion(ui, context),
+            FigureKind::Sweep => self.sweep(ui, context),
+            FigureKind::Attractor => self.attractor(ui, context),
+        }
+    }
+
     fn star(&self, ui: &mut egui::Ui, context: &mut Context) {
         ui.label(RichText::new("Star").color(Color32::WHITE));
 
@@ -135,6 +185,311 @@ impl SettingsComponent {
         });
     }
 
+    fn extrusion(&self, ui: &mut egui::Ui, context: &mut Context) {
+        ui.label(RichText::new("Extrusion").color(Color32::WHITE));
+
+        ui.add_space(5.0);
+
+        ui.horizontal(|ui| {
+            ui.label("Outline:");
+            ComboBox::from_id_salt("EXTRUSION_SOURCE")
+                .selected_text(context.figures.extrusion_source.name())
+                .show_ui(ui, |ui| {
+                    for source in ExtrusionSource::iter() {
+                        ui.selectable_value(
+                            &mut context.figures.extrusion_source,
+                            source,
+                            source.name(),
+                        );
+                    }
+                });
+        });
+
+        let extrusion = &mut context.figures.extrusion;
+
+        Grid::new("EXTRUSION_SETTINGS")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("Size:");
+                ui.add(
+                    DragValue::new(&mut context.figures.extrusion_size.0)
+                        .speed(0.1)
+                        .range(0.1..=f64::INFINITY)
+                        .fixed_decimals(2),
+                );
+                ui.end_row();
+
+                ui.label("Depth:");
+                ui.add(
+                    DragValue::new(&mut extrusion.depth.0)
+                        .speed(0.1)
+                        .fixed_decimals(2),
+                );
+                ui.end_row();
+
+                ui.label("Taper:");
+                ui.add(
+                    DragValue::new(&mut extrusion.taper)
+                        .speed(0.01)
+                        .range(0.0..=10.0)
+                        .fixed_decimals(2),
+                );
+                ui.end_row();
+
+                ui.label("Twist:");
+                ui.add(DragValue::new(&mut extrusion.twist).speed(1).suffix(" °"));
+                ui.end_row();
+
+                ui.label("Layers:");
+                ui.add(
+                    DragValue::new(&mut extrusion.layers)
+                        .speed(1)
+                        .range(1..=100),
+                );
+                ui.end_row();
+
+                ui.label("Side Edge Step:");
+                ui.add(
+                    DragValue::new(&mut extrusion.side_edge_step)
+                        .speed(1)
+                        .range(1..=100),
+                );
+                ui.end_row();
+            });
+
+        ui.checkbox(&mut extrusion.is_layers_visible, "Show Layers");
+
+        ui.add_space(5.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Reset").clicked() {
+                context.figures.extrusion.reset();
+            }
+        });
+    }
+
+    fn attractor(&self, ui: &mut egui::Ui, context: &mut Context) {
+        ui.label(RichText::new("Strange Attractor").color(Color32::WHITE));
+
+        ui.add_space(5.0);
+
+        let attractor = &mut context.figures.attractor;
+        let animation = &mut context.animations.attractor;
+
+        Grid::new("ATTRACTOR_SETTINGS")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("Flow:");
+                let mut flow = attractor.flow;
+                ComboBox::from_id_salt("ATTRACTOR_FLOW")
+                    .selected_text(flow.name())
+                    .show_ui(ui, |ui| {
+                        for option in Flow3D::iter() {
+                            ui.selectable_value(&mut flow, option, option.name());
+                        }
+                    });
+                if flow != attractor.flow {
+                    let (steps, stroke) = (attractor.steps, attractor.stroke);
+                    *attractor = Attractor3D::new(flow);
+                    attractor.steps = steps;
+                    attractor.stroke = stroke;
+                    *animation = Default::default();
+                }
+                ui.end_row();
+
+                for (name, value) in attractor
+                    .flow
+                    .parameter_names()
+                    .iter()
+                    .zip(attractor.parameters.iter_mut())
+                {
+                    ui.label(format!("{name}:"));
+                    ui.add(DragValue::new(value).speed(0.01).fixed_decimals(3));
+                    ui.end_row();
+                }
+
+                ui.label("Steps:");
+                ui.add(
+                    DragValue::new(&mut attractor.steps)
+                        .speed(10)
+                        .range(10..=200_000),
+                );
+                ui.end_row();
+
+                ui.label("Time Step:");
+                ui.add(
+                    DragValue::new(&mut attractor.dt)
+                        .speed(0.0001)
+                        .range(0.0001..=0.1)
+                        .fixed_decimals(4),
+                );
+                ui.end_row();
+
+                ui.label("Scale:");
+                ui.add(
+                    DragValue::new(&mut attractor.scale)
+                        .speed(0.01)
+                        .range(0.01..=100.0)
+                        .fixed_decimals(2),
+                );
+                ui.end_row();
+
+                ui.label("Color:");
+                ui.color_edit_button_srgba(&mut attractor.stroke.color);
+                ui.end_row();
+
+                let names = attractor.flow.parameter_names();
+
+                ui.label("Animate:");
+                ComboBox::from_id_salt("ATTRACTOR_PARAMETER")
+                    .selected_text(names.get(animation.parameter).copied().unwrap_or("-"))
+                    .show_ui(ui, |ui| {
+                        for (index, name) in names.iter().enumerate() {
+                            ui.add_enabled_ui(!animation.playback.is_enabled, |ui| {
+                                ui.selectable_value(
+                                    &mut animation.parameter,
+                                    index,
+                                    *name,
+                                );
+                            });
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("Amplitude:");
+                ui.add(
+                    DragValue::new(&mut animation.amplitude)
+                        .speed(0.01)
+                        .range(0.01..=1.0),
+                );
+                ui.end_row();
+            });
+
+        widgets::playback::show(ui, &mut animation.playback, "ATTRACTOR");
+
+        ui.add_space(5.0);
+
+        ui.vertical_centered_justified(|ui| {
+            let text = if animation.playback.is_enabled {
+                "Stop Parameter Animation"
+            } else {
+                "Start Parameter Animation"
+            };
+
+            if ui.button(text).clicked() {
+                animation.toggle(&attractor.parameters);
+            }
+        });
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Reset").clicked() {
+                context.figures.attractor.reset();
+                context.animations.attractor = Default::default();
+            }
+        });
+    }
+
+    fn sweep(&self, ui: &mut egui::Ui, context: &mut Context) {
+        ui.label(RichText::new("Sweep").color(Color32::WHITE));
+
+        ui.add_space(5.0);
+
+        let sweep = &mut context.figures.sweep;
+
+        Grid::new("SWEEP_SETTINGS").num_columns(2).show(ui, |ui| {
+            ui.label("Path:");
+            ComboBox::from_id_salt("SWEEP_PATH")
+                .selected_text(sweep.path.name())
+                .show_ui(ui, |ui| {
+                    for path in SweepPath::iter() {
+                        ui.selectable_value(&mut sweep.path, path, path.name());
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Path Radius:");
+            ui.add(
+                DragValue::new(&mut sweep.path_radius.0)
+                    .speed(0.1)
+                    .fixed_decimals(2),
+            );
+            ui.end_row();
+
+            if sweep.path == SweepPath::Helix {
+                ui.label("Pitch:");
+                ui.add(
+                    DragValue::new(&mut sweep.pitch.0)
+                        .speed(0.1)
+                        .fixed_decimals(2),
+                );
+                ui.end_row();
+
+                ui.label("Turns:");
+                ui.add(
+                    DragValue::new(&mut sweep.turns)
+                        .speed(0.1)
+                        .range(0.1..=50.0)
+                        .fixed_decimals(1),
+                );
+                ui.end_row();
+            }
+
+            ui.label("Samples:");
+            ui.add(DragValue::new(&mut sweep.samples).speed(1).range(2..=2000));
+            ui.end_row();
+
+            ui.label("Profile:");
+            ComboBox::from_id_salt("SWEEP_PROFILE")
+                .selected_text(sweep.profile.name())
+                .show_ui(ui, |ui| {
+                    for profile in SweepProfile::iter() {
+                        ui.selectable_value(&mut sweep.profile, profile, profile.name());
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Profile Size:");
+            ui.add(
+                DragValue::new(&mut sweep.profile_size.0)
+                    .speed(0.05)
+                    .fixed_decimals(2),
+            );
+            ui.end_row();
+
+            if sweep.profile == SweepProfile::Polygon {
+                ui.label("Sides:");
+                ui.add(
+                    DragValue::new(&mut sweep.profile_sides)
+                        .speed(1)
+                        .range(3..=64),
+                );
+                ui.end_row();
+            }
+
+            ui.label("Frame:");
+            ComboBox::from_id_salt("SWEEP_FRAME")
+                .selected_text(sweep.frame.name())
+                .show_ui(ui, |ui| {
+                    for frame in FrameMode::iter() {
+                        ui.selectable_value(&mut sweep.frame, frame, frame.name());
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Ring Step:");
+            ui.add(DragValue::new(&mut sweep.ring_step).speed(1).range(1..=100));
+            ui.end_row();
+        });
+
+        ui.add_space(5.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Reset").clicked() {
+                sweep.reset();
+            }
+        });
+    }
+
     fn animation(&self, ui: &mut egui::Ui, context: &mut Context) {
         ui.group(|ui| {
             ui.vertical_centered_justified(|ui| {
@@ -145,15 +500,17 @@ impl SettingsComponent {
 
             ui.horizontal(|ui| {
                 ui.label("Status:");
-                if context.animations.star.is_enabled {
+                if context.animations.star.playback.is_enabled {
                     ui.colored_label(Color32::LIGHT_GREEN, "Running");
                 } else {
                     ui.colored_label(Color32::RED, "Stopped");
                 }
             });
 
+            widgets::playback::show(ui, &mut context.animations.star.playback, "STAR");
+
             ui.vertical_centered_justified(|ui| {
-                let text = if context.animations.star.is_enabled {
+                let text = if context.animations.star.playback.is_enabled {
                     "Stop"
                 } else {
                     "Start"
@@ -166,6 +523,24 @@ impl SettingsComponent {
         });
     }
 
+    fn keyframes(&self, ui: &mut egui::Ui, context: &mut Context) {
+        ui.group(|ui| {
+            ui.vertical_centered_justified(|ui| {
+                ui.label(RichText::new("Keyframes").color(Color32::WHITE));
+            });
+
+            ui.add_space(5.0);
+
+            let animations = &mut context.animations;
+            widgets::keyframes::show(
+                ui,
+                &mut animations.keyframes,
+                &mut context.figures,
+                &animations.timeline,
+            );
+        });
+    }
+
     fn matrix(&self, ui: &mut egui::Ui, context: &mut Context) {
         ui.label(RichText::new("Perspective Coefficients").color(Color32::WHITE));
 
@@ -203,6 +578,83 @@ impl SettingsComponent {
         });
     }
 
+    fn trail(&self, ui: &mut egui::Ui, context: &mut Context) {
+        ui.group(|ui| {
+            ui.vertical_centered_justified(|ui| {
+                ui.label(RichText::new("Trail").color(Color32::WHITE));
+            });
+
+            ui.add_space(5.0);
+
+            let trail = &mut context.animations.trail;
+            Grid::new("TRAIL").num_columns(2).show(ui, |ui| {
+                ui.checkbox(&mut trail.is_ghosts_enabled, "Ghosts:");
+                ui.add(
+                    DragValue::new(&mut trail.ghosts)
+                        .speed(0.2)
+                        .range(1..=60)
+                        .suffix(" frames"),
+                );
+                ui.end_row();
+
+                ui.label("Every:");
+                ui.add(
+                    DragValue::new(&mut trail.spacing)
+                        .speed(0.2)
+                        .range(1..=60)
+                        .suffix(" frames"),
+                );
+                ui.end_row();
+
+                ui.label("Fade:");
+                ui.add(Slider::new(&mut trail.opacity, 0.05..=1.0).fixed_decimals(2));
+                ui.end_row();
+
+                ui.checkbox(&mut trail.is_traces_enabled, "Traces:");
+                ui.add(
+                    DragValue::new(&mut trail.trace_length)
+                        .speed(1)
+                        .range(2..=5000)
+                        .suffix(" frames"),
+                );
+                ui.end_row();
+
+                ui.label("Trace Color:");
+                ui.color_edit_button_srgba(&mut trail.trace_stroke.color);
+                ui.end_row();
+            });
+
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Clear Trail").clicked() {
+                    trail.clear();
+                }
+            });
+        });
+    }
+
+    fn orientation(&self, ui: &mut egui::Ui, context: &mut Context) {
+        ui.group(|ui| {
+            ui.vertical_centered_justified(|ui| {
+                ui.label(RichText::new("Orientation").color(Color32::WHITE));
+            });
+
+            ui.add_space(5.0);
+
+            let arcball = &mut context.transformations.arcball;
+            widgets::arcball::show(ui, arcball);
+
+            ui.add_space(5.0);
+
+            let animations = &mut context.animations;
+            widgets::orientation::show(
+                ui,
+                &mut animations.orientation,
+                &mut arcball.orientation,
+                &animations.timeline,
+            );
+        });
+    }
+
     fn euclidean(&self, ui: &mut egui::Ui, context: &mut Context) {
         ui.vertical_centered_justified(|ui| {
             ui.label(RichText::new("Euclidean Transformations").color(Color32::WHITE));
@@ -316,4 +768,203 @@ impl SettingsComponent {
             });
         });
     }
+
+    fn affine(&self, ui: &mut egui::Ui, context: &mut Context) {
+        ui.vertical_centered_justified(|ui| {
+            ui.label(RichText::new("Affine Transformations").color(Color32::WHITE));
+        });
+
+        ui.add_space(5.0);
+
+        ui.group(|ui| {
+            ui.vertical_centered(|ui| {
+                ui.label("Scaling about Pivot");
+            });
+
+            ui.add_space(5.0);
+
+            Grid::new("AFFINE_Scaling").num_columns(2).show(ui, |ui| {
+                ui.label("Mx:");
+                ui.add(
+                    DragValue::new(&mut context.transformations.scaling.mx)
+                        .speed(0.01)
+                        .fixed_decimals(2),
+                );
+                ui.end_row();
+
+                ui.label("My:");
+                ui.add(
+                    DragValue::new(&mut context.transformations.scaling.my)
+                        .speed(0.01)
+                        .fixed_decimals(2),
+                );
+                ui.end_row();
+
+                ui.label("Mz:");
+                ui.add(
+                    DragValue::new(&mut context.transformations.scaling.mz)
+                        .speed(0.01)
+                        .fixed_decimals(2),
+                );
+                ui.end_row();
+            });
+
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                ui.columns(2, |ui| {
+                    ui[0].vertical_centered_justified(|ui| {
+                        if ui.button("Apply").clicked() {
+                            context.transformations.scaling.run();
+                        }
+                    });
+                    ui[1].vertical_centered_justified(|ui| {
+                        if ui.button("Reset").clicked() {
+                            context.transformations.scaling.reset();
+                        }
+                    });
+                });
+            });
+        });
+
+        ui.add_space(10.0);
+
+        ui.group(|ui| {
+            ui.vertical_centered(|ui| {
+                ui.label("Shear");
+            });
+
+            ui.add_space(5.0);
+
+            Grid::new("AFFINE_Shear").num_columns(2).show(ui, |ui| {
+                ui.label("X by Y:");
+                ui.add(
+                    DragValue::new(&mut context.transformations.shear.xy)
+                        .speed(0.01)
+                        .fixed_decimals(2),
+                );
+                ui.end_row();
+
+                ui.label("X by Z:");
+                ui.add(
+                    DragValue::new(&mut context.transformations.shear.xz)
+                        .speed(0.01)
+                        .fixed_decimals(2),
+                );
+                ui.end_row();
+
+                ui.label("Y by X:");
+                ui.add(
+                    DragValue::new(&mut context.transformations.shear.yx)
+                        .speed(0.01)
+                        .fixed_decimals(2),
+                );
+                ui.end_row();
+
+                ui.label("Y by Z:");
+                ui.add(
+                    DragValue::new(&mut context.transformations.shear.yz)
+                        .speed(0.01)
+                        .fixed_decimals(2),
+                );
+                ui.end_row();
+
+                ui.label("Z by X:");
+                ui.add(
+                    DragValue::new(&mut context.transformations.shear.zx)
+                        .speed(0.01)
+                        .fixed_decimals(2),
+                );
+                ui.end_row();
+
+                ui.label("Z by Y:");
+                ui.add(
+                    DragValue::new(&mut context.transformations.shear.zy)
+                        .speed(0.01)
+                        .fixed_decimals(2),
+                );
+                ui.end_row();
+            });
+
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                ui.columns(2, |ui| {
+                    ui[0].vertical_centered_justified(|ui| {
+                        if ui.button("Apply").clicked() {
+                            context.transformations.shear.run();
+                        }
+                    });
+                    ui[1].vertical_centered_justified(|ui| {
+                        if ui.button("Reset").clicked() {
+                            context.transformations.shear.reset();
+                        }
+                    });
+                });
+            });
+        });
+
+        ui.add_space(10.0);
+
+        ui.group(|ui| {
+            ui.vertical_centered(|ui| {
+                ui.label("Plane Reflection");
+            });
+
+            ui.add_space(5.0);
+
+            Grid::new("AFFINE_Plane_Reflection")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label("Normal X:");
+                    ui.add(
+                        DragValue::new(&mut context.transformations.reflection.normal_x)
+                            .speed(0.01)
+                            .fixed_decimals(2),
+                    );
+                    ui.end_row();
+
+                    ui.label("Normal Y:");
+                    ui.add(
+                        DragValue::new(&mut context.transformations.reflection.normal_y)
+                            .speed(0.01)
+                            .fixed_decimals(2),
+                    );
+                    ui.end_row();
+
+                    ui.label("Normal Z:");
+                    ui.add(
+                        DragValue::new(&mut context.transformations.reflection.normal_z)
+                            .speed(0.01)
+                            .fixed_decimals(2),
+                    );
+                    ui.end_row();
+
+                    ui.label("Offset:");
+                    ui.add(
+                        DragValue::new(&mut context.transformations.reflection.offset.0)
+                            .speed(0.1)
+                            .fixed_decimals(2),
+                    );
+                    ui.end_row();
+                });
+
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                ui.columns(2, |ui| {
+                    ui[0].vertical_centered_justified(|ui| {
+                        if ui.button("Apply").clicked() {
+                            context.transformations.reflection.run();
+                        }
+                    });
+                    ui[1].vertical_centered_justified(|ui| {
+                        if ui.button("Reset").clicked() {
+                            context.transformations.reflection.reset();
+                        }
+                    });
+                });
+            });
+        });
+    }
 }
@@ -1,10 +1,12 @@
 use crate::context::Context;
 use crate::ui::components::canvas::CanvasComponent;
 use crate::ui::components::settings::SettingsComponent;
+use crate::ui::components::timeline::TimelineComponent;
 
 pub struct Workspace {
     pub canvas: CanvasComponent,
     pub settings: SettingsComponent,
+    pub timeline: TimelineComponent,
 }
 
 impl Workspace {
@@ -12,11 +14,13 @@ impl Workspace {
         Self {
             canvas: CanvasComponent,
             settings: SettingsComponent::default(),
+            timeline: TimelineComponent,
         }
     }
 
     pub fn show(&mut self, ui: &mut egui::Ui, context: &mut Context) {
         self.settings.show(ui, context);
+        self.timeline.show(ui, context);
         self.canvas.show(ui, context);
     }
 }
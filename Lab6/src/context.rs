@@ -1,13 +1,26 @@
 use crate::ui::modals::error::ErrorModal;
 use crate::utils::channel::Channel;
-use geometry::animations::cone::AnimationCone;
+use geometry::animations::export::AnimationExport;
+use geometry::animations::keyframes::KeyframeAnimation;
+use geometry::animations::orientation::AnimationOrientation;
+use geometry::animations::surface::AnimationSurface;
+use geometry::animations::timeline::Timeline;
+use geometry::animations::trail::Trail;
+use geometry::figures::grid::{Grid2D, Grid2DBuilder};
 use geometry::figures::grid3d::Grid3D;
+use geometry::figures::lathe::Lathe;
+use geometry::figures::patch::Patch;
 use geometry::figures::surface::Surface;
 use geometry::figures::texture::Texture;
 use geometry::pipeline::Pipeline3D;
 use geometry::projections::twopoint::TwoPointPerspective;
+use geometry::transformations::affine::reflection3d::AffinePlaneReflection3D;
+use geometry::transformations::affine::scaling3d::AffineScaling3D;
+use geometry::transformations::affine::shear3d::AffineShear3D;
+use geometry::transformations::euclidean::arcball::Arcball;
 use geometry::transformations::euclidean::offset3d::EuclideanOffset3D;
 use geometry::transformations::euclidean::rotation3d::EuclideanRotation3D;
+use geometry::units::Centimeter;
 use geometry::viewport::{Viewport, ViewportGeometry, ViewportState, ZeroPointLocation};
 
 #[derive(Debug)]
@@ -53,11 +66,34 @@ impl Context {
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct FiguresState {
     pub grid: Grid3D,
     pub surface: Surface,
-    pub texture: Texture,
+    pub textures: Vec<Texture>,
+    pub active_texture: usize,
+
+    pub lathe: Lathe,
+    // Used by the 2D profile editor of the lathe
+    pub profile_grid: Grid2D,
+
+    pub patch: Patch,
+}
+
+impl Default for FiguresState {
+    fn default() -> Self {
+        Self {
+            grid: Grid3D::default(),
+            surface: Surface::default(),
+            textures: vec![Texture::default()],
+            active_texture: 0,
+
+            lathe: Lathe::default(),
+            profile_grid: Grid2DBuilder::default().with_unit(Centimeter(1.0)).build(),
+
+            patch: Patch::default(),
+        }
+    }
 }
 
 #[derive(Debug, Default)]
@@ -69,11 +105,20 @@ pub struct Pipelines {
 pub struct TransformContext {
     pub offset: EuclideanOffset3D,
     pub rotation: EuclideanRotation3D,
+    pub scaling: AffineScaling3D,
+    pub shear: AffineShear3D,
+    pub reflection: AffinePlaneReflection3D,
+    pub arcball: Arcball,
 }
 
 #[derive(Debug, Default)]
 pub struct AnimationsContext {
-    pub cone: AnimationCone,
+    pub timeline: Timeline,
+    pub export: AnimationExport,
+    pub surface: AnimationSurface,
+    pub keyframes: KeyframeAnimation,
+    pub orientation: AnimationOrientation,
+    pub trail: Trail,
 }
 
 #[derive(Debug, Default)]
@@ -1,7 +1,10 @@
 use crate::config::ConfigError;
 use crate::io::IoError;
 use crate::logs::LogError;
+use crate::scene::SceneError;
 use crate::ui::GraphicsBackendError;
+use geometry::animations::export::ExportError;
+use geometry::math::expression::ExpressionError;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -9,6 +12,12 @@ pub enum ProjectError {
     #[error("Configuration. {0}")]
     Config(#[from] ConfigError),
 
+    #[error("Formula. {0}")]
+    Formula(#[from] ExpressionError),
+
+    #[error("Export. {0}")]
+    Export(#[from] ExportError),
+
     #[error("Graphics Backend. {0}")]
     GraphicsBackend(#[from] GraphicsBackendError),
 
@@ -17,4 +26,7 @@ pub enum ProjectError {
 
     #[error("Logger. {0}")]
     Log(#[from] LogError),
+
+    #[error("Scene. {0}")]
+    Scene(#[from] SceneError),
 }
@@ -31,5 +31,6 @@ pub mod context;
 pub mod errors;
 pub mod io;
 pub mod logs;
+pub mod scene;
 pub mod ui;
 pub mod utils;
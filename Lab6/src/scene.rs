@@ -0,0 +1,136 @@
+use crate::errors::ProjectError;
+use geometry::primitives::line2d::Line2D;
+use geometry::primitives::point2d::Point2D;
+use geometry::textures::svg::{self, SvgError};
+use geometry::textures::{ImportedTexture, polyline};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use thiserror::Error;
+
+/// Line art stored as TOML: a list of polylines in centimeters.
+///
+/// ```toml
+/// name = "Triangle"
+///
+/// [[polylines]]
+/// points = [[0.0, 0.0], [4.0, 0.0], [2.0, 3.0], [0.0, 0.0]]
+/// ```
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Scene {
+    #[serde(default)]
+    pub name: String,
+    pub polylines: Vec<ScenePolyline>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ScenePolyline {
+    pub points: Vec<[f64; 2]>,
+}
+
+impl Scene {
+    pub const EXTENSION: &str = "toml";
+
+    pub fn from_lines(name: &str, lines: &[Line2D<Point2D>]) -> Self {
+        let mut polylines: Vec<ScenePolyline> = vec![];
+
+        for line in lines {
+            let start = [line.start.x.value(), line.start.y.value()];
+            let end = [line.end.x.value(), line.end.y.value()];
+
+            // Consecutive connected segments are stored as one polyline
+            match polylines.last_mut() {
+                Some(last) if last.points.last() == Some(&start) => last.points.push(end),
+                _ => polylines.push(ScenePolyline {
+                    points: vec![start, end],
+                }),
+            }
+        }
+
+        Self {
+            name: name.to_string(),
+            polylines,
+        }
+    }
+
+    pub fn to_texture(&self, fallback_name: &str) -> Result<ImportedTexture, SceneError> {
+        let lines: Vec<Line2D<Point2D>> = self
+            .polylines
+            .iter()
+            .flat_map(|polyline_data| {
+                let points: Vec<Point2D> = polyline_data
+                    .points
+                    .iter()
+                    .map(|[x, y]| Point2D::new(*x, *y))
+                    .collect();
+                polyline(&points)
+            })
+            .collect();
+
+        if lines.is_empty() {
+            return Err(SceneError::Empty);
+        }
+
+        let name = if self.name.is_empty() {
+            fallback_name
+        } else {
+            &self.name
+        };
+
+        Ok(ImportedTexture {
+            name: name.to_string(),
+            lines,
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<ImportedTexture, ProjectError> {
+        let text = std::fs::read_to_string(path).map_err(SceneError::Read)?;
+        let scene: Scene = toml::from_str(&text).map_err(SceneError::Deserialization)?;
+
+        Ok(scene.to_texture(&file_name(path))?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), ProjectError> {
+        let data = toml::to_string(self).map_err(SceneError::Serialization)?;
+        std::fs::write(path, data).map_err(SceneError::Write)?;
+
+        Ok(())
+    }
+}
+
+/// Imports the paths of an SVG file as line art.
+pub fn import_svg(path: &Path) -> Result<ImportedTexture, ProjectError> {
+    let text = std::fs::read_to_string(path).map_err(SceneError::Read)?;
+    let lines = svg::parse_document(&text).map_err(SceneError::Svg)?;
+
+    Ok(ImportedTexture {
+        name: file_name(path),
+        lines,
+    })
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "Imported".to_string())
+}
+
+#[derive(Debug, Error)]
+pub enum SceneError {
+    #[error("Failed to read file. {0}")]
+    Read(std::io::Error),
+
+    #[error("Failed to write to file. {0}")]
+    Write(std::io::Error),
+
+    #[error("Failed to serialize. {0}")]
+    Serialization(#[from] toml::ser::Error),
+
+    #[error("Failed to deserialize. {0}")]
+    Deserialization(#[from] toml::de::Error),
+
+    #[error("SVG. {0}")]
+    Svg(#[from] SvgError),
+
+    #[error("Scene contains no lines.")]
+    Empty,
+}
@@ -1,9 +1,21 @@
 use crate::context::Context;
 use egui::{CentralPanel, Color32, Frame, Painter, Response, Sense, Shape};
+use geometry::animations::Animation;
+use geometry::pipeline::Pipeline3D;
 use geometry::primitives::line2d::Line2D;
 use geometry::primitives::line3d::Line3D;
 use geometry::primitives::point2d::Point2D;
-use geometry::primitives::point3d::Point3D;
+use geometry::primitives::point3d::{Point3D, Pointable3D};
+use geometry::projections::twopoint::TwoPointPerspective;
+use geometry::transformations::affine::reflection3d::AffinePlaneReflection3D;
+use geometry::transformations::affine::scaling3d::AffineScaling3D;
+use geometry::transformations::affine::shear3d::AffineShear3D;
+use geometry::transformations::euclidean::rotation3d::Rotation3DOperation;
+use geometry::units::Centimeter;
+use geometry::viewport::Viewport;
+
+/// Side of the square the mirror plane is drawn as.
+const MIRROR_SIDE: f64 = 20.0;
 
 #[derive(Debug, Default)]
 pub struct CanvasComponent;
@@ -16,17 +28,37 @@ impl CanvasComponent {
                 .show(ui, |ui| {
                     ui.input(|i| context.viewport.handle_scroll(i));
                     let response = Self::pipeline(ui, context);
-                    context.viewport.handle_pan(ui, response);
+                    let lathe = &context.figures.lathe;
+                    let is_editing_profile =
+                        lathe.is_enabled && lathe.is_profile_editor_open;
+                    if is_editing_profile
+                        || !context.transformations.arcball.handle(ui, &response)
+                    {
+                        context.viewport.handle_pan(ui, response);
+                    }
                 });
         });
     }
 
     fn pipeline(ui: &mut egui::Ui, context: &mut Context) -> Response {
         let shapes = Self::create_shapes(ui, context);
+        let animations = &mut context.animations;
+        animations.export.capture(
+            ui.ctx(),
+            &shapes,
+            &[],
+            &context.viewport,
+            &mut animations.timeline,
+        );
         Self::draw(ui, context, shapes)
     }
 
     fn create_shapes(ui: &mut egui::Ui, context: &mut Context) -> Vec<Shape> {
+        let lathe = &context.figures.lathe;
+        if lathe.is_enabled && lathe.is_profile_editor_open {
+            return Self::profile_editor_shapes(context);
+        }
+
         let mut lines = vec![];
 
         let grid: Vec<Line2D<Point2D>> = context
@@ -38,18 +70,47 @@ impl CanvasComponent {
             .collect();
 
         // Animation
-        let cone = &mut context.figures.surface;
-        context.animations.cone.run(ui, cone);
-
-        let surface: Vec<Line3D<Point3D>> = context.figures.surface.lines();
-        let mut pivot = context.figures.surface.pivot_point();
-
-        let texture: Vec<Line3D<Point3D>> = context
-            .figures
+        let animations = &mut context.animations;
+        animations.export.prepare(&mut animations.timeline);
+        animations
+            .timeline
+            .tick(ui.input(|i| f64::from(i.stable_dt)));
+        let mut is_animating = animations
+            .keyframes
+            .update(&animations.timeline, &mut context.figures.surface);
+        is_animating |= animations
             .surface
-            .handle_texture(&context.figures.texture);
+            .update(&animations.timeline, &mut context.figures.surface);
+        is_animating |= animations.orientation.update(
+            &animations.timeline,
+            &mut context.transformations.arcball.orientation,
+        );
+        if animations.timeline.needs_repaint(is_animating) {
+            ui.ctx().request_repaint();
+        }
 
-        let mut lines3d = [surface, texture].concat();
+        let (mut lines3d, mut pivot) = if context.figures.patch.is_enabled {
+            let patch = &context.figures.patch;
+            (
+                [patch.lines(), patch.net_lines()].concat(),
+                patch.pivot_point(),
+            )
+        } else if context.figures.lathe.is_enabled {
+            let lathe = &context.figures.lathe;
+            (lathe.lines(), lathe.pivot_point())
+        } else {
+            let surface: Vec<Line3D<Point3D>> = context.figures.surface.lines();
+            let normals: Vec<Line3D<Point3D>> = context.figures.surface.normals();
+            let texture: Vec<Line3D<Point3D>> = context
+                .figures
+                .surface
+                .handle_texture(&context.figures.textures);
+
+            (
+                [surface, normals, texture].concat(),
+                context.figures.surface.pivot_point(),
+            )
+        };
 
         context
             .transformations
@@ -59,28 +120,169 @@ impl CanvasComponent {
             .transformations
             .rotation
             .handle(vec![&mut context.pipelines.surface]);
+        context
+            .transformations
+            .scaling
+            .handle(vec![&mut context.pipelines.surface]);
+        context
+            .transformations
+            .shear
+            .handle(vec![&mut context.pipelines.surface]);
+        context
+            .transformations
+            .reflection
+            .handle(vec![&mut context.pipelines.surface]);
 
         context.pipelines.surface.do_tasks(&mut lines3d, &mut pivot);
 
+        // Orientation from the arcball
+        let arcball = context.transformations.arcball.operation();
+        for line in &mut lines3d {
+            arcball.go(&mut line.start, &mut pivot);
+            arcball.go(&mut line.end, &mut pivot);
+        }
+
         // Conversion to shapes
         let lines3d: Vec<Line2D<Point2D>> = lines3d
             .iter()
             .map(|line| line.to_2d(&context.projections.twopoint))
             .collect();
 
+        let animations = &mut context.animations;
+        animations
+            .trail
+            .record(animations.timeline.time(), is_animating, &lines3d, &[]);
+
+        let grid_count = grid.len();
         lines.extend(grid);
         lines.extend(lines3d);
 
-        lines
+        let mut shapes = lines
             .iter()
             .map(|line| line.to_pixels(&context.viewport).to_shape())
-            .collect::<Vec<Shape>>()
+            .collect::<Vec<Shape>>();
+
+        // Trail between the grid and the surface
+        let trail = context.animations.trail.shapes(&context.viewport);
+        shapes.splice(grid_count..grid_count, trail);
+
+        shapes.extend(Self::leading_shapes(context, pivot));
+
+        if context.figures.patch.is_enabled {
+            let project = Self::patch_projector(
+                context.figures.patch.pivot_point(),
+                &context.pipelines.surface,
+                context.transformations.arcball.operation(),
+                &context.projections.twopoint,
+            );
+            shapes.extend(
+                context
+                    .figures
+                    .patch
+                    .handle_shapes(&context.viewport, project),
+            );
+        }
+
+        shapes
+    }
+
+    /// Maps a point of the patch control net to the canvas the same way as the
+    /// patch itself: through the surface pipeline, the arcball and the projection.
+    fn patch_projector<'a>(
+        pivot: Point3D, pipeline: &'a Pipeline3D, arcball: Rotation3DOperation,
+        projection: &'a TwoPointPerspective,
+    ) -> impl Fn(&Point3D) -> Point2D + 'a {
+        move |point: &Point3D| {
+            let mut points = [*point];
+            let mut pivot = pivot;
+            pipeline.do_tasks_points(&mut points, &mut pivot);
+            arcball.go(&mut points[0], &mut pivot);
+            points[0].to_2d(projection)
+        }
+    }
+
+    fn profile_editor_shapes(context: &mut Context) -> Vec<Shape> {
+        let mut shapes: Vec<Shape> = context
+            .figures
+            .profile_grid
+            .lines(&context.viewport)
+            .iter()
+            .map(|line| line.to_pixels(&context.viewport).to_shape())
+            .collect();
+
+        shapes.extend(context.figures.lathe.profile_shapes(&context.viewport));
+
+        shapes
+    }
+
+    /// Pivot of the affine maps being edited and the mirror plane, turned by the
+    /// arcball like the figure.
+    fn leading_shapes(context: &Context, pivot: Point3D) -> Vec<Shape> {
+        let transformations = &context.transformations;
+        let projection = &context.projections.twopoint;
+        let arcball = transformations.arcball.operation();
+        let mut origin = pivot;
+
+        let mut mirror = transformations
+            .reflection
+            .mirror(pivot, Centimeter(MIRROR_SIDE));
+        for line in &mut mirror {
+            arcball.go(&mut line.start, &mut origin);
+            arcball.go(&mut line.end, &mut origin);
+        }
+        let mut shapes = mirror
+            .iter()
+            .map(|line| {
+                line.to_2d(projection)
+                    .to_pixels(&context.viewport)
+                    .to_shape()
+            })
+            .collect::<Vec<Shape>>();
+
+        let mut dot = |point: Option<Point3D>, shape: fn(Point2D, &Viewport) -> Shape| {
+            if let Some(mut point) = point {
+                arcball.go(&mut point, &mut origin);
+                shapes.push(shape(point.to_2d(projection), &context.viewport));
+            }
+        };
+        dot(
+            transformations.scaling.leading_point(pivot),
+            AffineScaling3D::leading_shape,
+        );
+        dot(
+            transformations.shear.leading_point(pivot),
+            AffineShear3D::leading_shape,
+        );
+        dot(
+            transformations.reflection.leading_point(pivot),
+            AffinePlaneReflection3D::leading_shape,
+        );
+
+        shapes
     }
 
     fn draw(ui: &mut egui::Ui, context: &mut Context, shapes: Vec<Shape>) -> Response {
         let (response, painter) = Self::initialize_painter(ui, context);
         painter.extend(shapes);
 
+        let lathe = &mut context.figures.lathe;
+        if lathe.is_enabled && lathe.is_profile_editor_open {
+            lathe.update_profile(ui, &response, &context.viewport);
+        }
+
+        if context.figures.patch.is_enabled {
+            let project = Self::patch_projector(
+                context.figures.patch.pivot_point(),
+                &context.pipelines.surface,
+                context.transformations.arcball.operation(),
+                &context.projections.twopoint,
+            );
+            context
+                .figures
+                .patch
+                .update_net(ui, &response, &context.viewport, project);
+        }
+
         response
     }
 
@@ -1,6 +1,16 @@
 use crate::context::Context;
-use egui::{Color32, DragValue, Grid, RichText, ScrollArea, SidePanel};
+use crate::scene::{self, Scene};
+use crate::ui::modals::error::ErrorModal;
+use egui::{Color32, ComboBox, DragValue, Grid, RichText, ScrollArea, SidePanel, Slider};
+use geometry::figures::lathe::{ProfileSource, RevolutionAxis};
+use geometry::figures::patch::PatchKind;
+use geometry::figures::surface::TextureAddressing;
+use geometry::figures::texture::Texture;
+use geometry::primitives::point3d::DragPlane;
+use geometry::surfaces::SurfaceKind;
+use geometry::textures::TextureSource;
 use geometry::units::Percent;
+use strum::IntoEnumIterator;
 
 #[derive(Debug)]
 pub struct SettingsComponent {
@@ -99,13 +109,49 @@ impl SettingsComponent {
                     ui.separator();
                     ui.add_space(10.0);
 
+                    self.lathe(ui, context);
+
+                    ui.add_space(10.0);
+                    ui.separator();
+                    ui.add_space(10.0);
+
+                    self.patch(ui, context);
+
+                    ui.add_space(10.0);
+                    ui.separator();
+                    ui.add_space(10.0);
+
                     self.animation(ui, context);
 
                     ui.add_space(10.0);
                     ui.separator();
                     ui.add_space(10.0);
 
+                    self.keyframes(ui, context);
+
+                    ui.add_space(10.0);
+                    ui.separator();
+                    ui.add_space(10.0);
+
+                    self.orientation(ui, context);
+
+                    ui.add_space(10.0);
+                    ui.separator();
+                    ui.add_space(10.0);
+
+                    self.trail(ui, context);
+
+                    ui.add_space(10.0);
+                    ui.separator();
+                    ui.add_space(10.0);
+
                     self.euclidean(ui, context);
+
+                    ui.add_space(10.0);
+                    ui.separator();
+                    ui.add_space(10.0);
+
+                    self.affine(ui, context);
                 });
             });
     }
@@ -115,33 +161,109 @@ impl SettingsComponent {
 
         ui.add_space(5.0);
 
+        let surface = &mut context.figures.surface;
+
+        ui.horizontal(|ui| {
+            ui.label("Surface:");
+            ComboBox::from_id_salt("SURFACE_KIND")
+                .selected_text(surface.kind.name())
+                .show_ui(ui, |ui| {
+                    for kind in SurfaceKind::catalog() {
+                        let is_selected = surface.kind.name() == kind.name();
+                        if ui.selectable_label(is_selected, kind.name()).clicked()
+                            && !is_selected
+                        {
+                            surface.kind = kind;
+                            context.animations.surface.parameter = 0;
+                        }
+                    }
+                });
+        });
+
+        ui.add_space(5.0);
+
         Grid::new("Surface Settings").num_columns(2).show(ui, |ui| {
-            ui.label("Height:");
+            for parameter in surface.kind.surface_mut().parameters() {
+                ui.label(format!("{}:", parameter.name));
+                ui.add(
+                    DragValue::new(parameter.value)
+                        .speed(0.1)
+                        .range(parameter.range)
+                        .fixed_decimals(2),
+                );
+                ui.end_row();
+            }
+
+            ui.label("Mesh Density:");
             ui.add(
-                DragValue::new(&mut context.figures.surface.height.0)
-                    .speed(0.1)
-                    .range(1.0..=f64::INFINITY)
-                    .fixed_decimals(2),
+                DragValue::new(&mut surface.mesh)
+                    .speed(1)
+                    .range(10.0..=f64::INFINITY)
+                    .fixed_decimals(0),
             );
             ui.end_row();
 
-            ui.label("Radius:");
+            ui.checkbox(&mut surface.is_normals_enabled, "Normals:");
             ui.add(
-                DragValue::new(&mut context.figures.surface.radius_base.0)
-                    .speed(0.1)
-                    .range(1.0..=f64::INFINITY)
+                DragValue::new(&mut surface.normals_length.0)
+                    .speed(0.05)
+                    .range(0.1..=10.0)
                     .fixed_decimals(2),
             );
             ui.end_row();
+        });
 
-            ui.label("Mesh Density:");
-            ui.add(
-                DragValue::new(&mut context.figures.surface.mesh)
-                    .speed(1)
-                    .range(10.0..=f64::INFINITY)
-                    .fixed_decimals(0),
-            );
+        ui.add_space(5.0);
+
+        let SurfaceKind::Formula(formula) = &mut surface.kind else {
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Edit as Formula").clicked() {
+                    surface.kind.convert_to_formula();
+                }
+            });
+            return;
+        };
+
+        Grid::new("SURFACE_FORMULA").num_columns(2).show(ui, |ui| {
+            let labels = ["x(u, v):", "y(u, v):", "z(u, v):"];
+            for (label, input) in labels.iter().zip(formula.inputs.iter_mut()) {
+                ui.label(*label);
+                ui.text_edit_singleline(input);
+                ui.end_row();
+            }
+
+            ui.label("U Range:");
+            ui.horizontal(|ui| {
+                ui.add(DragValue::new(&mut formula.u_min).speed(0.1));
+                ui.add(DragValue::new(&mut formula.u_max).speed(0.1));
+            });
             ui.end_row();
+
+            ui.label("V Range:");
+            ui.horizontal(|ui| {
+                ui.add(DragValue::new(&mut formula.v_min).speed(0.1));
+                ui.add(DragValue::new(&mut formula.v_max).speed(0.1));
+            });
+            ui.end_row();
+
+            ui.label("Periodic:");
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut formula.is_u_periodic, "U");
+                ui.checkbox(&mut formula.is_v_periodic, "V");
+            });
+            ui.end_row();
+        });
+
+        ui.label(RichText::new("Variables: u, v, a, b").color(Color32::GRAY));
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Apply Formulas").clicked()
+                && let Err(error) = formula.compile()
+            {
+                context
+                    .errors_channel
+                    .try_send(ErrorModal::new(error.into()));
+            }
         });
     }
 
@@ -150,50 +272,452 @@ impl SettingsComponent {
 
         ui.add_space(5.0);
 
+        let surface = &mut context.figures.surface;
+
         Grid::new("Texture Settings").num_columns(2).show(ui, |ui| {
             ui.label("Texture Enabled:");
-            ui.checkbox(&mut context.figures.surface.is_texture_enabled, "");
+            ui.checkbox(&mut surface.is_texture_enabled, "");
             ui.end_row();
 
-            ui.label("Width Scale (U):");
-            ui.add(
-                DragValue::new(&mut context.figures.surface.texture_scale_width.0)
-                    .speed(0.01)
-                    .range(Percent::RANGE),
-            );
+            ui.label("Addressing (U):");
+            ComboBox::from_id_salt("TEXTURE_ADDRESSING")
+                .selected_text(surface.texture_addressing.name())
+                .show_ui(ui, |ui| {
+                    for addressing in TextureAddressing::iter() {
+                        ui.selectable_value(
+                            &mut surface.texture_addressing,
+                            addressing,
+                            addressing.name(),
+                        );
+                    }
+                });
             ui.end_row();
 
-            ui.label("Position Angle (U):");
+            ui.label("Max Segment (UV):");
             ui.add(
-                DragValue::new(&mut context.figures.surface.texture_offset_angle.0)
-                    .speed(0.01)
-                    .range(Percent::RANGE),
+                DragValue::new(&mut surface.texture_max_segment)
+                    .speed(0.001)
+                    .range(0.001..=1.0)
+                    .fixed_decimals(3),
             );
             ui.end_row();
+        });
 
-            ui.label("Height Scale (V):");
-            ui.add(
-                DragValue::new(&mut context.figures.surface.texture_scale_height.0)
-                    .speed(0.01)
-                    .range(Percent::RANGE),
-            );
+        ui.add_space(5.0);
+
+        let figures = &mut context.figures;
+        let layer_name = |index: usize, texture: &Texture| {
+            format!("Layer {}: {}", index + 1, texture.source.name())
+        };
+
+        ui.horizontal(|ui| {
+            ui.label("Layer:");
+            let selected = figures
+                .textures
+                .get(figures.active_texture)
+                .map(|texture| layer_name(figures.active_texture, texture))
+                .unwrap_or_default();
+
+            ComboBox::from_id_salt("TEXTURE_LAYER")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    for (index, texture) in figures.textures.iter().enumerate() {
+                        ui.selectable_value(
+                            &mut figures.active_texture,
+                            index,
+                            layer_name(index, texture),
+                        );
+                    }
+                });
+        });
+
+        ui.columns(2, |ui| {
+            ui[0].vertical_centered_justified(|ui| {
+                if ui.button("Add Layer").clicked() {
+                    figures.textures.push(Texture::default());
+                    figures.active_texture = figures.textures.len() - 1;
+                }
+            });
+            ui[1].vertical_centered_justified(|ui| {
+                let can_remove = figures.textures.len() > 1;
+                if ui
+                    .add_enabled(can_remove, egui::Button::new("Remove Layer"))
+                    .clicked()
+                {
+                    figures.textures.remove(figures.active_texture);
+                    figures.active_texture =
+                        figures.active_texture.min(figures.textures.len() - 1);
+                }
+            });
+        });
+
+        let Some(texture) = figures.textures.get_mut(figures.active_texture) else {
+            return;
+        };
+
+        ui.add_space(5.0);
+
+        Grid::new("TEXTURE_LAYER_SETTINGS")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("Layer Enabled:");
+                ui.checkbox(&mut texture.is_enabled, "");
+                ui.end_row();
+
+                ui.label("Source:");
+                ComboBox::from_id_salt("TEXTURE_SOURCE")
+                    .selected_text(texture.source.name().to_string())
+                    .show_ui(ui, |ui| {
+                        for source in TextureSource::catalog() {
+                            let is_selected = texture.source.name() == source.name();
+                            if ui.selectable_label(is_selected, source.name()).clicked()
+                                && !is_selected
+                            {
+                                texture.source = source;
+                            }
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("Color:");
+                ui.color_edit_button_srgba(&mut texture.style.stroke.color);
+                ui.end_row();
+
+                self.texture_source(ui, &mut texture.source);
+
+                ui.label("Width Scale (U):");
+                ui.add(
+                    DragValue::new(&mut texture.scale_width.0)
+                        .speed(0.01)
+                        .range(0.01..=10.0),
+                );
+                ui.end_row();
+
+                ui.label("Position Angle (U):");
+                ui.add(
+                    DragValue::new(&mut texture.offset_angle.0)
+                        .speed(0.01)
+                        .range(Percent::RANGE),
+                );
+                ui.end_row();
+
+                ui.label("Height Scale (V):");
+                ui.add(
+                    DragValue::new(&mut texture.scale_height.0)
+                        .speed(0.01)
+                        .range(0.01..=10.0),
+                );
+                ui.end_row();
+
+                ui.label("Position Height (V):");
+                ui.add(
+                    DragValue::new(&mut texture.offset_height.0)
+                        .speed(0.01)
+                        .range(Percent::RANGE),
+                );
+                ui.end_row();
+
+                ui.label("Rotation (Pivot Center):");
+                ui.add(
+                    DragValue::new(&mut texture.rotation_angle)
+                        .speed(1.0)
+                        .suffix(" °"),
+                );
+                ui.end_row();
+            });
+
+        ui.add_space(5.0);
+
+        let mut result = Ok(());
+        ui.columns(2, |ui| {
+            ui[0].vertical_centered_justified(|ui| {
+                if ui.button("Import SVG…").clicked()
+                    && let Some(path) = rfd::FileDialog::new()
+                        .add_filter("SVG", &["svg"])
+                        .pick_file()
+                {
+                    result = scene::import_svg(&path).map(|imported| {
+                        texture.source = TextureSource::Imported(imported);
+                    });
+                }
+            });
+            ui[1].vertical_centered_justified(|ui| {
+                if ui.button("Import Scene…").clicked()
+                    && let Some(path) = rfd::FileDialog::new()
+                        .add_filter("Scene", &[Scene::EXTENSION])
+                        .pick_file()
+                {
+                    result = Scene::load(&path).map(|imported| {
+                        texture.source = TextureSource::Imported(imported);
+                    });
+                }
+            });
+        });
+
+        ui.columns(2, |ui| {
+            ui[0].vertical_centered_justified(|ui| {
+                if ui.button("Export Scene…").clicked()
+                    && let Some(path) = rfd::FileDialog::new()
+                        .add_filter("Scene", &[Scene::EXTENSION])
+                        .save_file()
+                {
+                    let scene =
+                        Scene::from_lines(texture.source.name(), &texture.lines());
+                    result = scene.save(&path);
+                }
+            });
+            ui[1].vertical_centered_justified(|ui| {
+                if ui.button("Reset Placement").clicked() {
+                    texture.reset_placement();
+                }
+            });
+        });
+
+        if let Err(error) = result {
+            context.errors_channel.try_send(ErrorModal::new(error));
+        }
+    }
+
+    /// Parameters of procedural textures, as rows of the layer settings grid.
+    fn texture_source(&self, ui: &mut egui::Ui, source: &mut TextureSource) {
+        match source {
+            TextureSource::Shark | TextureSource::Imported(_) => {},
+            TextureSource::Checkerboard(checkerboard) => {
+                ui.label("Cells:");
+                ui.add(DragValue::new(&mut checkerboard.cells).range(1..=64));
+                ui.end_row();
+
+                ui.label("Hatching:");
+                ui.add(DragValue::new(&mut checkerboard.hatching).range(0..=32));
+                ui.end_row();
+            },
+            TextureSource::Stripes(stripes) => {
+                ui.label("Count:");
+                ui.add(DragValue::new(&mut stripes.count).range(1..=128));
+                ui.end_row();
+
+                ui.label("Angle:");
+                ui.add(DragValue::new(&mut stripes.angle).speed(1.0).suffix(" °"));
+                ui.end_row();
+            },
+            TextureSource::Spiral(spiral) => {
+                ui.label("Turns:");
+                ui.add(
+                    DragValue::new(&mut spiral.turns)
+                        .speed(0.1)
+                        .range(0.1..=50.0),
+                );
+                ui.end_row();
+
+                ui.label("Arms:");
+                ui.add(DragValue::new(&mut spiral.arms).range(1..=12));
+                ui.end_row();
+            },
+            TextureSource::Text(text) => {
+                ui.label("Text:");
+                ui.add(
+                    egui::TextEdit::multiline(&mut text.text)
+                        .desired_rows(2)
+                        .desired_width(120.0),
+                );
+                ui.end_row();
+            },
+        }
+    }
+
+    fn lathe(&self, ui: &mut egui::Ui, context: &mut Context) {
+        ui.label(RichText::new("Lathe (Surface of Revolution)").color(Color32::WHITE));
+
+        ui.add_space(5.0);
+
+        let lathe = &mut context.figures.lathe;
+
+        Grid::new("LATHE_SETTINGS").num_columns(2).show(ui, |ui| {
+            ui.label("Lathe Enabled:");
+            if ui.checkbox(&mut lathe.is_enabled, "").changed() && lathe.is_enabled {
+                context.figures.patch.is_enabled = false;
+            }
             ui.end_row();
 
-            ui.label("Position Height (V):");
+            ui.label("Profile:");
+            ComboBox::from_id_salt("LATHE_PROFILE")
+                .selected_text(lathe.source.name())
+                .show_ui(ui, |ui| {
+                    for source in ProfileSource::iter() {
+                        ui.selectable_value(&mut lathe.source, source, source.name());
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Axis:");
+            ComboBox::from_id_salt("LATHE_AXIS")
+                .selected_text(lathe.axis.name())
+                .show_ui(ui, |ui| {
+                    for axis in RevolutionAxis::iter() {
+                        ui.selectable_value(&mut lathe.axis, axis, axis.name());
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Angle From:");
             ui.add(
-                DragValue::new(&mut context.figures.surface.texture_offset_height.0)
-                    .speed(0.01)
-                    .range(Percent::RANGE),
+                DragValue::new(&mut lathe.angle_start)
+                    .speed(1.0)
+                    .range(-360.0..=360.0)
+                    .suffix(" °"),
             );
             ui.end_row();
 
-            ui.label("Rotation (Pivot Center):");
+            ui.label("Angle To:");
             ui.add(
-                DragValue::new(&mut context.figures.surface.texture_rotation_angle)
+                DragValue::new(&mut lathe.angle_end)
                     .speed(1.0)
+                    .range(-360.0..=360.0)
                     .suffix(" °"),
             );
             ui.end_row();
+
+            ui.label("Segments:");
+            ui.add(DragValue::new(&mut lathe.segments).speed(1).range(3..=360));
+            ui.end_row();
+        });
+
+        ui.add_space(5.0);
+
+        match lathe.source {
+            ProfileSource::Curve => {
+                Grid::new("LATHE_CURVE").num_columns(2).show(ui, |ui| {
+                    ui.label("Curve Step:");
+                    ui.add(
+                        DragValue::new(&mut lathe.curve.step)
+                            .speed(0.01)
+                            .range(0.01..=1.0)
+                            .fixed_decimals(2),
+                    );
+                    ui.end_row();
+                });
+            },
+            ProfileSource::Polyline => {
+                ui.horizontal(|ui| {
+                    ui.columns(2, |ui| {
+                        ui[0].vertical_centered_justified(|ui| {
+                            if ui.button("Add Point").clicked() {
+                                lathe.add_polyline_point();
+                            }
+                        });
+                        ui[1].vertical_centered_justified(|ui| {
+                            if ui.button("Remove Point").clicked() {
+                                lathe.remove_polyline_point();
+                            }
+                        });
+                    });
+                });
+            },
+            ProfileSource::Expression => {
+                let expression = &mut lathe.expression;
+
+                Grid::new("LATHE_EXPRESSION").num_columns(2).show(ui, |ui| {
+                    ui.label("r(t):");
+                    ui.text_edit_singleline(&mut expression.input);
+                    ui.end_row();
+
+                    ui.label("T Range:");
+                    ui.horizontal(|ui| {
+                        ui.add(DragValue::new(&mut expression.t_min).speed(0.1));
+                        ui.add(DragValue::new(&mut expression.t_max).speed(0.1));
+                    });
+                    ui.end_row();
+
+                    ui.label("Samples:");
+                    ui.add(
+                        DragValue::new(&mut expression.samples)
+                            .speed(1)
+                            .range(2..=500),
+                    );
+                    ui.end_row();
+                });
+
+                ui.vertical_centered_justified(|ui| {
+                    if ui.button("Apply Formula").clicked()
+                        && let Err(error) = expression.compile()
+                    {
+                        context
+                            .errors_channel
+                            .try_send(ErrorModal::new(error.into()));
+                    }
+                });
+            },
+        }
+
+        ui.vertical_centered_justified(|ui| {
+            let text = if lathe.is_profile_editor_open {
+                "Show 3D View"
+            } else {
+                "Edit Profile"
+            };
+
+            if ui
+                .add_enabled(lathe.is_enabled, egui::Button::new(text))
+                .clicked()
+            {
+                lathe.is_profile_editor_open = !lathe.is_profile_editor_open;
+            }
+        });
+    }
+
+    fn patch(&self, ui: &mut egui::Ui, context: &mut Context) {
+        ui.label(RichText::new("Surface Patch").color(Color32::WHITE));
+
+        ui.add_space(5.0);
+
+        let patch = &mut context.figures.patch;
+
+        Grid::new("PATCH_SETTINGS").num_columns(2).show(ui, |ui| {
+            ui.label("Patch Enabled:");
+            if ui.checkbox(&mut patch.is_enabled, "").changed() && patch.is_enabled {
+                context.figures.lathe.is_enabled = false;
+            }
+            ui.end_row();
+
+            ui.label("Kind:");
+            ComboBox::from_id_salt("PATCH_KIND")
+                .selected_text(patch.kind.name())
+                .show_ui(ui, |ui| {
+                    for kind in PatchKind::iter() {
+                        ui.selectable_value(&mut patch.kind, kind, kind.name());
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Iso-lines:");
+            ui.add(DragValue::new(&mut patch.iso_lines).speed(1).range(1..=50));
+            ui.end_row();
+
+            ui.label("Samples:");
+            ui.add(DragValue::new(&mut patch.samples).speed(1).range(2..=100));
+            ui.end_row();
+
+            ui.label("Control Net:");
+            ui.checkbox(&mut patch.is_net_visible, "");
+            ui.end_row();
+
+            ui.label("Drag Plane:");
+            ComboBox::from_id_salt("PATCH_DRAG_PLANE")
+                .selected_text(patch.drag_plane.name())
+                .show_ui(ui, |ui| {
+                    for plane in DragPlane::iter() {
+                        ui.selectable_value(&mut patch.drag_plane, plane, plane.name());
+                    }
+                });
+            ui.end_row();
+        });
+
+        ui.add_space(5.0);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Reset Control Net").clicked() {
+                patch.reset_net();
+            }
         });
     }
 
@@ -242,29 +766,152 @@ impl SettingsComponent {
 
             ui.add_space(5.0);
 
+            ui.horizontal(|ui| {
+                ui.label("Parameter:");
+
+                let animation = &mut context.animations.surface;
+                let parameters = context.figures.surface.kind.surface_mut().parameters();
+                let selected = parameters
+                    .get(animation.parameter)
+                    .map_or("None", |parameter| parameter.name);
+
+                ComboBox::from_id_salt("ANIMATED_PARAMETER")
+                    .selected_text(selected)
+                    .show_ui(ui, |ui| {
+                        for (index, parameter) in parameters.iter().enumerate() {
+                            ui.selectable_value(
+                                &mut animation.parameter,
+                                index,
+                                parameter.name,
+                            );
+                        }
+                    });
+            });
+
             ui.horizontal(|ui| {
                 ui.label("Status:");
-                if context.animations.cone.is_enabled {
+                if context.animations.surface.playback.is_enabled {
                     ui.colored_label(Color32::LIGHT_GREEN, "Running");
                 } else {
                     ui.colored_label(Color32::RED, "Stopped");
                 }
             });
 
+            widgets::playback::show(
+                ui,
+                &mut context.animations.surface.playback,
+                "SURFACE",
+            );
+
             ui.vertical_centered_justified(|ui| {
-                let text = if context.animations.cone.is_enabled {
+                let text = if context.animations.surface.playback.is_enabled {
                     "Stop"
                 } else {
                     "Start"
                 };
 
                 if ui.button(text).clicked() {
-                    context.animations.cone.toggle();
+                    context.animations.surface.toggle();
+                }
+            });
+        });
+    }
+
+    fn keyframes(&self, ui: &mut egui::Ui, context: &mut Context) {
+        ui.group(|ui| {
+            ui.vertical_centered_justified(|ui| {
+                ui.label(RichText::new("Keyframes").color(Color32::WHITE));
+            });
+
+            ui.add_space(5.0);
+
+            let animations = &mut context.animations;
+            widgets::keyframes::show(
+                ui,
+                &mut animations.keyframes,
+                &mut context.figures.surface,
+                &animations.timeline,
+            );
+        });
+    }
+
+    fn trail(&self, ui: &mut egui::Ui, context: &mut Context) {
+        ui.group(|ui| {
+            ui.vertical_centered_justified(|ui| {
+                ui.label(RichText::new("Trail").color(Color32::WHITE));
+            });
+
+            ui.add_space(5.0);
+
+            let trail = &mut context.animations.trail;
+            Grid::new("TRAIL").num_columns(2).show(ui, |ui| {
+                ui.checkbox(&mut trail.is_ghosts_enabled, "Ghosts:");
+                ui.add(
+                    DragValue::new(&mut trail.ghosts)
+                        .speed(0.2)
+                        .range(1..=60)
+                        .suffix(" frames"),
+                );
+                ui.end_row();
+
+                ui.label("Every:");
+                ui.add(
+                    DragValue::new(&mut trail.spacing)
+                        .speed(0.2)
+                        .range(1..=60)
+                        .suffix(" frames"),
+                );
+                ui.end_row();
+
+                ui.label("Fade:");
+                ui.add(Slider::new(&mut trail.opacity, 0.05..=1.0).fixed_decimals(2));
+                ui.end_row();
+
+                ui.checkbox(&mut trail.is_traces_enabled, "Traces:");
+                ui.add(
+                    DragValue::new(&mut trail.trace_length)
+                        .speed(1)
+                        .range(2..=5000)
+                        .suffix(" frames"),
+                );
+                ui.end_row();
+
+                ui.label("Trace Color:");
+                ui.color_edit_button_srgba(&mut trail.trace_stroke.color);
+                ui.end_row();
+            });
+
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Clear Trail").clicked() {
+                    trail.clear();
                 }
             });
         });
     }
 
+    fn orientation(&self, ui: &mut egui::Ui, context: &mut Context) {
+        ui.group(|ui| {
+            ui.vertical_centered_justified(|ui| {
+                ui.label(RichText::new("Orientation").color(Color32::WHITE));
+            });
+
+            ui.add_space(5.0);
+
+            let arcball = &mut context.transformations.arcball;
+            widgets::arcball::show(ui, arcball);
+
+            ui.add_space(5.0);
+
+            let animations = &mut context.animations;
+            widgets::orientation::show(
+                ui,
+                &mut animations.orientation,
+                &mut arcball.orientation,
+                &animations.timeline,
+            );
+        });
+    }
+
     fn euclidean(&self, ui: &mut egui::Ui, context: &mut Context) {
         ui.vertical_centered_justified(|ui| {
             ui.label(RichText::new("Euclidean Transformations").color(Color32::WHITE));
@@ -379,4 +1026,204 @@ impl SettingsComponent {
             });
         });
     }
+
+    fn affine(&self, ui: &mut egui::Ui, context: &mut Context) {
+        ui.vertical_centered_justified(|ui| {
+            ui.label(RichText::new("Affine Transformations").color(Color32::WHITE));
+            ui.label(RichText::new("(Surface)").color(Color32::GRAY));
+        });
+
+        ui.add_space(5.0);
+
+        ui.group(|ui| {
+            ui.vertical_centered(|ui| {
+                ui.label("Scaling about Pivot");
+            });
+
+            ui.add_space(5.0);
+
+            Grid::new("AFFINE_Scaling").num_columns(2).show(ui, |ui| {
+                ui.label("Mx:");
+                ui.add(
+                    DragValue::new(&mut context.transformations.scaling.mx)
+                        .speed(0.01)
+                        .fixed_decimals(2),
+                );
+                ui.end_row();
+
+                ui.label("My:");
+                ui.add(
+                    DragValue::new(&mut context.transformations.scaling.my)
+                        .speed(0.01)
+                        .fixed_decimals(2),
+                );
+                ui.end_row();
+
+                ui.label("Mz:");
+                ui.add(
+                    DragValue::new(&mut context.transformations.scaling.mz)
+                        .speed(0.01)
+                        .fixed_decimals(2),
+                );
+                ui.end_row();
+            });
+
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                ui.columns(2, |ui| {
+                    ui[0].vertical_centered_justified(|ui| {
+                        if ui.button("Apply").clicked() {
+                            context.transformations.scaling.run();
+                        }
+                    });
+                    ui[1].vertical_centered_justified(|ui| {
+                        if ui.button("Reset").clicked() {
+                            context.transformations.scaling.reset();
+                        }
+                    });
+                });
+            });
+        });
+
+        ui.add_space(10.0);
+
+        ui.group(|ui| {
+            ui.vertical_centered(|ui| {
+                ui.label("Shear");
+            });
+
+            ui.add_space(5.0);
+
+            Grid::new("AFFINE_Shear").num_columns(2).show(ui, |ui| {
+                ui.label("X by Y:");
+                ui.add(
+                    DragValue::new(&mut context.transformations.shear.xy)
+                        .speed(0.01)
+                        .fixed_decimals(2),
+                );
+                ui.end_row();
+
+                ui.label("X by Z:");
+                ui.add(
+                    DragValue::new(&mut context.transformations.shear.xz)
+                        .speed(0.01)
+                        .fixed_decimals(2),
+                );
+                ui.end_row();
+
+                ui.label("Y by X:");
+                ui.add(
+                    DragValue::new(&mut context.transformations.shear.yx)
+                        .speed(0.01)
+                        .fixed_decimals(2),
+                );
+                ui.end_row();
+
+                ui.label("Y by Z:");
+                ui.add(
+                    DragValue::new(&mut context.transformations.shear.yz)
+                        .speed(0.01)
+                        .fixed_decimals(2),
+                );
+                ui.end_row();
+
+                ui.label("Z by X:");
+                ui.add(
+                    DragValue::new(&mut context.transformations.shear.zx)
+                        .speed(0.01)
+                        .fixed_decimals(2),
+                );
+                ui.end_row();
+
+                ui.label("Z by Y:");
+                ui.add(
+                    DragValue::new(&mut context.transformations.shear.zy)
+                        .speed(0.01)
+                        .fixed_decimals(2),
+                );
+                ui.end_row();
+            });
+
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                ui.columns(2, |ui| {
+                    ui[0].vertical_centered_justified(|ui| {
+                        if ui.button("Apply").clicked() {
+                            context.transformations.shear.run();
+                        }
+                    });
+                    ui[1].vertical_centered_justified(|ui| {
+                        if ui.button("Reset").clicked() {
+                            context.transformations.shear.reset();
+                        }
+                    });
+                });
+            });
+        });
+
+        ui.add_space(10.0);
+
+        ui.group(|ui| {
+            ui.vertical_centered(|ui| {
+                ui.label("Plane Reflection");
+            });
+
+            ui.add_space(5.0);
+
+            Grid::new("AFFINE_Plane_Reflection")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label("Normal X:");
+                    ui.add(
+                        DragValue::new(&mut context.transformations.reflection.normal_x)
+                            .speed(0.01)
+                            .fixed_decimals(2),
+                    );
+                    ui.end_row();
+
+                    ui.label("Normal Y:");
+                    ui.add(
+                        DragValue::new(&mut context.transformations.reflection.normal_y)
+                            .speed(0.01)
+                            .fixed_decimals(2),
+                    );
+                    ui.end_row();
+
+                    ui.label("Normal Z:");
+                    ui.add(
+                        DragValue::new(&mut context.transformations.reflection.normal_z)
+                            .speed(0.01)
+                            .fixed_decimals(2),
+                    );
+                    ui.end_row();
+
+                    ui.label("Offset:");
+                    ui.add(
+                        DragValue::new(&mut context.transformations.reflection.offset.0)
+                            .speed(0.1)
+                            .fixed_decimals(2),
+                    );
+                    ui.end_row();
+                });
+
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                ui.columns(2, |ui| {
+                    ui[0].vertical_centered_justified(|ui| {
+                        if ui.button("Apply").clicked() {
+                            context.transformations.reflection.run();
+                        }
+                    });
+                    ui[1].vertical_centered_justified(|ui| {
+                        if ui.button("Reset").clicked() {
+                            context.transformations.reflection.reset();
+                        }
+                    });
+                });
+            });
+        });
+    }
 }
@@ -1,11 +1,42 @@
 use crate::ui::modals::error::ErrorModal;
 use crate::utils::channel::Channel;
-use egui::Shape;
+use crate::utils::worker::{Job, Worker};
+use egui::{Color32, ColorImage, Rect, Shape, Stroke, TextureHandle, TextureId};
+use geometry::animations::Animation;
+use geometry::animations::attractor::AnimationAttractor;
+use geometry::animations::export::AnimationExport;
+use geometry::animations::keyframes::KeyframeAnimation;
+use geometry::animations::timeline::Timeline;
+use geometry::figures::extrusion::fit;
 use geometry::figures::grid::{Grid2D, Grid2DBuilder};
-use geometry::fractals::FractalIFS;
-use geometry::fractals::zigzag::FractalZigZag;
+use geometry::fractals::ChaosGame;
+use geometry::fractals::attractors::Attractor2D;
+use geometry::fractals::custom::FractalCustom;
+use geometry::fractals::density::{DensityRenderer, Histogram, WARM_UP};
+use geometry::fractals::deterministic::DeterministicIFS;
+use geometry::fractals::dimension::{
+    DimensionEstimate, DimensionEstimator, similarity_dimension,
+};
+use geometry::fractals::editor::MapEditor;
+use geometry::fractals::escape::{EscapeKind, EscapeTime};
+use geometry::fractals::lsystem::presets::LSystemPreset;
+use geometry::fractals::lsystem::turtle::fit_3d;
+use geometry::fractals::lsystem::{LSystem, LSystemError};
+use geometry::fractals::presets::FractalPreset;
+use geometry::math::angle::Angle;
+use geometry::primitives::line2d::Line2D;
+use geometry::primitives::line3d::Line3D;
+use geometry::primitives::point2d::{Point2D, Point2DPixel};
+use geometry::primitives::point3d::{Point3D, Pointable3D};
+use geometry::projections::twopoint::TwoPointPerspective;
+use geometry::shapes::dot::DotMetadata;
+use geometry::transformations::euclidean::rotation3d::Rotation3DOperation;
 use geometry::units::Centimeter;
-use geometry::viewport::{Viewport, ViewportGeometry, ViewportState, ZeroPointLocation};
+use geometry::units::Pixel;
+use geometry::viewport::{
+    Viewport, ViewportBounds, ViewportGeometry, ViewportState, ZeroPointLocation,
+};
+use std::sync::atomic::Ordering;
 
 #[derive(Debug)]
 pub struct Context {
@@ -45,8 +76,32 @@ impl Context {
 #[derive(Debug)]
 pub struct FiguresState {
     pub grid: Grid2D,
-    pub fractal: FractalZigZag,
+    pub kind: FractalKind,
+    pub fractal: FractalCustom,
+    /// Entries of the last imported `.ifs` file.
+    pub library: Vec<FractalCustom>,
+    pub editor: MapEditor,
+    pub rendering: Rendering,
+    pub density: DensityRenderer,
+    pub deterministic: DeterministicIFS,
+    /// Points of the chaos game or of the attractor, in centimeters.
+    pub cloud: PointCloud,
+    /// The cloud converted to dots on the canvas.
     pub points: Vec<Shape>,
+    /// The cloud binned into the pixels of the canvas and tone mapped.
+    pub image: DensityImage,
+    /// Result of the deterministic algorithm.
+    pub lines: Vec<Line2D<Point2D>>,
+
+    pub lsystem: LSystemState,
+    pub escape: EscapeState,
+    pub attractor: Attractor2D,
+    pub attractor_animation: AnimationAttractor,
+    pub keyframes: KeyframeAnimation,
+    pub timeline: Timeline,
+    pub export: AnimationExport,
+
+    pub dimension: DimensionState,
 }
 
 impl Default for FiguresState {
@@ -54,17 +109,843 @@ impl Default for FiguresState {
         let mut grid = Grid2DBuilder::default().with_unit(Centimeter(1.0)).build();
         grid.is_enabled = false;
 
+        let fractal = FractalPreset::ZigZag.fractal();
+        let mut editor = MapEditor::default();
+        editor.fit(&fractal);
+        let mut deterministic = DeterministicIFS::default();
+        deterministic.fit(&fractal);
+
         Self {
             grid,
-            fractal: FractalZigZag::default(),
+            kind: FractalKind::Ifs,
+            fractal,
+            library: vec![],
+            editor,
+            rendering: Rendering::Points,
+            density: DensityRenderer::default(),
+            deterministic,
+            cloud: PointCloud::default(),
             points: vec![],
+            image: DensityImage::default(),
+            lines: vec![],
+
+            lsystem: LSystemState::default(),
+            escape: EscapeState::default(),
+            attractor: Attractor2D::default(),
+            attractor_animation: AnimationAttractor::default(),
+            keyframes: KeyframeAnimation::default(),
+            timeline: Timeline::default(),
+            export: AnimationExport::default(),
+
+            dimension: DimensionState::default(),
         }
     }
 }
 
 impl FiguresState {
+    /// Whether the attractor changes with the timeline.
+    pub fn is_animated(&self) -> bool {
+        self.attractor_animation.playback.is_enabled || self.keyframes.playback.is_enabled
+    }
+
+    /// Puts the attractor into its state at the time of the timeline and generates
+    /// it again if it changed. Returns whether an animation is running.
+    pub fn animate(&mut self, viewport: &Viewport) -> bool {
+        if self.kind != FractalKind::Attractor {
+            return false;
+        }
+
+        let before = (self.attractor.parameters, self.attractor.scale);
+        let mut is_animating = self.keyframes.update(&self.timeline, &mut self.attractor);
+        is_animating |= self
+            .attractor_animation
+            .update(&self.timeline, &mut self.attractor.parameters);
+
+        if (self.attractor.parameters, self.attractor.scale) != before {
+            self.regenerate_fractal(viewport);
+        }
+
+        is_animating
+    }
+
+    /// Generates the fractal again after its parameters changed. Point clouds are
+    /// generated in the background and shown as they come in.
     pub fn regenerate_fractal(&mut self, viewport: &Viewport) {
-        let fractals = self.fractal.shapes(viewport);
-        self.points = fractals;
+        self.lines.clear();
+
+        match self.kind {
+            FractalKind::Ifs => self.escape.cancel(),
+            FractalKind::LSystem => {
+                self.escape.cancel();
+                self.clear_cloud();
+                self.lsystem.regenerate();
+                return;
+            },
+            FractalKind::EscapeTime => {
+                self.clear_cloud();
+                self.escape.regenerate(viewport);
+                return;
+            },
+            FractalKind::Attractor => {
+                self.escape.cancel();
+                // A new orbit every frame would never get past the first chunk
+                if self.is_animated() {
+                    self.cloud.fill_attractor(&self.attractor);
+                    self.redraw_fractal(viewport);
+                } else {
+                    self.cloud.generate_attractor(&self.attractor);
+                }
+                return;
+            },
+        }
+
+        match self.rendering {
+            Rendering::Points => {
+                let iterations = self.fractal.iterations as usize;
+                self.cloud.generate_ifs(&self.fractal, iterations);
+            },
+            Rendering::Density => {
+                self.cloud.generate_ifs(&self.fractal, self.density.samples);
+            },
+            Rendering::Deterministic => {
+                self.clear_cloud();
+                self.lines = self.deterministic.lines(&self.fractal.systems);
+            },
+        }
+    }
+
+    /// Follows a pan, a zoom or a resize of the canvas. Point clouds are not
+    /// generated again, just converted to the new view; images are rendered again
+    /// in the background.
+    pub fn move_view(&mut self, viewport: &Viewport) {
+        match self.kind {
+            FractalKind::EscapeTime => self.escape.regenerate(viewport),
+            FractalKind::Ifs | FractalKind::Attractor => self.redraw_fractal(viewport),
+            FractalKind::LSystem => {},
+        }
+    }
+
+    /// Converts the whole cloud to the canvas again, after the view or the style
+    /// of the points changed.
+    pub fn redraw_fractal(&mut self, viewport: &Viewport) {
+        self.points.clear();
+        self.draw_cloud(0, viewport);
+    }
+
+    /// Takes the points generated in the background since the last frame, and the
+    /// density image rendered from them. Returns whether more are on the way.
+    pub fn receive_cloud(&mut self, viewport: &Viewport) -> bool {
+        // Checked first: once the job is finished, all its points are in the channel
+        let is_busy = self.cloud.is_busy();
+
+        match self.cloud.receive() {
+            Some(0) => self.redraw_fractal(viewport),
+            Some(start) => self.draw_cloud(start, viewport),
+            None => {},
+        }
+
+        // A render that is under way is finished first, so that the image keeps up
+        // while the view is moved on every frame
+        self.image.receive();
+        if self.image.is_outdated() && !self.image.is_busy() {
+            self.image
+                .render(self.cloud.points(), self.density, viewport);
+        }
+
+        is_busy || self.image.is_busy() || self.image.is_outdated()
+    }
+
+    /// Adds the points of the cloud from `start` on to the dots or to the density image.
+    fn draw_cloud(&mut self, start: usize, viewport: &Viewport) {
+        let points = self.cloud.points().get(start..).unwrap_or_default();
+
+        match (self.kind, self.rendering) {
+            (FractalKind::Ifs, Rendering::Points) => {
+                let metadata = |color| DotMetadata {
+                    radius: self.fractal.radius as f32,
+                    fill: color,
+                    stroke: Stroke::new(0.0, Color32::TRANSPARENT),
+                };
+                let bounds = viewport.state.bounds;
+                let visible = Rect::from_min_max(
+                    egui::pos2(
+                        bounds.minimum_x.value() as f32,
+                        bounds.minimum_y.value() as f32,
+                    ),
+                    egui::pos2(
+                        bounds.maximum_x.value() as f32,
+                        bounds.maximum_y.value() as f32,
+                    ),
+                )
+                .expand(self.fractal.radius as f32);
+
+                self.points
+                    .extend(points.iter().filter_map(|(point, color)| {
+                        let position = point.to_pixels(viewport);
+                        visible
+                            .contains(position.into())
+                            .then(|| position.to_dot(&metadata(*color)))
+                    }));
+            },
+            (FractalKind::Ifs, Rendering::Density) | (FractalKind::Attractor, _) => {
+                self.image.invalidate();
+            },
+            _ => {},
+        }
+    }
+
+    fn clear_cloud(&mut self) {
+        self.cloud.cancel();
+        self.points.clear();
+        self.image.clear();
+    }
+
+    /// Estimates the dimension of the fractal that is currently shown.
+    pub fn estimate_dimension(&mut self) {
+        let estimator = &self.dimension.estimator;
+
+        let (estimate, similarity) = match self.kind {
+            FractalKind::Ifs => {
+                let estimate = match self.rendering {
+                    Rendering::Points | Rendering::Density => {
+                        estimator.estimate(&self.cloud.positions())
+                    },
+                    Rendering::Deterministic => estimator.estimate_lines(&self.lines),
+                };
+
+                (estimate, similarity_dimension(&self.fractal.systems))
+            },
+            FractalKind::LSystem => {
+                (estimator.estimate_lines(&self.lsystem.lines()), None)
+            },
+            FractalKind::Attractor => (estimator.estimate(&self.cloud.positions()), None),
+            FractalKind::EscapeTime => (None, None),
+        };
+
+        self.dimension.estimate = estimate;
+        self.dimension.similarity = similarity;
+        self.dimension.is_estimated = true;
+    }
+
+    /// Replaces the maps, keeping the rendering settings.
+    pub fn load_fractal(&mut self, fractal: FractalCustom, viewport: &Viewport) {
+        self.fractal = FractalCustom {
+            iterations: self.fractal.iterations,
+            radius: self.fractal.radius,
+            seed: self.fractal.seed,
+            ..fractal
+        };
+        self.editor.fit(&self.fractal);
+        self.deterministic.fit(&self.fractal);
+        self.regenerate_fractal(viewport);
+    }
+
+    /// Applies dragged map handles, then regenerates the fractal.
+    pub fn update_maps(
+        &mut self, ui: &egui::Ui, response: &egui::Response, viewport: &Viewport,
+    ) {
+        let systems = &mut self.fractal.systems;
+        if !self.editor.update(systems, ui, response, viewport) {
+            return;
+        }
+
+        if self.editor.is_auto_probability {
+            self.fractal.suggest_probabilities();
+        }
+        self.regenerate_fractal(viewport);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FractalKind {
+    Ifs,
+    LSystem,
+    EscapeTime,
+    Attractor,
+}
+
+impl FractalKind {
+    pub const ALL: [Self; 4] =
+        [Self::Ifs, Self::LSystem, Self::EscapeTime, Self::Attractor];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Ifs => "Iterated Function System",
+            Self::LSystem => "L-System",
+            Self::EscapeTime => "Escape-Time",
+            Self::Attractor => "Strange Attractor",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Rendering {
+    /// Every point of the chaos game is a separate dot.
+    Points,
+    /// Hits are accumulated into a log-density image.
+    Density,
+    /// The maps are applied to a whole shape, generation by generation.
+    Deterministic,
+}
+
+impl Rendering {
+    pub const ALL: [Self; 3] = [Self::Points, Self::Density, Self::Deterministic];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Points => "Points",
+            Self::Density => "Density",
+            Self::Deterministic => "Deterministic",
+        }
+    }
+}
+
+/// Density image of the point cloud, rendered in the background. The last
+/// finished image stays on the canvas, attached to the plane, until the next one
+/// is ready. Uploading needs the egui context, so it happens when the image is
+/// drawn. The image is kept for exports.
+#[derive(Default)]
+pub struct DensityImage {
+    worker: Worker<PlaneImage>,
+    /// The cloud or the view changed since the last render was started.
+    is_outdated: bool,
+    image: Option<ColorImage>,
+    is_pending: bool,
+    texture: Option<TextureHandle>,
+    bounds: ViewportBounds<Centimeter>,
+}
+
+impl std::fmt::Debug for DensityImage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DensityImage")
+            .field("worker", &self.worker)
+            .field("is_outdated", &self.is_outdated)
+            .field("image", &self.image.as_ref().map(|image| image.size))
+            .field("is_pending", &self.is_pending)
+            .field("texture", &self.texture.as_ref().map(TextureHandle::id))
+            .field("bounds", &self.bounds)
+            .finish()
+    }
+}
+
+impl DensityImage {
+    /// Starts plotting the points over the visible part of the plane and tone
+    /// mapping them, cancelling the previous render.
+    pub fn render(
+        &mut self, points: &[CloudPoint], renderer: DensityRenderer, viewport: &Viewport,
+    ) {
+        const CHECK_EVERY: usize = 100_000;
+
+        let mut histogram = Histogram::new(viewport);
+        let bounds = viewport.state.bounds.to_centimeters(viewport);
+        let points = points.to_vec();
+
+        self.is_outdated = false;
+        self.worker.start(move |job| {
+            for (index, (point, color)) in points.into_iter().enumerate() {
+                if index % CHECK_EVERY == 0 && job.is_cancelled().load(Ordering::Relaxed)
+                {
+                    return;
+                }
+                histogram.plot(point, color);
+            }
+
+            job.send((renderer.tone_map(&histogram), bounds));
+        });
+    }
+
+    /// Marks the image to be rendered again, once the current render is done.
+    pub fn invalidate(&mut self) {
+        self.is_outdated = true;
+    }
+
+    pub fn is_outdated(&self) -> bool {
+        self.is_outdated
+    }
+
+    pub fn is_busy(&self) -> bool {
+        self.worker.is_busy()
+    }
+
+    /// Takes the latest finished image.
+    pub fn receive(&mut self) {
+        if let Some((image, bounds)) = self.worker.try_recv() {
+            self.image = Some(image);
+            self.bounds = bounds;
+            self.is_pending = true;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.worker.cancel();
+        self.is_outdated = false;
+        self.image = None;
+        self.is_pending = false;
+        self.texture = None;
+    }
+
+    /// The uploaded texture and its image.
+    pub fn texture(&self) -> Option<(TextureId, &ColorImage)> {
+        Some((self.texture.as_ref()?.id(), self.image.as_ref()?))
+    }
+
+    /// Uploads the latest finished image and places it over the plane.
+    pub fn shape(&mut self, ctx: &egui::Context, viewport: &Viewport) -> Option<Shape> {
+        if self.is_pending
+            && let Some(image) = self.image.clone()
+        {
+            self.is_pending = false;
+            match &mut self.texture {
+                Some(texture) => texture.set(image, egui::TextureOptions::NEAREST),
+                None => {
+                    self.texture = Some(ctx.load_texture(
+                        "IFS_DENSITY",
+                        image,
+                        egui::TextureOptions::NEAREST,
+                    ));
+                },
+            }
+        }
+
+        let texture = self.texture.as_ref()?;
+        let top_left = Point2D {
+            x: self.bounds.minimum_x,
+            y: self.bounds.maximum_y,
+        };
+        let bottom_right = Point2D {
+            x: self.bounds.maximum_x,
+            y: self.bounds.minimum_y,
+        };
+        let rect = Rect::from_min_max(
+            top_left.to_pixels(viewport).into(),
+            bottom_right.to_pixels(viewport).into(),
+        );
+        let uv = Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+
+        Some(Shape::image(texture.id(), rect, uv, egui::Color32::WHITE))
+    }
+}
+
+#[derive(Debug)]
+pub struct LSystemState {
+    pub system: LSystem,
+    /// Entries of the last imported `.l` file.
+    pub library: Vec<LSystem>,
+
+    /// Scale the result so that it always has the same size.
+    pub is_fitted: bool,
+    pub size: Centimeter,
+    /// View rotation of 3D systems, in degrees.
+    pub view_x: f64,
+    pub view_y: f64,
+
+    lines: Vec<Line2D<Point2D>>,
+    lines_3d: Vec<Line3D<Point3D>>,
+    /// Iterations performed, or the error in the rules.
+    pub status: Result<usize, LSystemError>,
+}
+
+impl Default for LSystemState {
+    fn default() -> Self {
+        Self {
+            system: LSystemPreset::KochSnowflake.system(),
+            library: vec![],
+
+            is_fitted: true,
+            size: Centimeter(15.0),
+            view_x: -20.0,
+            view_y: 30.0,
+
+            lines: vec![],
+            lines_3d: vec![],
+            status: Ok(0),
+        }
+    }
+}
+
+impl LSystemState {
+    pub fn regenerate(&mut self) {
+        self.lines.clear();
+        self.lines_3d.clear();
+
+        let derivation = match self.system.derive() {
+            Ok(derivation) => derivation,
+            Err(error) => {
+                self.status = Err(error);
+                return;
+            },
+        };
+        self.status = Ok(derivation.iterations);
+
+        let turtle = &self.system.turtle;
+        if self.system.is_3d {
+            let lines = turtle.lines_3d(&derivation.word);
+            self.lines_3d = if self.is_fitted {
+                fit_3d(&lines, self.size)
+            } else {
+                lines
+            };
+        } else {
+            let lines = turtle.lines(&derivation.word);
+            self.lines = if self.is_fitted {
+                fit(&lines, self.size)
+            } else {
+                lines
+            };
+        }
+    }
+
+    /// Replaces the system, keeping the view settings.
+    pub fn load(&mut self, system: LSystem) {
+        self.system = system;
+        self.regenerate();
+    }
+
+    /// Lines on the canvas. 3D systems are rotated by the view angles and
+    /// projected orthographically.
+    pub fn lines(&self) -> Vec<Line2D<Point2D>> {
+        if !self.system.is_3d {
+            return self.lines.clone();
+        }
+
+        let rotation = Rotation3DOperation::from_euler(
+            Angle::from_degree(self.view_x),
+            Angle::from_degree(self.view_y),
+            Angle::from_degree(0.0),
+        );
+        let projection = TwoPointPerspective { q: 0.0, r: 0.0 };
+        let mut pivot = Point3D::zero();
+
+        self.lines_3d
+            .iter()
+            .map(|line| {
+                let mut line = *line;
+                rotation.go(&mut line.start, &mut pivot);
+                rotation.go(&mut line.end, &mut pivot);
+                Line2D::new(
+                    line.start.to_2d(&projection),
+                    line.end.to_2d(&projection),
+                    line.stroke,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Image of a fractal and the part of the plane it covers.
+type PlaneImage = (ColorImage, ViewportBounds<Centimeter>);
+
+/// Escape-time fractal rendered in the background. The last finished image stays
+/// on the canvas, attached to the plane, until the next one is ready.
+pub struct EscapeState {
+    pub fractal: EscapeTime,
+    /// Clicking on the Mandelbrot set picks the parameter of the Julia set.
+    pub is_julia_picking: bool,
+    /// A quick low resolution pass is shown before the full image.
+    pub is_preview: bool,
+
+    worker: Worker<PlaneImage>,
+    texture: Option<TextureHandle>,
+    bounds: ViewportBounds<Centimeter>,
+}
+
+impl Default for EscapeState {
+    fn default() -> Self {
+        Self {
+            fractal: EscapeTime::default(),
+            is_julia_picking: true,
+            is_preview: true,
+
+            worker: Worker::default(),
+            texture: None,
+            bounds: ViewportBounds::default(),
+        }
+    }
+}
+
+impl std::fmt::Debug for EscapeState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EscapeState")
+            .field("fractal", &self.fractal)
+            .field("is_julia_picking", &self.is_julia_picking)
+            .field("is_preview", &self.is_preview)
+            .field("worker", &self.worker)
+            .field("texture", &self.texture.as_ref().map(TextureHandle::id))
+            .field("bounds", &self.bounds)
+            .finish()
+    }
+}
+
+impl EscapeState {
+    /// Starts rendering the visible part of the plane, cancelling the previous render.
+    pub fn regenerate(&mut self, viewport: &Viewport) {
+        let pixels = viewport.state.bounds;
+        let width = (pixels.maximum_x - pixels.minimum_x).value().max(1.0) as usize;
+        let height = (pixels.maximum_y - pixels.minimum_y).value().max(1.0) as usize;
+        let bounds = pixels.to_centimeters(viewport);
+        let pixels_per_centimeter = viewport.geometry.pixels_per_centimeter;
+
+        let fractal = self.fractal.clone();
+        let is_preview = self.is_preview;
+        self.worker.start(move |job| {
+            const PREVIEW_FACTOR: usize = 4;
+
+            if is_preview && width > PREVIEW_FACTOR && height > PREVIEW_FACTOR {
+                let size = [width / PREVIEW_FACTOR, height / PREVIEW_FACTOR];
+                let preview = fractal.render(
+                    &bounds,
+                    size,
+                    pixels_per_centimeter,
+                    job.is_cancelled(),
+                );
+                if let Some(image) = preview {
+                    job.send((image, bounds));
+                }
+            }
+
+            let image = fractal.render(
+                &bounds,
+                [width, height],
+                pixels_per_centimeter,
+                job.is_cancelled(),
+            );
+            if let Some(image) = image {
+                job.send((image, bounds));
+            }
+        });
+    }
+
+    pub fn cancel(&mut self) {
+        self.worker.cancel();
+        self.texture = None;
+    }
+
+    pub fn is_busy(&self) -> bool {
+        self.worker.is_busy()
+    }
+
+    /// Uploads the latest finished image and places it over the plane.
+    pub fn shape(&mut self, ctx: &egui::Context, viewport: &Viewport) -> Option<Shape> {
+        if let Some((image, bounds)) = self.worker.try_recv() {
+            self.bounds = bounds;
+            match &mut self.texture {
+                Some(texture) => texture.set(image, egui::TextureOptions::LINEAR),
+                None => {
+                    self.texture = Some(ctx.load_texture(
+                        "ESCAPE_TIME",
+                        image,
+                        egui::TextureOptions::LINEAR,
+                    ));
+                },
+            }
+        }
+
+        let texture = self.texture.as_ref()?;
+        let top_left = Point2D {
+            x: self.bounds.minimum_x,
+            y: self.bounds.maximum_y,
+        };
+        let bottom_right = Point2D {
+            x: self.bounds.maximum_x,
+            y: self.bounds.minimum_y,
+        };
+        let rect = Rect::from_min_max(
+            top_left.to_pixels(viewport).into(),
+            bottom_right.to_pixels(viewport).into(),
+        );
+        let uv = Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+
+        Some(Shape::image(texture.id(), rect, uv, egui::Color32::WHITE))
+    }
+
+    /// Sets the Julia parameter to the point of the Mandelbrot set under the cursor
+    /// and switches to the Julia set. Returns whether the parameter was picked.
+    pub fn pick_julia(&mut self, position: egui::Pos2, viewport: &Viewport) -> bool {
+        if !self.is_julia_picking || self.fractal.kind != EscapeKind::Mandelbrot {
+            return false;
+        }
+
+        let point = Point2DPixel::from(position).to_centimeters(viewport);
+        self.fractal.julia = self.fractal.to_complex(point);
+        self.fractal.kind = EscapeKind::Julia;
+
+        true
+    }
+
+    /// Scales the plane relative to the canvas, keeping the center of the canvas in place.
+    /// Unlike the viewport zoom, it is not limited in depth.
+    pub fn zoom(&mut self, factor: f64, viewport: &mut Viewport) {
+        let center = viewport.state.bounds.to_centimeters(viewport);
+        let shift = (factor - 1.0) * viewport.geometry.pixels_per_centimeter;
+
+        self.fractal.unit *= factor;
+        viewport.geometry.offset.x += Pixel(-center.center_x.value() * shift);
+        viewport.geometry.offset.y += Pixel(center.center_y.value() * shift);
+    }
+
+    /// Magnification relative to the initial view.
+    pub fn magnification(&self, viewport: &Viewport) -> f64 {
+        let initial = EscapeTime::default().unit.value()
+            * ViewportGeometry::default().pixels_per_centimeter;
+
+        self.fractal.unit.value() * viewport.geometry.pixels_per_centimeter / initial
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct DimensionState {
+    pub is_visible: bool,
+    pub estimator: DimensionEstimator,
+
+    /// Whether the estimation was run at least once.
+    pub is_estimated: bool,
+    pub estimate: Option<DimensionEstimate>,
+    /// Similarity dimension of the IFS maps, if the current fractal is an IFS.
+    pub similarity: Option<f64>,
+}
+
+/// Point of a cloud and the color of the map that produced it.
+type CloudPoint = (Point2D, Color32);
+
+/// Points of the chaos game or of an attractor orbit, in centimeters. They are
+/// generated once per parameter change, in the background and in growing chunks,
+/// and stay valid however the view is moved.
+#[derive(Default)]
+pub struct PointCloud {
+    points: Vec<CloudPoint>,
+    /// Number of points the current job will generate.
+    total: usize,
+    worker: Worker<Vec<CloudPoint>>,
+    /// The points of the previous parameters stay on the canvas until the first
+    /// chunk of the new ones arrives, so that editing does not flicker.
+    is_stale: bool,
+}
+
+impl std::fmt::Debug for PointCloud {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PointCloud")
+            .field("points", &self.points.len())
+            .field("total", &self.total)
+            .field("worker", &self.worker)
+            .field("is_stale", &self.is_stale)
+            .finish()
+    }
+}
+
+impl PointCloud {
+    /// Starts the chaos game, cancelling the previous job.
+    pub fn generate_ifs(&mut self, fractal: &FractalCustom, samples: usize) {
+        let systems = fractal.systems.clone();
+        let seed = fractal.seed;
+
+        self.start(samples, move |job| {
+            if let Some(game) = ChaosGame::new(&systems, seed) {
+                let points = game
+                    .skip(WARM_UP)
+                    .take(samples)
+                    .map(|(point, system)| (point, system.color));
+                Self::send_chunks(job, points);
+            } else {
+                job.send(vec![]);
+            }
+        });
+    }
+
+    /// Starts iterating the map of the attractor, cancelling the previous job.
+    pub fn generate_attractor(&mut self, attractor: &Attractor2D) {
+        let attractor = attractor.clone();
+
+        self.start(attractor.samples, move |job| {
+            let points = attractor.orbit().map(|point| (point, attractor.color));
+            Self::send_chunks(job, points);
+        });
+    }
+
+    /// Iterates the map of the attractor right away, for animations.
+    pub fn fill_attractor(&mut self, attractor: &Attractor2D) {
+        self.cancel();
+        self.total = attractor.samples;
+        self.points = attractor
+            .orbit()
+            .map(|point| (point, attractor.color))
+            .collect();
+    }
+
+    fn start(
+        &mut self, total: usize, job: impl FnOnce(&Job<Vec<CloudPoint>>) + Send + 'static,
+    ) {
+        self.total = total;
+        self.is_stale = true;
+        self.worker.start(job);
+    }
+
+    /// Sends the points in chunks that double in size, so that the first ones show
+    /// up immediately and large clouds are not redrawn too often.
+    fn send_chunks(job: &Job<Vec<CloudPoint>>, points: impl Iterator<Item = CloudPoint>) {
+        const FIRST_CHUNK: usize = 10_000;
+        const MAX_CHUNK: usize = 500_000;
+
+        let mut size = FIRST_CHUNK;
+        let mut chunk = Vec::with_capacity(size);
+        for point in points {
+            chunk.push(point);
+
+            if chunk.len() == size {
+                if job.is_cancelled().load(Ordering::Relaxed) {
+                    return;
+                }
+                size = (size * 2).min(MAX_CHUNK);
+                job.send(std::mem::replace(&mut chunk, Vec::with_capacity(size)));
+            }
+        }
+
+        // Sent even when empty, so that the stale points are replaced
+        job.send(chunk);
+    }
+
+    /// Appends the chunks that arrived since the last call. Returns the index of the
+    /// first new point, zero if the stale points were replaced.
+    pub fn receive(&mut self) -> Option<usize> {
+        let chunks = self.worker.drain();
+        if chunks.is_empty() {
+            return None;
+        }
+
+        if self.is_stale {
+            self.points.clear();
+            self.is_stale = false;
+        }
+        let start = self.points.len();
+        self.points.extend(chunks.into_iter().flatten());
+
+        Some(start)
+    }
+
+    pub fn cancel(&mut self) {
+        self.worker.cancel();
+        self.points.clear();
+        self.total = 0;
+        self.is_stale = false;
+    }
+
+    pub fn is_busy(&self) -> bool {
+        self.worker.is_busy()
+    }
+
+    pub fn points(&self) -> &[CloudPoint] {
+        &self.points
+    }
+
+    pub fn positions(&self) -> Vec<Point2D> {
+        self.points.iter().map(|(point, _)| *point).collect()
+    }
+
+    /// Points received so far and the number of points being generated.
+    pub fn progress(&self) -> (usize, usize) {
+        if self.is_stale {
+            (0, self.total)
+        } else {
+            (self.points.len(), self.total)
+        }
     }
 }
@@ -1,7 +1,10 @@
 use crate::config::ConfigError;
+use crate::ifs_file::IfsFileError;
 use crate::io::IoError;
 use crate::logs::LogError;
+use crate::lsystem_file::LSystemFileError;
 use crate::ui::GraphicsBackendError;
+use geometry::animations::export::ExportError;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -9,12 +12,21 @@ pub enum ProjectError {
     #[error("Configuration. {0}")]
     Config(#[from] ConfigError),
 
+    #[error("Export. {0}")]
+    Export(#[from] ExportError),
+
     #[error("Graphics Backend. {0}")]
     GraphicsBackend(#[from] GraphicsBackendError),
 
+    #[error("IFS File. {0}")]
+    IfsFile(#[from] IfsFileError),
+
     #[error("I/O. {0}")]
     Io(#[from] IoError),
 
+    #[error("L-System File. {0}")]
+    LSystemFile(#[from] LSystemFileError),
+
     #[error("Logger. {0}")]
     Log(#[from] LogError),
 }
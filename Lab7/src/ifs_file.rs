@@ -0,0 +1,30 @@
+use crate::errors::ProjectError;
+use geometry::fractals::custom::FractalCustom;
+use geometry::fractals::ifs::{self, IfsError};
+use std::path::Path;
+use thiserror::Error;
+
+/// Reads all 2D entries of a Fractint `.ifs` file.
+pub fn load(path: &Path) -> Result<Vec<FractalCustom>, ProjectError> {
+    let text = std::fs::read_to_string(path).map_err(IfsFileError::Read)?;
+
+    Ok(ifs::parse(&text).map_err(IfsFileError::Parse)?)
+}
+
+pub fn save(path: &Path, fractal: &FractalCustom) -> Result<(), ProjectError> {
+    std::fs::write(path, ifs::write(fractal)).map_err(IfsFileError::Write)?;
+
+    Ok(())
+}
+
+#[derive(Debug, Error)]
+pub enum IfsFileError {
+    #[error("Failed to read file. {0}")]
+    Read(std::io::Error),
+
+    #[error("Failed to write to file. {0}")]
+    Write(std::io::Error),
+
+    #[error("Failed to parse. {0}")]
+    Parse(#[from] IfsError),
+}
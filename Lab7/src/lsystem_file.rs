@@ -0,0 +1,21 @@
+use crate::errors::ProjectError;
+use geometry::fractals::lsystem::LSystem;
+use geometry::fractals::lsystem::fractint::{self, FractintError};
+use std::path::Path;
+use thiserror::Error;
+
+/// Reads all entries of a Fractint `.l` file.
+pub fn load(path: &Path) -> Result<Vec<LSystem>, ProjectError> {
+    let text = std::fs::read_to_string(path).map_err(LSystemFileError::Read)?;
+
+    Ok(fractint::parse(&text).map_err(LSystemFileError::Parse)?)
+}
+
+#[derive(Debug, Error)]
+pub enum LSystemFileError {
+    #[error("Failed to read file. {0}")]
+    Read(std::io::Error),
+
+    #[error("Failed to parse. {0}")]
+    Parse(#[from] FractintError),
+}
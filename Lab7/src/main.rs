@@ -29,7 +29,9 @@ fn main() {
 pub mod config;
 pub mod context;
 pub mod errors;
+pub mod ifs_file;
 pub mod io;
 pub mod logs;
+pub mod lsystem_file;
 pub mod ui;
 pub mod utils;
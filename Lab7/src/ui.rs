@@ -68,5 +68,7 @@ pub mod themes;
 pub mod workspace;
 pub mod components {
     pub mod canvas;
+    pub mod dimension;
     pub mod settings;
+    pub mod timeline;
 }
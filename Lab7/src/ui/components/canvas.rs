@@ -1,7 +1,10 @@
-use crate::context::Context;
-use egui::{CentralPanel, Color32, Frame, Painter, Response, Sense, Shape};
+use crate::context::{Context, FractalKind, Rendering};
+use egui::{CentralPanel, Color32, Frame, Painter, Response, Sense, Shape, Stroke};
+use geometry::fractals::escape::EscapeKind;
 use geometry::primitives::line2d::Line2D;
 use geometry::primitives::point2d::Point2D;
+use geometry::shapes::dot::DotMetadata;
+use std::time::Duration;
 
 #[derive(Debug, Default)]
 pub struct CanvasComponent;
@@ -14,12 +17,16 @@ impl CanvasComponent {
                 .show(ui, |ui| {
                     ui.input(|i| {
                         if context.viewport.handle_scroll(i) {
-                            context.figures.regenerate_fractal(&context.viewport);
+                            context.figures.move_view(&context.viewport);
                         }
                     });
+                    let bounds = context.viewport.state.bounds;
                     let response = Self::pipeline(ui, context);
-                    if context.viewport.handle_pan(ui, response) {
-                        context.figures.regenerate_fractal(&context.viewport);
+                    // A resized canvas shows another part of the plane
+                    if context.viewport.handle_pan(ui, response)
+                        || context.viewport.state.bounds != bounds
+                    {
+                        context.figures.move_view(&context.viewport);
                     };
                 });
         });
@@ -27,32 +34,142 @@ impl CanvasComponent {
 
     fn pipeline(ui: &mut egui::Ui, context: &mut Context) -> Response {
         let shapes = Self::create_shapes(ui, context);
+        let figures = &mut context.figures;
+        let textures: Vec<_> = figures.image.texture().into_iter().collect();
+        figures.export.capture(
+            ui.ctx(),
+            &shapes,
+            &textures,
+            &context.viewport,
+            &mut figures.timeline,
+        );
         Self::draw(ui, context, shapes)
     }
 
-    fn create_shapes(_ui: &mut egui::Ui, context: &mut Context) -> Vec<Shape> {
+    fn create_shapes(ui: &mut egui::Ui, context: &mut Context) -> Vec<Shape> {
         let mut lines = vec![];
 
+        let figures = &mut context.figures;
+        figures.export.prepare(&mut figures.timeline);
+        context
+            .figures
+            .timeline
+            .tick(ui.input(|i| f64::from(i.stable_dt)));
+        let is_animating = context.figures.animate(&context.viewport);
+        if context.figures.timeline.needs_repaint(is_animating) {
+            ui.ctx().request_repaint();
+        }
+
+        if context.figures.receive_cloud(&context.viewport) {
+            ui.ctx().request_repaint_after(Duration::from_millis(50));
+        }
+
         let grid: Vec<Line2D<Point2D>> = context.figures.grid.lines(&context.viewport);
-        let fractal = context.figures.points.clone();
+        let (fractal, density) = match (context.figures.kind, context.figures.rendering) {
+            (FractalKind::Ifs, Rendering::Points) => {
+                (context.figures.points.clone(), None)
+            },
+            (FractalKind::Ifs, Rendering::Density) | (FractalKind::Attractor, _) => (
+                vec![],
+                context.figures.image.shape(ui.ctx(), &context.viewport),
+            ),
+            _ => (vec![], None),
+        };
+        let (figure, maps, handles) = match context.figures.kind {
+            FractalKind::Ifs => (
+                context.figures.lines.clone(),
+                context
+                    .figures
+                    .editor
+                    .lines(&context.figures.fractal.systems),
+                context
+                    .figures
+                    .editor
+                    .handle_shapes(&context.figures.fractal.systems, &context.viewport),
+            ),
+            FractalKind::LSystem => (context.figures.lsystem.lines(), vec![], vec![]),
+            FractalKind::EscapeTime => (vec![], vec![], Self::escape_markers(context)),
+            FractalKind::Attractor => (vec![], vec![], vec![]),
+        };
+        let escape = match context.figures.kind {
+            FractalKind::EscapeTime => {
+                if context.figures.escape.is_busy() {
+                    ui.ctx().request_repaint_after(Duration::from_millis(50));
+                }
+                context.figures.escape.shape(ui.ctx(), &context.viewport)
+            },
+            _ => None,
+        };
 
         // Conversion to shapes
         lines.extend(grid);
+        lines.extend(figure);
+        lines.extend(maps);
 
-        let mut shapes = lines
-            .iter()
-            .map(|line| line.to_pixels(&context.viewport).to_shape())
-            .collect::<Vec<Shape>>();
+        let mut shapes: Vec<Shape> = escape.into_iter().collect();
+        shapes.extend(
+            lines
+                .iter()
+                .map(|line| line.to_pixels(&context.viewport).to_shape()),
+        );
 
+        shapes.extend(density);
         shapes.extend(fractal);
+        shapes.extend(handles);
 
         shapes
     }
 
+    /// Julia parameter on the Mandelbrot set, roots on the Newton basins.
+    fn escape_markers(context: &Context) -> Vec<Shape> {
+        let fractal = &context.figures.escape.fractal;
+        let marker = DotMetadata {
+            radius: 4.0,
+            fill: Color32::WHITE,
+            stroke: Stroke::new(1.0, Color32::BLACK),
+        };
+
+        let points = match fractal.kind {
+            EscapeKind::Mandelbrot => vec![fractal.julia],
+            EscapeKind::Julia => vec![],
+            EscapeKind::Newton => fractal.roots.clone(),
+        };
+
+        points
+            .into_iter()
+            .map(|z| {
+                fractal
+                    .to_point(z)
+                    .to_pixels(&context.viewport)
+                    .to_dot(&marker)
+            })
+            .collect()
+    }
+
     fn draw(ui: &mut egui::Ui, context: &mut Context, shapes: Vec<Shape>) -> Response {
         let (response, painter) = Self::initialize_painter(ui, context);
         painter.extend(shapes);
 
+        match context.figures.kind {
+            FractalKind::Ifs => {
+                context
+                    .figures
+                    .update_maps(ui, &response, &context.viewport);
+            },
+            FractalKind::LSystem | FractalKind::Attractor => {},
+            FractalKind::EscapeTime => {
+                if response.clicked()
+                    && let Some(position) = response.interact_pointer_pos()
+                    && context
+                        .figures
+                        .escape
+                        .pick_julia(position, &context.viewport)
+                {
+                    context.figures.regenerate_fractal(&context.viewport);
+                }
+            },
+        }
+
         response
     }
 
@@ -0,0 +1,254 @@
+use crate::context::{Context, FractalKind};
+use egui::{
+    Align2, Color32, ComboBox, DragValue, FontId, Grid, Rect, RichText, ScrollArea,
+    Sense, Shape, SidePanel, Stroke, pos2, vec2,
+};
+use geometry::fractals::dimension::{DimensionEstimate, DimensionMethod};
+use strum::IntoEnumIterator;
+
+#[derive(Debug)]
+pub struct DimensionComponent {
+    width: f32,
+    plot_height: f32,
+}
+
+impl Default for DimensionComponent {
+    fn default() -> Self {
+        Self {
+            width: 280.0,
+            plot_height: 220.0,
+        }
+    }
+}
+
+impl DimensionComponent {
+    pub fn show(&mut self, ui: &mut egui::Ui, context: &mut Context) {
+        if !context.figures.dimension.is_visible {
+            return;
+        }
+
+        SidePanel::right("DIMENSION_PANEL")
+            .resizable(false)
+            .default_width(self.width)
+            .min_width(self.width)
+            .max_width(self.width)
+            .show_separator_line(true)
+            .show_inside(ui, |ui| {
+                ScrollArea::vertical().show(ui, |ui| {
+                    ui.vertical_centered_justified(|ui| {
+                        ui.heading(RichText::new("Dimension").color(Color32::WHITE));
+                    });
+
+                    ui.add_space(10.0);
+
+                    self.estimator(ui, context);
+
+                    ui.add_space(10.0);
+                    ui.separator();
+                    ui.add_space(10.0);
+
+                    self.results(ui, context);
+                });
+            });
+    }
+
+    fn estimator(&self, ui: &mut egui::Ui, context: &mut Context) {
+        let estimator = &mut context.figures.dimension.estimator;
+
+        Grid::new("DIMENSION_ESTIMATOR")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("Method:");
+                ComboBox::from_id_salt("DIMENSION_METHOD")
+                    .selected_text(estimator.method.name())
+                    .show_ui(ui, |ui| {
+                        for method in DimensionMethod::iter() {
+                            ui.selectable_value(
+                                &mut estimator.method,
+                                method,
+                                method.name(),
+                            );
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("Scales:");
+                ui.add(
+                    DragValue::new(&mut estimator.scales)
+                        .speed(0.1)
+                        .range(3..=16),
+                );
+                ui.end_row();
+
+                if estimator.method == DimensionMethod::Correlation {
+                    ui.label("Max Points:");
+                    ui.add(
+                        DragValue::new(&mut estimator.max_points)
+                            .speed(10)
+                            .range(100..=10_000),
+                    );
+                    ui.end_row();
+                }
+            });
+
+        ui.add_space(5.0);
+
+        ui.add_enabled_ui(context.figures.kind != FractalKind::EscapeTime, |ui| {
+            ui.vertical_centered_justified(|ui| {
+                if ui.button("Estimate").clicked() {
+                    context.figures.estimate_dimension();
+                }
+            });
+        });
+        if context.figures.kind == FractalKind::EscapeTime {
+            ui.label("Escape-time images have no point set to measure.");
+        }
+    }
+
+    fn results(&self, ui: &mut egui::Ui, context: &mut Context) {
+        let dimension = &context.figures.dimension;
+        if !dimension.is_estimated {
+            ui.label("Press Estimate to measure the current fractal.");
+            return;
+        }
+
+        let Some(estimate) = &dimension.estimate else {
+            ui.colored_label(
+                Color32::LIGHT_RED,
+                "Not enough points for the regression. Add iterations or scales.",
+            );
+            return;
+        };
+
+        Grid::new("DIMENSION_RESULTS")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label(format!("{} D:", estimate.method.name()));
+                ui.label(
+                    RichText::new(format!("{:.4}", estimate.slope)).color(Color32::WHITE),
+                );
+                ui.end_row();
+
+                ui.label("R²:");
+                ui.label(format!("{:.5}", estimate.r_squared));
+                ui.end_row();
+
+                ui.label("Points:");
+                ui.label(estimate.points.to_string());
+                ui.end_row();
+
+                if let Some(similarity) = dimension.similarity {
+                    ui.label("Similarity D:");
+                    ui.label(format!("{similarity:.4}"));
+                    ui.end_row();
+
+                    ui.label("Difference:");
+                    ui.label(format!("{:+.4}", estimate.slope - similarity));
+                    ui.end_row();
+                }
+            });
+
+        if dimension.similarity.is_some() {
+            ui.label(
+                RichText::new(
+                    "The similarity dimension solves Σ rᵢˢ = 1 for the largest \
+                     stretch rᵢ of every map. It is exact for similarities without \
+                     overlaps and an upper bound otherwise.",
+                )
+                .small(),
+            );
+        }
+
+        ui.add_space(10.0);
+
+        self.plot(ui, estimate);
+    }
+
+    /// Log-log samples, the fitted ones filled, and the regression line.
+    fn plot(&self, ui: &mut egui::Ui, estimate: &DimensionEstimate) {
+        let (response, painter) = ui.allocate_painter(
+            vec2(ui.available_width(), self.plot_height),
+            Sense::hover(),
+        );
+        let frame = response.rect;
+        painter.rect_filled(frame, 2.0, Color32::WHITE);
+
+        let area =
+            Rect::from_min_max(frame.min + vec2(34.0, 8.0), frame.max - vec2(8.0, 26.0));
+
+        let (mut min_x, mut max_x) = (f64::INFINITY, f64::NEG_INFINITY);
+        let (mut min_y, mut max_y) = (f64::INFINITY, f64::NEG_INFINITY);
+        for sample in &estimate.samples {
+            (min_x, max_x) = (min_x.min(sample.x), max_x.max(sample.x));
+            (min_y, max_y) = (min_y.min(sample.y), max_y.max(sample.y));
+        }
+        let span_x = (max_x - min_x).max(1e-9);
+        let span_y = (max_y - min_y).max(1e-9);
+
+        let to_screen = |x: f64, y: f64| {
+            pos2(
+                area.min.x + ((x - min_x) / span_x) as f32 * area.width(),
+                area.max.y - ((y - min_y) / span_y) as f32 * area.height(),
+            )
+        };
+
+        let axis = Stroke::new(1.0, Color32::DARK_GRAY);
+        painter.line_segment([area.left_bottom(), area.right_bottom()], axis);
+        painter.line_segment([area.left_bottom(), area.left_top()], axis);
+
+        let regression = [
+            to_screen(min_x, estimate.slope * min_x + estimate.intercept),
+            to_screen(max_x, estimate.slope * max_x + estimate.intercept),
+        ];
+        painter
+            .with_clip_rect(area.expand(2.0))
+            .line_segment(regression, Stroke::new(1.5, Color32::from_rgb(200, 60, 40)));
+
+        for sample in &estimate.samples {
+            let center = to_screen(sample.x, sample.y);
+            let color = Color32::from_rgb(30, 60, 140);
+            painter.add(if sample.is_fitted {
+                Shape::circle_filled(center, 3.5, color)
+            } else {
+                Shape::circle_stroke(center, 3.5, Stroke::new(1.0, Color32::GRAY))
+            });
+        }
+
+        let (x_label, y_label) = estimate.method.axes();
+        let font = FontId::proportional(11.0);
+        let text = Color32::DARK_GRAY;
+        painter.text(
+            pos2(area.center().x, frame.max.y - 4.0),
+            Align2::CENTER_BOTTOM,
+            x_label,
+            font.clone(),
+            text,
+        );
+        painter.text(
+            pos2(frame.min.x + 4.0, area.center().y),
+            Align2::LEFT_CENTER,
+            y_label,
+            font.clone(),
+            text,
+        );
+        for (value, position, align) in [
+            (min_x, area.left_bottom() + vec2(0.0, 2.0), Align2::LEFT_TOP),
+            (
+                max_x,
+                area.right_bottom() + vec2(0.0, 2.0),
+                Align2::RIGHT_TOP,
+            ),
+        ] {
+            painter.text(position, align, format!("{value:.1}"), font.clone(), text);
+        }
+        for (value, position) in [(min_y, area.left_bottom()), (max_y, area.left_top())] {
+            painter.text(
+                position - vec2(3.0, 0.0),
+                Align2::RIGHT_CENTER,
+                format!("{value:.1}"),
+                font.clone(),
+                text,
+            );
+        }
+    }
+}
@@ -1,6 +1,22 @@
-use crate::context::{Context, FiguresState};
-use egui::{Color32, DragValue, Grid, RichText, ScrollArea, SidePanel};
+use crate::context::{Context, FiguresState, FractalKind, PointCloud, Rendering};
+use crate::ifs_file;
+use crate::lsystem_file;
+use crate::ui::modals::error::ErrorModal;
+use egui::{
+    Color32, ComboBox, DragValue, Grid, RichText, ScrollArea, SidePanel, TextEdit,
+};
 use geometry::figures::grid;
+use geometry::fractals::EquationSystem;
+use geometry::fractals::attractors::{Attractor2D, Map2D};
+use geometry::fractals::deterministic::{DeterministicIFS, InitialShape};
+use geometry::fractals::escape::{EscapeKind, EscapeTime, Palette};
+use geometry::fractals::ifs;
+use geometry::fractals::lsystem::presets::LSystemPreset;
+use geometry::fractals::lsystem::{LSystem, Rule, fractint};
+use geometry::fractals::presets::{FractalPreset, palette_color};
+use geometry::fractals::variations::Variation;
+use geometry::math::complex::Complex;
+use strum::IntoEnumIterator;
 
 #[derive(Debug)]
 pub struct SettingsComponent {
@@ -94,48 +110,1037 @@ impl SettingsComponent {
                         }
                     });
 
+                    ui.checkbox(
+                        &mut context.figures.dimension.is_visible,
+                        "Show Dimension Panel",
+                    );
+
                     ui.add_space(10.0);
                     ui.separator();
                     ui.add_space(10.0);
 
-                    self.fractal_settings(ui, context);
+                    self.fractal_kind(ui, context);
+
+                    ui.add_space(10.0);
+
+                    match context.figures.kind {
+                        FractalKind::Ifs => {
+                            self.fractal_settings(ui, context);
+
+                            ui.add_space(10.0);
+                            ui.separator();
+                            ui.add_space(10.0);
+
+                            self.maps(ui, context);
+
+                            ui.add_space(10.0);
+                            ui.separator();
+                            ui.add_space(10.0);
+
+                            self.map_editor(ui, context);
+
+                            ui.add_space(10.0);
+                            ui.separator();
+                            ui.add_space(10.0);
+
+                            self.ifs_file(ui, context);
+                        },
+                        FractalKind::LSystem => {
+                            self.lsystem_settings(ui, context);
+
+                            ui.add_space(10.0);
+                            ui.separator();
+                            ui.add_space(10.0);
+
+                            self.lsystem_rules(ui, context);
+
+                            ui.add_space(10.0);
+                            ui.separator();
+                            ui.add_space(10.0);
+
+                            self.lsystem_file(ui, context);
+                        },
+                        FractalKind::EscapeTime => {
+                            self.escape_settings(ui, context);
+
+                            ui.add_space(10.0);
+                            ui.separator();
+                            ui.add_space(10.0);
+
+                            self.escape_parameters(ui, context);
+                        },
+                        FractalKind::Attractor => {
+                            self.attractor(ui, context);
+
+                            ui.add_space(10.0);
+                            ui.separator();
+                            ui.add_space(10.0);
+
+                            self.attractor_animation(ui, context);
+
+                            ui.add_space(10.0);
+                            ui.separator();
+                            ui.add_space(10.0);
+
+                            self.keyframes(ui, context);
+                        },
+                    }
                 });
             });
     }
 
+    fn fractal_kind(&self, ui: &mut egui::Ui, context: &mut Context) {
+        Grid::new("FRACTAL_KIND_GRID")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("Fractal:");
+                let mut kind = context.figures.kind;
+                ComboBox::from_id_salt("FRACTAL_KIND")
+                    .selected_text(kind.name())
+                    .show_ui(ui, |ui| {
+                        for option in FractalKind::ALL {
+                            ui.selectable_value(&mut kind, option, option.name());
+                        }
+                    });
+                if kind != context.figures.kind {
+                    context.figures.kind = kind;
+                    context.figures.regenerate_fractal(&context.viewport);
+                }
+                ui.end_row();
+            });
+    }
+
+    fn lsystem_settings(&self, ui: &mut egui::Ui, context: &mut Context) {
+        ui.label(RichText::new("L-System Settings").color(Color32::WHITE));
+
+        ui.add_space(5.0);
+
+        let state = &mut context.figures.lsystem;
+        let mut is_changed = false;
+
+        Grid::new("L-System Settings")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("Preset:");
+                let mut picked: Option<LSystem> = None;
+                ComboBox::from_id_salt("LSYSTEM_PRESET")
+                    .selected_text(state.system.name.as_str())
+                    .show_ui(ui, |ui| {
+                        for preset in LSystemPreset::iter() {
+                            if ui.selectable_label(false, preset.name()).clicked() {
+                                picked = Some(preset.system());
+                            }
+                        }
+
+                        if !state.library.is_empty() {
+                            ui.separator();
+                        }
+                        for system in &state.library {
+                            if ui.selectable_label(false, system.name.as_str()).clicked()
+                            {
+                                picked = Some(system.clone());
+                            }
+                        }
+                    });
+                if let Some(system) = picked {
+                    state.system = system;
+                    is_changed = true;
+                }
+                ui.end_row();
+
+                ui.label("Axiom:");
+                is_changed |= ui
+                    .add(
+                        TextEdit::singleline(&mut state.system.axiom)
+                            .desired_width(120.0),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("Iterations:");
+                ui.horizontal(|ui| {
+                    if ui.button("◀").clicked() && state.system.iterations > 0 {
+                        state.system.iterations -= 1;
+                        is_changed = true;
+                    }
+                    is_changed |= ui
+                        .add(
+                            DragValue::new(&mut state.system.iterations)
+                                .speed(0.1)
+                                .range(0..=30),
+                        )
+                        .changed();
+                    if ui.button("▶").clicked() {
+                        state.system.iterations += 1;
+                        is_changed = true;
+                    }
+                });
+                ui.end_row();
+
+                if let Ok(done) = state.status
+                    && done < state.system.iterations
+                {
+                    ui.label("");
+                    ui.label(format!(
+                        "Limited to {done} ({} modules).",
+                        LSystem::MAX_MODULES
+                    ));
+                    ui.end_row();
+                }
+
+                ui.label("Angle (deg):");
+                is_changed |= ui
+                    .add(
+                        DragValue::new(&mut state.system.turtle.angle)
+                            .speed(0.5)
+                            .range(-360.0..=360.0),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("Seed:");
+                ui.horizontal(|ui| {
+                    is_changed |= ui
+                        .add(DragValue::new(&mut state.system.seed.0).speed(1))
+                        .changed();
+                    if ui.button("Randomize").clicked() {
+                        state.system.seed.randomize();
+                        is_changed = true;
+                    }
+                });
+                ui.end_row();
+
+                ui.label("Fit to Size:");
+                is_changed |= ui.checkbox(&mut state.is_fitted, "").changed();
+                ui.end_row();
+
+                if state.is_fitted {
+                    ui.label("Size:");
+                    is_changed |= ui
+                        .add(
+                            DragValue::new(&mut state.size.0)
+                                .speed(0.1)
+                                .range(1.0..=100.0),
+                        )
+                        .changed();
+                } else {
+                    ui.label("Step:");
+                    is_changed |= ui
+                        .add(
+                            DragValue::new(&mut state.system.turtle.step.0)
+                                .speed(0.01)
+                                .range(0.01..=10.0),
+                        )
+                        .changed();
+                }
+                ui.end_row();
+
+                ui.label("Line Width:");
+                is_changed |= ui
+                    .add(
+                        DragValue::new(&mut state.system.turtle.stroke.width)
+                            .speed(0.1)
+                            .range(0.1..=10.0),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("Color:");
+                is_changed |= ui
+                    .color_edit_button_srgba(&mut state.system.turtle.stroke.color)
+                    .changed();
+                ui.end_row();
+
+                ui.label("3D:");
+                is_changed |= ui.checkbox(&mut state.system.is_3d, "").changed();
+                ui.end_row();
+
+                if state.system.is_3d {
+                    // Only the projection changes, the derivation is kept.
+                    ui.label("View X (deg):");
+                    ui.add(
+                        DragValue::new(&mut state.view_x)
+                            .speed(0.5)
+                            .range(-180.0..=180.0),
+                    );
+                    ui.end_row();
+
+                    ui.label("View Y (deg):");
+                    ui.add(
+                        DragValue::new(&mut state.view_y)
+                            .speed(0.5)
+                            .range(-180.0..=180.0),
+                    );
+                    ui.end_row();
+                }
+            });
+
+        if let Err(error) = &state.status {
+            ui.label(RichText::new(error.to_string()).color(Color32::LIGHT_RED));
+        }
+
+        if is_changed {
+            state.regenerate();
+        }
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Reset L-System Settings").clicked() {
+                context.figures.lsystem = Default::default();
+                context.figures.lsystem.regenerate();
+            }
+        });
+    }
+
+    /// Rewriting rules: `predecessor : condition → successor` with a stochastic weight.
+    fn lsystem_rules(&self, ui: &mut egui::Ui, context: &mut Context) {
+        ui.label(RichText::new("Rules").color(Color32::WHITE));
+
+        ui.add_space(5.0);
+
+        let state = &mut context.figures.lsystem;
+        let mut is_changed = false;
+        let mut removed = None;
+
+        for (index, rule) in state.system.rules.iter_mut().enumerate() {
+            Grid::new(("LSYSTEM_RULE", index))
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label(format!("Rule {}:", index + 1));
+                    is_changed |= ui
+                        .add(
+                            TextEdit::singleline(&mut rule.predecessor)
+                                .desired_width(120.0),
+                        )
+                        .changed();
+                    ui.end_row();
+
+                    ui.label("Condition:");
+                    is_changed |= ui
+                        .add(
+                            TextEdit::singleline(&mut rule.condition)
+                                .desired_width(120.0),
+                        )
+                        .changed();
+                    ui.end_row();
+
+                    ui.label("Successor:");
+                    is_changed |= ui
+                        .add(
+                            TextEdit::singleline(&mut rule.successor)
+                                .desired_width(120.0),
+                        )
+                        .changed();
+                    ui.end_row();
+
+                    ui.label("Weight:");
+                    ui.horizontal(|ui| {
+                        is_changed |= ui
+                            .add(
+                                DragValue::new(&mut rule.weight)
+                                    .speed(0.01)
+                                    .range(0.0..=100.0),
+                            )
+                            .changed();
+                        if ui.button("Remove").clicked() {
+                            removed = Some(index);
+                        }
+                    });
+                    ui.end_row();
+                });
+
+            ui.add_space(5.0);
+        }
+
+        if let Some(index) = removed {
+            state.system.rules.remove(index);
+            is_changed = true;
+        }
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Add Rule").clicked() {
+                state.system.rules.push(Rule::new("F", "F"));
+                is_changed = true;
+            }
+        });
+
+        if is_changed {
+            state.regenerate();
+        }
+    }
+
+    fn lsystem_file(&self, ui: &mut egui::Ui, context: &mut Context) {
+        ui.label(RichText::new("Fractint L-System File").color(Color32::WHITE));
+
+        ui.add_space(5.0);
+
+        let state = &mut context.figures.lsystem;
+        let mut result = Ok(());
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Import…").clicked()
+                && let Some(path) = rfd::FileDialog::new()
+                    .add_filter("L-System", &[fractint::EXTENSION])
+                    .pick_file()
+            {
+                result = lsystem_file::load(&path).map(|library| {
+                    if let Some(first) = library.first() {
+                        state.load(first.clone());
+                    }
+                    state.library = library;
+                });
+            }
+        });
+
+        if !state.library.is_empty() {
+            ui.label(format!(
+                "{} entries imported, see Preset.",
+                state.library.len()
+            ));
+        }
+
+        if let Err(error) = result {
+            context.errors_channel.try_send(ErrorModal::new(error));
+        }
+    }
+
+    fn escape_settings(&self, ui: &mut egui::Ui, context: &mut Context) {
+        ui.label(RichText::new("Escape-Time Settings").color(Color32::WHITE));
+
+        ui.add_space(5.0);
+
+        let mut is_changed = false;
+
+        Grid::new("Escape-Time Settings")
+            .num_columns(2)
+            .show(ui, |ui| {
+                let fractal = &mut context.figures.escape.fractal;
+
+                ui.label("Set:");
+                ComboBox::from_id_salt("ESCAPE_KIND")
+                    .selected_text(fractal.kind.name())
+                    .show_ui(ui, |ui| {
+                        for kind in EscapeKind::iter() {
+                            is_changed |= ui
+                                .selectable_value(&mut fractal.kind, kind, kind.name())
+                                .changed();
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("Palette:");
+                ComboBox::from_id_salt("ESCAPE_PALETTE")
+                    .selected_text(fractal.palette.name())
+                    .show_ui(ui, |ui| {
+                        for palette in Palette::iter() {
+                            is_changed |= ui
+                                .selectable_value(
+                                    &mut fractal.palette,
+                                    palette,
+                                    palette.name(),
+                                )
+                                .changed();
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("Palette Period:");
+                is_changed |= ui
+                    .add(
+                        DragValue::new(&mut fractal.period)
+                            .speed(0.5)
+                            .range(1.0..=1000.0),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("Smooth Coloring:");
+                is_changed |= ui.checkbox(&mut fractal.is_smooth, "").changed();
+                ui.end_row();
+
+                ui.label("Iterations:");
+                is_changed |= ui
+                    .add(
+                        DragValue::new(&mut fractal.iterations)
+                            .speed(1)
+                            .range(1..=100_000),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("Grow with Zoom:");
+                is_changed |= ui.checkbox(&mut fractal.is_zoom_iterations, "").changed();
+                ui.end_row();
+
+                if fractal.is_zoom_iterations {
+                    ui.label("Per Octave:");
+                    is_changed |= ui
+                        .add(
+                            DragValue::new(&mut fractal.iterations_per_octave)
+                                .speed(1)
+                                .range(0..=10_000),
+                        )
+                        .changed();
+                    ui.end_row();
+                }
+
+                ui.label("");
+                ui.label(format!(
+                    "Limit: {}",
+                    fractal
+                        .max_iterations(context.viewport.geometry.pixels_per_centimeter)
+                ));
+                ui.end_row();
+
+                if fractal.kind != EscapeKind::Newton {
+                    ui.label("Bailout:");
+                    is_changed |= ui
+                        .add(
+                            DragValue::new(&mut fractal.bailout)
+                                .speed(1.0)
+                                .range(2.0..=1e6),
+                        )
+                        .changed();
+                    ui.end_row();
+                }
+
+                ui.label("Preview Pass:");
+                ui.checkbox(&mut context.figures.escape.is_preview, "");
+                ui.end_row();
+            });
+
+        ui.add_space(5.0);
+
+        let magnification = context.figures.escape.magnification(&context.viewport);
+        ui.label(format!("Magnification: ×{magnification:.3e}"));
+        ui.columns(2, |ui| {
+            ui[0].vertical_centered_justified(|ui| {
+                if ui.button("Zoom In ×2").clicked() {
+                    context.figures.escape.zoom(2.0, &mut context.viewport);
+                    is_changed = true;
+                }
+            });
+            ui[1].vertical_centered_justified(|ui| {
+                if ui.button("Zoom Out ÷2").clicked() {
+                    context.figures.escape.zoom(0.5, &mut context.viewport);
+                    is_changed = true;
+                }
+            });
+        });
+
+        if is_changed {
+            context.figures.regenerate_fractal(&context.viewport);
+        }
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Reset Escape-Time Settings").clicked() {
+                context.figures.escape.fractal.reset();
+                context.viewport.geometry.reset_offset();
+                context.viewport.geometry.reset_pixels_per_centimeter();
+                context.figures.regenerate_fractal(&context.viewport);
+            }
+        });
+    }
+
+    /// Julia parameter or the roots of the Newton polynomial.
+    fn escape_parameters(&self, ui: &mut egui::Ui, context: &mut Context) {
+        let escape = &mut context.figures.escape;
+        let mut is_changed = false;
+
+        match escape.fractal.kind {
+            EscapeKind::Mandelbrot | EscapeKind::Julia => {
+                ui.label(RichText::new("Julia Parameter").color(Color32::WHITE));
+
+                ui.add_space(5.0);
+
+                Grid::new("JULIA_PARAMETER").num_columns(2).show(ui, |ui| {
+                    ui.label("c:");
+                    ui.horizontal(|ui| {
+                        for value in
+                            [&mut escape.fractal.julia.re, &mut escape.fractal.julia.im]
+                        {
+                            is_changed |= ui
+                                .add(DragValue::new(value).speed(0.001).range(-2.0..=2.0))
+                                .changed();
+                        }
+                        ui.label("i");
+                    });
+                    ui.end_row();
+                });
+
+                ui.checkbox(
+                    &mut escape.is_julia_picking,
+                    "Pick c by clicking on the Mandelbrot set",
+                );
+
+                // Only the Julia image depends on c
+                if escape.fractal.kind == EscapeKind::Mandelbrot {
+                    is_changed = false;
+                }
+            },
+            EscapeKind::Newton => {
+                ui.label(RichText::new("Polynomial Roots").color(Color32::WHITE));
+
+                ui.add_space(5.0);
+
+                let mut removed = None;
+                Grid::new("NEWTON_ROOTS").num_columns(2).show(ui, |ui| {
+                    for (index, root) in escape.fractal.roots.iter_mut().enumerate() {
+                        ui.label(format!("r{}:", index + 1));
+                        ui.horizontal(|ui| {
+                            for value in [&mut root.re, &mut root.im] {
+                                is_changed |= ui
+                                    .add(
+                                        DragValue::new(value)
+                                            .speed(0.01)
+                                            .range(-10.0..=10.0),
+                                    )
+                                    .changed();
+                            }
+                            ui.label("i");
+                            if ui.button("Remove").clicked() {
+                                removed = Some(index);
+                            }
+                        });
+                        ui.end_row();
+                    }
+
+                    ui.label("Relaxation:");
+                    is_changed |= ui
+                        .add(
+                            DragValue::new(&mut escape.fractal.relaxation)
+                                .speed(0.01)
+                                .range(0.1..=2.0),
+                        )
+                        .changed();
+                    ui.end_row();
+                });
+
+                if let Some(index) = removed {
+                    escape.fractal.roots.remove(index);
+                    is_changed = true;
+                }
+
+                ui.columns(2, |ui| {
+                    ui[0].vertical_centered_justified(|ui| {
+                        if ui.button("Add Root").clicked() {
+                            escape.fractal.roots.push(Complex::ZERO);
+                            is_changed = true;
+                        }
+                    });
+                    ui[1].vertical_centered_justified(|ui| {
+                        let degree = escape.fractal.roots.len().max(2);
+                        if ui.button(format!("z^{degree} - 1")).clicked() {
+                            escape.fractal.roots = EscapeTime::roots_of_unity(degree);
+                            is_changed = true;
+                        }
+                    });
+                });
+            },
+        }
+
+        if is_changed {
+            context.figures.regenerate_fractal(&context.viewport);
+        }
+    }
+
+    fn attractor(&self, ui: &mut egui::Ui, context: &mut Context) {
+        ui.label(RichText::new("Strange Attractor").color(Color32::WHITE));
+
+        ui.add_space(5.0);
+
+        let mut is_changed = false;
+        let mut is_restyled = false;
+
+        Grid::new("ATTRACTOR_SETTINGS")
+            .num_columns(2)
+            .show(ui, |ui| {
+                let attractor = &mut context.figures.attractor;
+
+                ui.label("Map:");
+                let mut map = attractor.map;
+                ComboBox::from_id_salt("ATTRACTOR_MAP")
+                    .selected_text(map.name())
+                    .show_ui(ui, |ui| {
+                        for option in Map2D::iter() {
+                            ui.selectable_value(&mut map, option, option.name());
+                        }
+                    });
+                if map != attractor.map {
+                    *attractor = Attractor2D {
+                        samples: attractor.samples,
+                        color: attractor.color,
+                        ..Attractor2D::new(map)
+                    };
+                    context.figures.attractor_animation = Default::default();
+                    is_changed = true;
+                }
+                ui.end_row();
+
+                for (name, value) in attractor
+                    .map
+                    .parameter_names()
+                    .iter()
+                    .zip(attractor.parameters.iter_mut())
+                {
+                    ui.label(format!("{name}:"));
+                    is_changed |= ui
+                        .add(DragValue::new(value).speed(0.001).range(-10.0..=10.0))
+                        .changed();
+                    ui.end_row();
+                }
+
+                ui.label("Samples:");
+                is_changed |= ui
+                    .add(
+                        DragValue::new(&mut attractor.samples)
+                            .speed(10_000)
+                            .range(1_000..=10_000_000),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("Scale:");
+                is_changed |= ui
+                    .add(
+                        DragValue::new(&mut attractor.scale.0)
+                            .speed(0.1)
+                            .range(0.1..=100.0),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("Color:");
+                is_changed |= ui.color_edit_button_srgba(&mut attractor.color).changed();
+                ui.end_row();
+
+                let density = &mut context.figures.density;
+
+                ui.label("Gamma:");
+                is_restyled |= ui
+                    .add(
+                        DragValue::new(&mut density.gamma)
+                            .speed(0.05)
+                            .range(0.1..=10.0),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("Brightness:");
+                is_restyled |= ui
+                    .add(
+                        DragValue::new(&mut density.brightness)
+                            .speed(0.05)
+                            .range(0.1..=10.0),
+                    )
+                    .changed();
+                ui.end_row();
+            });
+
+        Self::cloud_progress(ui, &context.figures.cloud);
+
+        ui.vertical_centered_justified(|ui| {
+            if ui.button("Reset Attractor Settings").clicked() {
+                context.figures.attractor.reset();
+                context.figures.attractor_animation.playback.is_enabled = false;
+                is_changed = true;
+            }
+        });
+
+        if is_changed {
+            context.figures.regenerate_fractal(&context.viewport);
+        } else if is_restyled {
+            context.figures.redraw_fractal(&context.viewport);
+        }
+    }
+
+    fn cloud_progress(ui: &mut egui::Ui, cloud: &PointCloud) {
+        let (generated, total) = cloud.progress();
+        if cloud.is_busy() {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(format!("Generated {generated} of {total} points"));
+            });
+        } else {
+            ui.label(format!("Points: {generated}"));
+        }
+    }
+
+    fn attractor_animation(&self, ui: &mut egui::Ui, context: &mut Context) {
+        let attractor = &mut context.figures.attractor;
+        let animation = &mut context.figures.attractor_animation;
+
+        ui.group(|ui| {
+            ui.vertical_centered_justified(|ui| {
+                ui.label(RichText::new("Animation").color(Color32::WHITE));
+            });
+
+            ui.add_space(5.0);
+
+            Grid::new("ATTRACTOR_ANIMATION")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    let names = attractor.map.parameter_names();
+
+                    ui.label("Parameter:");
+                    ComboBox::from_id_salt("ATTRACTOR_PARAMETER")
+                        .selected_text(
+                            names.get(animation.parameter).copied().unwrap_or("-"),
+                        )
+                        .show_ui(ui, |ui| {
+                            for (index, name) in names.iter().enumerate() {
+                                ui.add_enabled_ui(!animation.playback.is_enabled, |ui| {
+                                    ui.selectable_value(
+                                        &mut animation.parameter,
+                                        index,
+                                        *name,
+                                    );
+                                });
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label("Amplitude:");
+                    ui.add(
+                        DragValue::new(&mut animation.amplitude)
+                            .speed(0.01)
+                            .range(0.01..=1.0),
+                    );
+                    ui.end_row();
+
+                    ui.label("Status:");
+                    if animation.playback.is_enabled {
+                        ui.colored_label(Color32::LIGHT_GREEN, "Running");
+                    } else {
+                        ui.colored_label(Color32::RED, "Stopped");
+                    }
+                    ui.end_row();
+                });
+
+            widgets::playback::show(ui, &mut animation.playback, "ATTRACTOR");
+
+            ui.add_space(5.0);
+
+            ui.vertical_centered_justified(|ui| {
+                let text = if animation.playback.is_enabled {
+                    "Stop"
+                } else {
+                    "Start"
+                };
+
+                if ui.button(text).clicked() {
+                    animation.toggle(&attractor.parameters);
+                }
+            });
+        });
+    }
+
+    fn keyframes(&self, ui: &mut egui::Ui, context: &mut Context) {
+        let figures = &mut context.figures;
+
+        ui.group(|ui| {
+            ui.vertical_centered_justified(|ui| {
+                ui.label(RichText::new("Keyframes").color(Color32::WHITE));
+            });
+
+            ui.add_space(5.0);
+
+            widgets::keyframes::show(
+                ui,
+                &mut figures.keyframes,
+                &mut figures.attractor,
+                &figures.timeline,
+            );
+        });
+    }
+
     fn fractal_settings(&self, ui: &mut egui::Ui, context: &mut Context) {
         ui.label(RichText::new("Fractal Settings").color(Color32::WHITE));
 
         ui.add_space(5.0);
 
         Grid::new("Fractal Settings").num_columns(2).show(ui, |ui| {
-            ui.label("Point Radius:");
-            if ui
-                .add(
-                    DragValue::new(&mut context.figures.fractal.radius)
-                        .speed(0.1)
-                        .range(0.1..=100.0),
-                )
-                .changed()
-            {
-                context.figures.regenerate_fractal(&context.viewport);
-            };
+            ui.label("Preset:");
+            let mut picked = None;
+            ComboBox::from_id_salt("FRACTAL_PRESET")
+                .selected_text(context.figures.fractal.name.as_str())
+                .show_ui(ui, |ui| {
+                    for preset in FractalPreset::iter() {
+                        if ui.selectable_label(false, preset.name()).clicked() {
+                            picked = Some(preset.fractal());
+                        }
+                    }
+
+                    if !context.figures.library.is_empty() {
+                        ui.separator();
+                    }
+                    for fractal in &context.figures.library {
+                        if ui.selectable_label(false, fractal.name.as_str()).clicked() {
+                            picked = Some(fractal.clone());
+                        }
+                    }
+                });
+            if let Some(fractal) = picked {
+                context.figures.load_fractal(fractal, &context.viewport);
+            }
             ui.end_row();
 
-            ui.label("Iterations:");
-            if ui
-                .add(
-                    DragValue::new(&mut context.figures.fractal.iterations)
-                        .speed(1)
-                        .range(1..=100_000),
-                )
-                .changed()
-            {
+            ui.label("Rendering:");
+            let mut rendering = context.figures.rendering;
+            ComboBox::from_id_salt("FRACTAL_RENDERING")
+                .selected_text(rendering.name())
+                .show_ui(ui, |ui| {
+                    for mode in Rendering::ALL {
+                        ui.selectable_value(&mut rendering, mode, mode.name());
+                    }
+                });
+            if rendering != context.figures.rendering {
+                context.figures.rendering = rendering;
                 context.figures.regenerate_fractal(&context.viewport);
-            };
+            }
             ui.end_row();
+
+            let mut is_changed = false;
+            let mut is_restyled = false;
+
+            ui.label("Seed:");
+            ui.horizontal(|ui| {
+                is_changed |= ui
+                    .add(DragValue::new(&mut context.figures.fractal.seed.0).speed(1))
+                    .changed();
+                if ui.button("Randomize").clicked() {
+                    context.figures.fractal.seed.randomize();
+                    is_changed = true;
+                }
+            });
+            ui.end_row();
+
+            match context.figures.rendering {
+                Rendering::Points => {
+                    ui.label("Point Radius:");
+                    is_restyled |= ui
+                        .add(
+                            DragValue::new(&mut context.figures.fractal.radius)
+                                .speed(0.1)
+                                .range(0.1..=100.0),
+                        )
+                        .changed();
+                    ui.end_row();
+
+                    ui.label("Iterations:");
+                    is_changed |= ui
+                        .add(
+                            DragValue::new(&mut context.figures.fractal.iterations)
+                                .speed(100)
+                                .range(1..=2_000_000),
+                        )
+                        .changed();
+                    ui.end_row();
+                },
+                Rendering::Density => {
+                    let density = &mut context.figures.density;
+
+                    ui.label("Samples:");
+                    is_changed |= ui
+                        .add(
+                            DragValue::new(&mut density.samples)
+                                .speed(10_000)
+                                .range(1_000..=10_000_000),
+                        )
+                        .changed();
+                    ui.end_row();
+
+                    ui.label("Gamma:");
+                    is_restyled |= ui
+                        .add(
+                            DragValue::new(&mut density.gamma)
+                                .speed(0.05)
+                                .range(0.1..=10.0),
+                        )
+                        .changed();
+                    ui.end_row();
+
+                    ui.label("Brightness:");
+                    is_restyled |= ui
+                        .add(
+                            DragValue::new(&mut density.brightness)
+                                .speed(0.05)
+                                .range(0.1..=10.0),
+                        )
+                        .changed();
+                    ui.end_row();
+                },
+                Rendering::Deterministic => {
+                    let deterministic = &mut context.figures.deterministic;
+                    let maps = context.figures.fractal.systems.len();
+
+                    ui.label("Initial Shape:");
+                    ComboBox::from_id_salt("DETERMINISTIC_SHAPE")
+                        .selected_text(deterministic.shape.name())
+                        .show_ui(ui, |ui| {
+                            for shape in InitialShape::iter() {
+                                is_changed |= ui
+                                    .selectable_value(
+                                        &mut deterministic.shape,
+                                        shape,
+                                        shape.name(),
+                                    )
+                                    .changed();
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label("Generation:");
+                    ui.horizontal(|ui| {
+                        if ui.button("◀").clicked() && deterministic.generations > 0 {
+                            deterministic.generations -= 1;
+                            is_changed = true;
+                        }
+                        is_changed |= ui
+                            .add(
+                                DragValue::new(&mut deterministic.generations)
+                                    .speed(0.1)
+                                    .range(0..=20),
+                            )
+                            .changed();
+                        if ui.button("▶").clicked() {
+                            deterministic.generations += 1;
+                            is_changed = true;
+                        }
+                    });
+                    ui.end_row();
+
+                    let reachable = deterministic.reachable_generations(maps);
+                    if reachable < deterministic.generations {
+                        ui.label("");
+                        ui.label(format!(
+                            "Limited to {reachable} ({} lines).",
+                            DeterministicIFS::MAX_LINES
+                        ));
+                        ui.end_row();
+                    }
+
+                    ui.label("Line Width:");
+                    is_changed |= ui
+                        .add(
+                            DragValue::new(&mut deterministic.width)
+                                .speed(0.1)
+                                .range(0.1..=10.0),
+                        )
+                        .changed();
+                    ui.end_row();
+                },
+            }
+
+            if is_changed {
+                context.figures.regenerate_fractal(&context.viewport);
+            } else if is_restyled {
+                context.figures.redraw_fractal(&context.viewport);
+            }
         });
 
+        if context.figures.rendering != Rendering::Deterministic {
+            Self::cloud_progress(ui, &context.figures.cloud);
+        }
+
         ui.vertical_centered_justified(|ui| {
             if ui.button("Generate").clicked() {
                 context.figures.regenerate_fractal(&context.viewport);
@@ -149,4 +1154,242 @@ impl SettingsComponent {
             }
         });
     }
+
+    /// Coefficients of every map: x' = a·x + b·y + c, y' = d·x + e·y + f.
+    fn maps(&self, ui: &mut egui::Ui, context: &mut Context) {
+        ui.label(RichText::new("Maps").color(Color32::WHITE));
+
+        ui.add_space(5.0);
+
+        ui.label("x' = a·x + b·y + c");
+        ui.label("y' = d·x + e·y + f");
+
+        ui.add_space(5.0);
+
+        let mut is_changed = false;
+        let mut removed = None;
+        let is_removable = context.figures.fractal.systems.len() > 1;
+
+        for (index, system) in context.figures.fractal.systems.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(format!("Map {}", index + 1));
+                if ui
+                    .add_enabled(is_removable, egui::Button::new("Remove"))
+                    .clicked()
+                {
+                    removed = Some(index);
+                }
+            });
+
+            Grid::new(("MAP_COEFFICIENTS", index))
+                .num_columns(4)
+                .show(ui, |ui| {
+                    let mut coefficient =
+                        |ui: &mut egui::Ui, name: &str, value: &mut f64| {
+                            ui.label(name);
+                            is_changed |= ui
+                                .add(DragValue::new(value).speed(0.01).max_decimals(6))
+                                .changed();
+                        };
+
+                    coefficient(ui, "a:", &mut system.a);
+                    coefficient(ui, "b:", &mut system.b);
+                    ui.end_row();
+
+                    coefficient(ui, "d:", &mut system.d);
+                    coefficient(ui, "e:", &mut system.e);
+                    ui.end_row();
+
+                    coefficient(ui, "c:", &mut system.c);
+                    coefficient(ui, "f:", &mut system.f);
+                    ui.end_row();
+
+                    ui.label("p:");
+                    is_changed |= ui
+                        .add(
+                            DragValue::new(&mut system.p)
+                                .speed(0.01)
+                                .range(0.0..=1.0)
+                                .max_decimals(6),
+                        )
+                        .changed();
+
+                    ui.label("Color:");
+                    is_changed |= ui.color_edit_button_srgba(&mut system.color).changed();
+                    ui.end_row();
+                });
+
+            is_changed |= self.variations(ui, index, system);
+
+            ui.add_space(5.0);
+        }
+
+        if let Some(index) = removed {
+            context.figures.fractal.systems.remove(index);
+            is_changed = true;
+        }
+
+        let total = context.figures.fractal.total_probability();
+        ui.label(format!("Σp = {total:.4}"));
+
+        ui.columns(2, |ui| {
+            ui[0].vertical_centered_justified(|ui| {
+                if ui.button("Add Map").clicked() {
+                    let systems = &mut context.figures.fractal.systems;
+                    systems.push(EquationSystem::new_colored(
+                        [0.5, 0.0, 0.0, 0.5, 0.0, 0.0, 0.1],
+                        palette_color(systems.len()),
+                    ));
+                    is_changed = true;
+                }
+            });
+            ui[1].vertical_centered_justified(|ui| {
+                if ui.button("Normalize p").clicked() {
+                    context.figures.fractal.normalize_probabilities();
+                    is_changed = true;
+                }
+            });
+        });
+
+        if is_changed {
+            context.figures.regenerate_fractal(&context.viewport);
+        }
+    }
+
+    fn ifs_file(&self, ui: &mut egui::Ui, context: &mut Context) {
+        ui.label(RichText::new("Fractint IFS File").color(Color32::WHITE));
+
+        ui.add_space(5.0);
+
+        let mut result = Ok(());
+        ui.columns(2, |ui| {
+            ui[0].vertical_centered_justified(|ui| {
+                if ui.button("Import…").clicked()
+                    && let Some(path) = rfd::FileDialog::new()
+                        .add_filter("IFS", &[ifs::EXTENSION])
+                        .pick_file()
+                {
+                    result = ifs_file::load(&path).map(|library| {
+                        if let Some(first) = library.first() {
+                            context
+                                .figures
+                                .load_fractal(first.clone(), &context.viewport);
+                        }
+                        context.figures.library = library;
+                    });
+                }
+            });
+            ui[1].vertical_centered_justified(|ui| {
+                if ui.button("Export…").clicked()
+                    && let Some(path) = rfd::FileDialog::new()
+                        .add_filter("IFS", &[ifs::EXTENSION])
+                        .save_file()
+                {
+                    result = ifs_file::save(&path, &context.figures.fractal);
+                }
+            });
+        });
+
+        if !context.figures.library.is_empty() {
+            ui.label(format!(
+                "{} entries imported, see Preset.",
+                context.figures.library.len()
+            ));
+        }
+
+        if let Err(error) = result {
+            context.errors_channel.try_send(ErrorModal::new(error));
+        }
+    }
+
+    fn map_editor(&self, ui: &mut egui::Ui, context: &mut Context) {
+        ui.label(RichText::new("Visual Map Editor").color(Color32::WHITE));
+
+        ui.add_space(5.0);
+
+        let editor = &mut context.figures.editor;
+        Grid::new("MAP_EDITOR").num_columns(2).show(ui, |ui| {
+            ui.label("Show Maps:");
+            ui.checkbox(&mut editor.is_enabled, "");
+            ui.end_row();
+
+            ui.label("Auto p (|det|):");
+            ui.checkbox(&mut editor.is_auto_probability, "");
+            ui.end_row();
+
+            ui.label("Square X:");
+            ui.add(DragValue::new(&mut editor.origin.x.0).speed(0.1));
+            ui.end_row();
+
+            ui.label("Square Y:");
+            ui.add(DragValue::new(&mut editor.origin.y.0).speed(0.1));
+            ui.end_row();
+
+            ui.label("Square Size:");
+            ui.add(
+                DragValue::new(&mut editor.size.0)
+                    .speed(0.1)
+                    .range(0.1..=100.0),
+            );
+            ui.end_row();
+        });
+
+        ui.label("Drag the center to move a map, the corners to scale and shear it, and the white corner to rotate it.");
+
+        ui.add_space(5.0);
+
+        ui.columns(2, |ui| {
+            ui[0].vertical_centered_justified(|ui| {
+                if ui.button("Fit Square").clicked() {
+                    context.figures.editor.fit(&context.figures.fractal);
+                }
+            });
+            ui[1].vertical_centered_justified(|ui| {
+                if ui.button("Suggest p").clicked() {
+                    context.figures.fractal.suggest_probabilities();
+                    context.figures.regenerate_fractal(&context.viewport);
+                }
+            });
+        });
+    }
+
+    /// Flame variations of a map: the used ones with their weights, and a picker to add more.
+    fn variations(
+        &self, ui: &mut egui::Ui, index: usize, system: &mut EquationSystem,
+    ) -> bool {
+        let mut is_changed = false;
+
+        Grid::new(("MAP_VARIATIONS", index))
+            .num_columns(2)
+            .show(ui, |ui| {
+                for variation in Variation::iter() {
+                    if system.weight(variation) == 0.0 {
+                        continue;
+                    }
+
+                    ui.label(format!("{}:", variation.name()));
+                    is_changed |= ui
+                        .add(DragValue::new(system.weight_mut(variation)).speed(0.01))
+                        .changed();
+                    ui.end_row();
+                }
+
+                ui.label("Add Variation:");
+                ComboBox::from_id_salt(("MAP_VARIATION_ADD", index))
+                    .selected_text("…")
+                    .show_ui(ui, |ui| {
+                        for variation in Variation::iter() {
+                            if system.weight(variation) == 0.0
+                                && ui.selectable_label(false, variation.name()).clicked()
+                            {
+                                *system.weight_mut(variation) = 1.0;
+                                is_changed = true;
+                            }
+                        }
+                    });
+                ui.end_row();
+            });
+
+        is_changed
+    }
 }
@@ -0,0 +1,19 @@
+use crate::context::Context;
+use crate::errors::ProjectError;
+use crate::ui::modals::error::ErrorModal;
+
+#[derive(Debug, Default)]
+pub struct TimelineComponent;
+
+impl TimelineComponent {
+    pub fn show(&mut self, ui: &mut egui::Ui, context: &mut Context) {
+        let state = &mut context.figures;
+        if let Some(error) =
+            widgets::timeline::panel(ui, &mut state.timeline, Some(&mut state.export))
+        {
+            context
+                .errors_channel
+                .try_send(ErrorModal::new(ProjectError::from(error)));
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use crossbeam::channel::{Receiver, Sender};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+
+/// Runs one job at a time on a background thread.
+/// Starting a new job cancels the previous one, and its late results are dropped.
+#[derive(Debug)]
+pub struct Worker<T> {
+    tx: Sender<(u64, T)>,
+    rx: Receiver<(u64, T)>,
+    generation: u64,
+    is_cancelled: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl<T> Default for Worker<T> {
+    fn default() -> Self {
+        let (tx, rx) = crossbeam::channel::unbounded();
+
+        Self {
+            tx,
+            rx,
+            generation: 0,
+            is_cancelled: Arc::new(AtomicBool::new(false)),
+            handle: None,
+        }
+    }
+}
+
+impl<T: Send + 'static> Worker<T> {
+    pub fn start(&mut self, job: impl FnOnce(&Job<T>) + Send + 'static) {
+        self.cancel();
+
+        let job_state = Job {
+            generation: self.generation,
+            is_cancelled: Arc::clone(&self.is_cancelled),
+            tx: self.tx.clone(),
+        };
+
+        let spawned = std::thread::Builder::new()
+            .name("worker".into())
+            .spawn(move || job(&job_state));
+        match spawned {
+            Ok(handle) => self.handle = Some(handle),
+            Err(error) => log::error!("Failed to start a background job: {}", error),
+        }
+    }
+
+    /// Latest result of the current job, older ones are skipped.
+    pub fn try_recv(&self) -> Option<T> {
+        self.rx
+            .try_iter()
+            .filter(|(generation, _)| *generation == self.generation)
+            .last()
+            .map(|(_, item)| item)
+    }
+
+    /// All results of the current job that arrived since the last call, in order.
+    pub fn drain(&self) -> Vec<T> {
+        self.rx
+            .try_iter()
+            .filter(|(generation, _)| *generation == self.generation)
+            .map(|(_, item)| item)
+            .collect()
+    }
+}
+
+impl<T> Worker<T> {
+    pub fn cancel(&mut self) {
+        self.is_cancelled.store(true, Ordering::Relaxed);
+        self.is_cancelled = Arc::new(AtomicBool::new(false));
+        self.generation += 1;
+        self.handle = None;
+    }
+
+    pub fn is_busy(&self) -> bool {
+        self.handle
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished())
+    }
+}
+
+impl<T> Drop for Worker<T> {
+    /// A replaced worker stops its job instead of finishing it for nobody.
+    fn drop(&mut self) {
+        self.is_cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Handle given to a running job.
+#[derive(Debug)]
+pub struct Job<T> {
+    generation: u64,
+    is_cancelled: Arc<AtomicBool>,
+    tx: Sender<(u64, T)>,
+}
+
+impl<T> Job<T> {
+    pub fn is_cancelled(&self) -> &AtomicBool {
+        &self.is_cancelled
+    }
+
+    pub fn send(&self, item: T) {
+        if self.is_cancelled.load(Ordering::Relaxed) {
+            return;
+        }
+        // The receiver lives as long as the worker, a closed channel only means
+        // that nobody waits for the result anymore.
+        let _ = self.tx.send((self.generation, item));
+    }
+}
@@ -1,27 +1,136 @@
-pub mod cone;
+use crate::animations::easing::Easing;
+use crate::animations::timeline::Timeline;
+use std::ops::RangeInclusive;
+use strum_macros::EnumIter;
+
+pub mod attractor;
+pub mod clock;
 pub mod contour;
+pub mod easing;
 pub mod epicycloid;
+pub mod export;
+pub mod keyframes;
+pub mod morph;
+pub mod orientation;
 pub mod star;
+pub mod surface;
+pub mod timeline;
+pub mod trail;
+pub mod transformation;
 pub mod walker;
 
-#[derive(Debug)]
-pub enum Direction {
-    Increase,
-    Decrease,
+/// Animation driven by the shared [`Timeline`]. Animations keep no clock of their
+/// own: the target is put into its state at the time of the timeline, so pausing,
+/// scrubbing and changing the speed work the same way for all of them.
+pub trait Animation {
+    /// What the animation changes.
+    type Target<'a>;
+
+    fn playback(&self) -> &Playback;
+    fn playback_mut(&mut self) -> &mut Playback;
+
+    /// Puts the target into its state at `time` seconds of the timeline.
+    fn apply(&mut self, time: f64, target: Self::Target<'_>);
+
+    /// Applies the animation at the time of the timeline, if it is enabled.
+    /// Returns whether it was applied.
+    fn update(&mut self, timeline: &Timeline, target: Self::Target<'_>) -> bool {
+        if !self.playback().is_enabled {
+            return false;
+        }
+
+        self.apply(timeline.time(), target);
+
+        true
+    }
+}
+
+/// How an animation maps the time of the timeline to its progress.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Playback {
+    pub is_enabled: bool,
+    /// Seconds from the start to the end.
+    pub duration: f64,
+    pub mode: PlaybackMode,
+    pub easing: Easing,
+    /// Runs from the end to the start.
+    pub is_reversed: bool,
 }
 
-impl Direction {
-    pub fn factor(&self) -> f64 {
-        match self {
-            Self::Increase => 1.0,
-            Self::Decrease => -1.0,
+impl Playback {
+    pub fn new(duration: f64, mode: PlaybackMode) -> Self {
+        Self {
+            is_enabled: false,
+            duration,
+            mode,
+            easing: Easing::Linear,
+            is_reversed: false,
         }
     }
 
-    pub fn toggle(&mut self) {
-        *self = match self {
-            Self::Increase => Self::Decrease,
-            Self::Decrease => Self::Increase,
+    /// Eased position between the start (0) and the end (1) at the given time.
+    pub fn progress(&self, time: f64) -> f64 {
+        let phase = time / self.duration.max(1e-6);
+        let t = match self.mode {
+            PlaybackMode::Once => phase.clamp(0.0, 1.0),
+            PlaybackMode::Loop => phase.rem_euclid(1.0),
+            PlaybackMode::PingPong => {
+                let phase = phase.rem_euclid(2.0);
+                if phase > 1.0 { 2.0 - phase } else { phase }
+            },
         };
+        let t = if self.is_reversed { 1.0 - t } else { t };
+
+        self.easing.apply(t)
+    }
+
+    /// Value of the range at the progress of the given time.
+    pub fn lerp(&self, time: f64, range: &RangeInclusive<f64>) -> f64 {
+        range.start() + (range.end() - range.start()) * self.progress(time)
+    }
+
+    pub fn toggle(&mut self) {
+        self.is_enabled = !self.is_enabled;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum PlaybackMode {
+    /// From the start to the end, then holds the end.
+    Once,
+    /// From the start to the end, then jumps back to the start.
+    Loop,
+    /// From the start to the end and back.
+    PingPong,
+}
+
+impl PlaybackMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Once => "Once",
+            Self::Loop => "Loop",
+            Self::PingPong => "Ping-Pong",
+        }
+    }
+}
+
+/// Numeric parameter of a figure, which can be edited in the UI or driven by
+/// keyframes.
+pub struct Parameter<'a> {
+    pub name: &'static str,
+    pub value: &'a mut f64,
+    pub range: RangeInclusive<f64>,
+}
+
+impl<'a> Parameter<'a> {
+    pub fn new(
+        name: &'static str, value: &'a mut f64, range: RangeInclusive<f64>,
+    ) -> Self {
+        Self { name, value, range }
     }
 }
+
+/// Figure whose numeric parameters can be animated with keyframes.
+pub trait Animatable {
+    fn parameters(&mut self) -> Vec<Parameter<'_>>;
+}
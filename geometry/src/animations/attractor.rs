@@ -0,0 +1,61 @@
+use crate::animations::{Animation, Playback, PlaybackMode};
+use crate::fractals::attractors::Parameters;
+
+/// Field: one parameter of an attractor, swept back and forth around the value
+/// it had when the animation was started.
+#[derive(Debug)]
+pub struct AnimationAttractor {
+    /// Index of the animated parameter.
+    pub parameter: usize,
+    /// Half-width of the sweep, relative to the starting value.
+    pub amplitude: f64,
+    pub playback: Playback,
+
+    base: f64,
+}
+
+impl Default for AnimationAttractor {
+    fn default() -> Self {
+        Self {
+            parameter: 0,
+            amplitude: 0.1,
+            playback: Playback::new(2.0, PlaybackMode::PingPong),
+
+            base: 0.0,
+        }
+    }
+}
+
+impl Animation for AnimationAttractor {
+    type Target<'a> = &'a mut Parameters;
+
+    fn playback(&self) -> &Playback {
+        &self.playback
+    }
+
+    fn playback_mut(&mut self) -> &mut Playback {
+        &mut self.playback
+    }
+
+    fn apply(&mut self, time: f64, parameters: Self::Target<'_>) {
+        let Some(field) = parameters.get_mut(self.parameter) else {
+            return;
+        };
+
+        // Parameters equal to zero are still swept a little
+        let spread = (self.base.abs() * self.amplitude).max(0.01);
+        *field = self
+            .playback
+            .lerp(time, &(self.base - spread..=self.base + spread));
+    }
+}
+
+impl AnimationAttractor {
+    pub fn toggle(&mut self, parameters: &Parameters) {
+        self.playback.toggle();
+
+        if self.playback.is_enabled {
+            self.base = parameters.get(self.parameter).copied().unwrap_or(0.0);
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use strum_macros::EnumIter;
+
+/// How the time of the timeline moves on from one frame to the next.
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum ClockMode {
+    /// By the real duration of the frame.
+    RealTime,
+    /// By one step per frame, however long the frame took.
+    FixedStep,
+    /// Only when a step is asked for.
+    Manual,
+}
+
+impl ClockMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::RealTime => "Real Time",
+            Self::FixedStep => "Fixed Step",
+            Self::Manual => "Manual",
+        }
+    }
+}
+
+/// Source of the time deltas of a [`Timeline`](crate::animations::timeline::Timeline).
+/// It knows nothing about the UI: the caller passes the real duration of every
+/// frame, so the same sequence of frames always gives the same times.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Clock {
+    pub mode: ClockMode,
+    /// Steps per second of the fixed-step and manual modes.
+    pub fps: f64,
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self {
+            mode: ClockMode::RealTime,
+            fps: 30.0,
+        }
+    }
+}
+
+impl Clock {
+    /// Longest frame that is still treated as real time. Longer pauses, like a
+    /// window that was minimized, do not make the animations jump.
+    const MAX_FRAME: f64 = 0.1;
+
+    /// Seconds of one fixed step.
+    pub fn step(&self) -> f64 {
+        1.0 / self.fps.max(1.0)
+    }
+
+    /// Time of the given step from the start. Computed from the index rather than
+    /// summed, so that a frame can be reproduced exactly.
+    pub fn frame_time(&self, frame: u64) -> f64 {
+        frame as f64 / self.fps.max(1.0)
+    }
+
+    /// Index of the step at the given time.
+    pub fn frame(&self, time: f64) -> u64 {
+        (time * self.fps.max(1.0)).round().max(0.0) as u64
+    }
+
+    /// Seconds to move on by after a frame that took `elapsed` seconds.
+    pub fn delta(&self, elapsed: f64) -> f64 {
+        match self.mode {
+            ClockMode::RealTime => elapsed.clamp(0.0, Self::MAX_FRAME),
+            ClockMode::FixedStep => self.step(),
+            ClockMode::Manual => 0.0,
+        }
+    }
+}
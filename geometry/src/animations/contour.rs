@@ -1,121 +1,94 @@
-use crate::animations::Direction;
+use crate::animations::morph::{Interpolation, Morph, MorphShape};
+use crate::animations::{Animation, Playback, PlaybackMode};
 use crate::figures::contour::Contour;
-use crate::smooth::ferguson::{FergusonPoint, Knot};
+use crate::smooth::ferguson::{FergusonCurve, FergusonPoint, Knot};
 use crate::units::Centimeter;
 
+/// Morph of the contour from one shape into another.
 #[derive(Debug)]
 pub struct AnimationContour {
-    pub is_enabled: bool,
-    pub speed: f64,
-
-    t: f64,
-    direction: Direction,
-
-    // Start state
-    shark_knots: Vec<Knot>,
-    // End state
-    circle_knots: Vec<Knot>,
+    pub playback: Playback,
+    pub interpolation: Interpolation,
+
+    source: MorphShape,
+    target: MorphShape,
+    /// Knots of the morphed contour.
+    samples: usize,
+    /// Built on the first frame after the shapes change.
+    morph: Option<Morph>,
 }
 
 impl Default for AnimationContour {
     fn default() -> Self {
-        let shark = Self::shark_contour();
-        let circle = Self::circle_contour(shark.len(), Centimeter(12.0));
-
         Self {
-            is_enabled: false,
-            direction: Direction::Increase,
-            t: 0.0,
-            speed: 0.5,
-            shark_knots: shark,
-            circle_knots: circle,
-        }
-    }
-}
+            playback: Playback::new(2.0, PlaybackMode::PingPong),
+            interpolation: Interpolation::Linear,
 
-impl AnimationContour {
-    pub fn step(&mut self, ui: &egui::Ui, current_knots: &mut [Knot]) {
-        if !self.is_enabled {
-            return;
-        }
-
-        let dt = ui.input(|i| i.stable_dt).min(0.1) as f64;
-
-        match self.direction {
-            Direction::Increase => {
-                self.t += self.speed * dt;
-                if self.t >= 1.0 {
-                    self.t = 1.0;
-                    self.direction.toggle();
-                }
+            source: MorphShape::Ferguson {
+                knots: Self::shark_contour(),
+                is_closed: true,
             },
-            Direction::Decrease => {
-                self.t -= self.speed * dt;
-                if self.t <= 0.0 {
-                    self.t = 0.0;
-                    self.direction.toggle();
-                }
+            target: MorphShape::Ferguson {
+                knots: Self::circle_contour(16, Centimeter(12.0)),
+                is_closed: true,
             },
+            samples: 64,
+            morph: None,
         }
-
-        self.interpolate(current_knots);
-
-        ui.ctx().request_repaint();
     }
+}
 
-    /// Formula: R(u) = R_shark * (1 - t) + R_circle * t
-    fn interpolate(&self, target_knots: &mut [Knot]) {
-        if target_knots.len() != self.shark_knots.len()
-            || target_knots.len() != self.circle_knots.len()
-        {
-            return;
-        }
-
-        let t = self.t;
-        let one_minus_t = 1.0 - t;
-
-        for (i, knot) in target_knots.iter_mut().enumerate() {
-            let shark = &self.shark_knots[i];
-            let circle = &self.circle_knots[i];
+impl Animation for AnimationContour {
+    type Target<'a> = &'a mut FergusonCurve;
 
-            let sx = shark.control.point.coordinates.x.value();
-            let sy = shark.control.point.coordinates.y.value();
+    fn playback(&self) -> &Playback {
+        &self.playback
+    }
 
-            let cx = circle.control.point.coordinates.x.value();
-            let cy = circle.control.point.coordinates.y.value();
+    fn playback_mut(&mut self) -> &mut Playback {
+        &mut self.playback
+    }
 
-            knot.control.point.coordinates.x = Centimeter(sx * one_minus_t + cx * t);
-            knot.control.point.coordinates.y = Centimeter(sy * one_minus_t + cy * t);
+    fn apply(&mut self, time: f64, curve: Self::Target<'_>) {
+        let t = self.playback.progress(time);
+        let morph = self
+            .morph
+            .get_or_insert_with(|| Morph::new(&self.source, &self.target, self.samples));
 
-            let tx_s = shark.tangent.point.coordinates.x.value();
-            let ty_s = shark.tangent.point.coordinates.y.value();
+        curve.knots = morph.knots(t, self.interpolation);
+        curve.is_closed = morph.is_closed();
+    }
+}
 
-            let tx_c = circle.tangent.point.coordinates.x.value();
-            let ty_c = circle.tangent.point.coordinates.y.value();
+impl AnimationContour {
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
 
-            knot.tangent.point.coordinates.x = Centimeter(tx_s * one_minus_t + tx_c * t);
-            knot.tangent.point.coordinates.y = Centimeter(ty_s * one_minus_t + ty_c * t);
-        }
+    pub fn set_source(&mut self, shape: MorphShape) {
+        self.source = shape;
+        self.morph = None;
     }
 
-    pub fn play_forward(&mut self) {
-        self.direction = Direction::Increase;
-        self.is_enabled = true;
+    pub fn set_target(&mut self, shape: MorphShape) {
+        self.target = shape;
+        self.morph = None;
     }
 
-    pub fn play_backward(&mut self) {
-        self.direction = Direction::Decrease;
-        self.is_enabled = true;
+    pub fn set_samples(&mut self, samples: usize) {
+        self.samples = samples;
+        self.morph = None;
     }
 
-    pub fn reset(&mut self, current_knots: &mut [Knot]) {
-        self.is_enabled = false;
-        self.t = 0.0;
-        self.interpolate(current_knots);
+    /// Stops the animation and puts the source shape back.
+    pub fn reset(&mut self, curve: &mut FergusonCurve) {
+        self.playback.is_enabled = false;
+        curve.knots = self.source.knots();
+        curve.is_closed = self.source.is_closed();
     }
 
     pub fn toggle(&mut self) {
-        self.is_enabled = !self.is_enabled;
+        self.playback.toggle();
     }
 
     pub fn shark_contour() -> Vec<Knot> {
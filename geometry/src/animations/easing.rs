@@ -0,0 +1,81 @@
+use std::f64::consts::PI;
+use strum_macros::EnumIter;
+
+/// Curves that map the linear progress of an animation, from 0 to 1,
+/// to the progress that is shown. All of them keep 0 and 1 in place.
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum Easing {
+    Linear,
+    /// Starts slowly, t².
+    EaseIn,
+    /// Ends slowly, 1 - (1 - t)².
+    EaseOut,
+    /// Starts and ends slowly, cubic.
+    EaseInOut,
+    /// Half a period of the cosine.
+    Sine,
+    /// Overshoots the end and settles back, like a spring.
+    Elastic,
+    /// Hits the end and bounces off it a few times.
+    Bounce,
+}
+
+impl Easing {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Linear => "Linear",
+            Self::EaseIn => "Ease In",
+            Self::EaseOut => "Ease Out",
+            Self::EaseInOut => "Ease In-Out",
+            Self::Sine => "Sine",
+            Self::Elastic => "Elastic",
+            Self::Bounce => "Bounce",
+        }
+    }
+
+    pub fn apply(&self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+
+        match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t,
+            Self::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Self::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            },
+            Self::Sine => (1.0 - (PI * t).cos()) / 2.0,
+            Self::Elastic => {
+                if t == 0.0 || t == 1.0 {
+                    t
+                } else {
+                    2f64.powf(-10.0 * t) * ((10.0 * t - 0.75) * 2.0 * PI / 3.0).sin()
+                        + 1.0
+                }
+            },
+            Self::Bounce => Self::bounce(t),
+        }
+    }
+
+    /// Parabolic arcs of decreasing height, each one touching 1.
+    fn bounce(t: f64) -> f64 {
+        const N: f64 = 7.5625;
+        const D: f64 = 2.75;
+
+        if t < 1.0 / D {
+            N * t * t
+        } else if t < 2.0 / D {
+            let t = t - 1.5 / D;
+            N * t * t + 0.75
+        } else if t < 2.5 / D {
+            let t = t - 2.25 / D;
+            N * t * t + 0.9375
+        } else {
+            let t = t - 2.625 / D;
+            N * t * t + 0.984375
+        }
+    }
+}
@@ -1,51 +1,41 @@
-use crate::animations::Direction;
+use crate::animations::{Animation, Playback, PlaybackMode};
 use crate::figures::epicycloid::Epicycloid;
 use std::ops::RangeInclusive;
 
 /// Field: Pen Offset
 #[derive(Debug)]
 pub struct AnimationEpicycloid {
-    pub is_enabled: bool,
-
-    direction: Direction,
+    pub playback: Playback,
 }
 
 impl Default for AnimationEpicycloid {
     fn default() -> Self {
         Self {
-            is_enabled: false,
-            direction: Direction::Increase,
+            playback: Playback::new(2.5, PlaybackMode::PingPong),
         }
     }
 }
 
-impl AnimationEpicycloid {
-    pub fn run(&mut self, ui: &mut egui::Ui, model: &mut Epicycloid) {
-        let field = &mut model.pen_offset.0;
+impl Animation for AnimationEpicycloid {
+    type Target<'a> = &'a mut Epicycloid;
 
-        if self.is_enabled {
-            self.step(field);
-            ui.ctx().request_repaint();
-        }
+    fn playback(&self) -> &Playback {
+        &self.playback
     }
 
-    fn step(&mut self, field: &mut f64) {
-        *field += STEP_SIZE * self.direction.factor();
+    fn playback_mut(&mut self) -> &mut Playback {
+        &mut self.playback
+    }
 
-        // Clamp the value within the defined range
-        if *field < *RANGE.start() {
-            *field = *RANGE.start();
-            self.direction.toggle();
-        } else if *field > *RANGE.end() {
-            *field = *RANGE.end();
-            self.direction.toggle();
-        }
+    fn apply(&mut self, time: f64, model: Self::Target<'_>) {
+        model.pen_offset.0 = self.playback.lerp(time, &RANGE);
     }
+}
 
+impl AnimationEpicycloid {
     pub fn toggle(&mut self) {
-        self.is_enabled = !self.is_enabled;
+        self.playback.toggle();
     }
 }
 
-const STEP_SIZE: f64 = 0.5;
 const RANGE: RangeInclusive<f64> = 1.0..=100.0;
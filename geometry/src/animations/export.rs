@@ -0,0 +1,515 @@
+use crate::animations::timeline::Timeline;
+use crate::viewport::Viewport;
+use derive_more::Display;
+use egui::epaint::{ClippedShape, Primitive, Vertex};
+use egui::{Color32, ColorImage, Pos2, Rect, Shape, TextureId, pos2, vec2};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, SyncSender};
+use std::thread::JoinHandle;
+use strum_macros::EnumIter;
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum ExportFormat {
+    Gif,
+    Apng,
+    /// One numbered PNG file per frame.
+    PngSequence,
+}
+
+impl ExportFormat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Gif => "GIF",
+            Self::Apng => "Animated PNG",
+            Self::PngSequence => "PNG Sequence",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Gif => "gif",
+            Self::Apng | Self::PngSequence => "png",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExportSettings {
+    pub format: ExportFormat,
+    pub width: u32,
+    pub height: u32,
+    /// Frames per second. The timeline is stepped by exactly one frame each time.
+    pub fps: f64,
+    /// Seconds of the timeline, from its start.
+    pub duration: f64,
+    pub background: Color32,
+}
+
+impl Default for ExportSettings {
+    fn default() -> Self {
+        Self {
+            format: ExportFormat::Gif,
+            width: 640,
+            height: 480,
+            // GIF delays are in hundredths of a second, so 25 fps is exact
+            fps: 25.0,
+            duration: 4.0,
+            background: Color32::WHITE,
+        }
+    }
+}
+
+impl ExportSettings {
+    pub fn frame_count(&self) -> usize {
+        ((self.duration * self.fps) - 1e-9).ceil().max(1.0) as usize
+    }
+
+    /// Time of the timeline at the given frame. Computed from the index, so that
+    /// every frame is the same however the export is run.
+    pub fn frame_time(&self, frame: usize) -> f64 {
+        frame as f64 / self.fps
+    }
+
+    /// Writes the frames in the format of the settings, one by one as they come.
+    /// Fewer frames than the settings ask for mean the recording was cut short.
+    pub fn write(
+        &self, path: &Path, frames: impl IntoIterator<Item = ColorImage>,
+    ) -> Result<(), ExportError> {
+        match self.format {
+            ExportFormat::Gif => self.write_gif(path, frames),
+            ExportFormat::Apng => self.write_apng(path, frames),
+            ExportFormat::PngSequence => {
+                let stem = path
+                    .file_stem()
+                    .map_or("frame".into(), |stem| stem.to_string_lossy());
+                let mut count = 0;
+                for (index, frame) in frames.into_iter().enumerate() {
+                    let path =
+                        path.with_file_name(format!("{stem}_{:04}.png", index + 1));
+                    self.write_png(&path, &frame)?;
+                    count += 1;
+                }
+
+                if count < self.frame_count() {
+                    return Err(ExportError::Interrupted);
+                }
+
+                Ok(())
+            },
+        }
+    }
+
+    fn write_gif(
+        &self, path: &Path, frames: impl IntoIterator<Item = ColorImage>,
+    ) -> Result<(), ExportError> {
+        let file = BufWriter::new(File::create(path).map_err(ExportError::Io)?);
+        let (width, height) = (self.width as u16, self.height as u16);
+        let mut encoder =
+            gif::Encoder::new(file, width, height, &[]).map_err(ExportError::Gif)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(ExportError::Gif)?;
+
+        // In hundredths of a second
+        let delay = (100.0 / self.fps).round().max(1.0) as u16;
+        let mut count = 0;
+        for frame in frames {
+            let mut pixels = bytes(&frame);
+            let mut frame = gif::Frame::from_rgba_speed(width, height, &mut pixels, 10);
+            frame.delay = delay;
+            encoder.write_frame(&frame).map_err(ExportError::Gif)?;
+            count += 1;
+        }
+
+        if count < self.frame_count() {
+            return Err(ExportError::Interrupted);
+        }
+
+        Ok(())
+    }
+
+    fn write_apng(
+        &self, path: &Path, frames: impl IntoIterator<Item = ColorImage>,
+    ) -> Result<(), ExportError> {
+        let file = BufWriter::new(File::create(path).map_err(ExportError::Io)?);
+        let mut encoder = self.png_encoder(file);
+        encoder
+            .set_animated(self.frame_count() as u32, 0)
+            .map_err(ExportError::Png)?;
+        // In milliseconds
+        let delay = (1000.0 / self.fps).round() as u16;
+        encoder
+            .set_frame_delay(delay, 1000)
+            .map_err(ExportError::Png)?;
+
+        let mut writer = encoder.write_header().map_err(ExportError::Png)?;
+        let mut count = 0;
+        for frame in frames {
+            writer
+                .write_image_data(&bytes(&frame))
+                .map_err(ExportError::Png)?;
+            count += 1;
+        }
+
+        if count < self.frame_count() {
+            return Err(ExportError::Interrupted);
+        }
+
+        writer.finish().map_err(ExportError::Png)
+    }
+
+    fn write_png(&self, path: &Path, frame: &ColorImage) -> Result<(), ExportError> {
+        let file = BufWriter::new(File::create(path).map_err(ExportError::Io)?);
+        let mut writer = self
+            .png_encoder(file)
+            .write_header()
+            .map_err(ExportError::Png)?;
+        writer
+            .write_image_data(&bytes(frame))
+            .map_err(ExportError::Png)?;
+
+        writer.finish().map_err(ExportError::Png)
+    }
+
+    fn png_encoder(
+        &self, file: BufWriter<File>,
+    ) -> png::Encoder<'static, BufWriter<File>> {
+        let mut encoder = png::Encoder::new(file, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        encoder
+    }
+}
+
+/// RGBA bytes of the image, row by row.
+fn bytes(image: &ColorImage) -> Vec<u8> {
+    image
+        .pixels
+        .iter()
+        .flat_map(|pixel| pixel.to_array())
+        .collect()
+}
+
+#[derive(Debug, Display)]
+pub enum ExportError {
+    #[display("Failed to write the file. {_0}")]
+    Io(std::io::Error),
+
+    #[display("Failed to encode the GIF. {_0}")]
+    Gif(gif::EncodingError),
+
+    #[display("Failed to encode the PNG. {_0}")]
+    Png(png::EncodingError),
+
+    #[display("Writing the file was interrupted.")]
+    Interrupted,
+}
+
+impl std::error::Error for ExportError {}
+
+/// Records the animations of a lab: steps the timeline frame by frame, rasterizes
+/// the shapes of the canvas at every frame and hands every frame over to a writer
+/// in the background, so that only a few of them are held in memory at once.
+#[derive(Debug, Default)]
+pub struct AnimationExport {
+    pub settings: ExportSettings,
+    /// Whether the settings window is shown.
+    pub is_open: bool,
+    recording: Option<Recording>,
+    writer: Option<JoinHandle<Result<(), ExportError>>>,
+}
+
+#[derive(Debug)]
+struct Recording {
+    /// Frames recorded so far.
+    count: usize,
+    frames: SyncSender<ColorImage>,
+    rasterizer: Rasterizer,
+    /// Timeline as it was before the recording.
+    resume: Timeline,
+}
+
+impl AnimationExport {
+    /// Frames waiting for the writer. Recording waits while the queue is full.
+    const QUEUE: usize = 4;
+
+    /// Pauses the timeline, starts recording from its start and the writer.
+    pub fn start(&mut self, path: PathBuf, timeline: &mut Timeline) {
+        let resume = *timeline;
+        timeline.is_playing = false;
+        timeline.length = timeline.length.max(self.settings.duration);
+
+        let (frames, receiver) = mpsc::sync_channel(Self::QUEUE);
+        let settings = self.settings;
+        self.writer = Some(std::thread::spawn(move || {
+            let result = settings.write(&path, receiver);
+            // No half-written animation is left behind
+            if result.is_err() && settings.format != ExportFormat::PngSequence {
+                std::fs::remove_file(&path).ok();
+            }
+
+            result
+        }));
+
+        self.recording = Some(Recording {
+            count: 0,
+            frames,
+            rasterizer: Rasterizer::default(),
+            resume,
+        });
+    }
+
+    /// Stops recording. The writer is left to finish the file it was given.
+    pub fn cancel(&mut self, timeline: &mut Timeline) {
+        if let Some(recording) = self.recording.take() {
+            *timeline = recording.resume;
+            self.writer = None;
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// Whether the file is still being written after the last frame.
+    pub fn is_writing(&self) -> bool {
+        self.recording.is_none() && self.writer.is_some()
+    }
+
+    /// Share of the frames recorded so far.
+    pub fn progress(&self) -> f32 {
+        self.recording.as_ref().map_or(0.0, |recording| {
+            recording.count as f32 / self.settings.frame_count() as f32
+        })
+    }
+
+    /// Moves the timeline to the frame that is recorded next. Must be called before
+    /// the animations are updated.
+    pub fn prepare(&self, timeline: &mut Timeline) {
+        if let Some(recording) = &self.recording {
+            timeline.seek(self.settings.frame_time(recording.count));
+        }
+    }
+
+    /// Records the shapes of the canvas as the next frame. Images are drawn with the
+    /// given textures, text with the font atlas. After the last frame, or once the
+    /// writer has failed, the timeline is restored.
+    pub fn capture(
+        &mut self, ctx: &egui::Context, shapes: &[Shape],
+        textures: &[(TextureId, &ColorImage)], viewport: &Viewport,
+        timeline: &mut Timeline,
+    ) {
+        let Some(recording) = &mut self.recording else {
+            return;
+        };
+
+        let bounds = viewport.state.bounds;
+        let source = Rect::from_min_max(
+            pos2(
+                bounds.minimum_x.value() as f32,
+                bounds.minimum_y.value() as f32,
+            ),
+            pos2(
+                bounds.maximum_x.value() as f32,
+                bounds.maximum_y.value() as f32,
+            ),
+        );
+        let frame =
+            recording
+                .rasterizer
+                .render(ctx, shapes, textures, source, &self.settings);
+        // A closed queue means the writer has stopped with an error
+        let is_sent = recording.frames.send(frame).is_ok();
+        recording.count += 1;
+        ctx.request_repaint();
+
+        if is_sent && recording.count < self.settings.frame_count() {
+            return;
+        }
+
+        // Dropping the queue lets the writer finish
+        if let Some(recording) = self.recording.take() {
+            *timeline = recording.resume;
+        }
+    }
+
+    /// Result of writing the file, once it is done.
+    pub fn receive(&mut self, ctx: &egui::Context) -> Option<Result<(), ExportError>> {
+        if self.recording.is_some() || !self.writer.as_ref()?.is_finished() {
+            ctx.request_repaint_after(std::time::Duration::from_millis(100));
+            return None;
+        }
+
+        self.writer
+            .take()
+            .map(|writer| writer.join().unwrap_or(Err(ExportError::Interrupted)))
+    }
+}
+
+/// Draws the triangles egui tessellates the shapes into, so that frames can have
+/// any resolution, independent of the window.
+#[derive(Debug)]
+struct Rasterizer {
+    font: ColorImage,
+}
+
+impl Default for Rasterizer {
+    fn default() -> Self {
+        Self {
+            font: ColorImage::filled([0, 0], Color32::TRANSPARENT),
+        }
+    }
+}
+
+impl Rasterizer {
+    /// Image of the shapes inside the source rectangle, fitted to the resolution
+    /// of the settings.
+    fn render(
+        &mut self, ctx: &egui::Context, shapes: &[Shape],
+        textures: &[(TextureId, &ColorImage)], source: Rect, settings: &ExportSettings,
+    ) -> ColorImage {
+        // The atlas grows as new glyphs are drawn
+        if ctx.fonts(|fonts| fonts.font_image_size()) != self.font.size {
+            self.font = ctx.fonts(|fonts| fonts.image());
+        }
+
+        let size = [settings.width as usize, settings.height as usize];
+        let mut image = ColorImage::filled(size, settings.background);
+
+        let scale =
+            (size[0] as f32 / source.width()).min(size[1] as f32 / source.height());
+        if !scale.is_finite() || scale <= 0.0 {
+            return image;
+        }
+        let offset = (vec2(size[0] as f32, size[1] as f32) - source.size() * scale) / 2.0;
+        let to_image = |point: Pos2| (offset + (point - source.min) * scale).to_pos2();
+
+        let clipped = shapes
+            .iter()
+            .map(|shape| ClippedShape {
+                clip_rect: source,
+                shape: shape.clone(),
+            })
+            .collect();
+
+        // Feathering of one pixel of the image
+        for primitive in ctx.tessellate(clipped, scale) {
+            let Primitive::Mesh(mesh) = primitive.primitive else {
+                continue;
+            };
+
+            let clip = Rect::from_min_max(
+                to_image(primitive.clip_rect.min),
+                to_image(primitive.clip_rect.max),
+            );
+            let texture = if mesh.texture_id == TextureId::default() {
+                Some(&self.font)
+            } else {
+                textures
+                    .iter()
+                    .find(|(id, _)| *id == mesh.texture_id)
+                    .map(|(_, texture)| *texture)
+            };
+
+            for triangle in mesh.indices.chunks_exact(3) {
+                let vertex = |index: u32| {
+                    mesh.vertices
+                        .get(index as usize)
+                        .map(|vertex| (to_image(vertex.pos), vertex))
+                };
+                if let (Some(a), Some(b), Some(c)) = (
+                    vertex(triangle[0]),
+                    vertex(triangle[1]),
+                    vertex(triangle[2]),
+                ) {
+                    Self::fill_triangle(&mut image, [a, b, c], clip, texture);
+                }
+            }
+        }
+
+        image
+    }
+
+    /// Blends the triangle over the image, with the vertex colors interpolated and
+    /// multiplied by the texture.
+    fn fill_triangle(
+        image: &mut ColorImage, [(p0, v0), (p1, v1), (p2, v2)]: [(Pos2, &Vertex); 3],
+        clip: Rect, texture: Option<&ColorImage>,
+    ) {
+        let edge = |a: Pos2, b: Pos2, p: Pos2| {
+            (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+        };
+
+        let area = edge(p0, p1, p2);
+        if area.abs() < f32::EPSILON {
+            return;
+        }
+
+        let [width, height] = image.size;
+        let bounds = Rect::from_points(&[p0, p1, p2]).intersect(clip).intersect(
+            Rect::from_min_size(Pos2::ZERO, vec2(width as f32, height as f32)),
+        );
+        if !bounds.is_positive() {
+            return;
+        }
+
+        let rgba =
+            |color: Color32| color.to_array().map(|channel| channel as f32 / 255.0);
+        let (c0, c1, c2) = (rgba(v0.color), rgba(v1.color), rgba(v2.color));
+
+        for y in bounds.min.y.floor() as usize..(bounds.max.y.ceil() as usize).min(height)
+        {
+            for x in
+                bounds.min.x.floor() as usize..(bounds.max.x.ceil() as usize).min(width)
+            {
+                let point = pos2(x as f32 + 0.5, y as f32 + 0.5);
+                let w0 = edge(p1, p2, point) / area;
+                let w1 = edge(p2, p0, point) / area;
+                let w2 = 1.0 - w0 - w1;
+                if w0 < 0.0 || w1 < 0.0 || w2 < 0.0 {
+                    continue;
+                }
+
+                let mut color: [f32; 4] =
+                    std::array::from_fn(|i| c0[i] * w0 + c1[i] * w1 + c2[i] * w2);
+                if let Some(texture) = texture {
+                    let uv = (v0.uv.to_vec2() * w0
+                        + v1.uv.to_vec2() * w1
+                        + v2.uv.to_vec2() * w2)
+                        .to_pos2();
+                    let texel = rgba(Self::sample(texture, uv));
+                    color = std::array::from_fn(|i| color[i] * texel[i]);
+                }
+
+                // Colors are premultiplied, so "over" is a sum
+                if let Some(pixel) = image.pixels.get_mut(y * width + x) {
+                    let below = rgba(*pixel);
+                    let [r, g, b, a] = std::array::from_fn(|i| {
+                        ((color[i] + below[i] * (1.0 - color[3])) * 255.0).round() as u8
+                    });
+                    *pixel = Color32::from_rgba_premultiplied(r, g, b, a);
+                }
+            }
+        }
+    }
+
+    /// Nearest texel at the normalized coordinates.
+    fn sample(texture: &ColorImage, uv: Pos2) -> Color32 {
+        let [width, height] = texture.size;
+        if width == 0 || height == 0 {
+            return Color32::WHITE;
+        }
+
+        let x = ((uv.x * width as f32) as usize).min(width - 1);
+        let y = ((uv.y * height as f32) as usize).min(height - 1);
+
+        texture
+            .pixels
+            .get(y * width + x)
+            .copied()
+            .unwrap_or(Color32::WHITE)
+    }
+}
@@ -0,0 +1,157 @@
+use crate::animations::easing::Easing;
+use crate::animations::timeline::Timeline;
+use crate::animations::{Animatable, Animation, Playback, PlaybackMode};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Keyframe {
+    /// Seconds from the start of the animation.
+    pub time: f64,
+    pub value: f64,
+    /// Curve from the previous keyframe to this one.
+    pub easing: Easing,
+}
+
+/// Keyframes of one parameter, sorted by time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Track {
+    /// Name of the driven [`Parameter`](crate::animations::Parameter).
+    pub parameter: &'static str,
+    pub is_enabled: bool,
+    keyframes: Vec<Keyframe>,
+}
+
+impl Track {
+    pub fn new(parameter: &'static str) -> Self {
+        Self {
+            parameter,
+            is_enabled: true,
+            keyframes: vec![],
+        }
+    }
+
+    pub fn keyframes(&self) -> &[Keyframe] {
+        &self.keyframes
+    }
+
+    /// Adds the keyframe, replacing the one at the same time.
+    pub fn insert(&mut self, keyframe: Keyframe) {
+        const SAME_TIME: f64 = 1e-3;
+
+        self.keyframes
+            .retain(|other| (other.time - keyframe.time).abs() > SAME_TIME);
+        self.keyframes.push(keyframe);
+        self.sort();
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index < self.keyframes.len() {
+            self.keyframes.remove(index);
+        }
+    }
+
+    /// Keyframes that may be edited in place. Call [`Self::sort`] after their
+    /// times were changed.
+    pub fn keyframes_mut(&mut self) -> &mut [Keyframe] {
+        &mut self.keyframes
+    }
+
+    /// Restores the order by time.
+    pub fn sort(&mut self) {
+        self.keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+    }
+
+    /// Value at the given time. Before the first keyframe and after the last one
+    /// their values are held. `None` if there are no keyframes.
+    pub fn value_at(&self, time: f64) -> Option<f64> {
+        let first = self.keyframes.first()?;
+        if time <= first.time {
+            return Some(first.value);
+        }
+
+        for pair in self.keyframes.windows(2) {
+            let (from, to) = (&pair[0], &pair[1]);
+            if time <= to.time {
+                let span = to.time - from.time;
+                if span <= 0.0 {
+                    return Some(to.value);
+                }
+
+                let t = to.easing.apply((time - from.time) / span);
+                return Some(from.value + (to.value - from.value) * t);
+            }
+        }
+
+        self.keyframes.last().map(|last| last.value)
+    }
+}
+
+/// Keyframe tracks over the parameters of a figure.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyframeAnimation {
+    pub playback: Playback,
+    pub tracks: Vec<Track>,
+    /// Index of the parameter a new keyframe is added to.
+    pub selected: usize,
+}
+
+impl Default for KeyframeAnimation {
+    fn default() -> Self {
+        Self {
+            playback: Playback::new(5.0, PlaybackMode::Loop),
+            tracks: vec![],
+            selected: 0,
+        }
+    }
+}
+
+impl Animation for KeyframeAnimation {
+    type Target<'a> = &'a mut dyn Animatable;
+
+    fn playback(&self) -> &Playback {
+        &self.playback
+    }
+
+    fn playback_mut(&mut self) -> &mut Playback {
+        &mut self.playback
+    }
+
+    fn apply(&mut self, time: f64, target: Self::Target<'_>) {
+        let time = self.local_time(time);
+
+        for parameter in target.parameters() {
+            let track = self
+                .tracks
+                .iter()
+                .find(|track| track.is_enabled && track.parameter == parameter.name);
+            if let Some(value) = track.and_then(|track| track.value_at(time)) {
+                *parameter.value =
+                    value.clamp(*parameter.range.start(), *parameter.range.end());
+            }
+        }
+    }
+}
+
+impl KeyframeAnimation {
+    /// Time inside the animation that corresponds to the time of the timeline.
+    pub fn local_time(&self, time: f64) -> f64 {
+        self.playback.progress(time) * self.playback.duration
+    }
+
+    /// Records the current value of the parameter at the current time.
+    pub fn add_keyframe(&mut self, name: &'static str, value: f64, timeline: &Timeline) {
+        let keyframe = Keyframe {
+            time: self.local_time(timeline.time()),
+            value,
+            easing: Easing::Linear,
+        };
+
+        match self.tracks.iter_mut().find(|track| track.parameter == name) {
+            Some(track) => track.insert(keyframe),
+            None => {
+                let mut track = Track::new(name);
+                track.insert(keyframe);
+                self.tracks.push(track);
+            },
+        }
+    }
+}
@@ -0,0 +1,371 @@
+use crate::primitives::point2d::Point2D;
+use crate::smooth::ferguson::{FergusonCurve, FergusonPoint, Knot};
+use crate::units::Centimeter;
+use nalgebra::Vector2;
+use std::f64::consts::{PI, TAU};
+use strum_macros::EnumIter;
+
+/// Outline a contour is morphed from or into.
+#[derive(Debug, Clone)]
+pub enum MorphShape {
+    /// Ferguson curve through the knots.
+    Ferguson { knots: Vec<Knot>, is_closed: bool },
+    Polyline {
+        points: Vec<Point2D>,
+        is_closed: bool,
+    },
+}
+
+impl MorphShape {
+    /// Segments of a Ferguson curve are sampled this many times to measure it.
+    const SEGMENT_SAMPLES: f64 = 32.0;
+
+    pub fn is_closed(&self) -> bool {
+        match self {
+            Self::Ferguson { is_closed, .. } | Self::Polyline { is_closed, .. } => {
+                *is_closed
+            },
+        }
+    }
+
+    /// Knots that draw the shape as a Ferguson curve. Edges of a polyline get
+    /// zero tangents, which makes them straight.
+    pub fn knots(&self) -> Vec<Knot> {
+        match self {
+            Self::Ferguson { knots, .. } => knots.clone(),
+            Self::Polyline { points, .. } => points
+                .iter()
+                .map(|point| Knot {
+                    control: FergusonPoint::control(point.x, point.y),
+                    tangent: FergusonPoint::tangent(point.x, point.y),
+                })
+                .collect(),
+        }
+    }
+
+    /// Dense points along the outline, without the closing point.
+    fn outline(&self) -> Vec<Vector2<f64>> {
+        let points = match self {
+            Self::Ferguson { knots, is_closed } => {
+                let curve = FergusonCurve {
+                    knots: knots.clone(),
+                    is_closed: *is_closed,
+                    step: 1.0 / Self::SEGMENT_SAMPLES,
+                    style: Default::default(),
+                };
+                let lines = curve.lines();
+                let mut points: Vec<Point2D> =
+                    lines.iter().map(|line| line.start).collect();
+                if !is_closed && let Some(last) = lines.last() {
+                    points.push(last.end);
+                }
+
+                points
+            },
+            Self::Polyline { points, .. } => points.clone(),
+        };
+
+        points
+            .iter()
+            .map(|point| Vector2::new(point.x.value(), point.y.value()))
+            .collect()
+    }
+
+    /// Points spaced evenly along the outline.
+    fn resample(&self, count: usize) -> Vec<Vector2<f64>> {
+        let outline = self.outline();
+        let is_closed = self.is_closed();
+        let Some(&first) = outline.first() else {
+            return vec![Vector2::zeros(); count];
+        };
+
+        let mut vertices = outline.clone();
+        if is_closed {
+            vertices.push(first);
+        }
+
+        let mut distances = vec![0.0];
+        for pair in vertices.windows(2) {
+            let total = distances.last().copied().unwrap_or(0.0);
+            distances.push(total + (pair[1] - pair[0]).norm());
+        }
+        let length = distances.last().copied().unwrap_or(0.0);
+        if length <= f64::EPSILON {
+            return vec![first; count];
+        }
+
+        let spacing = if is_closed {
+            length / count as f64
+        } else {
+            length / (count - 1).max(1) as f64
+        };
+
+        let mut segment = 0;
+        (0..count)
+            .map(|index| {
+                let distance = (spacing * index as f64).min(length);
+                while segment + 2 < distances.len() && distances[segment + 1] < distance {
+                    segment += 1;
+                }
+
+                let span = distances[segment + 1] - distances[segment];
+                let t = if span > 0.0 {
+                    (distance - distances[segment]) / span
+                } else {
+                    0.0
+                };
+
+                vertices[segment].lerp(&vertices[segment + 1], t)
+            })
+            .collect()
+    }
+}
+
+/// How the points of the two shapes are blended.
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum Interpolation {
+    /// Every point moves along a straight line.
+    Linear,
+    /// Edge lengths and directions are blended, which keeps the shape from
+    /// collapsing when it turns.
+    Intrinsic,
+}
+
+impl Interpolation {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Linear => "Linear",
+            Self::Intrinsic => "Intrinsic (Angle/Length)",
+        }
+    }
+}
+
+/// Correspondence between two shapes: both resampled to the same number of
+/// points, with the start point and the orientation of the target chosen to
+/// twist the least.
+#[derive(Debug, Clone)]
+pub struct Morph {
+    source: Vec<Vector2<f64>>,
+    target: Vec<Vector2<f64>>,
+    is_closed: bool,
+}
+
+impl Morph {
+    pub fn new(source: &MorphShape, target: &MorphShape, samples: usize) -> Self {
+        let samples = samples.max(3);
+        let is_closed = source.is_closed() && target.is_closed();
+
+        let source_points = source.resample(samples);
+        let mut target_points = target.resample(samples);
+
+        if is_closed {
+            if signed_area(&source_points) * signed_area(&target_points) < 0.0 {
+                // Reversed, keeping the start point
+                target_points[1..].reverse();
+            }
+
+            let shift = best_shift(&source_points, &target_points);
+            target_points.rotate_left(shift);
+        } else {
+            let mut reversed = target_points.clone();
+            reversed.reverse();
+            if mismatch(&source_points, &reversed)
+                < mismatch(&source_points, &target_points)
+            {
+                target_points = reversed;
+            }
+        }
+
+        Self {
+            source: source_points,
+            target: target_points,
+            is_closed,
+        }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.is_closed
+    }
+
+    /// Knots of the shape at `t`, from the source (0) to the target (1).
+    pub fn knots(&self, t: f64, interpolation: Interpolation) -> Vec<Knot> {
+        let points = match interpolation {
+            Interpolation::Linear => self
+                .source
+                .iter()
+                .zip(&self.target)
+                .map(|(source, target)| source.lerp(target, t))
+                .collect(),
+            Interpolation::Intrinsic => self.intrinsic(t),
+        };
+
+        to_knots(&points, self.is_closed)
+    }
+
+    /// Sederberg's intrinsic blend: the lengths and directions of the edges are
+    /// interpolated, the gap of a closed outline is spread over its edges in
+    /// proportion to their lengths, and the centroid moves along a straight line.
+    fn intrinsic(&self, t: f64) -> Vec<Vector2<f64>> {
+        let source = edges(&self.source, self.is_closed);
+        let target = edges(&self.target, self.is_closed);
+        let source_angles = unwrapped_angles(&source);
+        let mut target_angles = unwrapped_angles(&target);
+
+        // Whole turns between the two are twists, not rotations
+        let offset = source_angles
+            .iter()
+            .zip(&target_angles)
+            .map(|(source, target)| target - source)
+            .sum::<f64>()
+            / source_angles.len().max(1) as f64;
+        let turns = ((offset + PI) / TAU).floor();
+        for angle in &mut target_angles {
+            *angle -= turns * TAU;
+        }
+
+        let mut blended: Vec<Vector2<f64>> = source
+            .iter()
+            .zip(&target)
+            .zip(source_angles.iter().zip(&target_angles))
+            .map(|((source, target), (from, to))| {
+                let length = source.norm() + (target.norm() - source.norm()) * t;
+                let angle = from + (to - from) * t;
+
+                Vector2::new(angle.cos(), angle.sin()) * length
+            })
+            .collect();
+
+        if self.is_closed {
+            let gap: Vector2<f64> = blended.iter().sum();
+            let perimeter: f64 = blended.iter().map(|edge| edge.norm()).sum();
+            if perimeter > f64::EPSILON {
+                for edge in &mut blended {
+                    *edge -= gap * (edge.norm() / perimeter);
+                }
+            }
+        }
+
+        let mut points = Vec::with_capacity(self.source.len());
+        let mut point = Vector2::zeros();
+        points.push(point);
+        for edge in blended.iter().take(self.source.len() - 1) {
+            point += edge;
+            points.push(point);
+        }
+
+        let center = centroid(&self.source).lerp(&centroid(&self.target), t);
+        let offset = center - centroid(&points);
+        points.iter().map(|point| point + offset).collect()
+    }
+}
+
+/// Ferguson knots through the points, with tangents from the neighbours.
+fn to_knots(points: &[Vector2<f64>], is_closed: bool) -> Vec<Knot> {
+    let count = points.len();
+
+    (0..count)
+        .map(|index| {
+            let previous = if index > 0 {
+                points[index - 1]
+            } else if is_closed {
+                points[count - 1]
+            } else {
+                points[index]
+            };
+            let next = if index + 1 < count {
+                points[index + 1]
+            } else if is_closed {
+                points[0]
+            } else {
+                points[index]
+            };
+            let current = points[index];
+
+            // One-sided at the ends of an open outline
+            let span = if is_closed || (index > 0 && index + 1 < count) {
+                2.0
+            } else {
+                1.0
+            };
+            let tangent = current + (next - previous) / span;
+
+            Knot {
+                control: FergusonPoint::control(
+                    Centimeter(current.x),
+                    Centimeter(current.y),
+                ),
+                tangent: FergusonPoint::tangent(
+                    Centimeter(tangent.x),
+                    Centimeter(tangent.y),
+                ),
+            }
+        })
+        .collect()
+}
+
+fn edges(points: &[Vector2<f64>], is_closed: bool) -> Vec<Vector2<f64>> {
+    let mut edges: Vec<Vector2<f64>> =
+        points.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    if is_closed && let (Some(first), Some(last)) = (points.first(), points.last()) {
+        edges.push(first - last);
+    }
+
+    edges
+}
+
+/// Directions of the edges, without jumps of a full turn between neighbours.
+fn unwrapped_angles(edges: &[Vector2<f64>]) -> Vec<f64> {
+    let mut angles: Vec<f64> = Vec::with_capacity(edges.len());
+    for edge in edges {
+        let angle = edge.y.atan2(edge.x);
+        let angle = match angles.last() {
+            Some(previous) => previous + (angle - previous + PI).rem_euclid(TAU) - PI,
+            None => angle,
+        };
+        angles.push(angle);
+    }
+
+    angles
+}
+
+fn centroid(points: &[Vector2<f64>]) -> Vector2<f64> {
+    points.iter().sum::<Vector2<f64>>() / points.len().max(1) as f64
+}
+
+/// Positive for counterclockwise outlines.
+fn signed_area(points: &[Vector2<f64>]) -> f64 {
+    let count = points.len();
+
+    (0..count)
+        .map(|index| {
+            let (a, b) = (points[index], points[(index + 1) % count]);
+            a.x * b.y - b.x * a.y
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+/// Sum of the squared distances between the points, both centered.
+fn mismatch(source: &[Vector2<f64>], target: &[Vector2<f64>]) -> f64 {
+    let offset = centroid(target) - centroid(source);
+
+    source
+        .iter()
+        .zip(target)
+        .map(|(source, target)| (target - offset - source).norm_squared())
+        .sum()
+}
+
+/// Rotation of the target points that matches the source the best.
+fn best_shift(source: &[Vector2<f64>], target: &[Vector2<f64>]) -> usize {
+    let mut shifted = target.to_vec();
+
+    (0..target.len())
+        .map(|shift| {
+            let cost = mismatch(source, &shifted);
+            shifted.rotate_left(1);
+            (shift, cost)
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map_or(0, |(shift, _)| shift)
+}
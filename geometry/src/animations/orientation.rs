@@ -0,0 +1,174 @@
+use crate::animations::timeline::Timeline;
+use crate::animations::{Animation, Playback, PlaybackMode};
+use crate::math::quaternion::Quaternion;
+use strum_macros::EnumIter;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrientationKeyframe {
+    /// Seconds from the start of the animation.
+    pub time: f64,
+    pub orientation: Quaternion,
+}
+
+/// How the orientation moves between the keyframes.
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum OrientationInterpolation {
+    /// Along the great arc at a constant speed, turning sharply at keyframes.
+    Slerp,
+    /// Along a smooth spline through the keyframes.
+    Squad,
+}
+
+impl OrientationInterpolation {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Slerp => "Slerp",
+            Self::Squad => "Squad",
+        }
+    }
+}
+
+/// Keyframed rotation of a figure, sorted by time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationOrientation {
+    pub playback: Playback,
+    pub interpolation: OrientationInterpolation,
+    keyframes: Vec<OrientationKeyframe>,
+}
+
+impl Default for AnimationOrientation {
+    fn default() -> Self {
+        Self {
+            playback: Playback::new(5.0, PlaybackMode::Loop),
+            interpolation: OrientationInterpolation::Squad,
+            keyframes: vec![],
+        }
+    }
+}
+
+impl Animation for AnimationOrientation {
+    type Target<'a> = &'a mut Quaternion;
+
+    fn playback(&self) -> &Playback {
+        &self.playback
+    }
+
+    fn playback_mut(&mut self) -> &mut Playback {
+        &mut self.playback
+    }
+
+    fn apply(&mut self, time: f64, target: Self::Target<'_>) {
+        if let Some(orientation) = self.orientation_at(self.local_time(time)) {
+            *target = orientation;
+        }
+    }
+}
+
+impl AnimationOrientation {
+    pub fn keyframes(&self) -> &[OrientationKeyframe] {
+        &self.keyframes
+    }
+
+    /// Time inside the animation that corresponds to the time of the timeline.
+    pub fn local_time(&self, time: f64) -> f64 {
+        self.playback.progress(time) * self.playback.duration
+    }
+
+    /// Adds the keyframe, replacing the one at the same time.
+    pub fn insert(&mut self, keyframe: OrientationKeyframe) {
+        const SAME_TIME: f64 = 1e-3;
+
+        self.keyframes
+            .retain(|other| (other.time - keyframe.time).abs() > SAME_TIME);
+        self.keyframes.push(keyframe);
+        self.sort();
+    }
+
+    /// Records the orientation at the current time.
+    pub fn add_keyframe(&mut self, orientation: Quaternion, timeline: &Timeline) {
+        self.insert(OrientationKeyframe {
+            time: self.local_time(timeline.time()),
+            orientation: orientation.normalize(),
+        });
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index < self.keyframes.len() {
+            self.keyframes.remove(index);
+        }
+    }
+
+    /// Keyframes that may be edited in place. Call [`Self::sort`] after their
+    /// times were changed.
+    pub fn keyframes_mut(&mut self) -> &mut [OrientationKeyframe] {
+        &mut self.keyframes
+    }
+
+    /// Restores the order by time.
+    pub fn sort(&mut self) {
+        self.keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+    }
+
+    /// Orientation at the given time. Before the first keyframe and after the
+    /// last one their orientations are held. `None` if there are no keyframes.
+    pub fn orientation_at(&self, time: f64) -> Option<Quaternion> {
+        let first = self.keyframes.first()?;
+        if time <= first.time {
+            return Some(first.orientation);
+        }
+
+        let path = self.path();
+        for index in 0..path.len().saturating_sub(1) {
+            let (from, to) = (&self.keyframes[index], &self.keyframes[index + 1]);
+            if time > to.time {
+                continue;
+            }
+
+            let span = to.time - from.time;
+            if span <= 0.0 {
+                return Some(path[index + 1]);
+            }
+
+            let t = (time - from.time) / span;
+            let orientation = match self.interpolation {
+                OrientationInterpolation::Slerp => path[index].slerp(&path[index + 1], t),
+                OrientationInterpolation::Squad => {
+                    let control = |index: usize| {
+                        Quaternion::control(
+                            &path[index.saturating_sub(1)],
+                            &path[index],
+                            &path[(index + 1).min(path.len() - 1)],
+                        )
+                    };
+
+                    path[index].squad(
+                        &path[index + 1],
+                        &control(index),
+                        &control(index + 1),
+                        t,
+                    )
+                },
+            };
+
+            return Some(orientation);
+        }
+
+        self.keyframes.last().map(|last| last.orientation)
+    }
+
+    /// Orientations of the keyframes, each on the same side of the sphere as the
+    /// previous one, so that every segment takes the shorter way.
+    fn path(&self) -> Vec<Quaternion> {
+        let mut path: Vec<Quaternion> = Vec::with_capacity(self.keyframes.len());
+        for keyframe in &self.keyframes {
+            let orientation = keyframe.orientation.normalize();
+            let orientation = match path.last() {
+                Some(previous) if previous.dot(&orientation) < 0.0 => -orientation,
+                _ => orientation,
+            };
+            path.push(orientation);
+        }
+
+        path
+    }
+}
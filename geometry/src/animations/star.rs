@@ -1,61 +1,50 @@
-use crate::animations::Direction;
+use crate::animations::{Animation, Playback, PlaybackMode};
 use crate::figures::star3d::Star3D;
 use crate::math::angle::Angle;
+use crate::math::quaternion::Quaternion;
 use crate::transformations::euclidean::rotation3d::Rotation3DOperation;
+use nalgebra::Vector3;
 use std::ops::RangeInclusive;
 
-/// Field: Radius
+/// Field: Radius, while the star spins around the Y axis.
 #[derive(Debug)]
 pub struct AnimationStar {
-    pub is_enabled: bool,
-
-    direction: Direction,
+    pub playback: Playback,
 }
 
 impl Default for AnimationStar {
     fn default() -> Self {
         Self {
-            is_enabled: false,
-            direction: Direction::Increase,
+            playback: Playback::new(1.0, PlaybackMode::PingPong),
         }
     }
 }
 
-impl AnimationStar {
-    pub fn run(
-        &mut self, ui: &mut egui::Ui, model: &mut Star3D,
-        rotation: &mut Rotation3DOperation,
-    ) {
-        let field = &mut model.radius.0;
-
-        if self.is_enabled {
-            self.step_radius(field);
-            self.step_rotation(rotation);
-            ui.ctx().request_repaint();
-        }
-    }
+impl Animation for AnimationStar {
+    type Target<'a> = (&'a mut Star3D, &'a mut Rotation3DOperation);
 
-    fn step_radius(&mut self, field: &mut f64) {
-        *field += STEP_SIZE * self.direction.factor();
+    fn playback(&self) -> &Playback {
+        &self.playback
+    }
 
-        // Clamp the value within the defined range
-        if *field < *RANGE.start() {
-            *field = *RANGE.start();
-            self.direction.toggle();
-        } else if *field > *RANGE.end() {
-            *field = *RANGE.end();
-            self.direction.toggle();
-        }
+    fn playback_mut(&mut self) -> &mut Playback {
+        &mut self.playback
     }
 
-    fn step_rotation(&mut self, rotation: &mut Rotation3DOperation) {
-        rotation.angle_y = Angle::from_degree(rotation.angle_y.degree() + 1.0);
+    fn apply(&mut self, time: f64, (model, rotation): Self::Target<'_>) {
+        model.radius.0 = self.playback.lerp(time, &RANGE);
+        rotation.orientation = Quaternion::from_axis_angle(
+            Vector3::y(),
+            Angle::from_degree((DEGREES_PER_SECOND * time).rem_euclid(360.0)),
+        );
     }
+}
 
+impl AnimationStar {
     pub fn toggle(&mut self) {
-        self.is_enabled = !self.is_enabled;
+        self.playback.toggle();
     }
 }
 
-const STEP_SIZE: f64 = 0.5;
 const RANGE: RangeInclusive<f64> = 1.0..=30.0;
+const DEGREES_PER_SECOND: f64 = 60.0;
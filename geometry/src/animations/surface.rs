@@ -0,0 +1,44 @@
+use crate::animations::{Animation, Playback, PlaybackMode};
+use crate::figures::surface::Surface;
+
+/// Field: any parameter of the current surface, chosen by index.
+#[derive(Debug)]
+pub struct AnimationSurface {
+    /// Index into `ParametricSurface::parameters`.
+    pub parameter: usize,
+    pub playback: Playback,
+}
+
+impl Default for AnimationSurface {
+    fn default() -> Self {
+        Self {
+            parameter: 0,
+            playback: Playback::new(1.0, PlaybackMode::PingPong),
+        }
+    }
+}
+
+impl Animation for AnimationSurface {
+    type Target<'a> = &'a mut Surface;
+
+    fn playback(&self) -> &Playback {
+        &self.playback
+    }
+
+    fn playback_mut(&mut self) -> &mut Playback {
+        &mut self.playback
+    }
+
+    fn apply(&mut self, time: f64, model: Self::Target<'_>) {
+        let mut parameters = model.kind.surface_mut().parameters();
+        if let Some(parameter) = parameters.get_mut(self.parameter) {
+            *parameter.value = self.playback.lerp(time, &parameter.range);
+        }
+    }
+}
+
+impl AnimationSurface {
+    pub fn toggle(&mut self) {
+        self.playback.toggle();
+    }
+}
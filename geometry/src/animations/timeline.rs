@@ -0,0 +1,104 @@
+use crate::animations::clock::{Clock, ClockMode};
+
+/// Time shared by all animations of a lab.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Timeline {
+    time: f64,
+    pub clock: Clock,
+    pub is_playing: bool,
+    /// Multiplier of the real time. Fixed steps are not scaled, so that step `n`
+    /// is always at the same time.
+    pub speed: f64,
+    /// Seconds covered by the scrubber.
+    pub length: f64,
+    /// Starts over when the end is reached, otherwise stops there.
+    pub is_looping: bool,
+}
+
+impl Default for Timeline {
+    fn default() -> Self {
+        Self {
+            time: 0.0,
+            clock: Clock::default(),
+            is_playing: true,
+            speed: 1.0,
+            // A multiple of the default durations, so that looping is seamless
+            length: 20.0,
+            is_looping: true,
+        }
+    }
+}
+
+impl Timeline {
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    /// Index of the current step of the clock.
+    pub fn frame(&self) -> u64 {
+        self.clock.frame(self.time)
+    }
+
+    /// Moves on after a frame that took `elapsed` seconds of real time, if it is
+    /// playing. How far depends on the mode of the clock.
+    pub fn tick(&mut self, elapsed: f64) {
+        if !self.is_playing {
+            return;
+        }
+
+        match self.clock.mode {
+            ClockMode::RealTime => self.advance(self.clock.delta(elapsed) * self.speed),
+            ClockMode::FixedStep => self.step(true),
+            ClockMode::Manual => {},
+        }
+    }
+
+    pub fn advance(&mut self, seconds: f64) {
+        self.seek(self.time + seconds);
+    }
+
+    /// Moves to the given time, wrapped or clamped to the length.
+    pub fn seek(&mut self, time: f64) {
+        let length = self.length.max(1e-6);
+
+        if self.is_looping {
+            self.time = time.rem_euclid(length);
+        } else {
+            self.time = time.clamp(0.0, length);
+            if self.time >= length {
+                self.is_playing = false;
+            }
+        }
+    }
+
+    /// Moves to the given step of the clock.
+    pub fn seek_frame(&mut self, frame: u64) {
+        self.seek(self.clock.frame_time(frame));
+    }
+
+    /// Moves one step of the clock forward or back.
+    pub fn step(&mut self, is_forward: bool) {
+        let frame = self.frame();
+        let frame = if is_forward {
+            frame + 1
+        } else {
+            frame.saturating_sub(1)
+        };
+
+        self.seek_frame(frame);
+    }
+
+    /// Whether the frames must keep coming: the clock runs and something is
+    /// animated.
+    pub fn needs_repaint(&self, is_animating: bool) -> bool {
+        self.is_playing && is_animating && self.clock.mode != ClockMode::Manual
+    }
+
+    pub fn toggle(&mut self) {
+        self.is_playing = !self.is_playing;
+    }
+
+    pub fn rewind(&mut self) {
+        self.time = 0.0;
+    }
+}
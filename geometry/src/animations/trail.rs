@@ -0,0 +1,150 @@
+use crate::primitives::line2d::Line2D;
+use crate::primitives::point2d::Point2D;
+use crate::viewport::Viewport;
+use egui::{Color32, Shape, Stroke};
+use std::collections::VecDeque;
+
+/// Onion skin of an animated figure: fading copies of its last frames, and the
+/// paths left behind by tracked points. Frames are kept in centimeters, so the
+/// trail stays in place when the canvas is panned or zoomed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trail {
+    pub is_ghosts_enabled: bool,
+    /// Past frames drawn as ghosts.
+    pub ghosts: usize,
+    /// Frames between two ghosts. Neighbouring frames mostly overlap.
+    pub spacing: usize,
+    /// Opacity of the newest ghost, the older ones fade out linearly.
+    pub opacity: f32,
+
+    pub is_traces_enabled: bool,
+    /// Frames a point of a trace stays for.
+    pub trace_length: usize,
+    pub trace_stroke: Stroke,
+
+    frames: VecDeque<Vec<Line2D<Point2D>>>,
+    traces: Vec<VecDeque<Point2D>>,
+    last_time: Option<f64>,
+    skipped: usize,
+}
+
+impl Default for Trail {
+    fn default() -> Self {
+        Self {
+            is_ghosts_enabled: false,
+            ghosts: 8,
+            spacing: 4,
+            opacity: 0.5,
+
+            is_traces_enabled: false,
+            trace_length: 240,
+            trace_stroke: Stroke::new(2.0, Color32::from_rgb(230, 90, 30)),
+
+            frames: VecDeque::new(),
+            traces: vec![],
+            last_time: None,
+            skipped: 0,
+        }
+    }
+}
+
+impl Trail {
+    pub fn is_enabled(&self) -> bool {
+        self.is_ghosts_enabled || self.is_traces_enabled
+    }
+
+    /// Records the figure and the tracked points of a frame at the time of the
+    /// timeline. A paused timeline records nothing, and a timeline that went
+    /// back, by looping or by the scrubber, starts the trail over. When nothing
+    /// is animated the trail is dropped.
+    pub fn record(
+        &mut self, time: f64, is_animating: bool, lines: &[Line2D<Point2D>],
+        points: &[Point2D],
+    ) {
+        if !self.is_enabled() || !is_animating {
+            self.clear();
+            return;
+        }
+
+        match self.last_time {
+            Some(last) if time == last => return,
+            Some(last) if time < last => self.clear(),
+            _ => {},
+        }
+        self.last_time = Some(time);
+
+        if self.is_ghosts_enabled {
+            if self.skipped == 0 {
+                let lines = lines
+                    .iter()
+                    .filter(|line| !line.is_transparent())
+                    .copied()
+                    .collect();
+                self.frames.push_back(lines);
+            }
+            self.skipped = (self.skipped + 1) % self.spacing.max(1);
+        }
+        while self.frames.len() > self.ghosts {
+            self.frames.pop_front();
+        }
+        if !self.is_ghosts_enabled {
+            self.frames.clear();
+        }
+
+        if !self.is_traces_enabled {
+            self.traces.clear();
+            return;
+        }
+        if self.traces.len() != points.len() {
+            self.traces = vec![VecDeque::new(); points.len()];
+        }
+        for (trace, point) in self.traces.iter_mut().zip(points) {
+            trace.push_back(*point);
+            while trace.len() > self.trace_length.max(2) {
+                trace.pop_front();
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.frames.clear();
+        self.traces.clear();
+        self.last_time = None;
+        self.skipped = 0;
+    }
+
+    /// Ghosts from the oldest to the newest, then the traces, to be drawn under
+    /// the figure.
+    pub fn shapes(&self, viewport: &Viewport) -> Vec<Shape> {
+        let mut shapes = vec![];
+
+        let count = self.frames.len();
+        for (index, frame) in self.frames.iter().enumerate() {
+            let alpha = self.opacity * (index + 1) as f32 / count as f32;
+            shapes.extend(frame.iter().map(|line| {
+                let mut line = *line;
+                line.stroke.color = line.stroke.color.gamma_multiply(alpha);
+                line.to_pixels(viewport).to_shape()
+            }));
+        }
+
+        for trace in &self.traces {
+            let count = trace.len();
+            let points: Vec<Point2D> = trace.iter().copied().collect();
+            for (index, pair) in points.windows(2).enumerate() {
+                let alpha = (index + 1) as f32 / count as f32;
+                let line = Line2D::new(
+                    pair[0],
+                    pair[1],
+                    Stroke::new(
+                        self.trace_stroke.width,
+                        self.trace_stroke.color.gamma_multiply(alpha),
+                    ),
+                );
+                shapes.push(line.to_pixels(viewport).to_shape());
+            }
+        }
+
+        shapes
+    }
+}
@@ -0,0 +1,136 @@
+use crate::animations::{Animation, Playback, PlaybackMode};
+use crate::primitives::line2d::Line2D;
+use crate::primitives::point2d::Point2D;
+use crate::transformations::affine::AffineDecomposition;
+use crate::transformations::projective::{project_lines, project_point};
+use nalgebra::Matrix3;
+
+/// Plane transformation that grows from the identity into the target matrix.
+/// Affine maps are decomposed and every step is interpolated on its own, so a
+/// rotation turns rather than shrinking through zero. Projective maps follow
+/// the straight homotopy between the identity and the normalized matrix.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationTransformation {
+    pub playback: Playback,
+    /// Point the rotation, the scale and the shear of an affine map are about.
+    pub center: Point2D,
+    /// Time of the timeline the animation starts at.
+    pub start: f64,
+    target: Matrix3<f64>,
+    matrix: Matrix3<f64>,
+    progress: f64,
+}
+
+impl Default for AnimationTransformation {
+    fn default() -> Self {
+        Self::new(Playback::new(2.0, PlaybackMode::Once))
+    }
+}
+
+impl Animation for AnimationTransformation {
+    type Target<'a> = &'a mut Matrix3<f64>;
+
+    fn playback(&self) -> &Playback {
+        &self.playback
+    }
+
+    fn playback_mut(&mut self) -> &mut Playback {
+        &mut self.playback
+    }
+
+    fn apply(&mut self, time: f64, target: Self::Target<'_>) {
+        self.progress = self.playback.progress(time - self.start);
+        self.matrix = interpolate(&self.target, self.center, self.progress);
+        *target = self.matrix;
+    }
+}
+
+impl AnimationTransformation {
+    pub fn new(playback: Playback) -> Self {
+        Self {
+            playback,
+            center: Point2D::default(),
+            start: 0.0,
+            target: Matrix3::identity(),
+            matrix: Matrix3::identity(),
+            progress: 0.0,
+        }
+    }
+
+    pub fn set_target(&mut self, target: Matrix3<f64>) {
+        self.target = target;
+    }
+
+    pub fn target(&self) -> &Matrix3<f64> {
+        &self.target
+    }
+
+    /// Matrix at the last applied time.
+    pub fn matrix(&self) -> &Matrix3<f64> {
+        &self.matrix
+    }
+
+    pub fn progress(&self) -> f64 {
+        self.progress
+    }
+
+    /// Whether one run from the start is over at the given time. A time before
+    /// the start means the timeline has wrapped around.
+    pub fn is_finished(&self, time: f64) -> bool {
+        time < self.start || time - self.start >= self.playback.duration
+    }
+
+    /// Steps of the current matrix, if the target is affine.
+    pub fn steps(&self) -> Option<AffineDecomposition> {
+        is_affine(&self.target)
+            .then(|| AffineDecomposition::new(&normalize(&self.matrix), self.center))
+    }
+}
+
+/// Matrix part of the way from the identity (0) to the target (1).
+pub fn interpolate(target: &Matrix3<f64>, center: Point2D, t: f64) -> Matrix3<f64> {
+    let target = normalize(target);
+
+    if is_affine(&target) {
+        AffineDecomposition::new(&target, center)
+            .partial(t)
+            .matrix()
+    } else {
+        Matrix3::identity() * (1.0 - t) + target * t
+    }
+}
+
+/// Applies a row-vector matrix in homogeneous coordinates to the lines,
+/// splitting the ones that cross the vanishing line.
+pub fn transform_lines(matrix: &Matrix3<f64>, lines: &mut Vec<Line2D<Point2D>>) {
+    *lines = project_lines(matrix, lines);
+}
+
+/// Applies the matrix to the point. A point on the vanishing line has no image
+/// and is left where it is, as in the static projective transformation.
+pub fn transform_point(matrix: &Matrix3<f64>, point: &mut Point2D) {
+    if let Some(image) = project_point(matrix, *point) {
+        *point = image;
+    }
+}
+
+/// Without the projective column.
+fn is_affine(matrix: &Matrix3<f64>) -> bool {
+    matrix[(0, 2)].abs() < 1e-12 && matrix[(1, 2)].abs() < 1e-12
+}
+
+/// Same map scaled so that the identity and the matrix are comparable: by the
+/// bottom-right coefficient, or by the size of the matrix if that is zero.
+fn normalize(matrix: &Matrix3<f64>) -> Matrix3<f64> {
+    let corner = matrix[(2, 2)];
+    if corner.abs() > 1e-12 {
+        return matrix / corner;
+    }
+
+    let norm = matrix.norm();
+    if norm > 1e-12 {
+        matrix * (3.0_f64.sqrt() / norm)
+    } else {
+        Matrix3::identity()
+    }
+}
@@ -1,4 +1,4 @@
-use crate::animations::Direction;
+use crate::animations::{Animation, Playback, PlaybackMode};
 use crate::figures::epicycloid::Epicycloid;
 use crate::primitives::line2d::Line2D;
 use crate::primitives::point2d::Point2D;
@@ -7,13 +7,11 @@ use crate::units::Centimeter;
 use crate::viewport::Viewport;
 use egui::{Color32, Shape, Stroke};
 
-pub const STEP_RANGE: std::ops::RangeInclusive<u32> = 1..=10;
-
 #[derive(Debug)]
 pub struct CurveWalker {
-    pub is_enabled: bool,
     pub is_visible: bool,
-    pub step: u32,
+    /// One lap around the curve per pass.
+    pub playback: Playback,
 
     pub is_inflection_points_enabled: bool,
     pub is_normal_enabled: bool,
@@ -22,23 +20,16 @@ pub struct CurveWalker {
 
     current_point: Point2D,
     current_array_index: usize,
-    current_array_length: usize,
-
-    direction: Direction,
 }
 
 impl Default for CurveWalker {
     fn default() -> Self {
         Self {
-            is_enabled: false,
             is_visible: false,
-            step: 1,
+            playback: Playback::new(10.0, PlaybackMode::Loop),
 
             current_point: Point2D::new(0.0, 0.0),
             current_array_index: 0,
-            current_array_length: 0,
-
-            direction: Direction::Increase,
 
             is_inflection_points_enabled: false,
             is_normal_enabled: false,
@@ -48,51 +39,49 @@ impl Default for CurveWalker {
     }
 }
 
-impl CurveWalker {
-    pub fn step(&mut self, ui: &egui::Ui, lines: &mut [Line2D<Point2D>]) {
-        if !self.is_enabled || !self.is_visible {
-            return;
-        }
+impl Animation for CurveWalker {
+    type Target<'a> = &'a [Line2D<Point2D>];
 
-        if self.current_array_length != lines.len() {
-            if lines.is_empty() {
-                return;
-            }
+    fn playback(&self) -> &Playback {
+        &self.playback
+    }
+
+    fn playback_mut(&mut self) -> &mut Playback {
+        &mut self.playback
+    }
 
-            self.current_point = lines[0].start;
-            self.current_array_index = 0;
-            self.current_array_length = lines.len();
+    fn apply(&mut self, time: f64, lines: Self::Target<'_>) {
+        if !self.is_visible || lines.is_empty() {
             return;
         }
 
-        let delta = (self.direction.factor() as isize) * (self.step as isize);
-        let index = (self.current_array_index as isize + delta)
-            .rem_euclid(self.current_array_length as isize) as usize;
+        let progress = self.playback.progress(time);
+        let index = ((progress * lines.len() as f64) as usize).min(lines.len() - 1);
 
         self.current_array_index = index;
         self.current_point = lines[index].start;
-
-        ui.ctx().request_repaint();
     }
+}
 
+impl CurveWalker {
     pub fn set_increasing(&mut self) {
         if self.is_visible {
-            self.is_enabled = !self.is_enabled;
-            self.direction = Direction::Increase;
+            self.playback.toggle();
+            self.playback.is_reversed = false;
         }
     }
 
     pub fn set_decreasing(&mut self) {
         if self.is_visible {
-            self.is_enabled = !self.is_enabled;
-            self.direction = Direction::Decrease;
+            self.playback.toggle();
+            self.playback.is_reversed = true;
         }
     }
 
     pub fn show_toggle(&mut self) {
         self.is_visible = !self.is_visible;
         if !self.is_visible {
-            self.is_enabled = false;
+            self.playback.is_enabled = false;
         }
     }
 
@@ -176,6 +165,6 @@ impl CurveWalker {
         self.is_tangent_enabled = false;
         self.is_inflection_points_enabled = false;
         self.is_visible = false;
-        self.is_enabled = false;
+        self.playback.is_enabled = false;
     }
 }
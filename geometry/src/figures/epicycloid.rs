@@ -1,3 +1,4 @@
+use crate::animations::{Animatable, Parameter};
 use crate::primitives::line2d::Line2D;
 use crate::primitives::point2d::Point2D;
 use crate::units::Centimeter;
@@ -44,6 +45,16 @@ impl Default for Epicycloid {
     }
 }
 
+impl Animatable for Epicycloid {
+    fn parameters(&mut self) -> Vec<Parameter<'_>> {
+        vec![
+            Parameter::new("Fixed Radius", &mut self.fixed_radius.0, 0.5..=50.0),
+            Parameter::new("Rolling Radius", &mut self.rolling_radius.0, 0.5..=50.0),
+            Parameter::new("Pen Offset", &mut self.pen_offset.0, 0.0..=100.0),
+        ]
+    }
+}
+
 impl Epicycloid {
     pub fn lines(&self) -> Vec<Line2D<Point2D>> {
         let mut lines = vec![];
@@ -0,0 +1,208 @@
+use crate::animations::{Animatable, Parameter};
+use crate::figures::contour::Contour;
+use crate::figures::detail::Detail;
+use crate::figures::star3d::Star3D;
+use crate::math::angle::Angle;
+use crate::primitives::line2d::Line2D;
+use crate::primitives::line3d::Line3D;
+use crate::primitives::point2d::Point2D;
+use crate::primitives::point3d::Point3D;
+use crate::units::Centimeter;
+use egui::{Color32, Stroke};
+use strum_macros::EnumIter;
+
+/// Extrudes a 2D outline from the XY plane along Z.
+/// The outline is treated as a set of segments, so it may consist of several
+/// closed paths (for example, the detail with its inner hole).
+#[derive(Debug)]
+pub struct Extrusion {
+    pub depth: Centimeter,
+    /// Scale of the top cap relative to the bottom one. 1.0 gives a prism.
+    pub taper: f64,
+    /// Rotation of the top cap relative to the bottom one, in degrees.
+    pub twist: f64,
+    /// Number of slices between the caps. Twisted side edges are polylines through them.
+    pub layers: usize,
+    /// Draw a side edge from every n-th vertex of the outline.
+    pub side_edge_step: usize,
+    pub is_layers_visible: bool,
+
+    pub stroke: Stroke,
+}
+
+impl Default for Extrusion {
+    fn default() -> Self {
+        Self {
+            depth: Centimeter(5.0),
+            taper: 1.0,
+            twist: 0.0,
+            layers: 1,
+            side_edge_step: 1,
+            is_layers_visible: false,
+
+            stroke: Stroke::new(2.0, Color32::BLACK),
+        }
+    }
+}
+
+impl Animatable for Extrusion {
+    fn parameters(&mut self) -> Vec<Parameter<'_>> {
+        vec![
+            Parameter::new("Depth", &mut self.depth.0, 0.0..=30.0),
+            Parameter::new("Taper", &mut self.taper, 0.0..=3.0),
+            Parameter::new("Twist", &mut self.twist, -360.0..=360.0),
+        ]
+    }
+}
+
+impl Extrusion {
+    pub fn extrude(&self, outline: &[Line2D<Point2D>]) -> Vec<Line3D<Point3D>> {
+        let mut lines = vec![];
+        if outline.is_empty() {
+            return lines;
+        }
+
+        let pivot = bounds_center(outline);
+        let layers = self.layers.max(1);
+        let step = self.side_edge_step.max(1);
+
+        for layer in 0..=layers {
+            let is_cap = layer == 0 || layer == layers;
+            if !is_cap && !self.is_layers_visible {
+                continue;
+            }
+
+            let t = layer as f64 / layers as f64;
+            for line in outline {
+                lines.push(Line3D::new(
+                    self.slice_point(&line.start, &pivot, t),
+                    self.slice_point(&line.end, &pivot, t),
+                    self.stroke,
+                ));
+            }
+        }
+
+        for line in outline.iter().step_by(step) {
+            for layer in 0..layers {
+                let (t1, t2) = (
+                    layer as f64 / layers as f64,
+                    (layer + 1) as f64 / layers as f64,
+                );
+
+                lines.push(Line3D::new(
+                    self.slice_point(&line.start, &pivot, t1),
+                    self.slice_point(&line.start, &pivot, t2),
+                    self.stroke,
+                ));
+            }
+        }
+
+        lines
+    }
+
+    /// Point of the outline on the slice at t ∈ [0, 1] between the bottom and top caps.
+    fn slice_point(&self, point: &Point2D, pivot: &Point2D, t: f64) -> Point3D {
+        let scale = 1.0 + (self.taper - 1.0) * t;
+        let angle = Angle::from_degree(self.twist * t).radian();
+        let (sin, cos) = angle.sin_cos();
+
+        let dx = (point.x - pivot.x).value() * scale;
+        let dy = (point.y - pivot.y).value() * scale;
+
+        Point3D::new(
+            pivot.x.value() + dx * cos - dy * sin,
+            pivot.y.value() + dx * sin + dy * cos,
+            self.depth.value() * t,
+        )
+    }
+
+    pub fn pivot_point(&self, outline: &[Line2D<Point2D>]) -> Point3D {
+        let center = bounds_center(outline);
+
+        Point3D::new(center.x.value(), center.y.value(), self.depth.value() / 2.0)
+    }
+
+    pub fn reset(&mut self) {
+        *self = Default::default();
+    }
+}
+
+/// Outlines available for extrusion in the UI.
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum ExtrusionSource {
+    Star,
+    Contour,
+    Detail,
+}
+
+impl ExtrusionSource {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Star => "Star",
+            Self::Contour => "Contour (Shark)",
+            Self::Detail => "Detail",
+        }
+    }
+
+    /// Outline centered at the origin and scaled so that its larger side equals `size`.
+    pub fn outline(&self, size: Centimeter) -> Vec<Line2D<Point2D>> {
+        let lines = match self {
+            Self::Star => Star3D::default().outline(),
+            Self::Contour => Contour::default().lines(),
+            Self::Detail => Detail::default().lines(),
+        };
+
+        fit(&lines, size)
+    }
+}
+
+/// Centers the lines at the origin and scales them so that the larger side of the
+/// bounding box equals `size`.
+pub fn fit(lines: &[Line2D<Point2D>], size: Centimeter) -> Vec<Line2D<Point2D>> {
+    let (min, max) = bounds(lines);
+    let center = bounds_center(lines);
+    let extent = (max.x - min.x).value().max((max.y - min.y).value());
+    let factor = if extent > 1e-9 {
+        size.value() / extent
+    } else {
+        1.0
+    };
+
+    let transform = |point: &Point2D| Point2D {
+        x: (point.x - center.x) * factor,
+        y: (point.y - center.y) * factor,
+    };
+
+    lines
+        .iter()
+        .map(|line| {
+            Line2D::new(transform(&line.start), transform(&line.end), line.stroke)
+        })
+        .collect()
+}
+
+fn bounds(lines: &[Line2D<Point2D>]) -> (Point2D, Point2D) {
+    let mut min = Point2D::new(f64::INFINITY, f64::INFINITY);
+    let mut max = Point2D::new(f64::NEG_INFINITY, f64::NEG_INFINITY);
+
+    for point in lines.iter().flat_map(|line| [line.start, line.end]) {
+        min.x = Centimeter(min.x.value().min(point.x.value()));
+        min.y = Centimeter(min.y.value().min(point.y.value()));
+        max.x = Centimeter(max.x.value().max(point.x.value()));
+        max.y = Centimeter(max.y.value().max(point.y.value()));
+    }
+
+    (min, max)
+}
+
+fn bounds_center(lines: &[Line2D<Point2D>]) -> Point2D {
+    if lines.is_empty() {
+        return Point2D::zero();
+    }
+
+    let (min, max) = bounds(lines);
+    Point2D {
+        x: (min.x + max.x) / 2.0,
+        y: (min.y + max.y) / 2.0,
+    }
+}
@@ -0,0 +1,420 @@
+use crate::math::angle::Angle;
+use crate::math::expression::{Expression, ExpressionError};
+use crate::primitives::line2d::Line2D;
+use crate::primitives::line3d::Line3D;
+use crate::primitives::point2d::{MoveablePoint, Point2D};
+use crate::primitives::point3d::Point3D;
+use crate::shapes::dot::DotMetadata;
+use crate::smooth::ferguson::{FergusonCurve, FergusonPoint, Knot};
+use crate::units::Centimeter;
+use crate::viewport::Viewport;
+use egui::{Color32, Response, Shape, Stroke};
+use strum_macros::EnumIter;
+
+/// Surface of revolution: a 2D profile in the XY plane revolved around one of its axes.
+#[derive(Debug)]
+pub struct Lathe {
+    pub is_enabled: bool,
+    pub is_profile_editor_open: bool,
+
+    pub source: ProfileSource,
+    pub curve: FergusonCurve,
+    pub polyline: Vec<MoveablePoint>,
+    pub expression: ProfileExpression,
+
+    pub axis: RevolutionAxis,
+    /// Degrees.
+    pub angle_start: f64,
+    /// Degrees.
+    pub angle_end: f64,
+    pub segments: usize,
+
+    pub style: LatheStyle,
+}
+
+impl Default for Lathe {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+            is_profile_editor_open: false,
+
+            source: ProfileSource::Curve,
+            curve: FergusonCurve {
+                knots: Self::vase_knots(),
+                is_closed: false,
+                step: 0.05,
+                style: Default::default(),
+            },
+            polyline: Self::goblet_polyline(),
+            expression: ProfileExpression::default(),
+
+            axis: RevolutionAxis::Y,
+            angle_start: 0.0,
+            angle_end: 360.0,
+            segments: 24,
+
+            style: LatheStyle::default(),
+        }
+    }
+}
+
+impl Lathe {
+    pub fn profile_points(&self) -> Vec<Point2D> {
+        match self.source {
+            ProfileSource::Curve => {
+                let lines = self.curve.lines();
+                let mut points: Vec<Point2D> =
+                    lines.iter().map(|line| line.end).collect();
+                if let Some(first) = lines.first() {
+                    points.insert(0, first.start);
+                }
+                points
+            },
+            ProfileSource::Polyline => self
+                .polyline
+                .iter()
+                .map(|point| point.coordinates)
+                .collect(),
+            ProfileSource::Expression => self.expression.points(self.axis),
+        }
+    }
+
+    pub fn lines(&self) -> Vec<Line3D<Point3D>> {
+        let profile = self.profile_points();
+        if profile.len() < 2 || self.segments == 0 {
+            return vec![];
+        }
+
+        let sweep = self.angle_end - self.angle_start;
+        let is_full_turn = sweep.abs() >= 360.0 - 1e-9;
+
+        // Meridians: revolved copies of the profile. A full turn repeats the first one.
+        let meridians: Vec<Vec<Point3D>> = (0..=self.segments)
+            .map(|k| {
+                let degree = self.angle_start + sweep * k as f64 / self.segments as f64;
+                let angle = Angle::from_degree(degree).radian();
+                profile
+                    .iter()
+                    .map(|point| self.revolve(point, angle))
+                    .collect()
+            })
+            .collect();
+
+        let mut lines = vec![];
+        let meridian_count = if is_full_turn {
+            self.segments
+        } else {
+            self.segments + 1
+        };
+
+        for meridian in meridians.iter().take(meridian_count) {
+            for pair in meridian.windows(2) {
+                lines.push(Line3D::new(pair[0], pair[1], self.style.stroke));
+            }
+        }
+
+        // Parallels: circles (or arcs) traced by each profile point.
+        for pair in meridians.windows(2) {
+            for (start, end) in pair[0].iter().zip(&pair[1]) {
+                lines.push(Line3D::new(*start, *end, self.style.stroke));
+            }
+        }
+
+        lines
+    }
+
+    fn revolve(&self, point: &Point2D, angle: f64) -> Point3D {
+        let (sin, cos) = angle.sin_cos();
+        let (x, y) = (point.x.value(), point.y.value());
+
+        match self.axis {
+            RevolutionAxis::X => Point3D::new(x, y * cos, y * sin),
+            RevolutionAxis::Y => Point3D::new(x * cos, y, x * sin),
+        }
+    }
+
+    /// Middle of the profile's extent along the axis of revolution.
+    pub fn pivot_point(&self) -> Point3D {
+        let profile = self.profile_points();
+        let along_axis = |point: &Point2D| match self.axis {
+            RevolutionAxis::X => point.x.value(),
+            RevolutionAxis::Y => point.y.value(),
+        };
+
+        let min = profile.iter().map(along_axis).fold(f64::INFINITY, f64::min);
+        let max = profile
+            .iter()
+            .map(along_axis)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let middle = if min.is_finite() && max.is_finite() {
+            (min + max) / 2.0
+        } else {
+            0.0
+        };
+
+        match self.axis {
+            RevolutionAxis::X => Point3D::new(middle, 0.0, 0.0),
+            RevolutionAxis::Y => Point3D::new(0.0, middle, 0.0),
+        }
+    }
+
+    /// 2D view for the profile editor: axis, profile, its mirror image and handles.
+    pub fn profile_shapes(&self, viewport: &Viewport) -> Vec<Shape> {
+        let profile = self.profile_points();
+        let mut lines = vec![];
+
+        let extent = Centimeter(PROFILE_AXIS_EXTENT);
+        let axis = match self.axis {
+            RevolutionAxis::X => Line2D::new(
+                Point2D {
+                    x: -extent,
+                    y: Centimeter(0.0),
+                },
+                Point2D {
+                    x: extent,
+                    y: Centimeter(0.0),
+                },
+                self.style.axis,
+            ),
+            RevolutionAxis::Y => Line2D::new(
+                Point2D {
+                    x: Centimeter(0.0),
+                    y: -extent,
+                },
+                Point2D {
+                    x: Centimeter(0.0),
+                    y: extent,
+                },
+                self.style.axis,
+            ),
+        };
+        lines.push(axis);
+
+        for pair in profile.windows(2) {
+            lines.push(Line2D::new(pair[0], pair[1], self.style.profile));
+            lines.push(Line2D::new(
+                self.mirror(&pair[0]),
+                self.mirror(&pair[1]),
+                self.style.mirror,
+            ));
+        }
+
+        let mut shapes: Vec<Shape> = lines
+            .iter()
+            .map(|line| line.to_pixels(viewport).to_shape())
+            .collect();
+
+        match self.source {
+            ProfileSource::Curve => shapes.extend(self.curve.skeleton(viewport)),
+            ProfileSource::Polyline => shapes.extend(self.polyline.iter().map(|point| {
+                point
+                    .coordinates
+                    .to_pixels(viewport)
+                    .to_dot(&self.style.handle)
+            })),
+            ProfileSource::Expression => {},
+        }
+
+        shapes
+    }
+
+    fn mirror(&self, point: &Point2D) -> Point2D {
+        match self.axis {
+            RevolutionAxis::X => Point2D {
+                x: point.x,
+                y: -point.y,
+            },
+            RevolutionAxis::Y => Point2D {
+                x: -point.x,
+                y: point.y,
+            },
+        }
+    }
+
+    pub fn update_profile(
+        &mut self, ui: &egui::Ui, response: &Response, viewport: &Viewport,
+    ) {
+        match self.source {
+            ProfileSource::Curve => {
+                for knot in &mut self.curve.knots {
+                    knot.control
+                        .update_on_change_smoothness(ui, response, viewport);
+                    knot.control.point.update_on_pan(ui, response, viewport);
+                    knot.tangent.point.update_on_pan(ui, response, viewport);
+                }
+            },
+            ProfileSource::Polyline => {
+                for point in &mut self.polyline {
+                    point.update_on_pan(ui, response, viewport);
+                }
+            },
+            ProfileSource::Expression => {},
+        }
+    }
+
+    /// Appends a point to the polyline, continuing the direction of the last segment.
+    pub fn add_polyline_point(&mut self) {
+        let next = match self.polyline.as_slice() {
+            [.., previous, last] => Point2D {
+                x: last.coordinates.x * 2.0 - previous.coordinates.x,
+                y: last.coordinates.y * 2.0 - previous.coordinates.y,
+            },
+            [last] => Point2D {
+                x: last.coordinates.x,
+                y: last.coordinates.y + Centimeter(1.0),
+            },
+            [] => Point2D::zero(),
+        };
+
+        self.polyline.push(MoveablePoint::new(next));
+    }
+
+    pub fn remove_polyline_point(&mut self) {
+        if self.polyline.len() > 2 {
+            self.polyline.pop();
+        }
+    }
+
+    pub fn vase_knots() -> Vec<Knot> {
+        let knot = |x: f64, y: f64, tx: f64, ty: f64| Knot {
+            control: FergusonPoint::control(Centimeter(x), Centimeter(y)),
+            tangent: FergusonPoint::tangent(Centimeter(x + tx), Centimeter(y + ty)),
+        };
+
+        vec![
+            knot(0.0, 0.0, 3.0, 0.0),
+            knot(3.0, 0.5, 2.0, 1.5),
+            knot(4.5, 4.0, -0.5, 3.0),
+            knot(2.0, 8.0, -1.0, 1.5),
+            knot(3.0, 10.0, 2.0, 1.0),
+        ]
+    }
+
+    pub fn goblet_polyline() -> Vec<MoveablePoint> {
+        [
+            (0.0, 0.0),
+            (3.5, 0.0),
+            (3.5, 0.4),
+            (0.5, 1.0),
+            (0.5, 5.0),
+            (2.5, 6.0),
+            (3.5, 8.0),
+            (4.0, 11.0),
+        ]
+        .iter()
+        .map(|(x, y)| MoveablePoint::new(Point2D::new(*x, *y)))
+        .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum ProfileSource {
+    Curve,
+    Polyline,
+    Expression,
+}
+
+impl ProfileSource {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Curve => "Ferguson Curve",
+            Self::Polyline => "Polyline",
+            Self::Expression => "Expression",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum RevolutionAxis {
+    X,
+    Y,
+}
+
+impl RevolutionAxis {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::X => "X",
+            Self::Y => "Y",
+        }
+    }
+}
+
+/// Profile given as distance from the axis r(t), where t is the position along the axis.
+#[derive(Debug)]
+pub struct ProfileExpression {
+    /// Text of the formula as edited by the user. Applied by [`Self::compile`].
+    pub input: String,
+    pub t_min: f64,
+    pub t_max: f64,
+    pub samples: usize,
+
+    compiled: Expression,
+}
+
+impl Default for ProfileExpression {
+    fn default() -> Self {
+        let mut expression = Self {
+            input: "3 + sin(t)".to_string(),
+            t_min: 0.0,
+            t_max: 10.0,
+            samples: 40,
+            compiled: Expression::constant(0.0),
+        };
+        // Default formula is valid
+        let _ = expression.compile();
+
+        expression
+    }
+}
+
+impl ProfileExpression {
+    /// Parses the input. On error the previously compiled formula stays active.
+    pub fn compile(&mut self) -> Result<(), ExpressionError> {
+        self.compiled = Expression::parse(&self.input, &["t"])?;
+        Ok(())
+    }
+
+    pub fn points(&self, axis: RevolutionAxis) -> Vec<Point2D> {
+        let samples = self.samples.max(1);
+
+        (0..=samples)
+            .map(|i| {
+                let t =
+                    self.t_min + (self.t_max - self.t_min) * i as f64 / samples as f64;
+                let r = self.compiled.eval(&[t]);
+
+                match axis {
+                    RevolutionAxis::X => Point2D::new(t, r),
+                    RevolutionAxis::Y => Point2D::new(r, t),
+                }
+            })
+            .collect()
+    }
+}
+
+const PROFILE_AXIS_EXTENT: f64 = 1000.0;
+
+#[derive(Debug)]
+pub struct LatheStyle {
+    pub stroke: Stroke,
+    pub profile: Stroke,
+    pub mirror: Stroke,
+    pub axis: Stroke,
+    pub handle: DotMetadata,
+}
+
+impl Default for LatheStyle {
+    fn default() -> Self {
+        Self {
+            stroke: Stroke::new(1.5, Color32::BLACK),
+            profile: Stroke::new(3.0, Color32::BLACK),
+            mirror: Stroke::new(1.0, Color32::LIGHT_GRAY),
+            axis: Stroke::new(1.5, Color32::BLUE),
+            handle: DotMetadata {
+                radius: 5.0,
+                fill: Color32::RED,
+                stroke: Stroke::new(0.5, Color32::BLACK),
+            },
+        }
+    }
+}
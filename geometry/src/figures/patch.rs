@@ -0,0 +1,291 @@
+use crate::primitives::line3d::Line3D;
+use crate::primitives::point2d::Point2D;
+use crate::primitives::point3d::{DragPlane, MoveablePoint3D, Point3D};
+use crate::shapes::dot::DotMetadata;
+use crate::viewport::Viewport;
+use egui::{Color32, Response, Shape, Stroke};
+use strum_macros::EnumIter;
+
+/// Free-form surface patch over a 4×4 control net, u, v ∈ [0, 1].
+/// The first index of the net goes along u, the second along v.
+#[derive(Debug)]
+pub struct Patch {
+    pub is_enabled: bool,
+    pub kind: PatchKind,
+    pub net: [[MoveablePoint3D; 4]; 4],
+
+    /// Number of iso-lines in each direction.
+    pub iso_lines: usize,
+    /// Points per iso-line.
+    pub samples: usize,
+
+    pub is_net_visible: bool,
+    pub drag_plane: DragPlane,
+
+    pub style: PatchStyle,
+}
+
+impl Default for Patch {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+            kind: PatchKind::Bezier,
+            net: Self::hill_net(),
+
+            iso_lines: 10,
+            samples: 24,
+
+            is_net_visible: true,
+            drag_plane: DragPlane::XY,
+
+            style: PatchStyle::default(),
+        }
+    }
+}
+
+impl Patch {
+    pub fn point(&self, u: f64, v: f64) -> Point3D {
+        match self.kind {
+            PatchKind::Bezier => self.bezier(u, v),
+            PatchKind::Coons => self.coons(u, v),
+        }
+    }
+
+    /// S(u, v) = Σ Σ B_i(u) B_j(v) P_ij.
+    fn bezier(&self, u: f64, v: f64) -> Point3D {
+        let (bu, bv) = (bernstein(u), bernstein(v));
+        let mut point = [0.0; 3];
+
+        for (i, row) in self.net.iter().enumerate() {
+            for (j, control) in row.iter().enumerate() {
+                let weight = bu[i] * bv[j];
+                point[0] += weight * control.coordinates.x.value();
+                point[1] += weight * control.coordinates.y.value();
+                point[2] += weight * control.coordinates.z.value();
+            }
+        }
+
+        Point3D::new(point[0], point[1], point[2])
+    }
+
+    /// Bilinearly blended Coons patch. The boundary curves are the cubic Bézier
+    /// curves of the outer rows and columns of the net, the inner points are unused.
+    fn coons(&self, u: f64, v: f64) -> Point3D {
+        let corner = |i: usize, j: usize| self.net[i][j].coordinates;
+        let row = |i: usize| self.net[i].each_ref().map(|point| point.coordinates);
+        let column = |j: usize| [0, 1, 2, 3].map(|i| corner(i, j));
+
+        let bottom = bezier_curve(&column(0), u);
+        let top = bezier_curve(&column(3), u);
+        let left = bezier_curve(&row(0), v);
+        let right = bezier_curve(&row(3), v);
+
+        let ruled_v = blend(&[(bottom, 1.0 - v), (top, v)]);
+        let ruled_u = blend(&[(left, 1.0 - u), (right, u)]);
+        let bilinear = blend(&[
+            (corner(0, 0), (1.0 - u) * (1.0 - v)),
+            (corner(3, 0), u * (1.0 - v)),
+            (corner(0, 3), (1.0 - u) * v),
+            (corner(3, 3), u * v),
+        ]);
+
+        blend(&[(ruled_v, 1.0), (ruled_u, 1.0), (bilinear, -1.0)])
+    }
+
+    /// Iso-lines u = const and v = const.
+    pub fn lines(&self) -> Vec<Line3D<Point3D>> {
+        let mut lines = vec![];
+        let iso_lines = self.iso_lines.max(1);
+        let samples = self.samples.max(1);
+
+        for k in 0..=iso_lines {
+            let fixed = k as f64 / iso_lines as f64;
+
+            let along_u: Vec<Point3D> = (0..=samples)
+                .map(|s| self.point(s as f64 / samples as f64, fixed))
+                .collect();
+            let along_v: Vec<Point3D> = (0..=samples)
+                .map(|s| self.point(fixed, s as f64 / samples as f64))
+                .collect();
+
+            for curve in [along_u, along_v] {
+                for pair in curve.windows(2) {
+                    lines.push(Line3D::new(pair[0], pair[1], self.style.stroke));
+                }
+            }
+        }
+
+        lines
+    }
+
+    /// Control polygon. For a Coons patch only the boundary is shown.
+    pub fn net_lines(&self) -> Vec<Line3D<Point3D>> {
+        if !self.is_net_visible {
+            return vec![];
+        }
+
+        let mut lines = vec![];
+        for i in 0..4 {
+            for j in 0..4 {
+                let point = self.net[i][j].coordinates;
+                if j < 3 && self.is_handle_active(i, j) && self.is_handle_active(i, j + 1)
+                {
+                    let next = self.net[i][j + 1].coordinates;
+                    lines.push(Line3D::new(point, next, self.style.net));
+                }
+                if i < 3 && self.is_handle_active(i, j) && self.is_handle_active(i + 1, j)
+                {
+                    let next = self.net[i + 1][j].coordinates;
+                    lines.push(Line3D::new(point, next, self.style.net));
+                }
+            }
+        }
+
+        lines
+    }
+
+    /// Inner points of the net do not affect a Coons patch.
+    fn is_handle_active(&self, i: usize, j: usize) -> bool {
+        match self.kind {
+            PatchKind::Bezier => true,
+            PatchKind::Coons => i == 0 || i == 3 || j == 0 || j == 3,
+        }
+    }
+
+    fn active_handles(&self) -> impl Iterator<Item = &MoveablePoint3D> {
+        self.net.iter().enumerate().flat_map(move |(i, row)| {
+            row.iter()
+                .enumerate()
+                .filter(move |(j, _)| self.is_handle_active(i, *j))
+                .map(|(_, point)| point)
+        })
+    }
+
+    /// Handles of the net. `project` maps a point to the canvas.
+    pub fn handle_shapes(
+        &self, viewport: &Viewport, project: impl Fn(&Point3D) -> Point2D,
+    ) -> Vec<Shape> {
+        if !self.is_net_visible {
+            return vec![];
+        }
+
+        self.active_handles()
+            .map(|point| {
+                project(&point.coordinates)
+                    .to_pixels(viewport)
+                    .to_dot(&self.style.handle)
+            })
+            .collect()
+    }
+
+    pub fn update_net(
+        &mut self, ui: &egui::Ui, response: &Response, viewport: &Viewport,
+        project: impl Fn(&Point3D) -> Point2D,
+    ) {
+        if !self.is_net_visible {
+            return;
+        }
+
+        let plane = self.drag_plane;
+        for i in 0..4 {
+            for j in 0..4 {
+                if self.is_handle_active(i, j) {
+                    self.net[i][j].update_on_pan(ui, response, viewport, plane, &project);
+                }
+            }
+        }
+    }
+
+    /// Centroid of the control net.
+    pub fn pivot_point(&self) -> Point3D {
+        let points: Vec<(Point3D, f64)> = self
+            .net
+            .iter()
+            .flatten()
+            .map(|point| (point.coordinates, 1.0 / 16.0))
+            .collect();
+
+        blend(&points)
+    }
+
+    pub fn reset_net(&mut self) {
+        self.net = Self::hill_net();
+    }
+
+    /// 12 × 12 cm patch in the XZ plane with a raised middle.
+    pub fn hill_net() -> [[MoveablePoint3D; 4]; 4] {
+        std::array::from_fn(|i| {
+            std::array::from_fn(|j| {
+                let is_inner = (1..=2).contains(&i) && (1..=2).contains(&j);
+                let height = if is_inner { 8.0 } else { 0.0 };
+
+                MoveablePoint3D::new(Point3D::new(
+                    i as f64 * 4.0 - 6.0,
+                    height,
+                    j as f64 * 4.0 - 6.0,
+                ))
+            })
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum PatchKind {
+    Bezier,
+    Coons,
+}
+
+impl PatchKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Bezier => "Bicubic Bézier",
+            Self::Coons => "Coons (Bilinear)",
+        }
+    }
+}
+
+/// Cubic Bernstein polynomials B_0..B_3 at t.
+fn bernstein(t: f64) -> [f64; 4] {
+    let s = 1.0 - t;
+    [s * s * s, 3.0 * s * s * t, 3.0 * s * t * t, t * t * t]
+}
+
+fn bezier_curve(controls: &[Point3D; 4], t: f64) -> Point3D {
+    let weights = bernstein(t);
+    let points: Vec<(Point3D, f64)> = controls.iter().copied().zip(weights).collect();
+
+    blend(&points)
+}
+
+/// Weighted sum of points.
+fn blend(points: &[(Point3D, f64)]) -> Point3D {
+    let mut sum = [0.0; 3];
+    for (point, weight) in points {
+        sum[0] += weight * point.x.value();
+        sum[1] += weight * point.y.value();
+        sum[2] += weight * point.z.value();
+    }
+
+    Point3D::new(sum[0], sum[1], sum[2])
+}
+
+#[derive(Debug)]
+pub struct PatchStyle {
+    pub stroke: Stroke,
+    pub net: Stroke,
+    pub handle: DotMetadata,
+}
+
+impl Default for PatchStyle {
+    fn default() -> Self {
+        Self {
+            stroke: Stroke::new(1.5, Color32::BLACK),
+            net: Stroke::new(1.0, Color32::LIGHT_BLUE),
+            handle: DotMetadata {
+                radius: 5.0,
+                fill: Color32::RED,
+                stroke: Stroke::new(0.5, Color32::BLACK),
+            },
+        }
+    }
+}
@@ -1,4 +1,8 @@
+use crate::animations::{Animatable, Parameter};
+use crate::figures::extrusion::Extrusion;
+use crate::primitives::line2d::Line2D;
 use crate::primitives::line3d::Line3D;
+use crate::primitives::point2d::Point2D;
 use crate::primitives::point3d::Point3D;
 use crate::units::Centimeter;
 use egui::{Color32, Stroke};
@@ -22,63 +26,65 @@ impl Default for Star3D {
     }
 }
 
+impl Animatable for Star3D {
+    fn parameters(&mut self) -> Vec<Parameter<'_>> {
+        vec![
+            Parameter::new("Radius", &mut self.radius.0, 1.0..=30.0),
+            Parameter::new("Thickness", &mut self.thickness.0, 0.1..=20.0),
+        ]
+    }
+}
+
 impl Star3D {
     pub fn lines(&self) -> Vec<Line3D<Point3D>> {
-        let mut points: Vec<Point3D> = Vec::with_capacity(10);
-        let mut upper_points: Vec<Point3D> = Vec::with_capacity(10);
+        let extrusion = Extrusion {
+            depth: self.thickness,
+            stroke: self.stroke,
+            ..Default::default()
+        };
+
+        extrusion.extrude(&self.outline())
+    }
 
-        let mut lines: Vec<Line3D<Point3D>> = Vec::with_capacity(30);
+    /// Closed outline of the star in the XY plane: alternating outer and inner vertices.
+    pub fn outline(&self) -> Vec<Line2D<Point2D>> {
+        let mut points: Vec<Point2D> = Vec::with_capacity(11);
 
         let radius = self.radius;
         let inner_radius = radius / 2.0;
 
-        let initial_thickness = Centimeter(0.0);
-        let thickness = self.thickness;
-
         for k in 0..=4 {
             let angle =
                 k as f64 * 2.0 * std::f64::consts::PI / 5.0 + std::f64::consts::PI / 2.0;
             let offset_angle = angle + 2.0 * std::f64::consts::PI / 10.0;
 
-            let outer = self.create_point(angle, radius, initial_thickness);
-            let inner = self.create_point(offset_angle, inner_radius, initial_thickness);
-
-            let upper_outer = self.create_point(angle, radius, thickness);
-            let upper_inner = self.create_point(offset_angle, inner_radius, thickness);
-
-            points.push(outer);
-            points.push(inner);
-            upper_points.push(upper_outer);
-            upper_points.push(upper_inner);
-
-            // Add vertical lines directly
-            lines.push(Line3D::new(outer, upper_outer, self.stroke));
-            lines.push(Line3D::new(inner, upper_inner, self.stroke));
+            points.push(self.create_point(angle, radius));
+            points.push(self.create_point(offset_angle, inner_radius));
         }
 
-        // Close the loops
+        // Close the loop
         points.push(points[0]);
-        upper_points.push(upper_points[0]);
 
-        // Add horizontal lines
-        points.windows(2).for_each(|pair| {
-            lines.push(Line3D::new(pair[0], pair[1], self.stroke));
-        });
-        upper_points.windows(2).for_each(|pair| {
-            lines.push(Line3D::new(pair[0], pair[1], self.stroke));
-        });
+        points
+            .windows(2)
+            .map(|pair| Line2D::new(pair[0], pair[1], self.stroke))
+            .collect()
+    }
 
-        lines
+    /// Outer vertices of the front face.
+    pub fn tips(&self) -> Vec<Point3D> {
+        self.outline()
+            .iter()
+            .step_by(2)
+            .map(|line| Point3D::new(line.start.x.value(), line.start.y.value(), 0.0))
+            .collect()
     }
 
     // https://math.stackexchange.com/questions/3582342/coordinates-of-the-vertices-of-a-five-pointed-star
-    fn create_point(
-        &self, angle: f64, radius: Centimeter, thickness: Centimeter,
-    ) -> Point3D {
-        Point3D {
+    fn create_point(&self, angle: f64, radius: Centimeter) -> Point2D {
+        Point2D {
             x: radius * f64::cos(angle),
             y: radius * f64::sin(angle),
-            z: thickness,
         }
     }
 
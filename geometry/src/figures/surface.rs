@@ -1,141 +1,250 @@
+use crate::animations::{Animatable, Parameter};
 use crate::figures::texture::Texture;
 use crate::math::angle::Angle;
 use crate::primitives::line2d::Line2D;
 use crate::primitives::line3d::Line3D;
 use crate::primitives::point2d::Point2D;
 use crate::primitives::point3d::Point3D;
-use crate::units::{Centimeter, Percent};
+use crate::surfaces::{self, SurfaceKind};
+use crate::units::Centimeter;
 use egui::Stroke;
+use strum_macros::EnumIter;
 
 #[derive(Debug)]
 pub struct Surface {
-    pub height: Centimeter,
-    pub radius_base: Centimeter,
+    pub kind: SurfaceKind,
     pub mesh: usize,
     pub is_texture_enabled: bool,
-    pub texture_scale_width: Percent,
-    pub texture_scale_height: Percent,
-    pub texture_offset_angle: Percent,
-    pub texture_offset_height: Percent,
-    pub texture_rotation_angle: f64,
+    pub texture_addressing: TextureAddressing,
+    /// Longest texture segment in UV units before it is subdivided.
+    pub texture_max_segment: f64,
+    pub is_normals_enabled: bool,
+    pub normals_length: Centimeter,
     pub style: SurfaceStyle,
 }
 
 impl Default for Surface {
     fn default() -> Self {
         Self {
-            height: Centimeter(10.0),
-            radius_base: Centimeter(5.0),
+            kind: SurfaceKind::default(),
             mesh: 20,
-            texture_scale_width: Percent(0.3),
-            texture_scale_height: Percent(0.5),
-            texture_offset_angle: Percent(0.0),
-            texture_offset_height: Percent(0.5),
-            texture_rotation_angle: 0.0,
+            texture_addressing: TextureAddressing::Repeat,
+            texture_max_segment: 0.02,
             is_texture_enabled: false,
+            is_normals_enabled: false,
+            normals_length: Centimeter(1.0),
             style: SurfaceStyle::default(),
         }
     }
 }
 
+impl Animatable for Surface {
+    fn parameters(&mut self) -> Vec<Parameter<'_>> {
+        self.kind.surface_mut().parameters()
+    }
+}
+
 impl Surface {
     pub fn lines(&self) -> Vec<Line3D<Point3D>> {
         let mut lines = Vec::new();
         let steps = self.mesh;
+        let grid = self.grid();
+
+        let surface = self.kind.surface();
+        // Periodic directions skip the last row/column: it coincides with the first one.
+        let columns = if surface.is_u_periodic() {
+            steps
+        } else {
+            steps + 1
+        };
+        let rows = if surface.is_v_periodic() {
+            steps
+        } else {
+            steps + 1
+        };
+
+        // Lines along U
+        for row in grid.iter().take(rows) {
+            for pair in row.windows(2) {
+                lines.push(Line3D::new(pair[0], pair[1], self.style.stroke));
+            }
+        }
 
-        for i in 0..=steps {
-            let v = i as f64 / steps as f64;
+        // Lines along V
+        for pair in grid.windows(2) {
+            for (start, end) in pair[0].iter().zip(&pair[1]).take(columns) {
+                lines.push(Line3D::new(*start, *end, self.style.stroke));
+            }
+        }
 
-            for j in 0..steps {
-                let u1 = j as f64 / steps as f64;
-                let u2 = ((j + 1) % steps) as f64 / steps as f64;
+        lines
+    }
 
-                let p1 = self.point_at(u1, v);
-                let p2 = self.point_at(u2, v);
+    /// Unit normals at the mesh points, scaled to `normals_length`. Degenerate
+    /// points, like the apex of a cone, have none.
+    pub fn normals(&self) -> Vec<Line3D<Point3D>> {
+        if !self.is_normals_enabled {
+            return vec![];
+        }
 
-                lines.push(Line3D::new(p1, p2, self.style.stroke));
+        let surface = self.kind.surface();
+        let (u_domain, v_domain) = (surface.u_domain(), surface.v_domain());
+        let steps = self.mesh;
+
+        let mut lines = vec![];
+        for i in 0..=steps {
+            for j in 0..=steps {
+                let u = surfaces::denormalize(&u_domain, j as f64 / steps as f64);
+                let v = surfaces::denormalize(&v_domain, i as f64 / steps as f64);
 
-                if i > 0 {
-                    let v_prev = (i - 1) as f64 / steps as f64;
-                    let p_prev = self.point_at(u1, v_prev);
-                    lines.push(Line3D::new(p_prev, p1, self.style.stroke));
+                let normal = surface.normal(u, v);
+                if normal.length() < 0.5 {
+                    continue;
                 }
+
+                let start = surface.point(u, v);
+                let end = normal.scale(self.normals_length.value()).translate(&start);
+                lines.push(Line3D::new(start, end, self.style.normal_stroke));
             }
         }
+
         lines
     }
 
-    pub fn point_at(&self, u: f64, v: f64) -> Point3D {
-        let theta = u * 2.0 * std::f64::consts::PI;
+    /// Points of the mesh: `grid[i][j]` is the point at v_i, u_j.
+    fn grid(&self) -> Vec<Vec<Point3D>> {
+        let steps = self.mesh;
 
-        let current_radius = self.radius_base.value() * (1.0 - v);
+        (0..=steps)
+            .map(|i| {
+                let v = i as f64 / steps as f64;
+                (0..=steps)
+                    .map(|j| self.point_at(j as f64 / steps as f64, v))
+                    .collect()
+            })
+            .collect()
+    }
 
-        let y = self.height.value() * v;
+    /// Point at normalized coordinates: u, v ∈ [0, 1] cover the whole surface domain.
+    pub fn point_at(&self, u: f64, v: f64) -> Point3D {
+        let surface = self.kind.surface();
 
-        Point3D {
-            x: Centimeter(current_radius * theta.cos()),
-            y: Centimeter(y),
-            z: Centimeter(current_radius * theta.sin()),
-        }
+        surface.point(
+            surfaces::denormalize(&surface.u_domain(), u),
+            surfaces::denormalize(&surface.v_domain(), v),
+        )
     }
 
-    pub fn handle_texture(&self, texture: &Texture) -> Vec<Line3D<Point3D>> {
+    /// Maps every enabled texture layer, each with its own placement.
+    pub fn handle_texture(&self, textures: &[Texture]) -> Vec<Line3D<Point3D>> {
         if !self.is_texture_enabled {
             return Vec::new();
         }
 
-        let lines = texture.lines();
-        self.map_texture(&lines)
+        textures
+            .iter()
+            .filter(|texture| texture.is_enabled)
+            .flat_map(|texture| self.map_texture(texture))
+            .collect()
     }
 
-    pub fn map_texture(&self, texture_lines: &[Line2D<Point2D>]) -> Vec<Line3D<Point3D>> {
+    /// Maps texture lines onto the surface. Each line is subdivided in UV space so it
+    /// follows the surface curvature, then split wherever it crosses a tile border
+    /// (the seam of a periodic surface) and addressed with [`TextureAddressing`].
+    pub fn map_texture(&self, texture: &Texture) -> Vec<Line3D<Point3D>> {
+        let texture_lines = texture.lines();
         if texture_lines.is_empty() {
             return vec![];
         }
 
-        let (min_x, max_x, min_y, max_y) = self.calculate_bounds(texture_lines);
+        let (min_x, max_x, min_y, max_y) = self.calculate_bounds(&texture_lines);
         let width = (max_x - min_x).abs().max(1e-6);
         let height = (max_y - min_y).abs().max(1e-6);
 
-        texture_lines
-            .iter()
-            .map(|line| {
-                let start_uv_raw =
-                    self.normalize(line.start, min_x, min_y, width, height);
-                let end_uv_raw = self.normalize(line.end, min_x, min_y, width, height);
+        let mut lines = vec![];
+        for line in &texture_lines {
+            let start = self.normalize(line.start, min_x, min_y, width, height);
+            let end = self.normalize(line.end, min_x, min_y, width, height);
+
+            let start = self.apply_uv_transform(start, texture);
+            let end = self.apply_uv_transform(end, texture);
+
+            for (piece_start, piece_end) in self.subdivide(start, end) {
+                for (u_start, u_end) in self.address_u(piece_start, piece_end) {
+                    for (uv_start, uv_end) in self.address_v(u_start, u_end) {
+                        lines.push(Line3D::new(
+                            self.point_at(uv_start.0, uv_start.1),
+                            self.point_at(uv_end.0, uv_end.1),
+                            line.stroke,
+                        ));
+                    }
+                }
+            }
+        }
 
-                let start_uv_mapped = self.apply_uv_transform(start_uv_raw);
-                let end_uv_mapped = self.apply_uv_transform(end_uv_raw);
+        lines
+    }
 
-                if (start_uv_mapped.0 - end_uv_mapped.0).abs() > 0.8 {
-                    return Line3D::with_transparent(Point3D::zero(), Point3D::zero());
-                }
+    /// Splits a UV segment into pieces no longer than `texture_max_segment`.
+    fn subdivide(&self, start: (f64, f64), end: (f64, f64)) -> Vec<UvSegment> {
+        let length = (end.0 - start.0).hypot(end.1 - start.1);
+        let step = self.texture_max_segment.max(1e-4);
+        let count = ((length / step).ceil() as usize).clamp(1, MAX_SUBDIVISIONS);
+
+        (0..count)
+            .map(|k| {
+                let t1 = k as f64 / count as f64;
+                let t2 = (k + 1) as f64 / count as f64;
+                (lerp_uv(start, end, t1), lerp_uv(start, end, t2))
+            })
+            .collect()
+    }
 
-                let p1_3d = self.point_at(start_uv_mapped.0, start_uv_mapped.1);
-                let p2_3d = self.point_at(end_uv_mapped.0, end_uv_mapped.1);
+    fn address_u(&self, start: (f64, f64), end: (f64, f64)) -> Vec<UvSegment> {
+        split_at_tiles(start, end, |uv| uv.0)
+            .into_iter()
+            .filter_map(|(tile, (a, b))| {
+                let u = |value: f64| self.texture_addressing.address(value, tile);
+                Some(((u(a.0)?, a.1), (u(b.0)?, b.1)))
+            })
+            .collect()
+    }
 
-                Line3D::new(p1_3d, p2_3d, line.stroke)
+    /// A periodic V direction wraps like [`TextureAddressing::Repeat`];
+    /// otherwise the segment is clipped against the V domain.
+    fn address_v(&self, start: (f64, f64), end: (f64, f64)) -> Vec<UvSegment> {
+        let addressing = if self.kind.surface().is_v_periodic() {
+            TextureAddressing::Repeat
+        } else {
+            TextureAddressing::Clip
+        };
+
+        split_at_tiles(start, end, |uv| uv.1)
+            .into_iter()
+            .filter_map(|(tile, (a, b))| {
+                let v = |value: f64| addressing.address(value, tile);
+                Some(((a.0, v(a.1)?), (b.0, v(b.1)?)))
             })
             .collect()
     }
 
-    fn apply_uv_transform(&self, uv: (f64, f64)) -> (f64, f64) {
+    fn apply_uv_transform(&self, uv: (f64, f64), texture: &Texture) -> (f64, f64) {
         let (u_raw, v_raw) = uv;
 
         let u_local = u_raw - 0.5;
         let v_local = v_raw - 0.5;
 
-        let u_scaled = u_local * self.texture_scale_width.value();
-        let v_scaled = v_local * self.texture_scale_height.value();
+        let u_scaled = u_local * texture.scale_width.value();
+        let v_scaled = v_local * texture.scale_height.value();
 
-        let rad = Angle::from_degree(self.texture_rotation_angle).radian();
+        let rad = Angle::from_degree(texture.rotation_angle).radian();
         let (sin, cos) = (rad.sin(), rad.cos());
 
         let u_rotated = u_scaled * cos - v_scaled * sin;
         let v_rotated = u_scaled * sin + v_scaled * cos;
 
-        let u_final = self.texture_offset_angle.value() + u_rotated;
-        let v_final = self.texture_offset_height.value() + v_rotated;
+        let u_final = texture.offset_angle.value() + u_rotated;
+        let v_final = texture.offset_height.value() + v_rotated;
 
         (u_final, v_final)
     }
@@ -177,11 +286,105 @@ impl Surface {
         (min_x, max_x, min_y, max_y)
     }
 
+    /// Center of the mesh bounding box.
     pub fn pivot_point(&self) -> Point3D {
-        Point3D {
-            x: Centimeter(0.0),
-            y: Centimeter(self.height.value() / 2.0),
-            z: Centimeter(0.0),
+        let mut min = [f64::INFINITY; 3];
+        let mut max = [f64::NEG_INFINITY; 3];
+
+        for point in self.grid().iter().flatten() {
+            let coordinates = [point.x.value(), point.y.value(), point.z.value()];
+            for axis in 0..3 {
+                min[axis] = min[axis].min(coordinates[axis]);
+                max[axis] = max[axis].max(coordinates[axis]);
+            }
+        }
+
+        Point3D::new(
+            (min[0] + max[0]) / 2.0,
+            (min[1] + max[1]) / 2.0,
+            (min[2] + max[2]) / 2.0,
+        )
+    }
+}
+
+type UvSegment = ((f64, f64), (f64, f64));
+
+const MAX_SUBDIVISIONS: usize = 256;
+
+fn lerp_uv(start: (f64, f64), end: (f64, f64), t: f64) -> (f64, f64) {
+    (
+        start.0 + (end.0 - start.0) * t,
+        start.1 + (end.1 - start.1) * t,
+    )
+}
+
+/// Splits a segment where the chosen coordinate crosses an integer.
+/// Every piece lies within one tile [k, k + 1] and is returned with its k.
+fn split_at_tiles(
+    start: (f64, f64), end: (f64, f64), coordinate: impl Fn((f64, f64)) -> f64,
+) -> Vec<(i64, UvSegment)> {
+    let (from, to) = (coordinate(start), coordinate(end));
+    let mut cuts = vec![0.0];
+
+    if from != to {
+        let (low, high) = (from.min(to), from.max(to));
+        let mut border = low.floor() + 1.0;
+        while border < high {
+            cuts.push((border - from) / (to - from));
+            border += 1.0;
+        }
+        cuts.sort_by(f64::total_cmp);
+    }
+    cuts.push(1.0);
+
+    cuts.windows(2)
+        .map(|pair| {
+            let (a, b) = (lerp_uv(start, end, pair[0]), lerp_uv(start, end, pair[1]));
+            let middle = (coordinate(a) + coordinate(b)) / 2.0;
+            (middle.floor() as i64, (a, b))
+        })
+        .collect()
+}
+
+/// How texture coordinates outside [0, 1] are mapped onto the surface.
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum TextureAddressing {
+    /// The texture is tiled: every tile starts over from 0.
+    Repeat,
+    /// No tiling: outside the domain the coordinate stays on the nearest edge,
+    /// so the texture edge is stretched over the rest of the surface.
+    Clamp,
+    /// No tiling: parts of the texture outside the domain are clipped away.
+    Clip,
+    /// The texture is tiled, every other tile is flipped.
+    Mirror,
+}
+
+impl TextureAddressing {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Repeat => "Repeat",
+            Self::Clamp => "Clamp",
+            Self::Clip => "Clip",
+            Self::Mirror => "Mirror",
+        }
+    }
+
+    /// Maps a coordinate lying in tile [tile, tile + 1] into [0, 1].
+    /// Returns `None` when the coordinate is not drawn at all.
+    fn address(&self, value: f64, tile: i64) -> Option<f64> {
+        let local = (value - tile as f64).clamp(0.0, 1.0);
+
+        match self {
+            Self::Repeat => Some(local),
+            Self::Clamp => Some(match tile {
+                ..0 => 0.0,
+                0 => local,
+                1.. => 1.0,
+            }),
+            Self::Clip => (tile == 0).then_some(local),
+            Self::Mirror if tile.rem_euclid(2) == 1 => Some(1.0 - local),
+            Self::Mirror => Some(local),
         }
     }
 }
@@ -189,12 +392,14 @@ impl Surface {
 #[derive(Debug)]
 pub struct SurfaceStyle {
     pub stroke: Stroke,
+    pub normal_stroke: Stroke,
 }
 
 impl Default for SurfaceStyle {
     fn default() -> Self {
         Self {
             stroke: Stroke::new(2.0, egui::Color32::BLACK),
+            normal_stroke: Stroke::new(1.0, egui::Color32::RED),
         }
     }
 }
@@ -0,0 +1,411 @@
+use crate::animations::{Animatable, Parameter};
+use crate::figures::extrusion::fit;
+use crate::figures::star3d::Star3D;
+use crate::primitives::line2d::Line2D;
+use crate::primitives::line3d::Line3D;
+use crate::primitives::point2d::Point2D;
+use crate::primitives::point3d::Point3D;
+use crate::primitives::vector3d::Vector3D;
+use crate::units::Centimeter;
+use egui::{Color32, Stroke};
+use std::f64::consts::PI;
+use strum_macros::EnumIter;
+
+/// Moves a 2D profile along a 3D path. The profile's X and Y axes follow the
+/// normal and binormal of a moving frame, so tubes and springs keep their thickness.
+#[derive(Debug)]
+pub struct Sweep {
+    pub path: SweepPath,
+    pub path_radius: Centimeter,
+    /// Height gained by the helix per turn.
+    pub pitch: Centimeter,
+    pub turns: f64,
+    pub samples: usize,
+
+    pub profile: SweepProfile,
+    pub profile_size: Centimeter,
+    /// Number of sides of the polygon profile. Large values give a circle.
+    pub profile_sides: usize,
+
+    pub frame: FrameMode,
+    /// Draw the profile at every n-th sample of the path.
+    pub ring_step: usize,
+
+    pub stroke: Stroke,
+}
+
+impl Default for Sweep {
+    fn default() -> Self {
+        Self {
+            path: SweepPath::Helix,
+            path_radius: Centimeter(5.0),
+            pitch: Centimeter(3.0),
+            turns: 4.0,
+            samples: 200,
+
+            profile: SweepProfile::Polygon,
+            profile_size: Centimeter(1.5),
+            profile_sides: 12,
+
+            frame: FrameMode::RotationMinimizing,
+            ring_step: 4,
+
+            stroke: Stroke::new(1.5, Color32::BLACK),
+        }
+    }
+}
+
+impl Animatable for Sweep {
+    fn parameters(&mut self) -> Vec<Parameter<'_>> {
+        vec![
+            Parameter::new("Path Radius", &mut self.path_radius.0, 0.1..=30.0),
+            Parameter::new("Pitch", &mut self.pitch.0, 0.0..=20.0),
+            Parameter::new("Turns", &mut self.turns, 0.1..=50.0),
+            Parameter::new("Profile Size", &mut self.profile_size.0, 0.1..=10.0),
+        ]
+    }
+}
+
+impl Sweep {
+    pub fn lines(&self) -> Vec<Line3D<Point3D>> {
+        sweep(
+            &self.profile_outline(),
+            &self.path_points(),
+            self.path.is_closed(),
+            self.frame,
+            self.ring_step,
+            self.stroke,
+        )
+    }
+
+    pub fn path_points(&self) -> Vec<Point3D> {
+        let samples = self.samples.max(2);
+        let count = if self.path.is_closed() {
+            samples
+        } else {
+            samples + 1
+        };
+
+        (0..count)
+            .map(|i| self.path_point(i as f64 / samples as f64))
+            .collect()
+    }
+
+    /// Point of the path at t ∈ [0, 1].
+    fn path_point(&self, t: f64) -> Point3D {
+        let radius = self.path_radius.value();
+
+        match self.path {
+            SweepPath::Helix => {
+                let angle = 2.0 * PI * self.turns * t;
+                Point3D::new(
+                    radius * angle.cos(),
+                    radius * angle.sin(),
+                    self.pitch.value() * self.turns * t,
+                )
+            },
+            SweepPath::Circle => {
+                let angle = 2.0 * PI * t;
+                Point3D::new(radius * angle.cos(), radius * angle.sin(), 0.0)
+            },
+            SweepPath::TrefoilKnot => {
+                let angle = 2.0 * PI * t;
+                let scale = radius / 3.0;
+                Point3D::new(
+                    scale * (angle.sin() + 2.0 * (2.0 * angle).sin()),
+                    scale * (angle.cos() - 2.0 * (2.0 * angle).cos()),
+                    -scale * (3.0 * angle).sin(),
+                )
+            },
+        }
+    }
+
+    pub fn profile_outline(&self) -> Vec<Line2D<Point2D>> {
+        match self.profile {
+            SweepProfile::Polygon => {
+                let sides = self.profile_sides.max(3);
+                let radius = self.profile_size / 2.0;
+                let points: Vec<Point2D> = (0..=sides)
+                    .map(|k| {
+                        let angle = 2.0 * PI * k as f64 / sides as f64;
+                        Point2D {
+                            x: radius * angle.cos(),
+                            y: radius * angle.sin(),
+                        }
+                    })
+                    .collect();
+
+                points
+                    .windows(2)
+                    .map(|pair| Line2D::new(pair[0], pair[1], self.stroke))
+                    .collect()
+            },
+            SweepProfile::Star => fit(&Star3D::default().outline(), self.profile_size),
+        }
+    }
+
+    pub fn pivot_point(&self) -> Point3D {
+        match self.path {
+            SweepPath::Helix => {
+                Point3D::new(0.0, 0.0, self.pitch.value() * self.turns / 2.0)
+            },
+            SweepPath::Circle | SweepPath::TrefoilKnot => Point3D::zero(),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Default::default();
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum SweepPath {
+    Helix,
+    Circle,
+    TrefoilKnot,
+}
+
+impl SweepPath {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Helix => "Helix (Spring)",
+            Self::Circle => "Circle (Torus)",
+            Self::TrefoilKnot => "Trefoil Knot",
+        }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        match self {
+            Self::Helix => false,
+            Self::Circle | Self::TrefoilKnot => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum SweepProfile {
+    Polygon,
+    Star,
+}
+
+impl SweepProfile {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Polygon => "Polygon",
+            Self::Star => "Star",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum FrameMode {
+    /// Follows the curvature. Flips on inflections and twists on torsion.
+    Frenet,
+    /// Parallel transport of the first frame: no twist around the tangent.
+    RotationMinimizing,
+}
+
+impl FrameMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Frenet => "Frenet",
+            Self::RotationMinimizing => "Rotation Minimizing",
+        }
+    }
+}
+
+/// Orthonormal frame at a path sample. The profile lies in the normal-binormal plane.
+#[derive(Debug, Clone, Copy)]
+pub struct Frame {
+    pub tangent: Vector3D,
+    pub normal: Vector3D,
+    pub binormal: Vector3D,
+}
+
+/// Places a copy of the profile at every `ring_step`-th path sample and connects
+/// the profile vertices along the path.
+pub fn sweep(
+    profile: &[Line2D<Point2D>], path: &[Point3D], is_closed: bool, mode: FrameMode,
+    ring_step: usize, stroke: Stroke,
+) -> Vec<Line3D<Point3D>> {
+    let mut lines = vec![];
+    if profile.is_empty() || path.len() < 2 {
+        return lines;
+    }
+
+    let frames = match mode {
+        FrameMode::Frenet => frenet_frames(path, is_closed),
+        FrameMode::RotationMinimizing => rotation_minimizing_frames(path, is_closed),
+    };
+
+    let place = |point: &Point2D, index: usize| {
+        let frame = &frames[index];
+        let offset =
+            frame.normal.scale(point.x.value()) + frame.binormal.scale(point.y.value());
+
+        offset.translate(&path[index])
+    };
+
+    let last = path.len() - 1;
+    let step = ring_step.max(1);
+    let mut rings: Vec<usize> = (0..path.len()).step_by(step).collect();
+    if !is_closed && rings.last() != Some(&last) {
+        rings.push(last);
+    }
+
+    for index in rings {
+        for line in profile {
+            lines.push(Line3D::new(
+                place(&line.start, index),
+                place(&line.end, index),
+                stroke,
+            ));
+        }
+    }
+
+    let segments: Vec<(usize, usize)> = if is_closed {
+        (0..path.len()).map(|i| (i, (i + 1) % path.len())).collect()
+    } else {
+        (0..last).map(|i| (i, i + 1)).collect()
+    };
+
+    for line in profile {
+        for (from, to) in &segments {
+            lines.push(Line3D::new(
+                place(&line.start, *from),
+                place(&line.start, *to),
+                stroke,
+            ));
+        }
+    }
+
+    lines
+}
+
+/// Tangent by central differences (one-sided at the ends of an open path).
+fn tangents(path: &[Point3D], is_closed: bool) -> Vec<Vector3D> {
+    let n = path.len();
+
+    (0..n)
+        .map(|i| {
+            let (previous, next) = neighbours(i, n, is_closed);
+            Vector3D::between(&path[previous], &path[next]).normalized()
+        })
+        .collect()
+}
+
+fn neighbours(i: usize, n: usize, is_closed: bool) -> (usize, usize) {
+    if is_closed {
+        ((i + n - 1) % n, (i + 1) % n)
+    } else {
+        (i.saturating_sub(1), (i + 1).min(n - 1))
+    }
+}
+
+/// Any unit vector perpendicular to `tangent`.
+fn perpendicular(tangent: &Vector3D) -> Vector3D {
+    let helper = if tangent.x.value().abs() < 0.9 {
+        Vector3D::new(1.0, 0.0, 0.0)
+    } else {
+        Vector3D::new(0.0, 1.0, 0.0)
+    };
+
+    tangent.cross(&helper).normalized()
+}
+
+pub fn frenet_frames(path: &[Point3D], is_closed: bool) -> Vec<Frame> {
+    let n = path.len();
+    let tangents = tangents(path, is_closed);
+    let mut frames: Vec<Frame> = Vec::with_capacity(n);
+
+    for i in 0..n {
+        let tangent = tangents[i];
+        let (previous, next) = neighbours(i, n, is_closed);
+        // The normal points to the change of the tangent. On straight parts it is
+        // undefined, so the previous normal is kept.
+        let change = tangents[next] - tangents[previous];
+        let along = tangent.scale(change.dot(&tangent));
+        let curvature = (change - along).normalized();
+
+        let normal = if curvature.length() > 0.0 {
+            curvature
+        } else {
+            frames
+                .last()
+                .map(|frame| frame.normal)
+                .unwrap_or_else(|| perpendicular(&tangent))
+        };
+
+        frames.push(Frame {
+            tangent,
+            normal,
+            binormal: tangent.cross(&normal),
+        });
+    }
+
+    frames
+}
+
+/// Double reflection method: Wang, Jüttler, Zheng, Liu,
+/// "Computation of Rotation Minimizing Frames", 2008.
+/// On a closed path the remaining twist at the seam is spread evenly along the path.
+pub fn rotation_minimizing_frames(path: &[Point3D], is_closed: bool) -> Vec<Frame> {
+    let n = path.len();
+    let tangents = tangents(path, is_closed);
+    let first_normal = frenet_frames(path, is_closed)
+        .first()
+        .map(|frame| frame.normal)
+        .unwrap_or_else(|| perpendicular(&tangents[0]));
+
+    let mut normals = vec![first_normal];
+    let steps = if is_closed { n } else { n - 1 };
+
+    for i in 0..steps {
+        let next = (i + 1) % n;
+        let normal = normals[i];
+
+        let v1 = Vector3D::between(&path[i], &path[next]);
+        let c1 = v1.dot(&v1);
+        if c1 < 1e-12 {
+            normals.push(normal);
+            continue;
+        }
+
+        let normal_l = normal - v1.scale(2.0 / c1 * v1.dot(&normal));
+        let tangent_l = tangents[i] - v1.scale(2.0 / c1 * v1.dot(&tangents[i]));
+
+        let v2 = tangents[next] - tangent_l;
+        let c2 = v2.dot(&v2);
+        let reflected = if c2 < 1e-12 {
+            normal_l
+        } else {
+            normal_l - v2.scale(2.0 / c2 * v2.dot(&normal_l))
+        };
+
+        normals.push(reflected.normalized());
+    }
+
+    let mut twist = 0.0;
+    if is_closed {
+        // normals[n] is the transported frame back at the start.
+        let end = normals[n];
+        let binormal = tangents[0].cross(&first_normal);
+        twist = end.dot(&binormal).atan2(end.dot(&first_normal));
+    }
+
+    (0..n)
+        .map(|i| {
+            let tangent = tangents[i];
+            let binormal = tangent.cross(&normals[i]);
+            let angle = -twist * i as f64 / n as f64;
+            let (sin, cos) = angle.sin_cos();
+
+            let normal = normals[i].scale(cos) + binormal.scale(sin);
+            Frame {
+                tangent,
+                normal,
+                binormal: tangent.cross(&normal),
+            }
+        })
+        .collect()
+}
@@ -1,48 +1,63 @@
-use crate::figures::contour::Contour;
 use crate::primitives::line2d::Line2D;
 use crate::primitives::point2d::Point2D;
-use crate::smooth::ferguson::FergusonCurve;
+use crate::textures::TextureSource;
+use crate::units::Percent;
 use egui::Stroke;
 
+/// One texture layer of a surface: line art and its placement in UV space.
 #[derive(Debug)]
 pub struct Texture {
-    pub style: TextureStyle,
+    pub is_enabled: bool,
+    pub source: TextureSource,
+
+    pub scale_width: Percent,
+    pub scale_height: Percent,
+    pub offset_angle: Percent,
+    pub offset_height: Percent,
+    /// Degrees, around the center of the texture.
+    pub rotation_angle: f64,
 
-    lines: Vec<Line2D<Point2D>>,
+    pub style: TextureStyle,
 }
 
 impl Default for Texture {
     fn default() -> Self {
-        let contour = Contour {
-            curve: FergusonCurve {
-                knots: Contour::default_knots(),
-                is_closed: true,
-                step: 0.01,
-                style: Default::default(),
-            },
-            is_tooltips_mode_enabled: false,
-            is_skeleton_mode_enabled: false,
-        };
-        let lines = contour.lines();
-
         Self {
+            is_enabled: true,
+            source: TextureSource::default(),
+
+            scale_width: Percent(0.3),
+            scale_height: Percent(0.5),
+            offset_angle: Percent(0.0),
+            offset_height: Percent(0.5),
+            rotation_angle: 0.0,
+
             style: Default::default(),
-            lines,
         }
     }
 }
 
 impl Texture {
     pub fn lines(&self) -> Vec<Line2D<Point2D>> {
-        self.lines
-            .iter()
-            .map(|line| {
-                let mut line = *line;
+        self.source
+            .lines()
+            .into_iter()
+            .map(|mut line| {
                 line.stroke = self.style.stroke;
                 line
             })
             .collect()
     }
+
+    pub fn reset_placement(&mut self) {
+        let default = Self::default();
+
+        self.scale_width = default.scale_width;
+        self.scale_height = default.scale_height;
+        self.offset_angle = default.offset_angle;
+        self.offset_height = default.offset_height;
+        self.rotation_angle = default.rotation_angle;
+    }
 }
 
 #[derive(Debug)]
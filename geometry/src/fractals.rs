@@ -1,40 +1,46 @@
+use crate::fractals::variations::Variation;
 use crate::primitives::point2d::Point2D;
+use crate::random::Seed;
 use crate::shapes::dot::DotMetadata;
 use crate::units::Centimeter;
 use crate::viewport::Viewport;
 use egui::{Color32, Shape, Stroke};
 use rand::distr::Distribution;
 use rand::distr::weighted::WeightedIndex;
+use rand::rngs::StdRng;
+use strum::IntoEnumIterator;
 
+pub mod attractors;
+pub mod custom;
+pub mod density;
+pub mod deterministic;
+pub mod dimension;
+pub mod editor;
+pub mod escape;
+pub mod ifs;
+pub mod lsystem;
+pub mod presets;
+pub mod variations;
 pub mod zigzag;
 
 pub trait FractalIFS {
     fn iterations(&self) -> u32;
     fn radius(&self) -> f64;
     fn systems(&self) -> &[EquationSystem];
+    fn seed(&self) -> Seed;
 
     fn points(&self) -> Vec<(Point2D, Color32)> {
         let mut points: Vec<(Point2D, Color32)> = Vec::new();
+        points.push((Point2D::zero(), Color32::TRANSPARENT));
 
-        let start = Point2D::zero();
-        points.push((start, Color32::TRANSPARENT));
-
-        let probabilities: Vec<f64> = self
-            .systems()
-            .iter()
-            .map(|equation| equation.probability())
-            .collect();
-        let mut rng = rand::rng();
-
-        let dist =
-            WeightedIndex::new(&probabilities).expect("Failed to create WeightedIndex");
-
-        for current_index in 0..self.iterations() {
-            let equation = &self.systems()[dist.sample(&mut rng)];
-            let new_point = equation.next_point(&points[current_index as usize].0);
-
-            points.push(new_point);
-        }
+        chaos_game(
+            self.systems(),
+            self.iterations() as usize,
+            self.seed(),
+            |point, system| {
+                points.push((point, system.color));
+            },
+        );
 
         points
     }
@@ -55,20 +61,121 @@ pub trait FractalIFS {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+/// Runs the chaos game from the origin, calling `visit` with every new point
+/// and the map that produced it. The same seed always gives the same points.
+pub fn chaos_game(
+    systems: &[EquationSystem], iterations: usize, seed: Seed,
+    mut visit: impl FnMut(Point2D, &EquationSystem),
+) {
+    let Some(game) = ChaosGame::new(systems, seed) else {
+        return;
+    };
+
+    for (point, equation) in game.take(iterations) {
+        visit(point, equation);
+    }
+}
+
+/// Endless chaos game from the origin, yielding every new point and the map that
+/// produced it. Unlike [`chaos_game`], it can be stopped and resumed at any point.
+#[derive(Debug)]
+pub struct ChaosGame<'a> {
+    systems: &'a [EquationSystem],
+    dist: WeightedIndex<f64>,
+    rng: StdRng,
+    point: Point2D,
+}
+
+impl<'a> ChaosGame<'a> {
+    /// `None` if there are no maps.
+    pub fn new(systems: &'a [EquationSystem], seed: Seed) -> Option<Self> {
+        let probabilities: Vec<f64> = systems
+            .iter()
+            .map(|equation| equation.probability())
+            .collect();
+
+        // Edited or imported probabilities may all be zero: fall back to equal ones
+        let uniform = vec![1.0; probabilities.len()];
+        let dist = WeightedIndex::new(&probabilities)
+            .or_else(|_| WeightedIndex::new(&uniform))
+            .ok()?;
+
+        Some(Self {
+            systems,
+            dist,
+            rng: seed.rng(),
+            point: Point2D::zero(),
+        })
+    }
+}
+
+impl<'a> Iterator for ChaosGame<'a> {
+    type Item = (Point2D, &'a EquationSystem);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let equation = self.systems.get(self.dist.sample(&mut self.rng))?;
+        self.point = equation.next_point(&self.point).0;
+
+        Some((self.point, equation))
+    }
+}
+
+/// Square around the attractor, as its bottom left corner and side.
+pub fn bounding_square(fractal: &impl FractalIFS) -> Option<(Point2D, Centimeter)> {
+    let points = fractal.points();
+    // The first points are not yet on the attractor
+    let skipped = points.len().min(20);
+
+    square_around(points[skipped..].iter().map(|(point, _)| *point), 0.1)
+}
+
+/// Square centered on the points and at least `min_size` wide, as its bottom left
+/// corner and side. Points that are not finite are left out.
+pub fn square_around(
+    points: impl IntoIterator<Item = Point2D>, min_size: f64,
+) -> Option<(Point2D, Centimeter)> {
+    let mut min = (f64::INFINITY, f64::INFINITY);
+    let mut max = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+
+    for point in points {
+        let (x, y) = (point.x.value(), point.y.value());
+        if !x.is_finite() || !y.is_finite() {
+            continue;
+        }
+        min = (min.0.min(x), min.1.min(y));
+        max = (max.0.max(x), max.1.max(y));
+    }
+    if !min.0.is_finite() || !max.0.is_finite() {
+        return None;
+    }
+
+    let size = (max.0 - min.0).max(max.1 - min.1).max(min_size);
+    let center = ((min.0 + max.0) / 2.0, (min.1 + max.1) / 2.0);
+    let origin = Point2D::new(center.0 - size / 2.0, center.1 - size / 2.0);
+
+    Some((origin, Centimeter(size)))
+}
+
+/// Affine map x' = a·x + b·y + c, y' = d·x + e·y + f, chosen with probability p.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct EquationSystem {
-    a: f64,
-    b: f64,
-    d: f64,
-    e: f64,
-    c: f64,
-    f: f64,
-    p: f64,
-
-    color: Color32,
+    pub a: f64,
+    pub b: f64,
+    pub d: f64,
+    pub e: f64,
+    pub c: f64,
+    pub f: f64,
+    pub p: f64,
+
+    pub color: Color32,
+    /// Weights of the flame variations, indexed by [`Variation::index`].
+    /// Only [`Variation::Linear`] is set by default, which gives a classic IFS.
+    pub variations: [f64; Variation::COUNT],
 }
 
 impl EquationSystem {
+    /// Coefficients in the order of Fractint `.ifs` files. Fractint names the
+    /// translation e and f, so in terms of this struct the order is a, b, d, e, c, f, p.
     pub fn new(coefficients: [f64; 7]) -> Self {
         Self {
             a: coefficients[0],
@@ -80,6 +187,7 @@ impl EquationSystem {
             p: coefficients[6],
 
             color: Color32::BLACK,
+            variations: LINEAR_ONLY,
         }
     }
 
@@ -90,14 +198,42 @@ impl EquationSystem {
         system
     }
 
+    /// Inverse of [`EquationSystem::new`].
+    pub fn coefficients(&self) -> [f64; 7] {
+        [self.a, self.b, self.d, self.e, self.c, self.f, self.p]
+    }
+
+    /// Area scale factor of the map, a·e − b·d.
+    pub fn determinant(&self) -> f64 {
+        self.a * self.e - self.b * self.d
+    }
+
     pub fn probability(&self) -> f64 {
         self.p
     }
 
+    pub fn weight(&self, variation: Variation) -> f64 {
+        self.variations[variation.index()]
+    }
+
+    pub fn weight_mut(&mut self, variation: Variation) -> &mut f64 {
+        &mut self.variations[variation.index()]
+    }
+
+    pub fn is_linear(&self) -> bool {
+        self.variations == LINEAR_ONLY
+    }
+
     pub fn next_point(&self, point: &Point2D) -> (Point2D, Color32) {
         let x = self.a * point.x.value() + self.b * point.y.value() + self.c;
         let y = self.d * point.x.value() + self.e * point.y.value() + self.f;
 
+        let (x, y) = if self.is_linear() {
+            (x, y)
+        } else {
+            self.vary(x, y)
+        };
+
         let point = Point2D {
             x: Centimeter(x),
             y: Centimeter(y),
@@ -105,4 +241,24 @@ impl EquationSystem {
 
         (point, self.color)
     }
+
+    /// Weighted sum of the variations at (x, y).
+    fn vary(&self, x: f64, y: f64) -> (f64, f64) {
+        let mut sum = (0.0, 0.0);
+        for variation in Variation::iter() {
+            let weight = self.weight(variation);
+            if weight != 0.0 {
+                let (vx, vy) = variation.apply(x, y);
+                sum = (sum.0 + weight * vx, sum.1 + weight * vy);
+            }
+        }
+
+        sum
+    }
 }
+
+const LINEAR_ONLY: [f64; Variation::COUNT] = {
+    let mut weights = [0.0; Variation::COUNT];
+    weights[0] = 1.0;
+    weights
+};
@@ -0,0 +1,401 @@
+use crate::animations::{Animatable, Parameter};
+use crate::primitives::line3d::Line3D;
+use crate::primitives::point2d::Point2D;
+use crate::primitives::point3d::Point3D;
+use crate::units::Centimeter;
+use egui::{Color32, Stroke};
+use std::cell::RefCell;
+use strum_macros::EnumIter;
+
+/// Parameters of a map or a flow. Unused trailing values are ignored.
+pub type Parameters = [f64; 6];
+
+/// Iterations skipped before the orbit settles on the attractor.
+const TRANSIENT: usize = 100;
+
+/// Discrete two-dimensional maps whose orbits fill a strange attractor.
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum Map2D {
+    Clifford,
+    DeJong,
+    Henon,
+    Ikeda,
+}
+
+impl Map2D {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Clifford => "Clifford",
+            Self::DeJong => "Peter de Jong",
+            Self::Henon => "Hénon",
+            Self::Ikeda => "Ikeda",
+        }
+    }
+
+    pub fn parameter_names(&self) -> &'static [&'static str] {
+        match self {
+            Self::Clifford | Self::DeJong => &["a", "b", "c", "d"],
+            Self::Henon => &["a", "b"],
+            Self::Ikeda => &["u"],
+        }
+    }
+
+    pub fn default_parameters(&self) -> Parameters {
+        match self {
+            Self::Clifford => [-1.4, 1.6, 1.0, 0.7, 0.0, 0.0],
+            Self::DeJong => [1.4, -2.3, 2.4, -2.1, 0.0, 0.0],
+            Self::Henon => [1.4, 0.3, 0.0, 0.0, 0.0, 0.0],
+            Self::Ikeda => [0.9, 0.0, 0.0, 0.0, 0.0, 0.0],
+        }
+    }
+
+    /// Centimeters per unit of the map, so that the attractor is about 20 cm wide.
+    pub fn default_scale(&self) -> Centimeter {
+        match self {
+            Self::Clifford => Centimeter(4.0),
+            Self::DeJong => Centimeter(5.0),
+            Self::Henon => Centimeter(7.0),
+            Self::Ikeda => Centimeter(6.0),
+        }
+    }
+
+    /// Center of the attractor in the coordinates of the map.
+    fn center(&self) -> (f64, f64) {
+        match self {
+            Self::Clifford | Self::DeJong | Self::Henon => (0.0, 0.0),
+            Self::Ikeda => (0.6, -0.7),
+        }
+    }
+
+    pub fn next(&self, (x, y): (f64, f64), p: &Parameters) -> (f64, f64) {
+        match self {
+            // x' = sin(a·y) + c·cos(a·x), y' = sin(b·x) + d·cos(b·y)
+            Self::Clifford => (
+                (p[0] * y).sin() + p[2] * (p[0] * x).cos(),
+                (p[1] * x).sin() + p[3] * (p[1] * y).cos(),
+            ),
+            // x' = sin(a·y) - cos(b·x), y' = sin(c·x) - cos(d·y)
+            Self::DeJong => (
+                (p[0] * y).sin() - (p[1] * x).cos(),
+                (p[2] * x).sin() - (p[3] * y).cos(),
+            ),
+            // x' = 1 - a·x² + y, y' = b·x
+            Self::Henon => (1.0 - p[0] * x * x + y, p[1] * x),
+            // t = 0.4 - 6 / (1 + x² + y²), (x', y') = (1, 0) + u·R(t)·(x, y)
+            Self::Ikeda => {
+                let t = 0.4 - 6.0 / (1.0 + x * x + y * y);
+                let (sin, cos) = t.sin_cos();
+                (1.0 + p[0] * (x * cos - y * sin), p[0] * (x * sin + y * cos))
+            },
+        }
+    }
+}
+
+/// Point cloud of a two-dimensional map, drawn as a density image.
+#[derive(Debug, Clone)]
+pub struct Attractor2D {
+    pub map: Map2D,
+    pub parameters: Parameters,
+    pub samples: usize,
+    pub scale: Centimeter,
+    pub color: Color32,
+}
+
+impl Default for Attractor2D {
+    fn default() -> Self {
+        Self::new(Map2D::Clifford)
+    }
+}
+
+impl Animatable for Attractor2D {
+    fn parameters(&mut self) -> Vec<Parameter<'_>> {
+        let mut parameters: Vec<Parameter<'_>> = self
+            .map
+            .parameter_names()
+            .iter()
+            .zip(self.parameters.iter_mut())
+            .map(|(name, value)| Parameter::new(name, value, -10.0..=10.0))
+            .collect();
+        parameters.push(Parameter::new("Scale", &mut self.scale.0, 0.1..=100.0));
+
+        parameters
+    }
+}
+
+impl Attractor2D {
+    pub fn new(map: Map2D) -> Self {
+        Self {
+            map,
+            parameters: map.default_parameters(),
+            samples: 500_000,
+            scale: map.default_scale(),
+            color: Color32::from_rgb(30, 60, 140),
+        }
+    }
+
+    /// Points of the orbit after the transient.
+    /// An orbit that escapes to infinity is restarted.
+    pub fn orbit(&self) -> impl Iterator<Item = Point2D> + '_ {
+        const START: (f64, f64) = (0.1, 0.1);
+
+        let (center_x, center_y) = self.map.center();
+        let scale = self.scale.value();
+        let mut point = START;
+
+        std::iter::repeat_with(move || {
+            point = self.map.next(point, &self.parameters);
+            if !point.0.is_finite() || !point.1.is_finite() || point.0.abs() > 1e6 {
+                point = START;
+                return None;
+            }
+
+            Some(Point2D::new(
+                (point.0 - center_x) * scale,
+                (point.1 - center_y) * scale,
+            ))
+        })
+        .take(self.samples + TRANSIENT)
+        .skip(TRANSIENT)
+        .flatten()
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new(self.map);
+    }
+}
+
+/// Continuous three-dimensional flows, dx/dt = f(x).
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum Flow3D {
+    Lorenz,
+    Rossler,
+    Aizawa,
+}
+
+impl Flow3D {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Lorenz => "Lorenz",
+            Self::Rossler => "Rössler",
+            Self::Aizawa => "Aizawa",
+        }
+    }
+
+    pub fn parameter_names(&self) -> &'static [&'static str] {
+        match self {
+            Self::Lorenz => &["σ", "ρ", "β"],
+            Self::Rossler => &["a", "b", "c"],
+            Self::Aizawa => &["a", "b", "c", "d", "e", "f"],
+        }
+    }
+
+    pub fn default_parameters(&self) -> Parameters {
+        match self {
+            Self::Lorenz => [10.0, 28.0, 8.0 / 3.0, 0.0, 0.0, 0.0],
+            Self::Rossler => [0.2, 0.2, 5.7, 0.0, 0.0, 0.0],
+            Self::Aizawa => [0.95, 0.7, 0.6, 3.5, 0.25, 0.1],
+        }
+    }
+
+    pub fn default_step(&self) -> f64 {
+        match self {
+            Self::Lorenz | Self::Aizawa => 0.01,
+            Self::Rossler => 0.03,
+        }
+    }
+
+    pub fn default_scale(&self) -> f64 {
+        match self {
+            Self::Lorenz => 0.4,
+            Self::Rossler => 0.6,
+            Self::Aizawa => 6.0,
+        }
+    }
+
+    fn start(&self) -> [f64; 3] {
+        match self {
+            Self::Lorenz | Self::Rossler => [1.0, 1.0, 1.0],
+            Self::Aizawa => [0.1, 0.0, 0.0],
+        }
+    }
+
+    pub fn derivative(&self, [x, y, z]: [f64; 3], p: &Parameters) -> [f64; 3] {
+        match self {
+            Self::Lorenz => [p[0] * (y - x), x * (p[1] - z) - y, x * y - p[2] * z],
+            Self::Rossler => [-y - z, x + p[0] * y, p[1] + z * (x - p[2])],
+            Self::Aizawa => [
+                (z - p[1]) * x - p[3] * y,
+                p[3] * x + (z - p[1]) * y,
+                p[2] + p[0] * z - z * z * z / 3.0 - (x * x + y * y) * (1.0 + p[4] * z)
+                    + p[5] * z * x * x * x,
+            ],
+        }
+    }
+
+    /// One step of the classic fourth-order Runge–Kutta method.
+    pub fn rk4(&self, state: [f64; 3], dt: f64, p: &Parameters) -> [f64; 3] {
+        let shifted = |k: [f64; 3], factor: f64| {
+            [
+                state[0] + k[0] * factor,
+                state[1] + k[1] * factor,
+                state[2] + k[2] * factor,
+            ]
+        };
+
+        let k1 = self.derivative(state, p);
+        let k2 = self.derivative(shifted(k1, dt / 2.0), p);
+        let k3 = self.derivative(shifted(k2, dt / 2.0), p);
+        let k4 = self.derivative(shifted(k3, dt), p);
+
+        std::array::from_fn(|i| {
+            state[i] + dt / 6.0 * (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i])
+        })
+    }
+}
+
+/// Trajectory of a three-dimensional flow, drawn as a polyline.
+/// The vertical axis of the flow (z) is mapped to Y of the scene.
+#[derive(Debug, Clone)]
+pub struct Attractor3D {
+    pub flow: Flow3D,
+    pub parameters: Parameters,
+    pub steps: usize,
+    /// Time step of the integrator.
+    pub dt: f64,
+    pub scale: f64,
+    pub stroke: Stroke,
+
+    /// Points integrated last time, and what they were integrated from. Kept
+    /// behind the drawing methods, so that editing the public fields is enough.
+    trajectory: RefCell<Option<(TrajectoryKey, Vec<Point3D>)>>,
+}
+
+/// Everything the trajectory of a flow depends on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TrajectoryKey {
+    flow: Flow3D,
+    parameters: Parameters,
+    steps: usize,
+    dt: f64,
+    scale: f64,
+}
+
+impl Default for Attractor3D {
+    fn default() -> Self {
+        Self::new(Flow3D::Lorenz)
+    }
+}
+
+impl Animatable for Attractor3D {
+    fn parameters(&mut self) -> Vec<Parameter<'_>> {
+        let mut parameters: Vec<Parameter<'_>> = self
+            .flow
+            .parameter_names()
+            .iter()
+            .zip(self.parameters.iter_mut())
+            .map(|(name, value)| Parameter::new(name, value, -100.0..=100.0))
+            .collect();
+        parameters.push(Parameter::new("Time Step", &mut self.dt, 0.0001..=0.1));
+        parameters.push(Parameter::new("Scale", &mut self.scale, 0.01..=100.0));
+
+        parameters
+    }
+}
+
+impl Attractor3D {
+    pub fn new(flow: Flow3D) -> Self {
+        Self {
+            flow,
+            parameters: flow.default_parameters(),
+            steps: 10_000,
+            dt: flow.default_step(),
+            scale: flow.default_scale(),
+            stroke: Stroke::new(1.0, Color32::from_rgb(30, 60, 140)),
+            trajectory: RefCell::new(None),
+        }
+    }
+
+    /// Calls `f` with the trajectory, integrated again only if the flow, its
+    /// parameters, the steps or the scale have changed since the last call.
+    fn with_trajectory<R>(&self, f: impl FnOnce(&[Point3D]) -> R) -> R {
+        let key = TrajectoryKey {
+            flow: self.flow,
+            parameters: self.parameters,
+            steps: self.steps,
+            dt: self.dt,
+            scale: self.scale,
+        };
+
+        let mut trajectory = self.trajectory.borrow_mut();
+        let (_, points) = match trajectory.take() {
+            Some((integrated, points)) if integrated == key => {
+                trajectory.insert((integrated, points))
+            },
+            _ => trajectory.insert((key, self.integrate())),
+        };
+
+        f(points)
+    }
+
+    fn integrate(&self) -> Vec<Point3D> {
+        let mut state = self.flow.start();
+        let mut points = Vec::with_capacity(self.steps);
+
+        for index in 0..self.steps + TRANSIENT {
+            state = self.flow.rk4(state, self.dt, &self.parameters);
+            if state.iter().any(|value| !value.is_finite()) {
+                break;
+            }
+
+            if index >= TRANSIENT {
+                points.push(Point3D::new(
+                    state[0] * self.scale,
+                    state[2] * self.scale,
+                    state[1] * self.scale,
+                ));
+            }
+        }
+
+        points
+    }
+
+    pub fn lines(&self) -> Vec<Line3D<Point3D>> {
+        self.with_trajectory(|points| {
+            points
+                .windows(2)
+                .map(|pair| Line3D::new(pair[0], pair[1], self.stroke))
+                .collect()
+        })
+    }
+
+    /// Center of the bounding box of the trajectory.
+    pub fn pivot_point(&self) -> Point3D {
+        self.with_trajectory(|points| {
+            if points.is_empty() {
+                return Point3D::zero();
+            }
+
+            let mut min = [f64::INFINITY; 3];
+            let mut max = [f64::NEG_INFINITY; 3];
+            for point in points {
+                for (i, value) in [point.x.value(), point.y.value(), point.z.value()]
+                    .into_iter()
+                    .enumerate()
+                {
+                    min[i] = min[i].min(value);
+                    max[i] = max[i].max(value);
+                }
+            }
+
+            Point3D::new(
+                (min[0] + max[0]) / 2.0,
+                (min[1] + max[1]) / 2.0,
+                (min[2] + max[2]) / 2.0,
+            )
+        })
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new(self.flow);
+    }
+}
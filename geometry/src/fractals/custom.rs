@@ -0,0 +1,81 @@
+use crate::fractals::{EquationSystem, FractalIFS};
+use crate::random::Seed;
+
+/// Iterated function system with an arbitrary set of maps, loaded from a preset or a file.
+#[derive(Debug, Clone)]
+pub struct FractalCustom {
+    pub name: String,
+    pub iterations: u32,
+    pub radius: f64,
+    pub seed: Seed,
+
+    pub systems: Vec<EquationSystem>,
+}
+
+impl Default for FractalCustom {
+    fn default() -> Self {
+        Self {
+            name: "Untitled".to_string(),
+            iterations: 10000,
+            radius: 1.5,
+            seed: Seed::default(),
+            systems: vec![],
+        }
+    }
+}
+
+impl FractalCustom {
+    pub fn new(name: &str, systems: Vec<EquationSystem>) -> Self {
+        Self {
+            name: name.to_string(),
+            systems,
+            ..Default::default()
+        }
+    }
+
+    /// Sum of the probabilities of all maps.
+    pub fn total_probability(&self) -> f64 {
+        self.systems.iter().map(|system| system.p).sum()
+    }
+
+    /// Probabilities proportional to the area each map covers, |det|.
+    /// Degenerate maps (the stem of the fern) still get a small share.
+    pub fn suggest_probabilities(&mut self) {
+        const MIN_WEIGHT: f64 = 0.01;
+
+        for system in &mut self.systems {
+            system.p = system.determinant().abs().max(MIN_WEIGHT);
+        }
+        self.normalize_probabilities();
+    }
+
+    /// Scales the probabilities so that they sum to one.
+    pub fn normalize_probabilities(&mut self) {
+        let total = self.total_probability();
+        if total <= 0.0 {
+            return;
+        }
+
+        for system in &mut self.systems {
+            system.p /= total;
+        }
+    }
+}
+
+impl FractalIFS for FractalCustom {
+    fn iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    fn systems(&self) -> &[EquationSystem] {
+        &self.systems
+    }
+
+    fn seed(&self) -> Seed {
+        self.seed
+    }
+}
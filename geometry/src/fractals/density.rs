@@ -0,0 +1,128 @@
+use crate::primitives::point2d::Point2D;
+use crate::units::Centimeter;
+use crate::viewport::{Viewport, ViewportBounds};
+use egui::{Color32, ColorImage};
+
+/// Settings of the density image the chaos game is drawn as instead of separate
+/// dots: how many points are plotted into the [`Histogram`] and how it is tone mapped.
+#[derive(Debug, Clone, Copy)]
+pub struct DensityRenderer {
+    pub samples: usize,
+    /// Applied after the log-density mapping. Larger values reveal sparse areas.
+    pub gamma: f64,
+    pub brightness: f64,
+}
+
+impl Default for DensityRenderer {
+    fn default() -> Self {
+        Self {
+            samples: 1_000_000,
+            gamma: 2.2,
+            brightness: 1.0,
+        }
+    }
+}
+
+/// Iterations skipped before the point reaches the attractor.
+pub const WARM_UP: usize = 20;
+
+impl DensityRenderer {
+    /// Image of the points plotted so far.
+    pub fn tone_map(&self, histogram: &Histogram) -> ColorImage {
+        histogram.tone_map(self.gamma, self.brightness)
+    }
+
+    pub fn reset(&mut self) {
+        *self = Default::default();
+    }
+}
+
+/// Hits and colors of every pixel of the viewport. Points can be plotted in
+/// batches and the image tone mapped in between, so it fills in progressively.
+#[derive(Debug, Default)]
+pub struct Histogram {
+    width: usize,
+    height: usize,
+    /// Part of the plane the histogram covers.
+    bounds: ViewportBounds<Centimeter>,
+    hits: Vec<u32>,
+    /// Sums of the colors of the maps, in linear RGB.
+    colors: Vec<[f32; 3]>,
+}
+
+impl Histogram {
+    /// Empty histogram over the visible part of the viewport, one bin per pixel.
+    pub fn new(viewport: &Viewport) -> Self {
+        let bounds = viewport.state.bounds;
+        let width = (bounds.maximum_x - bounds.minimum_x).value().max(1.0) as usize;
+        let height = (bounds.maximum_y - bounds.minimum_y).value().max(1.0) as usize;
+
+        Self {
+            width,
+            height,
+            bounds: bounds.to_centimeters(viewport),
+            hits: vec![0; width * height],
+            colors: vec![[0.0; 3]; width * height],
+        }
+    }
+
+    /// Adds a hit to the pixel under the point. Points outside are ignored.
+    pub fn plot(&mut self, point: Point2D, color: Color32) {
+        let bounds = &self.bounds;
+        let (x0, x1) = (bounds.minimum_x.value(), bounds.maximum_x.value());
+        let (y0, y1) = (bounds.minimum_y.value(), bounds.maximum_y.value());
+        if x1 <= x0 || y1 <= y0 {
+            return;
+        }
+
+        // Pixel rows go down, Y goes up
+        let column = (point.x.value() - x0) / (x1 - x0) * self.width as f64;
+        let row = (y1 - point.y.value()) / (y1 - y0) * self.height as f64;
+        if !(0.0..self.width as f64).contains(&column)
+            || !(0.0..self.height as f64).contains(&row)
+        {
+            return;
+        }
+
+        let index = row as usize * self.width + column as usize;
+        self.hits[index] = self.hits[index].saturating_add(1);
+
+        let color = egui::Rgba::from(color);
+        for (sum, channel) in
+            self.colors[index]
+                .iter_mut()
+                .zip([color.r(), color.g(), color.b()])
+        {
+            *sum += channel;
+        }
+    }
+
+    /// Log-density mapping: the alpha of a pixel is log(1 + hits) / log(1 + max hits),
+    /// raised to 1 / gamma. The color is the average color of the hits.
+    fn tone_map(&self, gamma: f64, brightness: f64) -> ColorImage {
+        let max = self.hits.iter().copied().max().unwrap_or(0);
+        let mut image =
+            ColorImage::filled([self.width, self.height], Color32::TRANSPARENT);
+        if max == 0 {
+            return image;
+        }
+
+        let log_max = (1.0 + max as f64).ln();
+        let exponent = 1.0 / gamma.max(0.01);
+
+        for (index, pixel) in image.pixels.iter_mut().enumerate() {
+            let hits = self.hits[index];
+            if hits == 0 {
+                continue;
+            }
+
+            let density = (1.0 + hits as f64).ln() / log_max;
+            let alpha = (density.powf(exponent) * brightness).clamp(0.0, 1.0) as f32;
+            let [r, g, b] = self.colors[index].map(|sum| sum / hits as f32);
+
+            *pixel = egui::Rgba::from_rgba_unmultiplied(r, g, b, alpha).into();
+        }
+
+        image
+    }
+}
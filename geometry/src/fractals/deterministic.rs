@@ -0,0 +1,136 @@
+use crate::fractals::{EquationSystem, FractalIFS, bounding_square};
+use crate::primitives::line2d::Line2D;
+use crate::primitives::point2d::Point2D;
+use crate::units::Centimeter;
+use egui::{Color32, Stroke};
+use std::f64::consts::PI;
+use strum_macros::EnumIter;
+
+/// Deterministic IFS algorithm: the Hutchinson operator W(S) = w₁(S) ∪ … ∪ wₙ(S)
+/// applied to an initial shape again and again. Any shape converges to the same attractor.
+#[derive(Debug)]
+pub struct DeterministicIFS {
+    pub generations: usize,
+    pub shape: InitialShape,
+    /// Bottom left corner of the square the initial shape is inscribed in.
+    pub origin: Point2D,
+    pub size: Centimeter,
+    pub width: f32,
+}
+
+impl Default for DeterministicIFS {
+    fn default() -> Self {
+        Self {
+            generations: 4,
+            shape: InitialShape::Square,
+            origin: Point2D::new(-5.0, -5.0),
+            size: Centimeter(10.0),
+            width: 1.0,
+        }
+    }
+}
+
+impl DeterministicIFS {
+    /// Generations stop before the number of lines exceeds this.
+    pub const MAX_LINES: usize = 300_000;
+
+    /// Every line is colored by the last map applied to it.
+    pub fn lines(&self, systems: &[EquationSystem]) -> Vec<Line2D<Point2D>> {
+        let mut lines = self.initial_lines();
+
+        for _ in 0..self.reachable_generations(systems.len()) {
+            lines = systems
+                .iter()
+                .flat_map(|system| {
+                    let stroke = Stroke::new(self.width, system.color);
+                    lines.iter().map(move |line| {
+                        Line2D::new(
+                            system.next_point(&line.start).0,
+                            system.next_point(&line.end).0,
+                            stroke,
+                        )
+                    })
+                })
+                .collect();
+        }
+
+        lines
+    }
+
+    /// Generations that can be computed within [`Self::MAX_LINES`].
+    pub fn reachable_generations(&self, maps: usize) -> usize {
+        let mut count = self.initial_lines().len();
+        let mut generations = 0;
+
+        while generations < self.generations && maps > 0 {
+            count *= maps;
+            if count > Self::MAX_LINES {
+                break;
+            }
+            generations += 1;
+        }
+
+        generations
+    }
+
+    /// Makes the initial shape enclose the attractor.
+    pub fn fit(&mut self, fractal: &impl FractalIFS) {
+        if let Some((origin, size)) = bounding_square(fractal) {
+            self.origin = origin;
+            self.size = size;
+        }
+    }
+
+    fn initial_lines(&self) -> Vec<Line2D<Point2D>> {
+        let stroke = Stroke::new(self.width, Color32::BLACK);
+        let half = self.size.value() / 2.0;
+        let center = (self.origin.x.value() + half, self.origin.y.value() + half);
+
+        let vertices = match self.shape {
+            InitialShape::Square => 4,
+            InitialShape::Triangle => 3,
+            InitialShape::Circle => 32,
+        };
+        // The square fills the bounding square, the others are inscribed in it
+        let (radius, start) = match self.shape {
+            InitialShape::Square => (half * 2f64.sqrt(), PI / 4.0),
+            InitialShape::Triangle | InitialShape::Circle => (half, PI / 2.0),
+        };
+
+        let points: Vec<Point2D> = (0..=vertices)
+            .map(|k| {
+                let angle = start + 2.0 * PI * k as f64 / vertices as f64;
+                Point2D::new(
+                    center.0 + radius * angle.cos(),
+                    center.1 + radius * angle.sin(),
+                )
+            })
+            .collect();
+
+        points
+            .windows(2)
+            .map(|pair| Line2D::new(pair[0], pair[1], stroke))
+            .collect()
+    }
+
+    pub fn reset(&mut self) {
+        *self = Default::default();
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum InitialShape {
+    Square,
+    Triangle,
+    Circle,
+}
+
+impl InitialShape {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Square => "Square",
+            Self::Triangle => "Triangle",
+            Self::Circle => "Circle",
+        }
+    }
+}
@@ -0,0 +1,298 @@
+use crate::fractals::{EquationSystem, square_around};
+use crate::primitives::line2d::Line2D;
+use crate::primitives::point2d::Point2D;
+use std::collections::HashSet;
+use strum_macros::EnumIter;
+
+/// Most points `estimate_lines` samples, so that deep figures get a coarser
+/// spacing instead of more points.
+const MAX_LINE_SAMPLES: usize = 500_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum DimensionMethod {
+    /// Slope of ln N(ε) over ln(1/ε), N is the number of occupied ε-boxes.
+    BoxCounting,
+    /// Slope of ln C(r) over ln r, C is the fraction of point pairs closer than r.
+    Correlation,
+}
+
+impl DimensionMethod {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::BoxCounting => "Box-Counting",
+            Self::Correlation => "Correlation",
+        }
+    }
+
+    /// Labels of the axes of the log-log plot.
+    pub fn axes(&self) -> (&'static str, &'static str) {
+        match self {
+            Self::BoxCounting => ("ln(1/ε)", "ln N(ε)"),
+            Self::Correlation => ("ln r", "ln C(r)"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DimensionEstimator {
+    pub method: DimensionMethod,
+    /// Number of scales, each one half of the previous.
+    pub scales: usize,
+    /// Points used by the correlation method, which is quadratic in their number.
+    pub max_points: usize,
+}
+
+impl Default for DimensionEstimator {
+    fn default() -> Self {
+        Self {
+            method: DimensionMethod::BoxCounting,
+            scales: 10,
+            max_points: 2000,
+        }
+    }
+}
+
+/// Point of the log-log plot.
+#[derive(Debug, Clone, Copy)]
+pub struct LogSample {
+    pub x: f64,
+    pub y: f64,
+    /// Saturated scales (too coarse or too fine for the number of points) are shown
+    /// but left out of the regression.
+    pub is_fitted: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct DimensionEstimate {
+    pub method: DimensionMethod,
+    pub samples: Vec<LogSample>,
+    /// Least squares line y = slope·x + intercept. The slope is the dimension.
+    pub slope: f64,
+    pub intercept: f64,
+    pub r_squared: f64,
+    /// Number of points the estimate is based on.
+    pub points: usize,
+}
+
+impl DimensionEstimator {
+    pub fn estimate(&self, points: &[Point2D]) -> Option<DimensionEstimate> {
+        let (origin, size) = square_around(points.iter().copied(), 0.0)?;
+        // Points all in one place have no dimension
+        if size.value() <= 1e-12 {
+            return None;
+        }
+        // Slightly larger, so that the points on the far edges stay inside the last box
+        let origin = (origin.x.value(), origin.y.value());
+        let size = size.value() * (1.0 + 1e-9);
+
+        let samples = match self.method {
+            DimensionMethod::BoxCounting => self.box_counting(points, origin, size),
+            DimensionMethod::Correlation => self.correlation(points, size),
+        };
+        let (slope, intercept, r_squared) = regression(&samples)?;
+
+        Some(DimensionEstimate {
+            method: self.method,
+            samples,
+            slope,
+            intercept,
+            r_squared,
+            points: points.len(),
+        })
+    }
+
+    /// Same as `estimate`, for points sampled along the lines.
+    pub fn estimate_lines(&self, lines: &[Line2D<Point2D>]) -> Option<DimensionEstimate> {
+        let (_, size) =
+            square_around(lines.iter().flat_map(|line| [line.start, line.end]), 0.0)?;
+
+        let length: f64 = lines
+            .iter()
+            .map(|line| {
+                (line.end.x.value() - line.start.x.value())
+                    .hypot(line.end.y.value() - line.start.y.value())
+            })
+            .filter(|length| length.is_finite())
+            .sum();
+
+        // Finer than the smallest box, so that every line crosses all boxes it passes,
+        // unless that takes more samples than the budget allows
+        let spacing = (size.value() / 2f64.powi(self.scales as i32 + 1))
+            .max(length / MAX_LINE_SAMPLES as f64);
+
+        self.estimate(&sample_lines(lines, spacing))
+    }
+
+    fn box_counting(
+        &self, points: &[Point2D], origin: (f64, f64), size: f64,
+    ) -> Vec<LogSample> {
+        (1..=self.scales.max(2))
+            .map(|k| {
+                let epsilon = size / 2f64.powi(k as i32);
+                let boxes: HashSet<(i64, i64)> = points
+                    .iter()
+                    .map(|point| {
+                        (
+                            ((point.x.value() - origin.0) / epsilon) as i64,
+                            ((point.y.value() - origin.1) / epsilon) as i64,
+                        )
+                    })
+                    .collect();
+
+                // A box per point means the points are too sparse for this scale
+                let count = boxes.len();
+                LogSample {
+                    x: (1.0 / epsilon).ln(),
+                    y: (count as f64).ln(),
+                    is_fitted: k > 1 && count * 4 < points.len(),
+                }
+            })
+            .collect()
+    }
+
+    fn correlation(&self, points: &[Point2D], size: f64) -> Vec<LogSample> {
+        // Evenly spread subset, the order of the chaos game is random anyway
+        let stride = points.len().div_ceil(self.max_points.max(2));
+        let subset: Vec<(f64, f64)> = points
+            .iter()
+            .step_by(stride.max(1))
+            .map(|point| (point.x.value(), point.y.value()))
+            .collect();
+
+        let mut distances = Vec::with_capacity(subset.len() * subset.len() / 2);
+        for (i, a) in subset.iter().enumerate() {
+            for b in &subset[i + 1..] {
+                distances.push((a.0 - b.0).hypot(a.1 - b.1));
+            }
+        }
+        distances.sort_by(f64::total_cmp);
+        let pairs = distances.len().max(1) as f64;
+
+        (1..=self.scales.max(2))
+            .filter_map(|k| {
+                let radius = size / 2f64.powi(k as i32);
+                let count = distances.partition_point(|distance| *distance < radius);
+                if count == 0 {
+                    return None;
+                }
+
+                // Too few pairs make the count noisy, a radius close to the size of
+                // the set counts almost every pair
+                let fraction = count as f64 / pairs;
+                Some(LogSample {
+                    x: radius.ln(),
+                    y: fraction.ln(),
+                    is_fitted: count >= 10 && fraction < 0.5,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Points every `spacing` along the lines, measured over their total length, so
+/// that how many there are does not depend on how the lines are cut.
+pub fn sample_lines(lines: &[Line2D<Point2D>], spacing: f64) -> Vec<Point2D> {
+    let mut points = vec![];
+    if spacing <= 0.0 {
+        return points;
+    }
+
+    // Distance along the current line to the next point, carried over to the next one
+    let mut offset = 0.0;
+    for line in lines {
+        let (x0, y0) = (line.start.x.value(), line.start.y.value());
+        let (x1, y1) = (line.end.x.value(), line.end.y.value());
+        let length = (x1 - x0).hypot(y1 - y0);
+        if !length.is_finite() {
+            continue;
+        }
+
+        while offset <= length {
+            let t = if length > 0.0 { offset / length } else { 0.0 };
+            points.push(Point2D::new(x0 + (x1 - x0) * t, y0 + (y1 - y0) * t));
+            offset += spacing;
+        }
+        offset -= length;
+    }
+
+    points
+}
+
+/// Lipschitz constant of the linear part of the map: its largest singular value.
+pub fn contraction_factor(system: &EquationSystem) -> f64 {
+    let (a, b, d, e) = (system.a, system.b, system.d, system.e);
+
+    // Singular values of [[a, b], [d, e]] are the square roots of the eigenvalues of AᵀA
+    let sum = a * a + b * b + d * d + e * e;
+    let determinant = a * e - b * d;
+    let discriminant = (sum * sum - 4.0 * determinant * determinant).max(0.0);
+
+    ((sum + discriminant.sqrt()) / 2.0).sqrt()
+}
+
+/// Solution s of the Moran equation Σ rᵢˢ = 1, rᵢ being the contraction factors.
+/// Exact for similarities that satisfy the open set condition, an upper bound otherwise.
+/// `None` if some map does not contract.
+pub fn similarity_dimension(systems: &[EquationSystem]) -> Option<f64> {
+    let factors: Vec<f64> = systems
+        .iter()
+        .map(contraction_factor)
+        .filter(|factor| *factor > 1e-12)
+        .collect();
+    if factors.is_empty() || factors.iter().any(|factor| *factor >= 1.0) {
+        return None;
+    }
+
+    // Σ rᵢˢ decreases in s, so bisection finds the only root
+    let moran = |s: f64| factors.iter().map(|factor| factor.powf(s)).sum::<f64>() - 1.0;
+    let (mut low, mut high) = (0.0, 1.0);
+    while moran(high) > 0.0 {
+        high *= 2.0;
+        if high > 1e3 {
+            return None;
+        }
+    }
+    for _ in 0..100 {
+        let middle = (low + high) / 2.0;
+        if moran(middle) > 0.0 {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+
+    Some((low + high) / 2.0)
+}
+
+/// Least squares over the fitted samples: (slope, intercept, r²).
+fn regression(samples: &[LogSample]) -> Option<(f64, f64, f64)> {
+    let fitted: Vec<&LogSample> =
+        samples.iter().filter(|sample| sample.is_fitted).collect();
+    if fitted.len() < 2 {
+        return None;
+    }
+
+    let n = fitted.len() as f64;
+    let mean_x = fitted.iter().map(|sample| sample.x).sum::<f64>() / n;
+    let mean_y = fitted.iter().map(|sample| sample.y).sum::<f64>() / n;
+
+    let (mut sxx, mut sxy, mut syy) = (0.0, 0.0, 0.0);
+    for sample in &fitted {
+        let (dx, dy) = (sample.x - mean_x, sample.y - mean_y);
+        sxx += dx * dx;
+        sxy += dx * dy;
+        syy += dy * dy;
+    }
+    if sxx < 1e-12 {
+        return None;
+    }
+
+    let slope = sxy / sxx;
+    let r_squared = if syy < 1e-12 {
+        1.0
+    } else {
+        sxy * sxy / (sxx * syy)
+    };
+
+    Some((slope, mean_y - slope * mean_x, r_squared))
+}
@@ -0,0 +1,322 @@
+use crate::fractals::{EquationSystem, FractalIFS, bounding_square};
+use crate::primitives::line2d::Line2D;
+use crate::primitives::point2d::{MoveablePoint, Point2D};
+use crate::shapes::dot::DotMetadata;
+use crate::units::{Centimeter, Pixel};
+use crate::viewport::Viewport;
+use egui::{Color32, Response, Shape, Stroke};
+
+/// Draws every map as the image of a reference square and lets its corners be dragged.
+/// The coefficients are recomputed from the dragged parallelogram.
+#[derive(Debug)]
+pub struct MapEditor {
+    pub is_enabled: bool,
+    /// Bottom left corner of the reference square.
+    pub origin: Point2D,
+    pub size: Centimeter,
+    /// Suggest the probabilities from the determinants after every edit.
+    pub is_auto_probability: bool,
+
+    pub style: MapEditorStyle,
+}
+
+impl Default for MapEditor {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+            origin: Point2D::new(-5.0, -5.0),
+            size: Centimeter(10.0),
+            is_auto_probability: false,
+
+            style: MapEditorStyle::default(),
+        }
+    }
+}
+
+impl MapEditor {
+    /// Reference square and the parallelogram of every map.
+    /// The image of the bottom edge is drawn thicker to show orientation and reflection.
+    pub fn lines(&self, systems: &[EquationSystem]) -> Vec<Line2D<Point2D>> {
+        if !self.is_enabled {
+            return vec![];
+        }
+
+        let mut lines = vec![];
+        let square = self.square_corners();
+        for i in 0..4 {
+            lines.push(Line2D::new(
+                square[i],
+                square[(i + 1) % 4],
+                self.style.reference,
+            ));
+        }
+
+        for system in systems {
+            let corners = self.parallelogram(system).corners();
+            let stroke = Stroke::new(self.style.width, system.color);
+            for i in 0..4 {
+                let stroke = if i == 0 {
+                    Stroke::new(2.0 * self.style.width, system.color)
+                } else {
+                    stroke
+                };
+                lines.push(Line2D::new(corners[i], corners[(i + 1) % 4], stroke));
+            }
+        }
+
+        lines
+    }
+
+    pub fn handle_shapes(
+        &self, systems: &[EquationSystem], viewport: &Viewport,
+    ) -> Vec<Shape> {
+        if !self.is_enabled {
+            return vec![];
+        }
+
+        systems
+            .iter()
+            .flat_map(|system| {
+                let parallelogram = self.parallelogram(system);
+                MapHandle::ALL.map(|handle| {
+                    let fill = match handle {
+                        MapHandle::Rotate => Color32::WHITE,
+                        _ => system.color,
+                    };
+
+                    parallelogram.handle(handle).to_pixels(viewport).to_dot(
+                        &DotMetadata {
+                            fill,
+                            ..self.style.handle
+                        },
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Moves the dragged handle. Returns whether any coefficient has changed.
+    pub fn update(
+        &self, systems: &mut [EquationSystem], ui: &egui::Ui, response: &Response,
+        viewport: &Viewport,
+    ) -> bool {
+        if !self.is_enabled {
+            return false;
+        }
+
+        let mut is_changed = false;
+        for (index, system) in systems.iter_mut().enumerate() {
+            let mut parallelogram = self.parallelogram(system);
+
+            for handle in MapHandle::ALL {
+                let position = parallelogram.handle(handle);
+                let mut point = MoveablePoint {
+                    id: egui::Id::new(("IFS_MAP_HANDLE", index, handle)),
+                    coordinates: position,
+                    radius: Pixel(self.style.handle.radius as f64),
+                };
+                point.update_on_pan(ui, response, viewport);
+
+                if point.coordinates != position {
+                    parallelogram.drag(handle, point.coordinates);
+                    *system = parallelogram.system(system, self.origin, self.size);
+                    is_changed = true;
+                }
+            }
+        }
+
+        is_changed
+    }
+
+    /// Makes the reference square enclose the attractor.
+    pub fn fit(&mut self, fractal: &impl FractalIFS) {
+        if let Some((origin, size)) = bounding_square(fractal) {
+            self.origin = origin;
+            self.size = size;
+        }
+    }
+
+    fn square_corners(&self) -> [Point2D; 4] {
+        let (x, y, size) = (
+            self.origin.x.value(),
+            self.origin.y.value(),
+            self.size.value(),
+        );
+
+        [
+            Point2D::new(x, y),
+            Point2D::new(x + size, y),
+            Point2D::new(x + size, y + size),
+            Point2D::new(x, y + size),
+        ]
+    }
+
+    fn parallelogram(&self, system: &EquationSystem) -> Parallelogram {
+        let [origin, x_axis, _, y_axis] = self
+            .square_corners()
+            .map(|corner| apply(system, (corner.x.value(), corner.y.value())));
+
+        Parallelogram {
+            origin,
+            x_axis,
+            y_axis,
+        }
+    }
+}
+
+/// Handles of a map's parallelogram.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MapHandle {
+    /// Center: translates the map.
+    Translate,
+    /// Images of the corners (0, 0), (1, 0) and (0, 1): scale and shear.
+    Origin,
+    AxisX,
+    AxisY,
+    /// Image of (1, 1): rotates and scales about the center.
+    Rotate,
+}
+
+impl MapHandle {
+    pub const ALL: [Self; 5] = [
+        Self::Translate,
+        Self::Origin,
+        Self::AxisX,
+        Self::AxisY,
+        Self::Rotate,
+    ];
+}
+
+/// Image of the reference square, given by the images of three of its corners.
+#[derive(Debug, Clone, Copy)]
+struct Parallelogram {
+    origin: (f64, f64),
+    x_axis: (f64, f64),
+    y_axis: (f64, f64),
+}
+
+impl Parallelogram {
+    fn opposite(&self) -> (f64, f64) {
+        (
+            self.x_axis.0 + self.y_axis.0 - self.origin.0,
+            self.x_axis.1 + self.y_axis.1 - self.origin.1,
+        )
+    }
+
+    fn center(&self) -> (f64, f64) {
+        let opposite = self.opposite();
+        (
+            (self.origin.0 + opposite.0) / 2.0,
+            (self.origin.1 + opposite.1) / 2.0,
+        )
+    }
+
+    fn corners(&self) -> [Point2D; 4] {
+        [self.origin, self.x_axis, self.opposite(), self.y_axis]
+            .map(|(x, y)| Point2D::new(x, y))
+    }
+
+    fn handle(&self, handle: MapHandle) -> Point2D {
+        let (x, y) = match handle {
+            MapHandle::Translate => self.center(),
+            MapHandle::Origin => self.origin,
+            MapHandle::AxisX => self.x_axis,
+            MapHandle::AxisY => self.y_axis,
+            MapHandle::Rotate => self.opposite(),
+        };
+
+        Point2D::new(x, y)
+    }
+
+    fn drag(&mut self, handle: MapHandle, target: Point2D) {
+        let target = (target.x.value(), target.y.value());
+
+        match handle {
+            MapHandle::Origin => self.origin = target,
+            MapHandle::AxisX => self.x_axis = target,
+            MapHandle::AxisY => self.y_axis = target,
+            MapHandle::Translate => {
+                let center = self.center();
+                let offset = (target.0 - center.0, target.1 - center.1);
+                for corner in [&mut self.origin, &mut self.x_axis, &mut self.y_axis] {
+                    *corner = (corner.0 + offset.0, corner.1 + offset.1);
+                }
+            },
+            MapHandle::Rotate => {
+                // Multiplication by the complex ratio of the new and old center-to-handle
+                // vectors rotates and scales at once.
+                let center = self.center();
+                let opposite = self.opposite();
+                let from = (opposite.0 - center.0, opposite.1 - center.1);
+                let to = (target.0 - center.0, target.1 - center.1);
+                let length = from.0 * from.0 + from.1 * from.1;
+                if length < 1e-12 {
+                    return;
+                }
+
+                let ratio = (
+                    (to.0 * from.0 + to.1 * from.1) / length,
+                    (to.1 * from.0 - to.0 * from.1) / length,
+                );
+                for corner in [&mut self.origin, &mut self.x_axis, &mut self.y_axis] {
+                    let (dx, dy) = (corner.0 - center.0, corner.1 - center.1);
+                    *corner = (
+                        center.0 + ratio.0 * dx - ratio.1 * dy,
+                        center.1 + ratio.1 * dx + ratio.0 * dy,
+                    );
+                }
+            },
+        }
+    }
+
+    /// The map that takes the reference square to this parallelogram.
+    fn system(
+        &self, system: &EquationSystem, origin: Point2D, size: Centimeter,
+    ) -> EquationSystem {
+        let size = size.value();
+        let (x0, y0) = (origin.x.value(), origin.y.value());
+
+        let a = (self.x_axis.0 - self.origin.0) / size;
+        let d = (self.x_axis.1 - self.origin.1) / size;
+        let b = (self.y_axis.0 - self.origin.0) / size;
+        let e = (self.y_axis.1 - self.origin.1) / size;
+
+        EquationSystem {
+            a,
+            b,
+            d,
+            e,
+            c: self.origin.0 - (a * x0 + b * y0),
+            f: self.origin.1 - (d * x0 + e * y0),
+            ..*system
+        }
+    }
+}
+
+fn apply(system: &EquationSystem, (x, y): (f64, f64)) -> (f64, f64) {
+    (
+        system.a * x + system.b * y + system.c,
+        system.d * x + system.e * y + system.f,
+    )
+}
+
+#[derive(Debug)]
+pub struct MapEditorStyle {
+    pub width: f32,
+    pub reference: Stroke,
+    pub handle: DotMetadata,
+}
+
+impl Default for MapEditorStyle {
+    fn default() -> Self {
+        Self {
+            width: 1.5,
+            reference: Stroke::new(1.0, Color32::GRAY),
+            handle: DotMetadata {
+                radius: 5.0,
+                fill: Color32::RED,
+                stroke: Stroke::new(1.0, Color32::BLACK),
+            },
+        }
+    }
+}
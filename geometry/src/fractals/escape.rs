@@ -0,0 +1,354 @@
+use crate::fractals::presets::palette_color;
+use crate::math::complex::Complex;
+use crate::primitives::point2d::Point2D;
+use crate::units::Centimeter;
+use crate::viewport::ViewportBounds;
+use egui::{Color32, ColorImage, Rgba};
+use std::sync::atomic::{AtomicBool, Ordering};
+use strum_macros::EnumIter;
+
+/// Pixels per unit of the complex plane at which `EscapeTime::iterations` is used as is.
+const REFERENCE_PIXELS_PER_UNIT: f64 = 160.0;
+
+/// Escape-time fractals over the complex plane. The plane is laid over the canvas:
+/// one unit of it is `unit` centimeters, so panning and zooming the viewport explore the set.
+#[derive(Debug, Clone)]
+pub struct EscapeTime {
+    pub kind: EscapeKind,
+    pub unit: Centimeter,
+
+    /// Iteration limit at the initial zoom.
+    pub iterations: usize,
+    /// Raise the limit with the zoom, by `iterations_per_octave` for every doubling.
+    pub is_zoom_iterations: bool,
+    pub iterations_per_octave: usize,
+    /// Escape radius. Large values make the smooth coloring more accurate.
+    pub bailout: f64,
+
+    /// Parameter c of the Julia set z² + c.
+    pub julia: Complex,
+
+    /// Roots of the Newton polynomial p(z) = Π (z - rᵢ).
+    pub roots: Vec<Complex>,
+    /// Step factor of the Newton method. 1.0 is the classic method.
+    pub relaxation: f64,
+
+    pub palette: Palette,
+    pub is_smooth: bool,
+    /// Number of iterations covered by one pass through the palette.
+    pub period: f64,
+}
+
+impl Default for EscapeTime {
+    fn default() -> Self {
+        Self {
+            kind: EscapeKind::Mandelbrot,
+            unit: Centimeter(8.0),
+
+            iterations: 100,
+            is_zoom_iterations: true,
+            iterations_per_octave: 50,
+            bailout: 256.0,
+
+            julia: Complex::new(-0.8, 0.156),
+
+            roots: Self::roots_of_unity(3),
+            relaxation: 1.0,
+
+            palette: Palette::Classic,
+            is_smooth: true,
+            period: 32.0,
+        }
+    }
+}
+
+impl EscapeTime {
+    /// Roots of zⁿ - 1.
+    pub fn roots_of_unity(degree: usize) -> Vec<Complex> {
+        (0..degree.max(1))
+            .map(|k| {
+                let angle = std::f64::consts::TAU * k as f64 / degree.max(1) as f64;
+                Complex::new(angle.cos(), angle.sin())
+            })
+            .collect()
+    }
+
+    pub fn max_iterations(&self, pixels_per_centimeter: f64) -> usize {
+        if !self.is_zoom_iterations {
+            return self.iterations.max(1);
+        }
+
+        let zoom = pixels_per_centimeter * self.unit.value() / REFERENCE_PIXELS_PER_UNIT;
+        let octaves = zoom.log2().max(0.0);
+
+        (self.iterations as f64 + self.iterations_per_octave as f64 * octaves).max(1.0)
+            as usize
+    }
+
+    pub fn to_complex(&self, point: Point2D) -> Complex {
+        Complex::new(point.x.value(), point.y.value()).scale(1.0 / self.unit.value())
+    }
+
+    pub fn to_point(&self, z: Complex) -> Point2D {
+        Point2D::new(z.re * self.unit.value(), z.im * self.unit.value())
+    }
+
+    /// Image of the given part of the canvas. The rows are split between threads.
+    /// Returns `None` as soon as `is_cancelled` is set.
+    pub fn render(
+        &self, bounds: &ViewportBounds<Centimeter>, size: [usize; 2],
+        pixels_per_centimeter: f64, is_cancelled: &AtomicBool,
+    ) -> Option<ColorImage> {
+        let [width, height] = size;
+        let mut image = ColorImage::filled(size, Color32::TRANSPARENT);
+        if width == 0 || height == 0 {
+            return Some(image);
+        }
+
+        let max_iterations = self.max_iterations(pixels_per_centimeter);
+        let (x0, x1) = (bounds.minimum_x.value(), bounds.maximum_x.value());
+        let (y0, y1) = (bounds.minimum_y.value(), bounds.maximum_y.value());
+        let step_x = (x1 - x0) / width as f64;
+        let step_y = (y1 - y0) / height as f64;
+
+        let threads = std::thread::available_parallelism().map_or(1, usize::from);
+        let rows_per_thread = height.div_ceil(threads);
+
+        std::thread::scope(|scope| {
+            for (chunk, pixels) in
+                image.pixels.chunks_mut(rows_per_thread * width).enumerate()
+            {
+                scope.spawn(move || {
+                    for (index, row) in pixels.chunks_mut(width).enumerate() {
+                        if is_cancelled.load(Ordering::Relaxed) {
+                            return;
+                        }
+
+                        // Pixel rows go down, Y goes up
+                        let row_index = chunk * rows_per_thread + index;
+                        let y = y1 - (row_index as f64 + 0.5) * step_y;
+                        for (column, pixel) in row.iter_mut().enumerate() {
+                            let x = x0 + (column as f64 + 0.5) * step_x;
+                            *pixel = self.color(
+                                self.to_complex(Point2D::new(x, y)),
+                                max_iterations,
+                            );
+                        }
+                    }
+                });
+            }
+        });
+
+        (!is_cancelled.load(Ordering::Relaxed)).then_some(image)
+    }
+
+    fn color(&self, z: Complex, max_iterations: usize) -> Color32 {
+        match self.kind {
+            EscapeKind::Mandelbrot => match self.escape(Complex::ZERO, z, max_iterations)
+            {
+                Some(count) => self.palette.color(count / self.period),
+                None => Color32::BLACK,
+            },
+            EscapeKind::Julia => match self.escape(z, self.julia, max_iterations) {
+                Some(count) => self.palette.color(count / self.period),
+                None => Color32::BLACK,
+            },
+            EscapeKind::Newton => match self.newton(z, max_iterations) {
+                Some((root, count)) => {
+                    // Slowly converging points are darker
+                    let shade = 0.25 + 0.75 * (-count / self.period).exp();
+                    let color = Rgba::from(palette_color(root)) * shade as f32;
+
+                    Color32::from(color).to_opaque()
+                },
+                None => Color32::BLACK,
+            },
+        }
+    }
+
+    /// Iterates z ↦ z² + c. Returns the (smoothed) number of iterations before
+    /// the escape, or `None` for points of the set.
+    fn escape(&self, mut z: Complex, c: Complex, max_iterations: usize) -> Option<f64> {
+        if self.kind == EscapeKind::Mandelbrot && is_in_main_bulbs(c) {
+            return None;
+        }
+
+        let bailout = self.bailout.max(2.0);
+        let bailout_sqr = bailout * bailout;
+
+        for count in 0..max_iterations {
+            z = z * z + c;
+
+            let norm_sqr = z.norm_sqr();
+            if norm_sqr > bailout_sqr {
+                if !self.is_smooth {
+                    return Some(count as f64);
+                }
+
+                // Normalized iteration count: n + 1 - log₂(ln |z|)
+                let smooth = count as f64 + 1.0 - (0.5 * norm_sqr.ln()).ln().log2();
+                return Some(smooth.max(0.0));
+            }
+        }
+
+        None
+    }
+
+    /// Newton's method for p(z) = Π (z - rᵢ). Returns the index of the root the point
+    /// converged to and the (smoothed) number of iterations.
+    fn newton(&self, mut z: Complex, max_iterations: usize) -> Option<(usize, f64)> {
+        const TOLERANCE: f64 = 1e-6;
+
+        if self.roots.is_empty() {
+            return None;
+        }
+
+        let mut previous = f64::INFINITY;
+        for count in 0..max_iterations {
+            let (root, distance_sqr) = self
+                .roots
+                .iter()
+                .map(|root| (z - *root).norm_sqr())
+                .enumerate()
+                .min_by(|a, b| a.1.total_cmp(&b.1))?;
+            let distance = distance_sqr.sqrt();
+
+            if distance < TOLERANCE {
+                if !self.is_smooth || !previous.is_finite() || previous <= distance {
+                    return Some((root, count as f64));
+                }
+
+                // Fraction of the last step that was needed to reach the tolerance
+                let fraction =
+                    (TOLERANCE.ln() - previous.ln()) / (distance.ln() - previous.ln());
+                return Some((root, (count as f64 - 1.0 + fraction).max(0.0)));
+            }
+            previous = distance;
+
+            // p / p' = 1 / Σ 1 / (z - rᵢ)
+            let sum = self
+                .roots
+                .iter()
+                .fold(Complex::ZERO, |sum, root| sum + (z - *root).inverse());
+            if sum.norm_sqr() < 1e-300 {
+                return None;
+            }
+            z = z - sum.inverse().scale(self.relaxation);
+        }
+
+        None
+    }
+
+    pub fn reset(&mut self) {
+        *self = Default::default();
+    }
+}
+
+/// Points of the main cardioid and the period-2 bulb never escape.
+fn is_in_main_bulbs(c: Complex) -> bool {
+    let x = c.re - 0.25;
+    let q = x * x + c.im * c.im;
+    let is_in_cardioid = q * (q + x) <= 0.25 * c.im * c.im;
+    let is_in_bulb = (c.re + 1.0) * (c.re + 1.0) + c.im * c.im <= 0.0625;
+
+    is_in_cardioid || is_in_bulb
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum EscapeKind {
+    Mandelbrot,
+    Julia,
+    Newton,
+}
+
+impl EscapeKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Mandelbrot => "Mandelbrot",
+            Self::Julia => "Julia",
+            Self::Newton => "Newton",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum Palette {
+    Classic,
+    Fire,
+    Ocean,
+    Grayscale,
+    Rainbow,
+}
+
+impl Palette {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Classic => "Classic",
+            Self::Fire => "Fire",
+            Self::Ocean => "Ocean",
+            Self::Grayscale => "Grayscale",
+            Self::Rainbow => "Rainbow",
+        }
+    }
+
+    /// Color at t, the palette repeats every 1.0.
+    pub fn color(&self, t: f64) -> Color32 {
+        let t = t.rem_euclid(1.0) as f32;
+
+        match self {
+            Self::Rainbow => egui::ecolor::Hsva::new(t, 0.8, 1.0, 1.0).into(),
+            _ => gradient(self.stops(), t),
+        }
+    }
+
+    /// Evenly spaced colors of a cyclic gradient.
+    fn stops(&self) -> &'static [Color32] {
+        match self {
+            Self::Classic => &CLASSIC,
+            Self::Fire => &FIRE,
+            Self::Ocean => &OCEAN,
+            Self::Grayscale => &GRAYSCALE,
+            Self::Rainbow => &[],
+        }
+    }
+}
+
+const CLASSIC: [Color32; 5] = [
+    Color32::from_rgb(0, 7, 100),
+    Color32::from_rgb(32, 107, 203),
+    Color32::from_rgb(237, 255, 255),
+    Color32::from_rgb(255, 170, 0),
+    Color32::from_rgb(0, 2, 0),
+];
+
+const FIRE: [Color32; 5] = [
+    Color32::from_rgb(20, 0, 0),
+    Color32::from_rgb(180, 20, 0),
+    Color32::from_rgb(255, 160, 0),
+    Color32::from_rgb(255, 255, 200),
+    Color32::from_rgb(120, 10, 0),
+];
+
+const OCEAN: [Color32; 5] = [
+    Color32::from_rgb(0, 20, 40),
+    Color32::from_rgb(0, 90, 140),
+    Color32::from_rgb(80, 200, 220),
+    Color32::from_rgb(230, 250, 255),
+    Color32::from_rgb(0, 60, 100),
+];
+
+const GRAYSCALE: [Color32; 2] = [Color32::from_gray(20), Color32::from_gray(235)];
+
+fn gradient(stops: &[Color32], t: f32) -> Color32 {
+    let Some(first) = stops.first() else {
+        return Color32::BLACK;
+    };
+
+    // The last stop blends back into the first one
+    let position = t * stops.len() as f32;
+    let index = (position as usize).min(stops.len() - 1);
+    let next = stops.get(index + 1).unwrap_or(first);
+    let fraction = position - index as f32;
+
+    stops[index].lerp_to_gamma(*next, fraction)
+}
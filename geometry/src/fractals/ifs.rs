@@ -0,0 +1,279 @@
+//! Text format of Fractint `.ifs` files.
+//!
+//! ```text
+//! ; comment
+//! fern {
+//!   0    0    0   .16 0  0   .01
+//!   .85  .04 -.04 .85 0  1.6 .85 ; #2ca02c
+//! }
+//! ```
+//!
+//! Every entry is a name followed by seven numbers per map, the order of
+//! [`EquationSystem::new`]. Fractint ignores everything after `;`, so the
+//! color of a map and its flame variations are stored there as an optional
+//! comment, for example `; #2ca02c linear=0.5 swirl=0.5`.
+//! Entries marked `(3D)` have thirteen numbers per map and are skipped.
+
+use crate::fractals::EquationSystem;
+use crate::fractals::custom::FractalCustom;
+use crate::fractals::presets::palette_color;
+use crate::fractals::variations::Variation;
+use derive_more::Display;
+use egui::Color32;
+use strum::IntoEnumIterator;
+
+pub const EXTENSION: &str = "ifs";
+
+const COEFFICIENTS_2D: usize = 7;
+const COEFFICIENTS_3D: usize = 13;
+
+#[derive(Debug, Display, PartialEq)]
+pub enum IfsError {
+    #[display("No 2D entries found.")]
+    NoEntries,
+
+    #[display("Entry '{_0}' is not closed with '}}'.")]
+    UnclosedEntry(String),
+
+    #[display("Unexpected '{_0}' on line {_1}.")]
+    UnexpectedBrace(char, usize),
+
+    #[display("Invalid number '{token}' in entry '{entry}'.")]
+    InvalidNumber { entry: String, token: String },
+
+    #[display("Entry '{_0}' has an incomplete map.")]
+    IncompleteMap(String),
+
+    #[display("Entry '{_0}' has no maps.")]
+    EmptyEntry(String),
+}
+
+impl std::error::Error for IfsError {}
+
+#[derive(Debug, Default)]
+struct Entry {
+    header: String,
+    numbers: Vec<f64>,
+    /// Map comments, by the number of coefficients read before them.
+    annotations: Vec<(usize, Annotation)>,
+}
+
+/// Reads all 2D entries of a document.
+pub fn parse(source: &str) -> Result<Vec<FractalCustom>, IfsError> {
+    let mut fractals = vec![];
+    let mut header = String::new();
+    let mut entry: Option<Entry> = None;
+
+    for (index, line) in source.lines().enumerate() {
+        let (code, comment) = match line.split_once(';') {
+            Some((code, comment)) => (code, Some(comment)),
+            None => (line, None),
+        };
+
+        for part in split_braces(code) {
+            match (part, &mut entry) {
+                ("{", None) => {
+                    entry = Some(Entry {
+                        header: std::mem::take(&mut header).trim().to_string(),
+                        ..Default::default()
+                    });
+                },
+                ("}", Some(_)) => {
+                    if let Some(finished) = entry.take()
+                        && let Some(fractal) = finish(finished)?
+                    {
+                        fractals.push(fractal);
+                    }
+                },
+                ("{" | "}", _) => {
+                    let brace = part.chars().next().unwrap_or('{');
+                    return Err(IfsError::UnexpectedBrace(brace, index + 1));
+                },
+                (text, None) => {
+                    header.push(' ');
+                    header.push_str(text);
+                },
+                (text, Some(current)) => {
+                    for token in text.split_whitespace() {
+                        let value = token.parse::<f64>().map_err(|_| {
+                            IfsError::InvalidNumber {
+                                entry: current.header.clone(),
+                                token: token.to_string(),
+                            }
+                        })?;
+                        current.numbers.push(value);
+                    }
+                },
+            }
+        }
+
+        if let (Some(current), Some(annotation)) =
+            (&mut entry, comment.and_then(parse_annotation))
+        {
+            current
+                .annotations
+                .push((current.numbers.len(), annotation));
+        }
+    }
+
+    if let Some(unclosed) = entry {
+        return Err(IfsError::UnclosedEntry(unclosed.header));
+    }
+    if fractals.is_empty() {
+        return Err(IfsError::NoEntries);
+    }
+
+    Ok(fractals)
+}
+
+/// Splits a line into braces and the text between them.
+fn split_braces(code: &str) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut start = 0;
+
+    for (position, c) in code.char_indices() {
+        if c == '{' || c == '}' {
+            if !code[start..position].trim().is_empty() {
+                parts.push(&code[start..position]);
+            }
+            parts.push(&code[position..position + 1]);
+            start = position + 1;
+        }
+    }
+    if !code[start..].trim().is_empty() {
+        parts.push(&code[start..]);
+    }
+
+    parts
+}
+
+fn finish(entry: Entry) -> Result<Option<FractalCustom>, IfsError> {
+    let is_3d = entry.header.to_ascii_lowercase().contains("(3d)");
+    let name = entry
+        .header
+        .split_whitespace()
+        .next()
+        .unwrap_or("Untitled")
+        .to_string();
+
+    let count = if is_3d {
+        COEFFICIENTS_3D
+    } else {
+        COEFFICIENTS_2D
+    };
+    if entry.numbers.is_empty() {
+        return Err(IfsError::EmptyEntry(name));
+    }
+    if !entry.numbers.len().is_multiple_of(count) {
+        return Err(IfsError::IncompleteMap(name));
+    }
+    if is_3d {
+        return Ok(None);
+    }
+
+    let systems = entry
+        .numbers
+        .chunks_exact(COEFFICIENTS_2D)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let mut coefficients = [0.0; COEFFICIENTS_2D];
+            coefficients.copy_from_slice(chunk);
+
+            let mut system =
+                EquationSystem::new_colored(coefficients, palette_color(index));
+
+            // A comment belongs to the map that ends before it
+            let annotation = entry
+                .annotations
+                .iter()
+                .find(|(read, _)| *read == (index + 1) * COEFFICIENTS_2D)
+                .map(|(_, annotation)| annotation);
+            if let Some(annotation) = annotation {
+                system.color = annotation.color.unwrap_or(system.color);
+                if let Some(variations) = annotation.variations {
+                    system.variations = variations;
+                }
+            }
+
+            system
+        })
+        .collect();
+
+    Ok(Some(FractalCustom::new(&name, systems)))
+}
+
+#[derive(Debug, Default)]
+struct Annotation {
+    color: Option<Color32>,
+    variations: Option<[f64; Variation::COUNT]>,
+}
+
+/// Reads `#rrggbb` and `name=weight` words of a comment. Other comments are ignored.
+fn parse_annotation(comment: &str) -> Option<Annotation> {
+    let mut annotation = Annotation::default();
+
+    for word in comment.split_whitespace() {
+        if let Some(hex) = word.strip_prefix('#') {
+            annotation.color = Some(parse_color(hex)?);
+        } else if let Some((key, value)) = word.split_once('=') {
+            let variation = Variation::iter().find(|variation| variation.key() == key)?;
+            let weights = annotation.variations.get_or_insert([0.0; Variation::COUNT]);
+            weights[variation.index()] = value.parse().ok()?;
+        } else {
+            return None;
+        }
+    }
+
+    Some(annotation)
+}
+
+fn parse_color(hex: &str) -> Option<Color32> {
+    if hex.len() != 6 {
+        return None;
+    }
+
+    let channel =
+        |range: std::ops::Range<usize>| u8::from_str_radix(hex.get(range)?, 16).ok();
+    Some(Color32::from_rgb(
+        channel(0..2)?,
+        channel(2..4)?,
+        channel(4..6)?,
+    ))
+}
+
+/// Writes one entry. Whitespace in the name is replaced, as Fractint names are single words.
+pub fn write(fractal: &FractalCustom) -> String {
+    let name: String = fractal
+        .name
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join("_");
+    let name = if name.is_empty() { "Untitled" } else { &name };
+
+    let mut text = format!("{name} {{\n");
+    text.push_str("  ; a b c d e f p, with x' = a·x + b·y + e, y' = c·x + d·y + f\n");
+
+    for system in &fractal.systems {
+        let numbers: Vec<String> = system
+            .coefficients()
+            .iter()
+            .map(|value| format!("{value:>10.6}"))
+            .collect();
+        let [r, g, b, _] = system.color.to_array();
+
+        let mut comment = format!("#{r:02x}{g:02x}{b:02x}");
+        if !system.is_linear() {
+            for variation in Variation::iter() {
+                let weight = system.weight(variation);
+                if weight != 0.0 {
+                    comment.push_str(&format!(" {}={weight}", variation.key()));
+                }
+            }
+        }
+
+        text.push_str(&format!("  {} ; {comment}\n", numbers.join(" ")));
+    }
+    text.push_str("}\n");
+
+    text
+}
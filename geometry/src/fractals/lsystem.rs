@@ -0,0 +1,429 @@
+use crate::fractals::lsystem::turtle::Turtle;
+use crate::math::expression::{Expression, ExpressionError};
+use crate::random::Seed;
+use derive_more::Display;
+use rand::Rng;
+
+pub mod fractint;
+pub mod presets;
+pub mod turtle;
+
+/// Lindenmayer system: an axiom rewritten by production rules, then drawn by a turtle.
+///
+/// Modules may carry parameters, `F(2.5)` or `A(s, w)`, which rules can use in
+/// conditions and in the parameters of the successor. Several rules for the same
+/// module make the system stochastic: one of them is picked by weight.
+#[derive(Debug, Clone)]
+pub struct LSystem {
+    pub name: String,
+    pub axiom: String,
+    pub rules: Vec<Rule>,
+    pub iterations: usize,
+    pub seed: Seed,
+
+    pub turtle: Turtle,
+    /// Interpret `&`, `^`, `\` and `/` as pitch and roll.
+    pub is_3d: bool,
+}
+
+impl Default for LSystem {
+    fn default() -> Self {
+        Self {
+            name: "Untitled".to_string(),
+            axiom: "F".to_string(),
+            rules: vec![],
+            iterations: 4,
+            seed: Seed::default(),
+
+            turtle: Turtle::default(),
+            is_3d: false,
+        }
+    }
+}
+
+/// Production rule, kept as text so that it can be edited.
+///
+/// * `predecessor`: a module with optional formal parameters, `F` or `A(s, w)`.
+/// * `condition`: a comparison of two expressions, `s >= 1`. Empty means always.
+/// * `successor`: the replacement, `F(s * 0.5)[+A(s / 2, w)]`.
+/// * `weight`: relative probability among the rules of the same module.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    pub predecessor: String,
+    pub condition: String,
+    pub successor: String,
+    pub weight: f64,
+}
+
+impl Rule {
+    pub fn new(predecessor: &str, successor: &str) -> Self {
+        Self {
+            predecessor: predecessor.to_string(),
+            condition: String::new(),
+            successor: successor.to_string(),
+            weight: 1.0,
+        }
+    }
+
+    pub fn with_condition(mut self, condition: &str) -> Self {
+        self.condition = condition.to_string();
+        self
+    }
+
+    pub fn with_weight(mut self, weight: f64) -> Self {
+        self.weight = weight;
+        self
+    }
+}
+
+/// Symbol with its actual parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Module {
+    pub symbol: char,
+    pub parameters: Vec<f64>,
+}
+
+/// Result of the rewriting.
+#[derive(Debug, Clone)]
+pub struct Derivation {
+    pub word: Vec<Module>,
+    /// Iterations actually performed, fewer than requested if the word grew too long.
+    pub iterations: usize,
+}
+
+#[derive(Debug, Display, PartialEq)]
+pub enum LSystemError {
+    #[display("Invalid predecessor '{_0}'.")]
+    InvalidPredecessor(String),
+
+    #[display("Invalid condition '{_0}'. Expected a comparison such as 'x > 1'.")]
+    InvalidCondition(String),
+
+    #[display("Unbalanced parentheses in '{_0}'.")]
+    UnbalancedParentheses(String),
+
+    #[display("Expression '{source}'. {error}")]
+    Expression {
+        source: String,
+        error: ExpressionError,
+    },
+}
+
+impl std::error::Error for LSystemError {}
+
+impl LSystem {
+    /// Rewriting stops before the word gets longer than this.
+    pub const MAX_MODULES: usize = 1_000_000;
+
+    pub fn derive(&self) -> Result<Derivation, LSystemError> {
+        let rules = self
+            .rules
+            .iter()
+            .map(CompiledRule::compile)
+            .collect::<Result<Vec<CompiledRule>, LSystemError>>()?;
+
+        let mut word: Vec<Module> = parse_word(&self.axiom, &[])?
+            .iter()
+            .map(|template| template.instantiate(&[]))
+            .collect();
+        let mut rng = self.seed.rng();
+
+        let mut iterations = 0;
+        while iterations < self.iterations {
+            let mut next = Vec::with_capacity(word.len() * 2);
+            let mut is_capped = false;
+
+            for module in &word {
+                let candidates: Vec<&CompiledRule> =
+                    rules.iter().filter(|rule| rule.matches(module)).collect();
+                let rule = choose(&candidates, &mut rng);
+
+                // Checked before growing, a single long successor can overshoot a lot
+                let growth = rule.map_or(1, |rule| rule.successor.len());
+                if next.len() + growth > Self::MAX_MODULES {
+                    is_capped = true;
+                    break;
+                }
+
+                match rule {
+                    Some(rule) => next.extend(
+                        rule.successor
+                            .iter()
+                            .map(|template| template.instantiate(&module.parameters)),
+                    ),
+                    None => next.push(module.clone()),
+                }
+            }
+
+            if is_capped {
+                break;
+            }
+            word = next;
+            iterations += 1;
+        }
+
+        Ok(Derivation { word, iterations })
+    }
+}
+
+/// Picks one of the rules by weight.
+fn choose<'a>(
+    candidates: &[&'a CompiledRule], rng: &mut impl Rng,
+) -> Option<&'a CompiledRule> {
+    match candidates {
+        [] => None,
+        [single] => Some(single),
+        _ => {
+            let total: f64 = candidates.iter().map(|rule| rule.weight.max(0.0)).sum();
+            if total <= 0.0 {
+                return candidates.first().copied();
+            }
+
+            let mut target = rng.random::<f64>() * total;
+            for rule in candidates {
+                target -= rule.weight.max(0.0);
+                if target < 0.0 {
+                    return Some(rule);
+                }
+            }
+
+            candidates.last().copied()
+        },
+    }
+}
+
+#[derive(Debug)]
+struct CompiledRule {
+    symbol: char,
+    formals: usize,
+    condition: Option<Condition>,
+    successor: Vec<ModuleTemplate>,
+    weight: f64,
+}
+
+impl CompiledRule {
+    fn compile(rule: &Rule) -> Result<Self, LSystemError> {
+        let (symbol, formals) = parse_predecessor(&rule.predecessor)?;
+        let variables: Vec<&str> = formals.iter().map(String::as_str).collect();
+
+        let condition = match rule.condition.trim() {
+            "" | "*" => None,
+            condition => Some(Condition::parse(condition, &variables)?),
+        };
+
+        Ok(Self {
+            symbol,
+            formals: formals.len(),
+            condition,
+            successor: parse_word(&rule.successor, &variables)?,
+            weight: rule.weight,
+        })
+    }
+
+    fn matches(&self, module: &Module) -> bool {
+        module.symbol == self.symbol
+            && module.parameters.len() == self.formals
+            && self
+                .condition
+                .as_ref()
+                .is_none_or(|condition| condition.holds(&module.parameters))
+    }
+}
+
+#[derive(Debug)]
+struct Condition {
+    left: Expression,
+    comparison: Comparison,
+    right: Expression,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Comparison {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+    NotEqual,
+}
+
+impl Condition {
+    fn parse(source: &str, variables: &[&str]) -> Result<Self, LSystemError> {
+        // Two-character operators first, so that "<=" is not read as "<"
+        let operators = [
+            ("<=", Comparison::LessOrEqual),
+            (">=", Comparison::GreaterOrEqual),
+            ("==", Comparison::Equal),
+            ("!=", Comparison::NotEqual),
+            ("<", Comparison::Less),
+            (">", Comparison::Greater),
+            ("=", Comparison::Equal),
+        ];
+
+        let (left, comparison, right) = operators
+            .iter()
+            .find_map(|(operator, comparison)| {
+                source
+                    .split_once(operator)
+                    .map(|(left, right)| (left, *comparison, right))
+            })
+            .ok_or_else(|| LSystemError::InvalidCondition(source.to_string()))?;
+
+        Ok(Self {
+            left: parse_expression(left, variables)?,
+            comparison,
+            right: parse_expression(right, variables)?,
+        })
+    }
+
+    fn holds(&self, values: &[f64]) -> bool {
+        let (left, right) = (self.left.eval(values), self.right.eval(values));
+
+        match self.comparison {
+            Comparison::Less => left < right,
+            Comparison::LessOrEqual => left <= right,
+            Comparison::Greater => left > right,
+            Comparison::GreaterOrEqual => left >= right,
+            Comparison::Equal => (left - right).abs() < 1e-9,
+            Comparison::NotEqual => (left - right).abs() >= 1e-9,
+        }
+    }
+}
+
+/// Module of a successor, with expressions for its parameters.
+#[derive(Debug)]
+struct ModuleTemplate {
+    symbol: char,
+    arguments: Vec<Expression>,
+}
+
+impl ModuleTemplate {
+    fn instantiate(&self, values: &[f64]) -> Module {
+        Module {
+            symbol: self.symbol,
+            parameters: self
+                .arguments
+                .iter()
+                .map(|argument| argument.eval(values))
+                .collect(),
+        }
+    }
+}
+
+fn parse_expression(
+    source: &str, variables: &[&str],
+) -> Result<Expression, LSystemError> {
+    Expression::parse(source.trim(), variables).map_err(|error| {
+        LSystemError::Expression {
+            source: source.trim().to_string(),
+            error,
+        }
+    })
+}
+
+/// `F` or `A(s, w)`.
+fn parse_predecessor(source: &str) -> Result<(char, Vec<String>), LSystemError> {
+    let invalid = || LSystemError::InvalidPredecessor(source.to_string());
+    let source_trimmed = source.trim();
+
+    let mut chars = source_trimmed.chars();
+    let symbol = chars.next().ok_or_else(invalid)?;
+    let rest = chars.as_str().trim();
+
+    if rest.is_empty() {
+        return Ok((symbol, vec![]));
+    }
+
+    let inner = rest
+        .strip_prefix('(')
+        .and_then(|rest| rest.strip_suffix(')'))
+        .ok_or_else(invalid)?;
+    let formals: Vec<String> = inner
+        .split(',')
+        .map(|name| name.trim().to_string())
+        .collect();
+
+    let is_identifier = |name: &String| {
+        name.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    };
+    if !formals.iter().all(is_identifier) {
+        return Err(invalid());
+    }
+
+    Ok((symbol, formals))
+}
+
+/// Splits a word into modules. Whitespace is ignored.
+fn parse_word(
+    source: &str, variables: &[&str],
+) -> Result<Vec<ModuleTemplate>, LSystemError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut modules = vec![];
+    let mut index = 0;
+
+    while index < chars.len() {
+        let symbol = chars[index];
+        index += 1;
+        if symbol.is_whitespace() {
+            continue;
+        }
+        if symbol == '(' || symbol == ')' {
+            return Err(LSystemError::UnbalancedParentheses(source.to_string()));
+        }
+
+        let mut arguments = vec![];
+        if chars.get(index) == Some(&'(') {
+            let start = index + 1;
+            let mut depth = 0;
+            let mut end = None;
+            for (offset, c) in chars[index..].iter().enumerate() {
+                match c {
+                    '(' => depth += 1,
+                    ')' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            end = Some(index + offset);
+                            break;
+                        }
+                    },
+                    _ => {},
+                }
+            }
+            let end = end
+                .ok_or_else(|| LSystemError::UnbalancedParentheses(source.to_string()))?;
+
+            let inner: String = chars[start..end].iter().collect();
+            for argument in split_arguments(&inner) {
+                arguments.push(parse_expression(argument, variables)?);
+            }
+            index = end + 1;
+        }
+
+        modules.push(ModuleTemplate { symbol, arguments });
+    }
+
+    Ok(modules)
+}
+
+/// Splits at the commas that are not inside nested parentheses.
+fn split_arguments(source: &str) -> Vec<&str> {
+    let mut arguments = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (position, c) in source.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                arguments.push(&source[start..position]);
+                start = position + 1;
+            },
+            _ => {},
+        }
+    }
+    arguments.push(&source[start..]);
+
+    arguments
+}
@@ -0,0 +1,221 @@
+//! Fractint `.l` files.
+//!
+//! ```text
+//! Koch1 {          ; comment
+//!   Angle 6        ; a turn is 360° / 6
+//!   Axiom F--F--F
+//!   F=F+F--F+F
+//! }
+//! ```
+//!
+//! Fractint ignores case and uses its own turtle commands, so the words are
+//! translated to the syntax of [`Turtle`](crate::fractals::lsystem::turtle::Turtle):
+//! `D` draws like `F`, `G` and `M` move without drawing, `\nn` and `/nn` turn
+//! by nn degrees, `@nn` scales the step (`I` inverts, `Q` takes the square root),
+//! and the color commands `C`, `<`, `>` are dropped.
+
+use crate::fractals::lsystem::turtle::Turtle;
+use crate::fractals::lsystem::{LSystem, Rule};
+use derive_more::Display;
+
+pub const EXTENSION: &str = "l";
+
+#[derive(Debug, Display, PartialEq)]
+pub enum FractintError {
+    #[display("No entries found.")]
+    NoEntries,
+
+    #[display("Entry '{_0}' is not closed with '}}'.")]
+    UnclosedEntry(String),
+
+    #[display("Entry '{_0}' has no axiom.")]
+    MissingAxiom(String),
+
+    #[display("Invalid angle '{_1}' in entry '{_0}'.")]
+    InvalidAngle(String, String),
+
+    #[display("Unexpected line '{_1}' in entry '{_0}'.")]
+    InvalidLine(String, String),
+}
+
+impl std::error::Error for FractintError {}
+
+#[derive(Debug, Default)]
+struct Entry {
+    name: String,
+    angle: Option<f64>,
+    axiom: Option<String>,
+    rules: Vec<Rule>,
+}
+
+pub fn parse(source: &str) -> Result<Vec<LSystem>, FractintError> {
+    let mut systems = vec![];
+    let mut entry: Option<Entry> = None;
+
+    for line in source.lines() {
+        let code = line.split(';').next().unwrap_or_default();
+        let mut code = code.trim();
+
+        if entry.is_none() {
+            let Some((name, rest)) = code.split_once('{') else {
+                continue;
+            };
+            entry = Some(Entry {
+                name: name.trim().to_string(),
+                ..Default::default()
+            });
+            code = rest.trim();
+        }
+
+        let (content, is_closed) = match code.split_once('}') {
+            Some((content, _)) => (content.trim(), true),
+            None => (code, false),
+        };
+
+        if let Some(current) = &mut entry
+            && !content.is_empty()
+        {
+            read_line(current, content)?;
+        }
+
+        if is_closed && let Some(finished) = entry.take() {
+            systems.push(finish(finished)?);
+        }
+    }
+
+    if let Some(unclosed) = entry {
+        return Err(FractintError::UnclosedEntry(unclosed.name));
+    }
+    if systems.is_empty() {
+        return Err(FractintError::NoEntries);
+    }
+
+    Ok(systems)
+}
+
+fn read_line(entry: &mut Entry, content: &str) -> Result<(), FractintError> {
+    let lower = content.to_ascii_lowercase();
+
+    if let Some(value) = lower.strip_prefix("angle") {
+        let divisions: f64 = value.trim().parse().map_err(|_| {
+            FractintError::InvalidAngle(entry.name.clone(), value.trim().to_string())
+        })?;
+        if divisions == 0.0 {
+            return Err(FractintError::InvalidAngle(
+                entry.name.clone(),
+                value.trim().to_string(),
+            ));
+        }
+        entry.angle = Some(360.0 / divisions);
+    } else if lower.starts_with("axiom") {
+        entry.axiom = Some(translate(&content["axiom".len()..]));
+    } else if let Some((predecessor, successor)) = content.split_once('=') {
+        let predecessor = translate(predecessor);
+        if predecessor.chars().count() != 1 {
+            return Err(FractintError::InvalidLine(
+                entry.name.clone(),
+                content.to_string(),
+            ));
+        }
+        entry
+            .rules
+            .push(Rule::new(&predecessor, &translate(successor)));
+    } else {
+        return Err(FractintError::InvalidLine(
+            entry.name.clone(),
+            content.to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn finish(entry: Entry) -> Result<LSystem, FractintError> {
+    let axiom = entry
+        .axiom
+        .ok_or_else(|| FractintError::MissingAxiom(entry.name.clone()))?;
+
+    Ok(LSystem {
+        name: entry.name,
+        axiom,
+        rules: entry.rules,
+        turtle: Turtle {
+            angle: entry.angle.unwrap_or(90.0),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+/// Fractint word to the turtle syntax of this module.
+fn translate(word: &str) -> String {
+    let chars: Vec<char> = word
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    let mut result = String::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        let c = chars[index];
+        index += 1;
+
+        match c {
+            'F' => result.push('F'),
+            'D' => result.push('G'),
+            'G' => result.push('g'),
+            'M' => result.push('f'),
+            '\\' | '/' => {
+                let number = read_number(&chars, &mut index);
+                let turn = if c == '\\' { '+' } else { '-' };
+                result.push_str(&format!("{turn}({})", number.unwrap_or(0.0)));
+            },
+            '@' => {
+                // Flags come before the number: @IQ2 is 1 / sqrt(2)
+                let mut flags = vec![];
+                while let Some(flag @ ('I' | 'Q')) = chars.get(index) {
+                    flags.push(*flag);
+                    index += 1;
+                }
+                let Some(number) = read_number(&chars, &mut index) else {
+                    continue;
+                };
+
+                let mut expression = number.to_string();
+                for flag in flags.iter().rev() {
+                    expression = match flag {
+                        'I' => format!("1 / ({expression})"),
+                        _ => format!("sqrt({expression})"),
+                    };
+                }
+                result.push_str(&format!("@({expression})"));
+            },
+            'C' | '<' | '>' => {
+                read_number(&chars, &mut index);
+            },
+            other => result.push(other),
+        }
+    }
+
+    result
+}
+
+/// Reads a number starting at `index` and moves past it.
+fn read_number(chars: &[char], index: &mut usize) -> Option<f64> {
+    let start = *index;
+    if chars.get(*index) == Some(&'-') {
+        *index += 1;
+    }
+    while *index < chars.len() && (chars[*index].is_ascii_digit() || chars[*index] == '.')
+    {
+        *index += 1;
+    }
+
+    let number = chars[start..*index].iter().collect::<String>().parse().ok();
+    if number.is_none() {
+        *index = start;
+    }
+
+    number
+}
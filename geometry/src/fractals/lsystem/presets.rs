@@ -0,0 +1,121 @@
+use crate::fractals::lsystem::turtle::Turtle;
+use crate::fractals::lsystem::{LSystem, Rule};
+use egui::{Color32, Stroke};
+use strum_macros::EnumIter;
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum LSystemPreset {
+    KochSnowflake,
+    HilbertCurve,
+    DragonCurve,
+    SierpinskiArrowhead,
+    FractalPlant,
+    Bush,
+    StochasticPlant,
+    ParametricTree,
+    Tree3D,
+}
+
+impl LSystemPreset {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::KochSnowflake => "Koch Snowflake",
+            Self::HilbertCurve => "Hilbert Curve",
+            Self::DragonCurve => "Dragon Curve",
+            Self::SierpinskiArrowhead => "Sierpinski Arrowhead",
+            Self::FractalPlant => "Fractal Plant",
+            Self::Bush => "Bush",
+            Self::StochasticPlant => "Stochastic Plant",
+            Self::ParametricTree => "Parametric Tree",
+            Self::Tree3D => "Tree (3D)",
+        }
+    }
+
+    pub fn system(&self) -> LSystem {
+        let (axiom, rules, angle, iterations) = match self {
+            Self::KochSnowflake => ("F--F--F", vec![Rule::new("F", "F+F--F+F")], 60.0, 4),
+            Self::HilbertCurve => (
+                "X",
+                vec![Rule::new("X", "-YF+XFX+FY-"), Rule::new("Y", "+XF-YFY-FX+")],
+                90.0,
+                5,
+            ),
+            Self::DragonCurve => (
+                "FX",
+                vec![Rule::new("X", "X+YF+"), Rule::new("Y", "-FX-Y")],
+                90.0,
+                12,
+            ),
+            Self::SierpinskiArrowhead => (
+                "XF",
+                vec![Rule::new("X", "YF+XF+Y"), Rule::new("Y", "XF-YF-X")],
+                60.0,
+                6,
+            ),
+            // Algorithmic Beauty of Plants, figure 1.24 (f)
+            Self::FractalPlant => (
+                "X",
+                vec![Rule::new("X", "F+[[X]-X]-F[-FX]+X"), Rule::new("F", "FF")],
+                25.0,
+                6,
+            ),
+            // Algorithmic Beauty of Plants, figure 1.24 (d)
+            Self::Bush => ("F", vec![Rule::new("F", "FF+[+F-F-F]-[-F+F+F]")], 22.5, 4),
+            // Algorithmic Beauty of Plants, figure 1.27
+            Self::StochasticPlant => (
+                "F",
+                vec![
+                    Rule::new("F", "F[+F]F[-F]F").with_weight(0.33),
+                    Rule::new("F", "F[+F]F").with_weight(0.33),
+                    Rule::new("F", "F[-F]F").with_weight(0.34),
+                ],
+                25.7,
+                5,
+            ),
+            // Branches shrink until they are shorter than a tenth of the trunk
+            Self::ParametricTree => (
+                "A(1)",
+                vec![
+                    Rule::new("A(s)", "F(s)[+A(s * 0.75)][-A(s * 0.65)]")
+                        .with_condition("s >= 0.1"),
+                ],
+                30.0,
+                12,
+            ),
+            Self::Tree3D => (
+                "A",
+                vec![
+                    Rule::new("A", "F[&+A][&-A][^/+A][^\\-A]"),
+                    Rule::new("F", "F/F"),
+                ],
+                28.0,
+                5,
+            ),
+        };
+
+        LSystem {
+            name: self.name().to_string(),
+            axiom: axiom.to_string(),
+            rules,
+            iterations,
+            turtle: Turtle {
+                angle,
+                stroke: Stroke::new(1.0, self.color()),
+                ..Default::default()
+            },
+            is_3d: matches!(self, Self::Tree3D),
+            ..Default::default()
+        }
+    }
+
+    fn color(&self) -> Color32 {
+        match self {
+            Self::FractalPlant
+            | Self::Bush
+            | Self::StochasticPlant
+            | Self::ParametricTree
+            | Self::Tree3D => Color32::from_rgb(34, 110, 40),
+            _ => Color32::BLACK,
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use crate::fractals::lsystem::Module;
+use crate::primitives::line2d::Line2D;
+use crate::primitives::line3d::Line3D;
+use crate::primitives::point2d::Point2D;
+use crate::primitives::point3d::Point3D;
+use crate::primitives::vector3d::Vector3D;
+use crate::units::Centimeter;
+use egui::{Color32, Stroke};
+
+/// Turtle interpretation of an L-system word.
+///
+/// | Symbol      | Action                                                 |
+/// |-------------|--------------------------------------------------------|
+/// | `F`, `G`    | Move forward by the step, drawing a line               |
+/// | `f`, `g`    | Move forward by the step without drawing               |
+/// | `+`, `-`    | Turn left, right                                       |
+/// | `&`, `^`    | Pitch down, up (3D only)                               |
+/// | `\`, `/`    | Roll left, right (3D only)                             |
+/// | `\|`        | Turn around                                            |
+/// | `[`, `]`    | Push, pop the state                                    |
+/// | `!`         | Swap the meaning of `+` and `-`                        |
+/// | `@`         | Multiply the step by the parameter                     |
+///
+/// A parameter overrides the default: `F(2)` moves two steps, `+(90)` turns by 90°.
+/// Other symbols are ignored.
+#[derive(Debug, Clone, Copy)]
+pub struct Turtle {
+    pub step: Centimeter,
+    /// Default turning angle, in degrees.
+    pub angle: f64,
+    pub stroke: Stroke,
+}
+
+impl Default for Turtle {
+    fn default() -> Self {
+        Self {
+            step: Centimeter(1.0),
+            angle: 90.0,
+            stroke: Stroke::new(1.0, Color32::BLACK),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct State {
+    position: Point3D,
+    /// Heading, left and up: an orthonormal frame.
+    heading: Vector3D,
+    left: Vector3D,
+    up: Vector3D,
+    step: f64,
+    is_swapped: bool,
+}
+
+impl Turtle {
+    /// Lines in the XY plane. The turtle starts at the origin heading up (+Y).
+    pub fn lines(&self, word: &[Module]) -> Vec<Line2D<Point2D>> {
+        self.walk(word, false)
+            .into_iter()
+            .map(|(start, end)| {
+                Line2D::new(
+                    Point2D::new(start.x.value(), start.y.value()),
+                    Point2D::new(end.x.value(), end.y.value()),
+                    self.stroke,
+                )
+            })
+            .collect()
+    }
+
+    /// Lines in space. The turtle starts at the origin heading up (+Y), up vector +Z.
+    pub fn lines_3d(&self, word: &[Module]) -> Vec<Line3D<Point3D>> {
+        self.walk(word, true)
+            .into_iter()
+            .map(|(start, end)| Line3D::new(start, end, self.stroke))
+            .collect()
+    }
+
+    fn walk(&self, word: &[Module], is_3d: bool) -> Vec<(Point3D, Point3D)> {
+        let mut segments = vec![];
+        let mut stack: Vec<State> = vec![];
+        let mut state = State {
+            position: Point3D::zero(),
+            heading: Vector3D::new(0.0, 1.0, 0.0),
+            left: Vector3D::new(-1.0, 0.0, 0.0),
+            up: Vector3D::new(0.0, 0.0, 1.0),
+            step: self.step.value(),
+            is_swapped: false,
+        };
+
+        for module in word {
+            let parameter = module.parameters.first().copied();
+            let angle = parameter.unwrap_or(self.angle).to_radians();
+            let turn = if state.is_swapped { -angle } else { angle };
+
+            match module.symbol {
+                'F' | 'G' | 'f' | 'g' => {
+                    let distance = state.step * parameter.unwrap_or(1.0);
+                    let next = state.heading.scale(distance).translate(&state.position);
+                    if module.symbol.is_ascii_uppercase() {
+                        segments.push((state.position, next));
+                    }
+                    state.position = next;
+                },
+                '+' => {
+                    (state.heading, state.left) = rotate(state.heading, state.left, turn)
+                },
+                '-' => {
+                    (state.heading, state.left) = rotate(state.heading, state.left, -turn)
+                },
+                '&' if is_3d => {
+                    (state.heading, state.up) = rotate(state.heading, state.up, -angle)
+                },
+                '^' if is_3d => {
+                    (state.heading, state.up) = rotate(state.heading, state.up, angle)
+                },
+                '\\' if is_3d => {
+                    (state.left, state.up) = rotate(state.left, state.up, angle)
+                },
+                '/' if is_3d => {
+                    (state.left, state.up) = rotate(state.left, state.up, -angle)
+                },
+                '|' => {
+                    state.heading = state.heading.scale(-1.0);
+                    state.left = state.left.scale(-1.0);
+                },
+                '[' => stack.push(state),
+                ']' => {
+                    if let Some(saved) = stack.pop() {
+                        state = saved;
+                    }
+                },
+                '!' => state.is_swapped = !state.is_swapped,
+                '@' => state.step *= parameter.unwrap_or(1.0),
+                _ => {},
+            }
+        }
+
+        segments
+    }
+}
+
+/// Rotates the pair of frame vectors by `angle` in their plane, from `first` towards `second`.
+fn rotate(first: Vector3D, second: Vector3D, angle: f64) -> (Vector3D, Vector3D) {
+    let (sin, cos) = angle.sin_cos();
+
+    (
+        first.scale(cos) + second.scale(sin),
+        second.scale(cos) - first.scale(sin),
+    )
+}
+
+/// Centers the lines at the origin and scales them so that the largest side of
+/// the bounding box equals `size`.
+pub fn fit_3d(lines: &[Line3D<Point3D>], size: Centimeter) -> Vec<Line3D<Point3D>> {
+    let mut min = [f64::INFINITY; 3];
+    let mut max = [f64::NEG_INFINITY; 3];
+    for point in lines.iter().flat_map(|line| [line.start, line.end]) {
+        let coordinates = [point.x.value(), point.y.value(), point.z.value()];
+        for axis in 0..3 {
+            min[axis] = min[axis].min(coordinates[axis]);
+            max[axis] = max[axis].max(coordinates[axis]);
+        }
+    }
+
+    let extent = (0..3).map(|axis| max[axis] - min[axis]).fold(0.0, f64::max);
+    let factor = if extent > 1e-9 {
+        size.value() / extent
+    } else {
+        1.0
+    };
+    let center: [f64; 3] = std::array::from_fn(|axis| (min[axis] + max[axis]) / 2.0);
+
+    let transform = |point: &Point3D| {
+        Point3D::new(
+            (point.x.value() - center[0]) * factor,
+            (point.y.value() - center[1]) * factor,
+            (point.z.value() - center[2]) * factor,
+        )
+    };
+
+    lines
+        .iter()
+        .map(|line| {
+            Line3D::new(transform(&line.start), transform(&line.end), line.stroke)
+        })
+        .collect()
+}
@@ -0,0 +1,170 @@
+use crate::fractals::EquationSystem;
+use crate::fractals::custom::FractalCustom;
+use crate::fractals::zigzag::FractalZigZag;
+use egui::Color32;
+use strum_macros::EnumIter;
+
+/// Colors assigned to the maps of a preset, repeated if there are more maps.
+pub const PALETTE: [Color32; 8] = [
+    Color32::from_rgb(31, 119, 180),
+    Color32::from_rgb(255, 127, 14),
+    Color32::from_rgb(44, 160, 44),
+    Color32::from_rgb(214, 39, 40),
+    Color32::from_rgb(148, 103, 189),
+    Color32::from_rgb(140, 86, 75),
+    Color32::from_rgb(227, 119, 194),
+    Color32::from_rgb(23, 190, 207),
+];
+
+pub fn palette_color(index: usize) -> Color32 {
+    PALETTE[index % PALETTE.len()]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum FractalPreset {
+    ZigZag,
+    BarnsleyFern,
+    SierpinskiTriangle,
+    SierpinskiCarpet,
+    LevyCurve,
+    HeighwayDragon,
+    MapleLeaf,
+    Spiral,
+    Swirl,
+}
+
+impl FractalPreset {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::ZigZag => "Zig-Zag",
+            Self::BarnsleyFern => "Barnsley Fern",
+            Self::SierpinskiTriangle => "Sierpinski Triangle",
+            Self::SierpinskiCarpet => "Sierpinski Carpet",
+            Self::LevyCurve => "Lévy C Curve",
+            Self::HeighwayDragon => "Heighway Dragon",
+            Self::MapleLeaf => "Maple Leaf",
+            Self::Spiral => "Spiral",
+            Self::Swirl => "Swirl",
+        }
+    }
+
+    pub fn fractal(&self) -> FractalCustom {
+        let systems = match self {
+            Self::ZigZag => FractalZigZag::default().systems,
+            _ => {
+                let (scale, offset) = self.placement();
+                self.coefficients()
+                    .iter()
+                    .enumerate()
+                    .map(|(index, coefficients)| {
+                        let system = placed(coefficients, scale, offset);
+                        EquationSystem::new_colored(system, palette_color(index))
+                    })
+                    .collect()
+            },
+        };
+
+        FractalCustom::new(self.name(), systems)
+    }
+
+    /// Maps in the order of [`EquationSystem::new`], in their usual published form.
+    fn coefficients(&self) -> Vec<[f64; 7]> {
+        const THIRD: f64 = 1.0 / 3.0;
+
+        match self {
+            Self::ZigZag => vec![],
+            Self::BarnsleyFern => vec![
+                [0.0, 0.0, 0.0, 0.16, 0.0, 0.0, 0.01],
+                [0.85, 0.04, -0.04, 0.85, 0.0, 1.6, 0.85],
+                [0.2, -0.26, 0.23, 0.22, 0.0, 1.6, 0.07],
+                [-0.15, 0.28, 0.26, 0.24, 0.0, 0.44, 0.07],
+            ],
+            Self::SierpinskiTriangle => vec![
+                [0.5, 0.0, 0.0, 0.5, 0.0, 0.0, THIRD],
+                [0.5, 0.0, 0.0, 0.5, 0.5, 0.0, THIRD],
+                [0.5, 0.0, 0.0, 0.5, 0.25, 3f64.sqrt() / 4.0, THIRD],
+            ],
+            Self::SierpinskiCarpet => (0..9)
+                .filter(|cell| *cell != 4)
+                .map(|cell| {
+                    let (column, row) = ((cell % 3) as f64, (cell / 3) as f64);
+                    [
+                        THIRD,
+                        0.0,
+                        0.0,
+                        THIRD,
+                        column * THIRD,
+                        row * THIRD,
+                        1.0 / 8.0,
+                    ]
+                })
+                .collect(),
+            Self::LevyCurve => vec![
+                [0.5, -0.5, 0.5, 0.5, 0.0, 0.0, 0.5],
+                [0.5, 0.5, -0.5, 0.5, 0.5, 0.5, 0.5],
+            ],
+            Self::HeighwayDragon => vec![
+                [0.5, -0.5, 0.5, 0.5, 0.0, 0.0, 0.5],
+                [-0.5, -0.5, 0.5, -0.5, 1.0, 0.0, 0.5],
+            ],
+            Self::MapleLeaf => vec![
+                [0.14, 0.01, 0.0, 0.51, -0.08, -1.31, 0.1],
+                [0.43, 0.52, -0.45, 0.5, 1.49, -0.75, 0.35],
+                [0.45, -0.49, 0.47, 0.47, -1.62, -0.74, 0.35],
+                [0.49, 0.0, 0.0, 0.51, 0.02, 1.62, 0.2],
+            ],
+            Self::Spiral => vec![
+                [
+                    0.787879, -0.424242, 0.242424, 0.859848, 1.758647, 1.408065, 0.9,
+                ],
+                [
+                    -0.121212, 0.257576, 0.151515, 0.05303, -6.721654, 1.377236, 0.05,
+                ],
+                [
+                    0.181818, -0.136364, 0.090909, 0.181818, 6.086107, 1.568035, 0.05,
+                ],
+            ],
+            Self::Swirl => vec![
+                [
+                    0.74545, -0.459091, 0.406061, 0.887121, 1.460279, 0.691072, 0.912675,
+                ],
+                [
+                    -0.424242, -0.065152, -0.175758, -0.218182, 3.809567, 6.741476,
+                    0.087325,
+                ],
+            ],
+        }
+    }
+
+    /// Scale and offset that center the attractor at the origin with a size of about 10 cm.
+    fn placement(&self) -> (f64, (f64, f64)) {
+        match self {
+            Self::ZigZag => (1.0, (0.0, 0.0)),
+            Self::BarnsleyFern => (1.0, (0.0, -5.0)),
+            Self::SierpinskiTriangle => (10.0, (-5.0, -4.33)),
+            Self::SierpinskiCarpet => (10.0, (-5.0, -5.0)),
+            Self::LevyCurve => (5.0, (-2.5, -1.875)),
+            Self::HeighwayDragon => (7.0, (-2.917, -1.167)),
+            Self::MapleLeaf => (1.5, (0.0, 0.0)),
+            Self::Spiral => (1.0, (0.0, -5.0)),
+            Self::Swirl => (1.0, (0.0, -5.0)),
+        }
+    }
+}
+
+/// The same map acting on the attractor scaled by `scale` and moved by `offset`:
+/// g(x) = s·w((x − t) / s) + t, so only the translation changes.
+fn placed(coefficients: &[f64; 7], scale: f64, offset: (f64, f64)) -> [f64; 7] {
+    let [a, b, d, e, c, f, p] = *coefficients;
+    let (tx, ty) = offset;
+
+    [
+        a,
+        b,
+        d,
+        e,
+        scale * c + tx - (a * tx + b * ty),
+        scale * f + ty - (d * tx + e * ty),
+        p,
+    ]
+}
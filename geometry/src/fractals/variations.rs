@@ -0,0 +1,86 @@
+use std::f64::consts::PI;
+use strum_macros::EnumIter;
+
+/// Nonlinear functions of fractal flames (Draves, Reckase, "The Fractal Flame Algorithm").
+/// A map applies the weighted sum of its variations to the result of its affine part.
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum Variation {
+    Linear,
+    Sinusoidal,
+    Spherical,
+    Swirl,
+    Horseshoe,
+    Polar,
+    Handkerchief,
+    Heart,
+    Disc,
+    Spiral,
+    Hyperbolic,
+    Diamond,
+    Fisheye,
+    Bubble,
+}
+
+impl Variation {
+    pub const COUNT: usize = 14;
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Linear => "Linear",
+            Self::Sinusoidal => "Sinusoidal",
+            Self::Spherical => "Spherical",
+            Self::Swirl => "Swirl",
+            Self::Horseshoe => "Horseshoe",
+            Self::Polar => "Polar",
+            Self::Handkerchief => "Handkerchief",
+            Self::Heart => "Heart",
+            Self::Disc => "Disc",
+            Self::Spiral => "Spiral",
+            Self::Hyperbolic => "Hyperbolic",
+            Self::Diamond => "Diamond",
+            Self::Fisheye => "Fisheye",
+            Self::Bubble => "Bubble",
+        }
+    }
+
+    /// Position in [`EquationSystem::variations`](crate::fractals::EquationSystem::variations).
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    /// Lowercase name used in `.ifs` comments.
+    pub fn key(&self) -> String {
+        self.name().to_ascii_lowercase()
+    }
+
+    pub fn apply(&self, x: f64, y: f64) -> (f64, f64) {
+        // Avoids the division by zero at the origin
+        let r2 = (x * x + y * y).max(1e-12);
+        let r = r2.sqrt();
+        // The flame paper measures θ from the Y axis
+        let theta = x.atan2(y);
+
+        match self {
+            Self::Linear => (x, y),
+            Self::Sinusoidal => (x.sin(), y.sin()),
+            Self::Spherical => (x / r2, y / r2),
+            Self::Swirl => {
+                let (sin, cos) = r2.sin_cos();
+                (x * sin - y * cos, x * cos + y * sin)
+            },
+            Self::Horseshoe => ((x - y) * (x + y) / r, 2.0 * x * y / r),
+            Self::Polar => (theta / PI, r - 1.0),
+            Self::Handkerchief => (r * (theta + r).sin(), r * (theta - r).cos()),
+            Self::Heart => (r * (theta * r).sin(), -r * (theta * r).cos()),
+            Self::Disc => {
+                let (sin, cos) = (PI * r).sin_cos();
+                (theta / PI * sin, theta / PI * cos)
+            },
+            Self::Spiral => ((theta.cos() + r.sin()) / r, (theta.sin() - r.cos()) / r),
+            Self::Hyperbolic => (theta.sin() / r, r * theta.cos()),
+            Self::Diamond => (theta.sin() * r.cos(), theta.cos() * r.sin()),
+            Self::Fisheye => (2.0 / (r + 1.0) * y, 2.0 / (r + 1.0) * x),
+            Self::Bubble => (4.0 / (r2 + 4.0) * x, 4.0 / (r2 + 4.0) * y),
+        }
+    }
+}
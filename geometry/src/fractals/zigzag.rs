@@ -1,10 +1,12 @@
 use crate::fractals::{EquationSystem, FractalIFS};
+use crate::random::Seed;
 use egui::Color32;
 
 #[derive(Debug, Clone)]
 pub struct FractalZigZag {
     pub iterations: u32,
     pub radius: f64,
+    pub seed: Seed,
 
     pub systems: Vec<EquationSystem>,
 }
@@ -14,6 +16,7 @@ impl Default for FractalZigZag {
         Self {
             iterations: 10000,
             radius: 1.5,
+            seed: Seed::default(),
             systems: vec![
                 EquationSystem::new_colored(
                     [
@@ -46,4 +49,8 @@ impl FractalIFS for FractalZigZag {
     fn systems(&self) -> &[EquationSystem] {
         &self.systems
     }
+
+    fn seed(&self) -> Seed {
+        self.seed
+    }
 }
@@ -3,21 +3,31 @@ pub mod conversion;
 pub mod fractals;
 pub mod pipeline;
 pub mod projections;
+pub mod random;
 pub mod smooth;
+pub mod surfaces;
+pub mod textures;
 pub mod units;
 pub mod viewport;
 pub mod figures {
     pub mod contour;
     pub mod detail;
     pub mod epicycloid;
+    pub mod extrusion;
     pub mod grid;
     pub mod grid3d;
+    pub mod lathe;
+    pub mod patch;
     pub mod star3d;
     pub mod surface;
+    pub mod sweep;
     pub mod texture;
 }
 pub mod math {
     pub mod angle;
+    pub mod complex;
+    pub mod expression;
+    pub mod quaternion;
 }
 pub mod primitives {
     pub mod circle;
@@ -26,6 +36,7 @@ pub mod primitives {
     pub mod point2d;
     pub mod point3d;
     pub mod vector2d;
+    pub mod vector3d;
 }
 pub mod shapes {
     pub mod dot;
@@ -34,11 +45,13 @@ pub mod shapes {
 }
 pub mod transformations {
     pub mod euclidean {
+        pub mod arcball;
         pub mod offset;
         pub mod offset3d;
         pub mod rotation;
         pub mod rotation3d;
     }
     pub mod affine;
+    pub mod correspondence;
     pub mod projective;
 }
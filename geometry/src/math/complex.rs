@@ -0,0 +1,62 @@
+use derive_more::{Add, AddAssign, Neg, Sub};
+use std::ops::{Div, Mul};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Add, AddAssign, Sub, Neg)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    pub const ZERO: Self = Self::new(0.0, 0.0);
+    pub const ONE: Self = Self::new(1.0, 0.0);
+
+    pub const fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+
+    /// |z|², cheaper than the modulus.
+    pub fn norm_sqr(&self) -> f64 {
+        self.re * self.re + self.im * self.im
+    }
+
+    pub fn norm(&self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    pub fn arg(&self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    pub fn scale(&self, factor: f64) -> Self {
+        Self::new(self.re * factor, self.im * factor)
+    }
+
+    pub fn inverse(&self) -> Self {
+        let norm = self.norm_sqr();
+        Self::new(self.re / norm, -self.im / norm)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+impl Div for Complex {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        let norm = other.norm_sqr();
+        Self::new(
+            (self.re * other.re + self.im * other.im) / norm,
+            (self.im * other.re - self.re * other.im) / norm,
+        )
+    }
+}
@@ -0,0 +1,382 @@
+use derive_more::Display;
+
+/// Compiled arithmetic expression over a fixed set of named variables.
+///
+/// Supports `+ - * / ^`, unary minus, parentheses, the constants `pi` and `e`,
+/// and the functions listed in [`Function`].
+/// Variables are resolved at parse time, so evaluation never fails.
+#[derive(Debug, Clone)]
+pub struct Expression {
+    source: String,
+    root: Node,
+}
+
+impl Expression {
+    pub fn parse(source: &str, variables: &[&str]) -> Result<Self, ExpressionError> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+            variables,
+        };
+
+        let root = parser.expression()?;
+        if let Some(token) = parser.peek() {
+            return Err(ExpressionError::UnexpectedToken(token.to_string()));
+        }
+
+        Ok(Self {
+            source: source.to_string(),
+            root,
+        })
+    }
+
+    pub fn constant(value: f64) -> Self {
+        Self {
+            source: value.to_string(),
+            root: Node::Number(value),
+        }
+    }
+
+    /// Values must be passed in the same order as the variable names given to `parse`.
+    pub fn eval(&self, values: &[f64]) -> f64 {
+        self.root.eval(values)
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
+
+#[derive(Debug, Display, PartialEq)]
+pub enum ExpressionError {
+    #[display("Expression is empty.")]
+    Empty,
+
+    #[display("Unexpected character '{_0}'.")]
+    UnexpectedCharacter(char),
+
+    #[display("Unexpected token '{_0}'.")]
+    UnexpectedToken(String),
+
+    #[display("Unexpected end of expression.")]
+    UnexpectedEnd,
+
+    #[display("Unknown variable or constant '{_0}'.")]
+    UnknownVariable(String),
+
+    #[display("Unknown function '{_0}'.")]
+    UnknownFunction(String),
+
+    #[display("Invalid number '{_0}'.")]
+    InvalidNumber(String),
+}
+
+impl std::error::Error for ExpressionError {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Function {
+    Sin,
+    Cos,
+    Tan,
+    Asin,
+    Acos,
+    Atan,
+    Sinh,
+    Cosh,
+    Tanh,
+    Sqrt,
+    Abs,
+    Exp,
+    Ln,
+    Log,
+    Floor,
+    Ceil,
+    Sign,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        let function = match name {
+            "sin" => Self::Sin,
+            "cos" => Self::Cos,
+            "tan" => Self::Tan,
+            "asin" => Self::Asin,
+            "acos" => Self::Acos,
+            "atan" => Self::Atan,
+            "sinh" => Self::Sinh,
+            "cosh" => Self::Cosh,
+            "tanh" => Self::Tanh,
+            "sqrt" => Self::Sqrt,
+            "abs" => Self::Abs,
+            "exp" => Self::Exp,
+            "ln" => Self::Ln,
+            "log" => Self::Log,
+            "floor" => Self::Floor,
+            "ceil" => Self::Ceil,
+            "sign" => Self::Sign,
+            _ => return None,
+        };
+
+        Some(function)
+    }
+
+    fn apply(&self, x: f64) -> f64 {
+        match self {
+            Self::Sin => x.sin(),
+            Self::Cos => x.cos(),
+            Self::Tan => x.tan(),
+            Self::Asin => x.asin(),
+            Self::Acos => x.acos(),
+            Self::Atan => x.atan(),
+            Self::Sinh => x.sinh(),
+            Self::Cosh => x.cosh(),
+            Self::Tanh => x.tanh(),
+            Self::Sqrt => x.sqrt(),
+            Self::Abs => x.abs(),
+            Self::Exp => x.exp(),
+            Self::Ln => x.ln(),
+            Self::Log => x.log10(),
+            Self::Floor => x.floor(),
+            Self::Ceil => x.ceil(),
+            Self::Sign => x.signum(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Number(f64),
+    Variable(usize),
+    Negate(Box<Node>),
+    Binary(Box<Node>, BinaryOperator, Box<Node>),
+    Call(Function, Box<Node>),
+}
+
+impl Node {
+    fn eval(&self, values: &[f64]) -> f64 {
+        match self {
+            Self::Number(value) => *value,
+            Self::Variable(index) => values.get(*index).copied().unwrap_or(0.0),
+            Self::Negate(node) => -node.eval(values),
+            Self::Binary(left, operator, right) => {
+                let (left, right) = (left.eval(values), right.eval(values));
+                match operator {
+                    BinaryOperator::Add => left + right,
+                    BinaryOperator::Subtract => left - right,
+                    BinaryOperator::Multiply => left * right,
+                    BinaryOperator::Divide => left / right,
+                    BinaryOperator::Power => left.powf(right),
+                }
+            },
+            Self::Call(function, argument) => function.apply(argument.eval(values)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Power,
+}
+
+#[derive(Debug, Clone, PartialEq, Display)]
+enum Token {
+    #[display("{_0}")]
+    Number(f64),
+    #[display("{_0}")]
+    Identifier(String),
+    #[display("{_0}")]
+    Symbol(char),
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, ExpressionError> {
+    let mut tokens = vec![];
+    let chars: Vec<char> = source.chars().collect();
+    let mut index = 0;
+
+    while index < chars.len() {
+        let c = chars[index];
+
+        if c.is_whitespace() {
+            index += 1;
+        } else if c.is_ascii_digit() || c == '.' {
+            let start = index;
+            while index < chars.len()
+                && (chars[index].is_ascii_digit() || chars[index] == '.')
+            {
+                index += 1;
+            }
+            // Scientific notation: 1e-3, 2.5E+4
+            if index < chars.len()
+                && (chars[index] == 'e' || chars[index] == 'E')
+                && chars.get(index + 1).is_some_and(|next| {
+                    next.is_ascii_digit() || *next == '-' || *next == '+'
+                })
+            {
+                index += 2;
+                while index < chars.len() && chars[index].is_ascii_digit() {
+                    index += 1;
+                }
+            }
+
+            let text: String = chars[start..index].iter().collect();
+            let value = text
+                .parse::<f64>()
+                .map_err(|_| ExpressionError::InvalidNumber(text.clone()))?;
+            tokens.push(Token::Number(value));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = index;
+            while index < chars.len()
+                && (chars[index].is_alphanumeric() || chars[index] == '_')
+            {
+                index += 1;
+            }
+            tokens.push(Token::Identifier(chars[start..index].iter().collect()));
+        } else if "+-*/^(),".contains(c) {
+            tokens.push(Token::Symbol(c));
+            index += 1;
+        } else {
+            return Err(ExpressionError::UnexpectedCharacter(c));
+        }
+    }
+
+    if tokens.is_empty() {
+        return Err(ExpressionError::Empty);
+    }
+
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    position: usize,
+    variables: &'a [&'a str],
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn is_symbol(&self, symbol: char) -> bool {
+        self.peek() == Some(&Token::Symbol(symbol))
+    }
+
+    fn expect(&mut self, symbol: char) -> Result<(), ExpressionError> {
+        match self.next() {
+            Some(Token::Symbol(c)) if c == symbol => Ok(()),
+            Some(token) => Err(ExpressionError::UnexpectedToken(token.to_string())),
+            None => Err(ExpressionError::UnexpectedEnd),
+        }
+    }
+
+    // expression := term (('+' | '-') term)*
+    fn expression(&mut self) -> Result<Node, ExpressionError> {
+        let mut node = self.term()?;
+
+        loop {
+            let operator = if self.is_symbol('+') {
+                BinaryOperator::Add
+            } else if self.is_symbol('-') {
+                BinaryOperator::Subtract
+            } else {
+                return Ok(node);
+            };
+            self.position += 1;
+
+            node = Node::Binary(Box::new(node), operator, Box::new(self.term()?));
+        }
+    }
+
+    // term := unary (('*' | '/') unary)*
+    fn term(&mut self) -> Result<Node, ExpressionError> {
+        let mut node = self.unary()?;
+
+        loop {
+            let operator = if self.is_symbol('*') {
+                BinaryOperator::Multiply
+            } else if self.is_symbol('/') {
+                BinaryOperator::Divide
+            } else {
+                return Ok(node);
+            };
+            self.position += 1;
+
+            node = Node::Binary(Box::new(node), operator, Box::new(self.unary()?));
+        }
+    }
+
+    // unary := '-' unary | '+' unary | power
+    fn unary(&mut self) -> Result<Node, ExpressionError> {
+        if self.is_symbol('-') {
+            self.position += 1;
+            return Ok(Node::Negate(Box::new(self.unary()?)));
+        }
+        if self.is_symbol('+') {
+            self.position += 1;
+            return self.unary();
+        }
+
+        self.power()
+    }
+
+    // power := primary ('^' unary)?   (right-associative, so 2^-1 and 2^3^2 work)
+    fn power(&mut self) -> Result<Node, ExpressionError> {
+        let base = self.primary()?;
+
+        if self.is_symbol('^') {
+            self.position += 1;
+            let exponent = self.unary()?;
+            return Ok(Node::Binary(
+                Box::new(base),
+                BinaryOperator::Power,
+                Box::new(exponent),
+            ));
+        }
+
+        Ok(base)
+    }
+
+    fn primary(&mut self) -> Result<Node, ExpressionError> {
+        match self.next() {
+            Some(Token::Number(value)) => Ok(Node::Number(value)),
+            Some(Token::Symbol('(')) => {
+                let node = self.expression()?;
+                self.expect(')')?;
+                Ok(node)
+            },
+            Some(Token::Identifier(name)) => {
+                if self.is_symbol('(') {
+                    let function = Function::from_name(&name)
+                        .ok_or(ExpressionError::UnknownFunction(name))?;
+                    self.position += 1;
+                    let argument = self.expression()?;
+                    self.expect(')')?;
+                    return Ok(Node::Call(function, Box::new(argument)));
+                }
+
+                if let Some(index) = self.variables.iter().position(|v| *v == name) {
+                    return Ok(Node::Variable(index));
+                }
+
+                match name.as_str() {
+                    "pi" => Ok(Node::Number(std::f64::consts::PI)),
+                    "e" => Ok(Node::Number(std::f64::consts::E)),
+                    _ => Err(ExpressionError::UnknownVariable(name)),
+                }
+            },
+            Some(token) => Err(ExpressionError::UnexpectedToken(token.to_string())),
+            None => Err(ExpressionError::UnexpectedEnd),
+        }
+    }
+}
@@ -0,0 +1,293 @@
+use crate::math::angle::Angle;
+use derive_more::{Add, Neg, Sub};
+use nalgebra::{Matrix4, Vector3};
+use std::ops::Mul;
+
+/// Rotation in 3D as a quaternion `w + xi + yj + zk`. Rotations are unit
+/// quaternions, `q` and `-q` being the same rotation.
+#[derive(Debug, Clone, Copy, PartialEq, Add, Sub, Neg)]
+pub struct Quaternion {
+    pub w: f64,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Default for Quaternion {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Quaternion {
+    pub const IDENTITY: Self = Self::new(1.0, 0.0, 0.0, 0.0);
+
+    /// Below this norm a quaternion or an axis is treated as zero.
+    const EPSILON: f64 = 1e-12;
+
+    pub const fn new(w: f64, x: f64, y: f64, z: f64) -> Self {
+        Self { w, x, y, z }
+    }
+
+    /// Rotation by the angle around the axis, counterclockwise when looking from
+    /// the end of the axis. A zero axis gives the identity.
+    pub fn from_axis_angle(axis: Vector3<f64>, angle: Angle) -> Self {
+        let Some(axis) = axis.try_normalize(Self::EPSILON) else {
+            return Self::IDENTITY;
+        };
+        let half = angle.radian() / 2.0;
+        let sin = half.sin();
+
+        Self::new(half.cos(), axis.x * sin, axis.y * sin, axis.z * sin)
+    }
+
+    /// Same rotation as turning around OX, then OY, then OZ.
+    pub fn from_euler(x: Angle, y: Angle, z: Angle) -> Self {
+        Self::from_axis_angle(Vector3::z(), z)
+            * Self::from_axis_angle(Vector3::y(), y)
+            * Self::from_axis_angle(Vector3::x(), x)
+    }
+
+    /// Shortest rotation that turns the direction `from` into `to`.
+    pub fn from_arc(from: Vector3<f64>, to: Vector3<f64>) -> Self {
+        let (Some(from), Some(to)) = (
+            from.try_normalize(Self::EPSILON),
+            to.try_normalize(Self::EPSILON),
+        ) else {
+            return Self::IDENTITY;
+        };
+
+        let cross = from.cross(&to);
+        let dot = from.dot(&to);
+        if dot < -1.0 + 1e-9 {
+            // Opposite directions: half a turn around any perpendicular axis
+            let axis = from.cross(&Vector3::x());
+            let axis = if axis.norm() > 1e-6 {
+                axis
+            } else {
+                from.cross(&Vector3::y())
+            };
+
+            return Self::from_axis_angle(axis, Angle::from_radian(std::f64::consts::PI));
+        }
+
+        Self::new(1.0 + dot, cross.x, cross.y, cross.z).normalize()
+    }
+
+    /// Axis and angle of the rotation, the angle in `[0, 2π)`. The identity has
+    /// the OX axis.
+    pub fn axis_angle(&self) -> (Vector3<f64>, Angle) {
+        let q = self.normalize();
+        let vector = Vector3::new(q.x, q.y, q.z);
+        let sin = vector.norm();
+        if sin < Self::EPSILON {
+            return (Vector3::x(), Angle::from_radian(0.0));
+        }
+
+        (vector / sin, Angle::from_radian(2.0 * sin.atan2(q.w)))
+    }
+
+    pub fn norm(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    /// Same rotation with the unit norm. A zero quaternion gives the identity.
+    pub fn normalize(&self) -> Self {
+        let norm = self.norm();
+        if norm < Self::EPSILON {
+            return Self::IDENTITY;
+        }
+
+        self.scale(1.0 / norm)
+    }
+
+    pub fn conjugate(&self) -> Self {
+        Self::new(self.w, -self.x, -self.y, -self.z)
+    }
+
+    pub fn inverse(&self) -> Self {
+        let norm = self.dot(self);
+        if norm < Self::EPSILON {
+            return Self::IDENTITY;
+        }
+
+        self.conjugate().scale(1.0 / norm)
+    }
+
+    pub fn dot(&self, other: &Self) -> f64 {
+        self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn scale(&self, factor: f64) -> Self {
+        Self::new(
+            self.w * factor,
+            self.x * factor,
+            self.y * factor,
+            self.z * factor,
+        )
+    }
+
+    /// Rotates the vector, `q v q⁻¹`.
+    pub fn rotate(&self, vector: Vector3<f64>) -> Vector3<f64> {
+        let q = self.normalize();
+        let axis = Vector3::new(q.x, q.y, q.z);
+        let t = 2.0 * axis.cross(&vector);
+
+        vector + q.w * t + axis.cross(&t)
+    }
+
+    /// Rotation matrix for row vectors, `point * matrix`, like the other
+    /// matrices of the pipeline.
+    pub fn to_matrix4(&self) -> Matrix4<f64> {
+        let Self { w, x, y, z } = self.normalize();
+
+        Matrix4::new(
+            1.0 - 2.0 * (y * y + z * z),
+            2.0 * (x * y + w * z),
+            2.0 * (x * z - w * y),
+            0.0,
+            2.0 * (x * y - w * z),
+            1.0 - 2.0 * (x * x + z * z),
+            2.0 * (y * z + w * x),
+            0.0,
+            2.0 * (x * z + w * y),
+            2.0 * (y * z - w * x),
+            1.0 - 2.0 * (x * x + y * y),
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            1.0,
+        )
+    }
+
+    /// Rotation of a row-vector matrix. Only the upper 3×3 block is read, and it
+    /// is expected to be orthonormal.
+    pub fn from_matrix4(matrix: &Matrix4<f64>) -> Self {
+        // Column-vector form, the transpose of the matrix
+        let m = |row: usize, column: usize| matrix[(column, row)];
+        let trace = m(0, 0) + m(1, 1) + m(2, 2);
+
+        // Shepperd's method: divides by the largest of the four components
+        let q = if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            Self::new(
+                s / 4.0,
+                (m(2, 1) - m(1, 2)) / s,
+                (m(0, 2) - m(2, 0)) / s,
+                (m(1, 0) - m(0, 1)) / s,
+            )
+        } else if m(0, 0) > m(1, 1) && m(0, 0) > m(2, 2) {
+            let s = (1.0 + m(0, 0) - m(1, 1) - m(2, 2)).sqrt() * 2.0;
+            Self::new(
+                (m(2, 1) - m(1, 2)) / s,
+                s / 4.0,
+                (m(0, 1) + m(1, 0)) / s,
+                (m(0, 2) + m(2, 0)) / s,
+            )
+        } else if m(1, 1) > m(2, 2) {
+            let s = (1.0 + m(1, 1) - m(0, 0) - m(2, 2)).sqrt() * 2.0;
+            Self::new(
+                (m(0, 2) - m(2, 0)) / s,
+                (m(0, 1) + m(1, 0)) / s,
+                s / 4.0,
+                (m(1, 2) + m(2, 1)) / s,
+            )
+        } else {
+            let s = (1.0 + m(2, 2) - m(0, 0) - m(1, 1)).sqrt() * 2.0;
+            Self::new(
+                (m(1, 0) - m(0, 1)) / s,
+                (m(0, 2) + m(2, 0)) / s,
+                (m(1, 2) + m(2, 1)) / s,
+                s / 4.0,
+            )
+        };
+
+        q.normalize()
+    }
+
+    /// Logarithm of a unit quaternion, a pure quaternion.
+    pub fn log(&self) -> Self {
+        let vector = Vector3::new(self.x, self.y, self.z);
+        let sin = vector.norm();
+        if sin < Self::EPSILON {
+            return Self::new(0.0, 0.0, 0.0, 0.0);
+        }
+
+        let vector = vector * (sin.atan2(self.w) / sin);
+        Self::new(0.0, vector.x, vector.y, vector.z)
+    }
+
+    /// Exponent of a pure quaternion, a unit quaternion.
+    pub fn exp(&self) -> Self {
+        let vector = Vector3::new(self.x, self.y, self.z);
+        let angle = vector.norm();
+        if angle < Self::EPSILON {
+            return Self::IDENTITY;
+        }
+
+        let vector = vector * (angle.sin() / angle);
+        Self::new(angle.cos(), vector.x, vector.y, vector.z)
+    }
+
+    /// Spherical linear interpolation at a constant angular speed, along the
+    /// shorter arc.
+    pub fn slerp(&self, other: &Self, t: f64) -> Self {
+        let from = self.normalize();
+        let mut to = other.normalize();
+        let mut cos = from.dot(&to);
+        if cos < 0.0 {
+            to = -to;
+            cos = -cos;
+        }
+
+        // Nearly the same rotation: the sine below is too small to divide by
+        if cos > 1.0 - 1e-9 {
+            return (from + (to - from).scale(t)).normalize();
+        }
+
+        let angle = cos.acos();
+        let sin = angle.sin();
+
+        (from.scale(((1.0 - t) * angle).sin() / sin) + to.scale((t * angle).sin() / sin))
+            .normalize()
+    }
+
+    /// Spherical cubic interpolation from `self` to `other` through the inner
+    /// controls from [`Quaternion::control`]. Consecutive segments meet with a
+    /// continuous angular velocity.
+    pub fn squad(&self, other: &Self, from: &Self, to: &Self, t: f64) -> Self {
+        self.slerp(other, t)
+            .slerp(&from.slerp(to, t), 2.0 * t * (1.0 - t))
+    }
+
+    /// Inner control of `current` for [`Quaternion::squad`], from its
+    /// neighbours on the path.
+    pub fn control(previous: &Self, current: &Self, next: &Self) -> Self {
+        let current = current.normalize();
+        // Neighbours on the same side of the sphere as the current rotation
+        let side = |q: &Self| {
+            let q = q.normalize();
+            if q.dot(&current) < 0.0 { -q } else { q }
+        };
+        let inverse = current.conjugate();
+        let to_previous = (inverse * side(previous)).log();
+        let to_next = (inverse * side(next)).log();
+
+        current * (to_previous + to_next).scale(-0.25).exp()
+    }
+}
+
+impl Mul for Quaternion {
+    type Output = Self;
+
+    /// Hamilton product: the rotation `rhs` followed by `self`.
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(
+            self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+            self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+            self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+        )
+    }
+}
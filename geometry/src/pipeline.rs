@@ -2,6 +2,12 @@ use crate::primitives::line2d::Line2D;
 use crate::primitives::line3d::Line3D;
 use crate::primitives::point2d::Point2D;
 use crate::primitives::point3d::Point3D;
+use crate::transformations::affine::reflection::LineReflectionOperation;
+use crate::transformations::affine::reflection3d::PlaneReflectionOperation;
+use crate::transformations::affine::scaling::ScalingOperation;
+use crate::transformations::affine::scaling3d::Scaling3DOperation;
+use crate::transformations::affine::shear::ShearOperation;
+use crate::transformations::affine::shear3d::Shear3DOperation;
 use crate::transformations::affine::symmetry::PointSymmetryOperation;
 use crate::transformations::euclidean::offset::OffsetOperation;
 use crate::transformations::euclidean::offset3d::Offset3DOperation;
@@ -54,6 +60,9 @@ pub enum Operation {
     Offset(OffsetOperation),
     Rotation(RotationOperation),
     PointSymmetry(PointSymmetryOperation),
+    Shear(ShearOperation),
+    Scaling(ScalingOperation),
+    LineReflection(LineReflectionOperation),
 }
 
 impl Operation {
@@ -69,6 +78,9 @@ impl Operation {
             Self::Offset(operation) => operation.go(point),
             Self::Rotation(operation) => operation.go(point),
             Self::PointSymmetry(operation) => operation.go(point),
+            Self::Shear(operation) => operation.go(point),
+            Self::Scaling(operation) => operation.go(point),
+            Self::LineReflection(operation) => operation.go(point),
         }
     }
 }
@@ -89,6 +101,12 @@ impl Pipeline3D {
         }
     }
 
+    pub fn do_tasks_points(&self, points: &mut [Point3D], pivot: &mut Point3D) {
+        for operation in &self.buffer {
+            operation.go_points(points, pivot);
+        }
+    }
+
     pub fn clear(&mut self) {
         self.buffer.clear();
     }
@@ -102,6 +120,9 @@ impl Pipeline3D {
 pub enum Operation3D {
     Offset(Offset3DOperation),
     Rotation(Rotation3DOperation),
+    Scaling(Scaling3DOperation),
+    Shear(Shear3DOperation),
+    Reflection(PlaneReflectionOperation),
 }
 
 impl Operation3D {
@@ -120,6 +141,60 @@ impl Operation3D {
                     operation.go(&mut line.end, pivot);
                 }
             },
+            Self::Scaling(operation) => {
+                for line in lines.iter_mut() {
+                    operation.go(&mut line.start, pivot);
+                    operation.go(&mut line.end, pivot);
+                }
+            },
+            Self::Shear(operation) => {
+                for line in lines.iter_mut() {
+                    operation.go(&mut line.start, pivot);
+                    operation.go(&mut line.end, pivot);
+                }
+            },
+            // The pivot is mirrored with the figure when the plane is away from it
+            Self::Reflection(operation) => {
+                let origin = *pivot;
+                for line in lines.iter_mut() {
+                    operation.go(&mut line.start, &origin);
+                    operation.go(&mut line.end, &origin);
+                }
+                operation.go(pivot, &origin);
+            },
+        }
+    }
+
+    pub fn go_points(&self, points: &mut [Point3D], pivot: &mut Point3D) {
+        match self {
+            Self::Offset(operation) => {
+                for point in points.iter_mut() {
+                    operation.go(point);
+                }
+                operation.go(pivot);
+            },
+            Self::Rotation(operation) => {
+                for point in points.iter_mut() {
+                    operation.go(point, pivot);
+                }
+            },
+            Self::Scaling(operation) => {
+                for point in points.iter_mut() {
+                    operation.go(point, pivot);
+                }
+            },
+            Self::Shear(operation) => {
+                for point in points.iter_mut() {
+                    operation.go(point, pivot);
+                }
+            },
+            Self::Reflection(operation) => {
+                let origin = *pivot;
+                for point in points.iter_mut() {
+                    operation.go(point, &origin);
+                }
+                operation.go(pivot, &origin);
+            },
         }
     }
 }
@@ -1,4 +1,5 @@
 use crate::primitives::vector2d::{Vector2D, Vector2DPixel};
+use crate::random::unique_id;
 use crate::shapes::dot::DotMetadata;
 use crate::shapes::shape::ShapeMetadata;
 use crate::shapes::square::SquareMetadata;
@@ -143,7 +144,7 @@ const MOVEABLE_POINT_RADIUS: Pixel = Pixel(5.0);
 impl MoveablePoint {
     pub fn new(coordinates: Point2D) -> Self {
         Self {
-            id: egui::Id::new(rand::random::<i64>()),
+            id: unique_id(),
             coordinates,
             radius: MOVEABLE_POINT_RADIUS,
         }
@@ -1,7 +1,12 @@
 use crate::primitives::point2d::Point2D;
+use crate::primitives::vector2d::Vector2DPixel;
 use crate::projections::Projection;
-use crate::units::Centimeter;
-use nalgebra::SMatrix;
+use crate::random::unique_id;
+use crate::units::{Centimeter, Pixel};
+use crate::viewport::Viewport;
+use egui::{Pos2, Rect, Response, Sense};
+use nalgebra::{Matrix2, SMatrix, Vector2};
+use strum_macros::EnumIter;
 
 pub trait Pointable3D: Clone {
     fn x(&self) -> f64;
@@ -62,3 +67,116 @@ impl Pointable3D for Point3D {
         Point2D::new(vector.x / w, vector.y / w)
     }
 }
+
+/// Point in space that can be dragged by its projection on the canvas.
+#[derive(Debug, Clone)]
+pub struct MoveablePoint3D {
+    pub id: egui::Id,
+    pub coordinates: Point3D,
+    pub radius: Pixel,
+}
+
+const MOVEABLE_POINT_RADIUS: Pixel = Pixel(6.0);
+
+impl MoveablePoint3D {
+    pub fn new(coordinates: Point3D) -> Self {
+        Self {
+            id: unique_id(),
+            coordinates,
+            radius: MOVEABLE_POINT_RADIUS,
+        }
+    }
+
+    pub fn interact_area(&self, projected: Point2D, viewport: &Viewport) -> Rect {
+        let rect_size = egui::Vec2::splat(2.0 * self.radius.value() as f32);
+        let rect_center: Pos2 = projected.to_pixels(viewport).into();
+        Rect::from_center_size(rect_center, rect_size)
+    }
+
+    /// `project` maps the point to the canvas (transformations and projection included).
+    /// The drag is inverted through the local Jacobian of `project`, so the point moves
+    /// within `plane` and its projection follows the cursor.
+    pub fn update_on_pan(
+        &mut self, ui: &egui::Ui, response: &Response, viewport: &Viewport,
+        plane: DragPlane, project: impl Fn(&Point3D) -> Point2D,
+    ) {
+        let projected = project(&self.coordinates);
+        let area = self.interact_area(projected, viewport);
+
+        let response = ui.interact(area, response.id.with(self.id), Sense::drag());
+
+        let drag = response.drag_delta();
+        if drag == egui::Vec2::ZERO {
+            return;
+        }
+        let offset = Vector2DPixel::from(drag).to_centimeters(viewport);
+
+        let (first, second) = plane.axes();
+        let column = |axis: Axis3D| {
+            let mut moved = self.coordinates;
+            *axis.coordinate_mut(&mut moved) += Centimeter(JACOBIAN_STEP);
+            let shifted = project(&moved);
+            Vector2::new(
+                (shifted.x - projected.x).value() / JACOBIAN_STEP,
+                (shifted.y - projected.y).value() / JACOBIAN_STEP,
+            )
+        };
+
+        let jacobian = Matrix2::from_columns(&[column(first), column(second)]);
+        // The plane is seen edge-on: no movement within it is visible.
+        let Some(inverse) = jacobian.try_inverse() else {
+            return;
+        };
+
+        let delta = inverse * Vector2::new(offset.x.value(), offset.y.value());
+        *first.coordinate_mut(&mut self.coordinates) += Centimeter(delta.x);
+        *second.coordinate_mut(&mut self.coordinates) += Centimeter(delta.y);
+
+        ui.ctx().request_repaint();
+    }
+}
+
+const JACOBIAN_STEP: f64 = 1e-3;
+
+/// Plane in which a [`MoveablePoint3D`] moves while dragged.
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum DragPlane {
+    XY,
+    XZ,
+    YZ,
+}
+
+impl DragPlane {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::XY => "XY",
+            Self::XZ => "XZ",
+            Self::YZ => "YZ",
+        }
+    }
+
+    fn axes(&self) -> (Axis3D, Axis3D) {
+        match self {
+            Self::XY => (Axis3D::X, Axis3D::Y),
+            Self::XZ => (Axis3D::X, Axis3D::Z),
+            Self::YZ => (Axis3D::Y, Axis3D::Z),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Axis3D {
+    X,
+    Y,
+    Z,
+}
+
+impl Axis3D {
+    fn coordinate_mut(self, point: &mut Point3D) -> &mut Centimeter {
+        match self {
+            Self::X => &mut point.x,
+            Self::Y => &mut point.y,
+            Self::Z => &mut point.z,
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use crate::primitives::point3d::Point3D;
+use crate::units::Centimeter;
+use derive_more::{Add, Neg, Sub};
+
+#[derive(Add, Sub, Neg, Debug, Default, Clone, Copy, PartialEq)]
+pub struct Vector3D {
+    pub x: Centimeter,
+    pub y: Centimeter,
+    pub z: Centimeter,
+}
+
+impl Vector3D {
+    pub fn new(x: f64, y: f64, z: f64) -> Self {
+        Self {
+            x: Centimeter(x),
+            y: Centimeter(y),
+            z: Centimeter(z),
+        }
+    }
+
+    pub const fn zero() -> Self {
+        Self {
+            x: Centimeter(0.0),
+            y: Centimeter(0.0),
+            z: Centimeter(0.0),
+        }
+    }
+
+    /// Vector pointing from `start` to `end`.
+    pub fn between(start: &Point3D, end: &Point3D) -> Self {
+        Self {
+            x: end.x - start.x,
+            y: end.y - start.y,
+            z: end.z - start.z,
+        }
+    }
+
+    pub fn dot(&self, other: &Self) -> f64 {
+        self.x.value() * other.x.value()
+            + self.y.value() * other.y.value()
+            + self.z.value() * other.z.value()
+    }
+
+    pub fn cross(&self, other: &Self) -> Self {
+        Self::new(
+            self.y.value() * other.z.value() - self.z.value() * other.y.value(),
+            self.z.value() * other.x.value() - self.x.value() * other.z.value(),
+            self.x.value() * other.y.value() - self.y.value() * other.x.value(),
+        )
+    }
+
+    pub fn length(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    pub fn scale(&self, factor: f64) -> Self {
+        Self {
+            x: self.x * factor,
+            y: self.y * factor,
+            z: self.z * factor,
+        }
+    }
+
+    /// Returns the zero vector if the length is too small to normalize.
+    pub fn normalized(&self) -> Self {
+        let length = self.length();
+        if length < 1e-12 {
+            return Self::zero();
+        }
+
+        self.scale(1.0 / length)
+    }
+
+    pub fn translate(&self, point: &Point3D) -> Point3D {
+        Point3D {
+            x: point.x + self.x,
+            y: point.y + self.y,
+            z: point.z + self.z,
+        }
+    }
+}
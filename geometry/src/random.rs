@@ -0,0 +1,27 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Seed of a random generator. The same seed always gives the same sequence,
+/// so renders and screenshots can be reproduced.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Seed(pub u64);
+
+impl Seed {
+    pub fn rng(&self) -> StdRng {
+        StdRng::seed_from_u64(self.0)
+    }
+
+    /// Picks a new seed from the system's entropy.
+    pub fn randomize(&mut self) {
+        self.0 = rand::random();
+    }
+}
+
+/// Identifier for interactive points. Ids are taken from a counter rather than
+/// from random numbers, so they are unique and the same from run to run.
+pub fn unique_id() -> egui::Id {
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+
+    egui::Id::new(("GEOMETRY_ID", NEXT.fetch_add(1, Ordering::Relaxed)))
+}
@@ -0,0 +1,190 @@
+use crate::animations::Parameter;
+use crate::primitives::point3d::Point3D;
+use crate::primitives::vector3d::Vector3D;
+use crate::surfaces::cone::Cone;
+use crate::surfaces::cylinder::Cylinder;
+use crate::surfaces::formula::FormulaSurface;
+use crate::surfaces::helicoid::Helicoid;
+use crate::surfaces::hyperboloid::Hyperboloid;
+use crate::surfaces::klein::KleinBottle;
+use crate::surfaces::mobius::MobiusStrip;
+use crate::surfaces::paraboloid::Paraboloid;
+use crate::surfaces::sphere::Sphere;
+use crate::surfaces::torus::Torus;
+use std::ops::RangeInclusive;
+
+pub mod cone;
+pub mod cylinder;
+pub mod formula;
+pub mod helicoid;
+pub mod hyperboloid;
+pub mod klein;
+pub mod mobius;
+pub mod paraboloid;
+pub mod sphere;
+pub mod torus;
+
+/// Surface given by r(u, v) = (x(u, v), y(u, v), z(u, v)) over a rectangular domain.
+pub trait ParametricSurface {
+    fn name(&self) -> &'static str;
+
+    fn point(&self, u: f64, v: f64) -> Point3D;
+
+    fn u_domain(&self) -> RangeInclusive<f64>;
+    fn v_domain(&self) -> RangeInclusive<f64>;
+
+    /// Periodic direction: r(u_min, v) == r(u_max, v), so the mesh is closed there.
+    fn is_u_periodic(&self) -> bool {
+        false
+    }
+
+    fn is_v_periodic(&self) -> bool {
+        false
+    }
+
+    /// Partial derivative ∂r/∂u. Finite difference unless overridden.
+    fn derivative_u(&self, u: f64, v: f64) -> Vector3D {
+        difference(&self.u_domain(), self.is_u_periodic(), u, |u| {
+            self.point(u, v)
+        })
+    }
+
+    /// Partial derivative ∂r/∂v. Finite difference unless overridden.
+    fn derivative_v(&self, u: f64, v: f64) -> Vector3D {
+        difference(&self.v_domain(), self.is_v_periodic(), v, |v| {
+            self.point(u, v)
+        })
+    }
+
+    /// Unit normal, the zero vector where the surface is degenerate (an apex).
+    fn normal(&self, u: f64, v: f64) -> Vector3D {
+        self.derivative_u(u, v)
+            .cross(&self.derivative_v(u, v))
+            .normalized()
+    }
+
+    /// Shape parameters that can be edited in the UI or animated.
+    fn parameters(&mut self) -> Vec<SurfaceParameter<'_>>;
+
+    /// Formulas for x, y and z with the current parameter values substituted.
+    /// Used to turn any built-in surface into an editable [`FormulaSurface`].
+    fn formulas(&self) -> [String; 3];
+}
+
+/// Surface parameters are ordinary figure parameters, so keyframes can drive them.
+pub type SurfaceParameter<'a> = Parameter<'a>;
+
+#[derive(Debug, Clone)]
+pub enum SurfaceKind {
+    Cone(Cone),
+    Sphere(Sphere),
+    Cylinder(Cylinder),
+    Torus(Torus),
+    Hyperboloid(Hyperboloid),
+    Paraboloid(Paraboloid),
+    Helicoid(Helicoid),
+    MobiusStrip(MobiusStrip),
+    KleinBottle(KleinBottle),
+    Formula(Box<FormulaSurface>),
+}
+
+impl Default for SurfaceKind {
+    fn default() -> Self {
+        Self::Cone(Cone::default())
+    }
+}
+
+impl SurfaceKind {
+    /// Every surface with its default parameters, in the order shown in the UI.
+    pub fn catalog() -> Vec<Self> {
+        vec![
+            Self::Cone(Cone::default()),
+            Self::Sphere(Sphere::default()),
+            Self::Cylinder(Cylinder::default()),
+            Self::Torus(Torus::default()),
+            Self::Hyperboloid(Hyperboloid::default()),
+            Self::Paraboloid(Paraboloid::default()),
+            Self::Helicoid(Helicoid::default()),
+            Self::MobiusStrip(MobiusStrip::default()),
+            Self::KleinBottle(KleinBottle::default()),
+            Self::Formula(Box::default()),
+        ]
+    }
+
+    pub fn surface(&self) -> &dyn ParametricSurface {
+        match self {
+            Self::Cone(surface) => surface,
+            Self::Sphere(surface) => surface,
+            Self::Cylinder(surface) => surface,
+            Self::Torus(surface) => surface,
+            Self::Hyperboloid(surface) => surface,
+            Self::Paraboloid(surface) => surface,
+            Self::Helicoid(surface) => surface,
+            Self::MobiusStrip(surface) => surface,
+            Self::KleinBottle(surface) => surface,
+            Self::Formula(surface) => surface.as_ref(),
+        }
+    }
+
+    pub fn surface_mut(&mut self) -> &mut dyn ParametricSurface {
+        match self {
+            Self::Cone(surface) => surface,
+            Self::Sphere(surface) => surface,
+            Self::Cylinder(surface) => surface,
+            Self::Torus(surface) => surface,
+            Self::Hyperboloid(surface) => surface,
+            Self::Paraboloid(surface) => surface,
+            Self::Helicoid(surface) => surface,
+            Self::MobiusStrip(surface) => surface,
+            Self::KleinBottle(surface) => surface,
+            Self::Formula(surface) => surface.as_mut(),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.surface().name()
+    }
+
+    /// Replaces the current surface with a formula surface producing the same shape.
+    pub fn convert_to_formula(&mut self) {
+        if let Self::Formula(_) = self {
+            return;
+        }
+
+        *self = Self::Formula(Box::new(FormulaSurface::from_surface(self.surface())));
+    }
+}
+
+const DERIVATIVE_STEP: f64 = 1e-5;
+
+/// Derivative of the curve `point` at `value`. Central inside the domain and
+/// one-sided at its ends, so that the curve is never evaluated outside of it
+/// (unless it is periodic and simply wraps).
+fn difference(
+    domain: &RangeInclusive<f64>, is_periodic: bool, value: f64,
+    point: impl Fn(f64) -> Point3D,
+) -> Vector3D {
+    let h = DERIVATIVE_STEP * domain_length(domain).max(1.0);
+    let (low, high) = if is_periodic {
+        (value - h, value + h)
+    } else {
+        (
+            (value - h).max(*domain.start()),
+            (value + h).min(*domain.end()),
+        )
+    };
+    if high <= low {
+        return Vector3D::zero();
+    }
+
+    Vector3D::between(&point(low), &point(high)).scale(1.0 / (high - low))
+}
+
+pub fn domain_length(domain: &RangeInclusive<f64>) -> f64 {
+    domain.end() - domain.start()
+}
+
+/// Maps t ∈ [0, 1] onto the domain.
+pub fn denormalize(domain: &RangeInclusive<f64>, t: f64) -> f64 {
+    domain.start() + t * domain_length(domain)
+}
@@ -0,0 +1,76 @@
+use crate::primitives::point3d::Point3D;
+use crate::primitives::vector3d::Vector3D;
+use crate::surfaces::{ParametricSurface, SurfaceParameter};
+use crate::units::Centimeter;
+use std::f64::consts::TAU;
+use std::ops::RangeInclusive;
+
+/// u: angle around the axis, v: 0 at the base, 1 at the apex.
+#[derive(Debug, Clone)]
+pub struct Cone {
+    pub height: Centimeter,
+    pub radius: Centimeter,
+}
+
+impl Default for Cone {
+    fn default() -> Self {
+        Self {
+            height: Centimeter(10.0),
+            radius: Centimeter(5.0),
+        }
+    }
+}
+
+impl ParametricSurface for Cone {
+    fn name(&self) -> &'static str {
+        "Cone"
+    }
+
+    fn point(&self, u: f64, v: f64) -> Point3D {
+        let current_radius = self.radius.value() * (1.0 - v);
+
+        Point3D::new(
+            current_radius * u.cos(),
+            self.height.value() * v,
+            current_radius * u.sin(),
+        )
+    }
+
+    fn u_domain(&self) -> RangeInclusive<f64> {
+        0.0..=TAU
+    }
+
+    fn v_domain(&self) -> RangeInclusive<f64> {
+        0.0..=1.0
+    }
+
+    fn is_u_periodic(&self) -> bool {
+        true
+    }
+
+    fn derivative_u(&self, u: f64, v: f64) -> Vector3D {
+        let current_radius = self.radius.value() * (1.0 - v);
+        Vector3D::new(-current_radius * u.sin(), 0.0, current_radius * u.cos())
+    }
+
+    fn derivative_v(&self, u: f64, _v: f64) -> Vector3D {
+        let radius = self.radius.value();
+        Vector3D::new(-radius * u.cos(), self.height.value(), -radius * u.sin())
+    }
+
+    fn parameters(&mut self) -> Vec<SurfaceParameter<'_>> {
+        vec![
+            SurfaceParameter::new("Height", &mut self.height.0, 1.0..=30.0),
+            SurfaceParameter::new("Radius", &mut self.radius.0, 1.0..=20.0),
+        ]
+    }
+
+    fn formulas(&self) -> [String; 3] {
+        let (h, r) = (self.height.value(), self.radius.value());
+        [
+            format!("{r} * (1 - v) * cos(u)"),
+            format!("{h} * v"),
+            format!("{r} * (1 - v) * sin(u)"),
+        ]
+    }
+}
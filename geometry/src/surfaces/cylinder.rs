@@ -0,0 +1,71 @@
+use crate::primitives::point3d::Point3D;
+use crate::primitives::vector3d::Vector3D;
+use crate::surfaces::{ParametricSurface, SurfaceParameter};
+use crate::units::Centimeter;
+use std::f64::consts::TAU;
+use std::ops::RangeInclusive;
+
+/// u: angle around the axis, v: 0 at the base, 1 at the top.
+#[derive(Debug, Clone)]
+pub struct Cylinder {
+    pub height: Centimeter,
+    pub radius: Centimeter,
+}
+
+impl Default for Cylinder {
+    fn default() -> Self {
+        Self {
+            height: Centimeter(10.0),
+            radius: Centimeter(4.0),
+        }
+    }
+}
+
+impl ParametricSurface for Cylinder {
+    fn name(&self) -> &'static str {
+        "Cylinder"
+    }
+
+    fn point(&self, u: f64, v: f64) -> Point3D {
+        let r = self.radius.value();
+
+        Point3D::new(r * u.cos(), self.height.value() * v, r * u.sin())
+    }
+
+    fn u_domain(&self) -> RangeInclusive<f64> {
+        0.0..=TAU
+    }
+
+    fn v_domain(&self) -> RangeInclusive<f64> {
+        0.0..=1.0
+    }
+
+    fn is_u_periodic(&self) -> bool {
+        true
+    }
+
+    fn derivative_u(&self, u: f64, _v: f64) -> Vector3D {
+        let r = self.radius.value();
+        Vector3D::new(-r * u.sin(), 0.0, r * u.cos())
+    }
+
+    fn derivative_v(&self, _u: f64, _v: f64) -> Vector3D {
+        Vector3D::new(0.0, self.height.value(), 0.0)
+    }
+
+    fn parameters(&mut self) -> Vec<SurfaceParameter<'_>> {
+        vec![
+            SurfaceParameter::new("Height", &mut self.height.0, 1.0..=30.0),
+            SurfaceParameter::new("Radius", &mut self.radius.0, 1.0..=20.0),
+        ]
+    }
+
+    fn formulas(&self) -> [String; 3] {
+        let (h, r) = (self.height.value(), self.radius.value());
+        [
+            format!("{r} * cos(u)"),
+            format!("{h} * v"),
+            format!("{r} * sin(u)"),
+        ]
+    }
+}
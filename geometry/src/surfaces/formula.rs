@@ -0,0 +1,144 @@
+use crate::math::expression::{Expression, ExpressionError};
+use crate::primitives::point3d::Point3D;
+use crate::surfaces::{ParametricSurface, SurfaceParameter};
+use std::f64::consts::TAU;
+use std::ops::RangeInclusive;
+
+/// Variables available in the formulas, in evaluation order.
+pub const VARIABLES: [&str; 4] = ["u", "v", "a", "b"];
+
+/// Surface defined by user formulas x(u, v), y(u, v), z(u, v).
+/// `a` and `b` are free parameters, so they can be edited and animated like
+/// the parameters of the built-in surfaces.
+#[derive(Debug, Clone)]
+pub struct FormulaSurface {
+    /// Text of the formulas as edited by the user. Applied by [`Self::compile`].
+    pub inputs: [String; 3],
+
+    pub u_min: f64,
+    pub u_max: f64,
+    pub v_min: f64,
+    pub v_max: f64,
+    pub is_u_periodic: bool,
+    pub is_v_periodic: bool,
+
+    pub a: f64,
+    pub b: f64,
+
+    compiled: [Expression; 3],
+}
+
+impl Default for FormulaSurface {
+    fn default() -> Self {
+        let inputs = [
+            "(a + b * sin(3 * v)) * cos(u)".to_string(),
+            "v".to_string(),
+            "(a + b * sin(3 * v)) * sin(u)".to_string(),
+        ];
+
+        let mut surface = Self {
+            compiled: Self::placeholder(),
+            inputs,
+            u_min: 0.0,
+            u_max: TAU,
+            v_min: 0.0,
+            v_max: 10.0,
+            is_u_periodic: true,
+            is_v_periodic: false,
+            a: 4.0,
+            b: 1.0,
+        };
+        // Default formulas are valid
+        let _ = surface.compile();
+
+        surface
+    }
+}
+
+impl FormulaSurface {
+    /// Formula surface that reproduces the given surface.
+    pub fn from_surface(surface: &dyn ParametricSurface) -> Self {
+        let (u_domain, v_domain) = (surface.u_domain(), surface.v_domain());
+
+        let mut formula = Self {
+            inputs: surface.formulas(),
+            u_min: *u_domain.start(),
+            u_max: *u_domain.end(),
+            v_min: *v_domain.start(),
+            v_max: *v_domain.end(),
+            is_u_periodic: surface.is_u_periodic(),
+            is_v_periodic: surface.is_v_periodic(),
+            ..Default::default()
+        };
+        // Built-in formulas are valid
+        let _ = formula.compile();
+
+        formula
+    }
+
+    /// Parses the inputs. On error the previously compiled formulas stay active.
+    pub fn compile(&mut self) -> Result<(), ExpressionError> {
+        let x = Expression::parse(&self.inputs[0], &VARIABLES)?;
+        let y = Expression::parse(&self.inputs[1], &VARIABLES)?;
+        let z = Expression::parse(&self.inputs[2], &VARIABLES)?;
+
+        self.compiled = [x, y, z];
+
+        Ok(())
+    }
+
+    fn placeholder() -> [Expression; 3] {
+        [
+            Expression::constant(0.0),
+            Expression::constant(0.0),
+            Expression::constant(0.0),
+        ]
+    }
+}
+
+impl ParametricSurface for FormulaSurface {
+    fn name(&self) -> &'static str {
+        "Formula"
+    }
+
+    fn point(&self, u: f64, v: f64) -> Point3D {
+        let values = [u, v, self.a, self.b];
+
+        Point3D::new(
+            self.compiled[0].eval(&values),
+            self.compiled[1].eval(&values),
+            self.compiled[2].eval(&values),
+        )
+    }
+
+    fn u_domain(&self) -> RangeInclusive<f64> {
+        self.u_min..=self.u_max
+    }
+
+    fn v_domain(&self) -> RangeInclusive<f64> {
+        self.v_min..=self.v_max
+    }
+
+    fn is_u_periodic(&self) -> bool {
+        self.is_u_periodic
+    }
+
+    fn is_v_periodic(&self) -> bool {
+        self.is_v_periodic
+    }
+
+    fn parameters(&mut self) -> Vec<SurfaceParameter<'_>> {
+        vec![
+            SurfaceParameter::new("a", &mut self.a, -20.0..=20.0),
+            SurfaceParameter::new("b", &mut self.b, -20.0..=20.0),
+        ]
+    }
+
+    fn formulas(&self) -> [String; 3] {
+        [
+            self.compiled[0].source().to_string(),
+            self.compiled[1].source().to_string(),
+            self.compiled[2].source().to_string(),
+        ]
+    }
+}
@@ -0,0 +1,69 @@
+use crate::primitives::point3d::Point3D;
+use crate::primitives::vector3d::Vector3D;
+use crate::surfaces::{ParametricSurface, SurfaceParameter};
+use crate::units::Centimeter;
+use std::f64::consts::TAU;
+use std::ops::RangeInclusive;
+
+/// u: angle of the rotating ruling, v: signed distance from the axis.
+#[derive(Debug, Clone)]
+pub struct Helicoid {
+    pub radius: Centimeter,
+    /// Rise per full turn.
+    pub pitch: Centimeter,
+    pub turns: f64,
+}
+
+impl Default for Helicoid {
+    fn default() -> Self {
+        Self {
+            radius: Centimeter(5.0),
+            pitch: Centimeter(4.0),
+            turns: 2.0,
+        }
+    }
+}
+
+impl ParametricSurface for Helicoid {
+    fn name(&self) -> &'static str {
+        "Helicoid"
+    }
+
+    fn point(&self, u: f64, v: f64) -> Point3D {
+        Point3D::new(v * u.cos(), self.pitch.value() * u / TAU, v * u.sin())
+    }
+
+    fn u_domain(&self) -> RangeInclusive<f64> {
+        0.0..=TAU * self.turns
+    }
+
+    fn v_domain(&self) -> RangeInclusive<f64> {
+        let r = self.radius.value();
+        -r..=r
+    }
+
+    fn derivative_u(&self, u: f64, v: f64) -> Vector3D {
+        Vector3D::new(-v * u.sin(), self.pitch.value() / TAU, v * u.cos())
+    }
+
+    fn derivative_v(&self, u: f64, _v: f64) -> Vector3D {
+        Vector3D::new(u.cos(), 0.0, u.sin())
+    }
+
+    fn parameters(&mut self) -> Vec<SurfaceParameter<'_>> {
+        vec![
+            SurfaceParameter::new("Radius", &mut self.radius.0, 1.0..=20.0),
+            SurfaceParameter::new("Pitch", &mut self.pitch.0, 0.5..=20.0),
+            SurfaceParameter::new("Turns", &mut self.turns, 0.5..=5.0),
+        ]
+    }
+
+    fn formulas(&self) -> [String; 3] {
+        let pitch = self.pitch.value();
+        [
+            "v * cos(u)".to_string(),
+            format!("{pitch} * u / (2 * pi)"),
+            "v * sin(u)".to_string(),
+        ]
+    }
+}
@@ -0,0 +1,68 @@
+use crate::primitives::point3d::Point3D;
+use crate::surfaces::{ParametricSurface, SurfaceParameter};
+use crate::units::Centimeter;
+use std::f64::consts::TAU;
+use std::ops::RangeInclusive;
+
+/// Hyperboloid of one sheet: x² / a² + z² / a² - y² / c² = 1.
+/// u: angle around the axis, v: height from -h/2 to h/2.
+#[derive(Debug, Clone)]
+pub struct Hyperboloid {
+    /// Radius of the waist.
+    pub radius: Centimeter,
+    /// Steepness of the asymptotic cone.
+    pub c: f64,
+    pub height: Centimeter,
+}
+
+impl Default for Hyperboloid {
+    fn default() -> Self {
+        Self {
+            radius: Centimeter(3.0),
+            c: 3.0,
+            height: Centimeter(12.0),
+        }
+    }
+}
+
+impl ParametricSurface for Hyperboloid {
+    fn name(&self) -> &'static str {
+        "Hyperboloid"
+    }
+
+    fn point(&self, u: f64, v: f64) -> Point3D {
+        let ring = self.radius.value() * (1.0 + (v / self.c).powi(2)).sqrt();
+
+        Point3D::new(ring * u.cos(), v, ring * u.sin())
+    }
+
+    fn u_domain(&self) -> RangeInclusive<f64> {
+        0.0..=TAU
+    }
+
+    fn v_domain(&self) -> RangeInclusive<f64> {
+        let half = self.height.value() / 2.0;
+        -half..=half
+    }
+
+    fn is_u_periodic(&self) -> bool {
+        true
+    }
+
+    fn parameters(&mut self) -> Vec<SurfaceParameter<'_>> {
+        vec![
+            SurfaceParameter::new("Waist Radius", &mut self.radius.0, 0.5..=15.0),
+            SurfaceParameter::new("Steepness (c)", &mut self.c, 0.5..=10.0),
+            SurfaceParameter::new("Height", &mut self.height.0, 1.0..=30.0),
+        ]
+    }
+
+    fn formulas(&self) -> [String; 3] {
+        let (a, c) = (self.radius.value(), self.c);
+        [
+            format!("{a} * sqrt(1 + (v / {c})^2) * cos(u)"),
+            "v".to_string(),
+            format!("{a} * sqrt(1 + (v / {c})^2) * sin(u)"),
+        ]
+    }
+}
@@ -0,0 +1,72 @@
+use crate::primitives::point3d::Point3D;
+use crate::surfaces::{ParametricSurface, SurfaceParameter};
+use crate::units::Centimeter;
+use std::f64::consts::TAU;
+use std::ops::RangeInclusive;
+
+/// "Figure-8" immersion of the Klein bottle.
+/// u: angle around the central circle, v: angle around the figure-8 cross-section.
+#[derive(Debug, Clone)]
+pub struct KleinBottle {
+    pub radius: Centimeter,
+    pub scale: f64,
+}
+
+impl Default for KleinBottle {
+    fn default() -> Self {
+        Self {
+            radius: Centimeter(3.0),
+            scale: 2.0,
+        }
+    }
+}
+
+impl ParametricSurface for KleinBottle {
+    fn name(&self) -> &'static str {
+        "Klein Bottle"
+    }
+
+    fn point(&self, u: f64, v: f64) -> Point3D {
+        let (sin_half, cos_half) = (u / 2.0).sin_cos();
+        let ring = self.radius.value() + cos_half * v.sin() - sin_half * (2.0 * v).sin();
+        let height = sin_half * v.sin() + cos_half * (2.0 * v).sin();
+
+        Point3D::new(
+            self.scale * ring * u.cos(),
+            self.scale * height,
+            self.scale * ring * u.sin(),
+        )
+    }
+
+    fn u_domain(&self) -> RangeInclusive<f64> {
+        0.0..=TAU
+    }
+
+    fn v_domain(&self) -> RangeInclusive<f64> {
+        0.0..=TAU
+    }
+
+    fn is_v_periodic(&self) -> bool {
+        true
+    }
+
+    fn parameters(&mut self) -> Vec<SurfaceParameter<'_>> {
+        vec![
+            SurfaceParameter::new("Radius", &mut self.radius.0, 2.0..=10.0),
+            SurfaceParameter::new("Scale", &mut self.scale, 0.5..=5.0),
+        ]
+    }
+
+    fn formulas(&self) -> [String; 3] {
+        let (r, s) = (self.radius.value(), self.scale);
+        [
+            format!(
+                "{s} * ({r} + cos(u / 2) * sin(v) - sin(u / 2) * sin(2 * v)) * cos(u)"
+            ),
+            format!("{s} * (sin(u / 2) * sin(v) + cos(u / 2) * sin(2 * v))"),
+            format!(
+                "{s} * ({r} + cos(u / 2) * sin(v) - sin(u / 2) * sin(2 * v)) * sin(u)"
+            ),
+        ]
+    }
+}
@@ -0,0 +1,59 @@
+use crate::primitives::point3d::Point3D;
+use crate::surfaces::{ParametricSurface, SurfaceParameter};
+use crate::units::Centimeter;
+use std::f64::consts::TAU;
+use std::ops::RangeInclusive;
+
+/// u: angle around the center line, v: signed distance across the strip.
+/// Not periodic in u: r(2π, v) == r(0, -v), so the seam is drawn on both sides.
+#[derive(Debug, Clone)]
+pub struct MobiusStrip {
+    pub radius: Centimeter,
+    pub width: Centimeter,
+}
+
+impl Default for MobiusStrip {
+    fn default() -> Self {
+        Self {
+            radius: Centimeter(6.0),
+            width: Centimeter(4.0),
+        }
+    }
+}
+
+impl ParametricSurface for MobiusStrip {
+    fn name(&self) -> &'static str {
+        "Möbius Strip"
+    }
+
+    fn point(&self, u: f64, v: f64) -> Point3D {
+        let ring = self.radius.value() + v * (u / 2.0).cos();
+
+        Point3D::new(ring * u.cos(), v * (u / 2.0).sin(), ring * u.sin())
+    }
+
+    fn u_domain(&self) -> RangeInclusive<f64> {
+        0.0..=TAU
+    }
+
+    fn v_domain(&self) -> RangeInclusive<f64> {
+        let half = self.width.value() / 2.0;
+        -half..=half
+    }
+
+    fn parameters(&mut self) -> Vec<SurfaceParameter<'_>> {
+        vec![
+            SurfaceParameter::new("Radius", &mut self.radius.0, 1.0..=20.0),
+            SurfaceParameter::new("Width", &mut self.width.0, 0.5..=10.0),
+        ]
+    }
+
+    fn formulas(&self) -> [String; 3] {
+        let r = self.radius.value();
+        [
+            format!("({r} + v * cos(u / 2)) * cos(u)"),
+            "v * sin(u / 2)".to_string(),
+            format!("({r} + v * cos(u / 2)) * sin(u)"),
+        ]
+    }
+}
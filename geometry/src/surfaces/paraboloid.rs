@@ -0,0 +1,63 @@
+use crate::primitives::point3d::Point3D;
+use crate::surfaces::{ParametricSurface, SurfaceParameter};
+use crate::units::Centimeter;
+use std::f64::consts::TAU;
+use std::ops::RangeInclusive;
+
+/// Elliptic paraboloid of revolution.
+/// u: angle around the axis, v: 0 at the vertex, 1 at the rim.
+#[derive(Debug, Clone)]
+pub struct Paraboloid {
+    /// Radius of the rim.
+    pub radius: Centimeter,
+    pub height: Centimeter,
+}
+
+impl Default for Paraboloid {
+    fn default() -> Self {
+        Self {
+            radius: Centimeter(5.0),
+            height: Centimeter(10.0),
+        }
+    }
+}
+
+impl ParametricSurface for Paraboloid {
+    fn name(&self) -> &'static str {
+        "Paraboloid"
+    }
+
+    fn point(&self, u: f64, v: f64) -> Point3D {
+        let ring = self.radius.value() * v.sqrt();
+
+        Point3D::new(ring * u.cos(), self.height.value() * v, ring * u.sin())
+    }
+
+    fn u_domain(&self) -> RangeInclusive<f64> {
+        0.0..=TAU
+    }
+
+    fn v_domain(&self) -> RangeInclusive<f64> {
+        0.0..=1.0
+    }
+
+    fn is_u_periodic(&self) -> bool {
+        true
+    }
+
+    fn parameters(&mut self) -> Vec<SurfaceParameter<'_>> {
+        vec![
+            SurfaceParameter::new("Rim Radius", &mut self.radius.0, 1.0..=20.0),
+            SurfaceParameter::new("Height", &mut self.height.0, 1.0..=30.0),
+        ]
+    }
+
+    fn formulas(&self) -> [String; 3] {
+        let (r, h) = (self.radius.value(), self.height.value());
+        [
+            format!("{r} * sqrt(v) * cos(u)"),
+            format!("{h} * v"),
+            format!("{r} * sqrt(v) * sin(u)"),
+        ]
+    }
+}
@@ -0,0 +1,71 @@
+use crate::primitives::point3d::Point3D;
+use crate::primitives::vector3d::Vector3D;
+use crate::surfaces::{ParametricSurface, SurfaceParameter};
+use crate::units::Centimeter;
+use std::f64::consts::{FRAC_PI_2, TAU};
+use std::ops::RangeInclusive;
+
+/// u: longitude, v: latitude from the south pole to the north pole.
+#[derive(Debug, Clone)]
+pub struct Sphere {
+    pub radius: Centimeter,
+}
+
+impl Default for Sphere {
+    fn default() -> Self {
+        Self {
+            radius: Centimeter(6.0),
+        }
+    }
+}
+
+impl ParametricSurface for Sphere {
+    fn name(&self) -> &'static str {
+        "Sphere"
+    }
+
+    fn point(&self, u: f64, v: f64) -> Point3D {
+        let r = self.radius.value();
+
+        Point3D::new(r * v.cos() * u.cos(), r * v.sin(), r * v.cos() * u.sin())
+    }
+
+    fn u_domain(&self) -> RangeInclusive<f64> {
+        0.0..=TAU
+    }
+
+    fn v_domain(&self) -> RangeInclusive<f64> {
+        -FRAC_PI_2..=FRAC_PI_2
+    }
+
+    fn is_u_periodic(&self) -> bool {
+        true
+    }
+
+    fn derivative_u(&self, u: f64, v: f64) -> Vector3D {
+        let r = self.radius.value();
+        Vector3D::new(-r * v.cos() * u.sin(), 0.0, r * v.cos() * u.cos())
+    }
+
+    fn derivative_v(&self, u: f64, v: f64) -> Vector3D {
+        let r = self.radius.value();
+        Vector3D::new(-r * v.sin() * u.cos(), r * v.cos(), -r * v.sin() * u.sin())
+    }
+
+    fn parameters(&mut self) -> Vec<SurfaceParameter<'_>> {
+        vec![SurfaceParameter::new(
+            "Radius",
+            &mut self.radius.0,
+            1.0..=20.0,
+        )]
+    }
+
+    fn formulas(&self) -> [String; 3] {
+        let r = self.radius.value();
+        [
+            format!("{r} * cos(v) * cos(u)"),
+            format!("{r} * sin(v)"),
+            format!("{r} * cos(v) * sin(u)"),
+        ]
+    }
+}
@@ -0,0 +1,83 @@
+use crate::primitives::point3d::Point3D;
+use crate::primitives::vector3d::Vector3D;
+use crate::surfaces::{ParametricSurface, SurfaceParameter};
+use crate::units::Centimeter;
+use std::f64::consts::TAU;
+use std::ops::RangeInclusive;
+
+/// u: angle around the central axis, v: angle around the tube.
+#[derive(Debug, Clone)]
+pub struct Torus {
+    /// Distance from the center of the torus to the center of the tube.
+    pub major_radius: Centimeter,
+    /// Radius of the tube.
+    pub minor_radius: Centimeter,
+}
+
+impl Default for Torus {
+    fn default() -> Self {
+        Self {
+            major_radius: Centimeter(6.0),
+            minor_radius: Centimeter(2.0),
+        }
+    }
+}
+
+impl ParametricSurface for Torus {
+    fn name(&self) -> &'static str {
+        "Torus"
+    }
+
+    fn point(&self, u: f64, v: f64) -> Point3D {
+        let (major, minor) = (self.major_radius.value(), self.minor_radius.value());
+        let ring = major + minor * v.cos();
+
+        Point3D::new(ring * u.cos(), minor * v.sin(), ring * u.sin())
+    }
+
+    fn u_domain(&self) -> RangeInclusive<f64> {
+        0.0..=TAU
+    }
+
+    fn v_domain(&self) -> RangeInclusive<f64> {
+        0.0..=TAU
+    }
+
+    fn is_u_periodic(&self) -> bool {
+        true
+    }
+
+    fn is_v_periodic(&self) -> bool {
+        true
+    }
+
+    fn derivative_u(&self, u: f64, v: f64) -> Vector3D {
+        let ring = self.major_radius.value() + self.minor_radius.value() * v.cos();
+        Vector3D::new(-ring * u.sin(), 0.0, ring * u.cos())
+    }
+
+    fn derivative_v(&self, u: f64, v: f64) -> Vector3D {
+        let minor = self.minor_radius.value();
+        Vector3D::new(
+            -minor * v.sin() * u.cos(),
+            minor * v.cos(),
+            -minor * v.sin() * u.sin(),
+        )
+    }
+
+    fn parameters(&mut self) -> Vec<SurfaceParameter<'_>> {
+        vec![
+            SurfaceParameter::new("Major Radius", &mut self.major_radius.0, 1.0..=20.0),
+            SurfaceParameter::new("Minor Radius", &mut self.minor_radius.0, 0.5..=10.0),
+        ]
+    }
+
+    fn formulas(&self) -> [String; 3] {
+        let (major, minor) = (self.major_radius.value(), self.minor_radius.value());
+        [
+            format!("({major} + {minor} * cos(v)) * cos(u)"),
+            format!("{minor} * sin(v)"),
+            format!("({major} + {minor} * cos(v)) * sin(u)"),
+        ]
+    }
+}
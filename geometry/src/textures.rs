@@ -0,0 +1,94 @@
+use crate::figures::contour::Contour;
+use crate::primitives::line2d::Line2D;
+use crate::primitives::point2d::Point2D;
+use crate::smooth::ferguson::FergusonCurve;
+use crate::textures::checkerboard::Checkerboard;
+use crate::textures::spiral::Spiral;
+use crate::textures::stripes::Stripes;
+use crate::textures::text::TextOutline;
+use egui::Stroke;
+
+pub mod checkerboard;
+pub mod font;
+pub mod spiral;
+pub mod stripes;
+pub mod svg;
+pub mod text;
+
+/// Where the 2D line art of a texture comes from.
+/// Coordinates are arbitrary: the surface fits the bounding box of the lines into UV space.
+#[derive(Debug, Default, Clone)]
+pub enum TextureSource {
+    #[default]
+    Shark,
+    Checkerboard(Checkerboard),
+    Stripes(Stripes),
+    Spiral(Spiral),
+    Text(TextOutline),
+    Imported(ImportedTexture),
+}
+
+impl TextureSource {
+    /// Procedural sources with their default parameters, in the order shown in the UI.
+    pub fn catalog() -> Vec<Self> {
+        vec![
+            Self::Shark,
+            Self::Checkerboard(Checkerboard::default()),
+            Self::Stripes(Stripes::default()),
+            Self::Spiral(Spiral::default()),
+            Self::Text(TextOutline::default()),
+        ]
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Shark => "Shark Contour",
+            Self::Checkerboard(_) => "Checkerboard",
+            Self::Stripes(_) => "Stripes",
+            Self::Spiral(_) => "Spiral",
+            Self::Text(_) => "Text",
+            Self::Imported(imported) => &imported.name,
+        }
+    }
+
+    pub fn lines(&self) -> Vec<Line2D<Point2D>> {
+        match self {
+            Self::Shark => shark(),
+            Self::Checkerboard(checkerboard) => checkerboard.lines(),
+            Self::Stripes(stripes) => stripes.lines(),
+            Self::Spiral(spiral) => spiral.lines(),
+            Self::Text(text) => text.lines(),
+            Self::Imported(imported) => imported.lines.clone(),
+        }
+    }
+}
+
+fn shark() -> Vec<Line2D<Point2D>> {
+    let contour = Contour {
+        curve: FergusonCurve {
+            knots: Contour::default_knots(),
+            is_closed: true,
+            step: 0.01,
+            style: Default::default(),
+        },
+        is_tooltips_mode_enabled: false,
+        is_skeleton_mode_enabled: false,
+    };
+
+    contour.lines()
+}
+
+/// Line art loaded from a file.
+#[derive(Debug, Clone)]
+pub struct ImportedTexture {
+    pub name: String,
+    pub lines: Vec<Line2D<Point2D>>,
+}
+
+/// Connects consecutive points with lines.
+pub fn polyline(points: &[Point2D]) -> Vec<Line2D<Point2D>> {
+    points
+        .windows(2)
+        .map(|pair| Line2D::new(pair[0], pair[1], Stroke::default()))
+        .collect()
+}
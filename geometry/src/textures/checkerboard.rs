@@ -0,0 +1,71 @@
+use crate::primitives::line2d::Line2D;
+use crate::primitives::point2d::Point2D;
+use egui::Stroke;
+
+/// Square grid on the unit square with the dark cells hatched diagonally.
+#[derive(Debug, Clone)]
+pub struct Checkerboard {
+    /// Cells along each side.
+    pub cells: usize,
+    /// Hatching lines per dark cell.
+    pub hatching: usize,
+}
+
+impl Default for Checkerboard {
+    fn default() -> Self {
+        Self {
+            cells: 8,
+            hatching: 4,
+        }
+    }
+}
+
+impl Checkerboard {
+    pub fn lines(&self) -> Vec<Line2D<Point2D>> {
+        let cells = self.cells.max(1);
+        let size = 1.0 / cells as f64;
+        let mut lines = vec![];
+
+        for k in 0..=cells {
+            let position = k as f64 * size;
+            lines.push(line((position, 0.0), (position, 1.0)));
+            lines.push(line((0.0, position), (1.0, position)));
+        }
+
+        for row in 0..cells {
+            for column in (0..cells).filter(|column| (row + column) % 2 == 0) {
+                let (x, y) = (column as f64 * size, row as f64 * size);
+                lines.extend(self.hatch(x, y, size));
+            }
+        }
+
+        lines
+    }
+
+    /// Lines parallel to the cell diagonal running from its bottom-left corner.
+    fn hatch(&self, x: f64, y: f64, size: f64) -> Vec<Line2D<Point2D>> {
+        let count = self.hatching;
+        let step = 2.0 * size / (count + 1) as f64;
+
+        (1..=count)
+            .map(|k| {
+                // Offset along x + y of the hatch line from the cell corner
+                let offset = k as f64 * step;
+                if offset <= size {
+                    line((x, y + offset), (x + offset, y))
+                } else {
+                    let rest = offset - size;
+                    line((x + rest, y + size), (x + size, y + rest))
+                }
+            })
+            .collect()
+    }
+}
+
+fn line(start: (f64, f64), end: (f64, f64)) -> Line2D<Point2D> {
+    Line2D::new(
+        Point2D::new(start.0, start.1),
+        Point2D::new(end.0, end.1),
+        Stroke::default(),
+    )
+}
@@ -0,0 +1,239 @@
+//! Single-stroke font on a 4 × 6 grid: x ∈ [0, 4], baseline at y = 0, cap height 6.
+
+pub type GlyphStroke = &'static [(f64, f64)];
+
+pub const ADVANCE: f64 = 6.0;
+pub const LINE_HEIGHT: f64 = 9.0;
+
+const O: GlyphStroke = &[
+    (1.0, 0.0),
+    (0.0, 1.0),
+    (0.0, 5.0),
+    (1.0, 6.0),
+    (3.0, 6.0),
+    (4.0, 5.0),
+    (4.0, 1.0),
+    (3.0, 0.0),
+    (1.0, 0.0),
+];
+const P: GlyphStroke = &[
+    (0.0, 0.0),
+    (0.0, 6.0),
+    (3.0, 6.0),
+    (4.0, 5.0),
+    (4.0, 4.0),
+    (3.0, 3.0),
+    (0.0, 3.0),
+];
+
+/// Strokes of a character. Lowercase letters use the uppercase glyphs.
+/// Unknown characters have no strokes but still take up space.
+pub fn glyph(c: char) -> &'static [GlyphStroke] {
+    match c.to_ascii_uppercase() {
+        'A' => &[
+            &[(0.0, 0.0), (0.0, 4.0), (2.0, 6.0), (4.0, 4.0), (4.0, 0.0)],
+            &[(0.0, 3.0), (4.0, 3.0)],
+        ],
+        'B' => &[
+            &[
+                (0.0, 0.0),
+                (0.0, 6.0),
+                (3.0, 6.0),
+                (4.0, 5.0),
+                (4.0, 4.0),
+                (3.0, 3.0),
+                (0.0, 3.0),
+            ],
+            &[(3.0, 3.0), (4.0, 2.0), (4.0, 1.0), (3.0, 0.0), (0.0, 0.0)],
+        ],
+        'C' => &[&[
+            (4.0, 5.0),
+            (3.0, 6.0),
+            (1.0, 6.0),
+            (0.0, 5.0),
+            (0.0, 1.0),
+            (1.0, 0.0),
+            (3.0, 0.0),
+            (4.0, 1.0),
+        ]],
+        'D' => &[&[
+            (0.0, 0.0),
+            (0.0, 6.0),
+            (2.0, 6.0),
+            (4.0, 4.0),
+            (4.0, 2.0),
+            (2.0, 0.0),
+            (0.0, 0.0),
+        ]],
+        'E' => &[
+            &[(4.0, 6.0), (0.0, 6.0), (0.0, 0.0), (4.0, 0.0)],
+            &[(0.0, 3.0), (3.0, 3.0)],
+        ],
+        'F' => &[
+            &[(4.0, 6.0), (0.0, 6.0), (0.0, 0.0)],
+            &[(0.0, 3.0), (3.0, 3.0)],
+        ],
+        'G' => &[&[
+            (4.0, 5.0),
+            (3.0, 6.0),
+            (1.0, 6.0),
+            (0.0, 5.0),
+            (0.0, 1.0),
+            (1.0, 0.0),
+            (3.0, 0.0),
+            (4.0, 1.0),
+            (4.0, 3.0),
+            (2.0, 3.0),
+        ]],
+        'H' => &[
+            &[(0.0, 0.0), (0.0, 6.0)],
+            &[(4.0, 0.0), (4.0, 6.0)],
+            &[(0.0, 3.0), (4.0, 3.0)],
+        ],
+        'I' => &[
+            &[(1.0, 6.0), (3.0, 6.0)],
+            &[(2.0, 6.0), (2.0, 0.0)],
+            &[(1.0, 0.0), (3.0, 0.0)],
+        ],
+        'J' => &[&[(4.0, 6.0), (4.0, 1.0), (3.0, 0.0), (1.0, 0.0), (0.0, 1.0)]],
+        'K' => &[
+            &[(0.0, 0.0), (0.0, 6.0)],
+            &[(4.0, 6.0), (0.0, 2.0)],
+            &[(1.0, 3.0), (4.0, 0.0)],
+        ],
+        'L' => &[&[(0.0, 6.0), (0.0, 0.0), (4.0, 0.0)]],
+        'M' => &[&[(0.0, 0.0), (0.0, 6.0), (2.0, 3.0), (4.0, 6.0), (4.0, 0.0)]],
+        'N' => &[&[(0.0, 0.0), (0.0, 6.0), (4.0, 0.0), (4.0, 6.0)]],
+        'O' => &[O],
+        'P' => &[P],
+        'Q' => &[O, &[(2.0, 2.0), (4.0, 0.0)]],
+        'R' => &[P, &[(2.0, 3.0), (4.0, 0.0)]],
+        'S' => &[&[
+            (4.0, 5.0),
+            (3.0, 6.0),
+            (1.0, 6.0),
+            (0.0, 5.0),
+            (0.0, 4.0),
+            (1.0, 3.0),
+            (3.0, 3.0),
+            (4.0, 2.0),
+            (4.0, 1.0),
+            (3.0, 0.0),
+            (1.0, 0.0),
+            (0.0, 1.0),
+        ]],
+        'T' => &[&[(0.0, 6.0), (4.0, 6.0)], &[(2.0, 6.0), (2.0, 0.0)]],
+        'U' => &[&[
+            (0.0, 6.0),
+            (0.0, 1.0),
+            (1.0, 0.0),
+            (3.0, 0.0),
+            (4.0, 1.0),
+            (4.0, 6.0),
+        ]],
+        'V' => &[&[(0.0, 6.0), (2.0, 0.0), (4.0, 6.0)]],
+        'W' => &[&[(0.0, 6.0), (1.0, 0.0), (2.0, 4.0), (3.0, 0.0), (4.0, 6.0)]],
+        'X' => &[&[(0.0, 6.0), (4.0, 0.0)], &[(0.0, 0.0), (4.0, 6.0)]],
+        'Y' => &[
+            &[(0.0, 6.0), (2.0, 3.0), (4.0, 6.0)],
+            &[(2.0, 3.0), (2.0, 0.0)],
+        ],
+        'Z' => &[&[(0.0, 6.0), (4.0, 6.0), (0.0, 0.0), (4.0, 0.0)]],
+        '0' => &[O, &[(0.0, 1.0), (4.0, 5.0)]],
+        '1' => &[
+            &[(1.0, 5.0), (2.0, 6.0), (2.0, 0.0)],
+            &[(1.0, 0.0), (3.0, 0.0)],
+        ],
+        '2' => &[&[
+            (0.0, 5.0),
+            (1.0, 6.0),
+            (3.0, 6.0),
+            (4.0, 5.0),
+            (4.0, 4.0),
+            (0.0, 0.0),
+            (4.0, 0.0),
+        ]],
+        '3' => &[
+            &[
+                (0.0, 5.0),
+                (1.0, 6.0),
+                (3.0, 6.0),
+                (4.0, 5.0),
+                (4.0, 4.0),
+                (3.0, 3.0),
+                (1.0, 3.0),
+            ],
+            &[
+                (3.0, 3.0),
+                (4.0, 2.0),
+                (4.0, 1.0),
+                (3.0, 0.0),
+                (1.0, 0.0),
+                (0.0, 1.0),
+            ],
+        ],
+        '4' => &[&[(3.0, 0.0), (3.0, 6.0), (0.0, 2.0), (4.0, 2.0)]],
+        '5' => &[&[
+            (4.0, 6.0),
+            (0.0, 6.0),
+            (0.0, 3.0),
+            (3.0, 3.0),
+            (4.0, 2.0),
+            (4.0, 1.0),
+            (3.0, 0.0),
+            (0.0, 0.0),
+        ]],
+        '6' => &[&[
+            (4.0, 5.0),
+            (3.0, 6.0),
+            (1.0, 6.0),
+            (0.0, 5.0),
+            (0.0, 1.0),
+            (1.0, 0.0),
+            (3.0, 0.0),
+            (4.0, 1.0),
+            (4.0, 2.0),
+            (3.0, 3.0),
+            (0.0, 3.0),
+        ]],
+        '7' => &[&[(0.0, 6.0), (4.0, 6.0), (1.0, 0.0)]],
+        '8' => &[&[
+            (1.0, 3.0),
+            (0.0, 4.0),
+            (0.0, 5.0),
+            (1.0, 6.0),
+            (3.0, 6.0),
+            (4.0, 5.0),
+            (4.0, 4.0),
+            (3.0, 3.0),
+            (1.0, 3.0),
+            (0.0, 2.0),
+            (0.0, 1.0),
+            (1.0, 0.0),
+            (3.0, 0.0),
+            (4.0, 1.0),
+            (4.0, 2.0),
+            (3.0, 3.0),
+        ]],
+        '9' => &[&[
+            (4.0, 3.0),
+            (1.0, 3.0),
+            (0.0, 4.0),
+            (0.0, 5.0),
+            (1.0, 6.0),
+            (3.0, 6.0),
+            (4.0, 5.0),
+            (4.0, 1.0),
+            (3.0, 0.0),
+            (1.0, 0.0),
+            (0.0, 1.0),
+        ]],
+        '-' => &[&[(1.0, 3.0), (3.0, 3.0)]],
+        '+' => &[&[(1.0, 3.0), (3.0, 3.0)], &[(2.0, 2.0), (2.0, 4.0)]],
+        '.' => &[&[(2.0, 0.0), (2.0, 0.5)]],
+        ',' => &[&[(2.0, 0.5), (1.5, -1.0)]],
+        '!' => &[&[(2.0, 6.0), (2.0, 2.0)], &[(2.0, 0.0), (2.0, 0.5)]],
+        '/' => &[&[(0.0, 0.0), (4.0, 6.0)]],
+        _ => &[],
+    }
+}
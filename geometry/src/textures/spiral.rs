@@ -0,0 +1,46 @@
+use crate::primitives::line2d::Line2D;
+use crate::primitives::point2d::Point2D;
+use crate::textures::polyline;
+use std::f64::consts::PI;
+
+/// Archimedean spiral r = θ / (2π · turns) with several arms.
+#[derive(Debug, Clone)]
+pub struct Spiral {
+    pub turns: f64,
+    pub arms: usize,
+    pub samples_per_turn: usize,
+}
+
+impl Default for Spiral {
+    fn default() -> Self {
+        Self {
+            turns: 4.0,
+            arms: 1,
+            samples_per_turn: 48,
+        }
+    }
+}
+
+impl Spiral {
+    pub fn lines(&self) -> Vec<Line2D<Point2D>> {
+        let turns = self.turns.max(0.1);
+        let samples =
+            ((turns * self.samples_per_turn.max(8) as f64).ceil() as usize).max(2);
+        let arms = self.arms.max(1);
+
+        (0..arms)
+            .flat_map(|arm| {
+                let phase = 2.0 * PI * arm as f64 / arms as f64;
+                let points: Vec<Point2D> = (0..=samples)
+                    .map(|k| {
+                        let t = k as f64 / samples as f64;
+                        let angle = 2.0 * PI * turns * t + phase;
+                        Point2D::new(t * angle.cos(), t * angle.sin())
+                    })
+                    .collect();
+
+                polyline(&points)
+            })
+            .collect()
+    }
+}
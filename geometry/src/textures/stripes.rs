@@ -0,0 +1,82 @@
+use crate::math::angle::Angle;
+use crate::primitives::line2d::Line2D;
+use crate::primitives::point2d::Point2D;
+use egui::Stroke;
+
+/// Parallel lines across the unit square.
+#[derive(Debug, Clone)]
+pub struct Stripes {
+    pub count: usize,
+    /// Direction of the stripes, in degrees from the U axis.
+    pub angle: f64,
+}
+
+impl Default for Stripes {
+    fn default() -> Self {
+        Self {
+            count: 12,
+            angle: 45.0,
+        }
+    }
+}
+
+impl Stripes {
+    pub fn lines(&self) -> Vec<Line2D<Point2D>> {
+        let angle = Angle::from_degree(self.angle).radian();
+        let direction = (angle.cos(), angle.sin());
+        let normal = (-direction.1, direction.0);
+
+        // Stripes are spread across the projection of the square onto the normal
+        let corners = [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (1.0, 1.0)];
+        let projections = corners.map(|(x, y)| x * normal.0 + y * normal.1);
+        let low = projections.iter().copied().fold(f64::INFINITY, f64::min);
+        let high = projections
+            .iter()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max);
+
+        let count = self.count.max(1);
+        (1..=count)
+            .filter_map(|k| {
+                let distance = low + (high - low) * k as f64 / (count + 1) as f64;
+                let origin = (normal.0 * distance, normal.1 * distance);
+                clip_to_unit_square(origin, direction)
+            })
+            .map(|(start, end)| {
+                Line2D::new(
+                    Point2D::new(start.0, start.1),
+                    Point2D::new(end.0, end.1),
+                    Stroke::default(),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Part of the infinite line origin + t * direction inside [0, 1]²
+/// (Liang–Barsky clipping).
+fn clip_to_unit_square(
+    origin: (f64, f64), direction: (f64, f64),
+) -> Option<((f64, f64), (f64, f64))> {
+    let (mut t_min, mut t_max) = (f64::NEG_INFINITY, f64::INFINITY);
+
+    for (position, delta) in [(origin.0, direction.0), (origin.1, direction.1)] {
+        if delta.abs() < 1e-12 {
+            if !(0.0..=1.0).contains(&position) {
+                return None;
+            }
+            continue;
+        }
+
+        let (t1, t2) = ((0.0 - position) / delta, (1.0 - position) / delta);
+        t_min = t_min.max(t1.min(t2));
+        t_max = t_max.min(t1.max(t2));
+    }
+
+    if t_min >= t_max {
+        return None;
+    }
+
+    let at = |t: f64| (origin.0 + direction.0 * t, origin.1 + direction.1 * t);
+    Some((at(t_min), at(t_max)))
+}
@@ -0,0 +1,375 @@
+use crate::primitives::line2d::Line2D;
+use crate::primitives::point2d::Point2D;
+use crate::textures::polyline;
+use derive_more::Display;
+use std::f64::consts::PI;
+
+/// Segments used to flatten one curve command.
+const CURVE_SEGMENTS: usize = 16;
+
+#[derive(Debug, Display, PartialEq)]
+pub enum SvgError {
+    #[display("No path data found.")]
+    NoPaths,
+
+    #[display("Unknown path command '{_0}'.")]
+    UnknownCommand(char),
+
+    #[display("Path data must start with a move command.")]
+    MissingMoveTo,
+
+    #[display("Expected a number after '{_0}'.")]
+    ExpectedNumber(char),
+
+    #[display("Unexpected number after '{_0}'.")]
+    UnexpectedNumber(char),
+}
+
+impl std::error::Error for SvgError {}
+
+/// Reads every `d="…"` attribute of an SVG document.
+/// Input that is not markup is treated as bare path data.
+/// SVG's Y axis points down, so the result is flipped vertically.
+pub fn parse_document(source: &str) -> Result<Vec<Line2D<Point2D>>, SvgError> {
+    let paths = path_attributes(source);
+
+    let mut lines = vec![];
+    if paths.is_empty() {
+        // A document of other shapes only has nothing to read
+        if source.trim_start().starts_with('<') {
+            return Err(SvgError::NoPaths);
+        }
+        lines.extend(parse_path(source)?);
+    } else {
+        for path in paths {
+            lines.extend(parse_path(path)?);
+        }
+    }
+
+    if lines.is_empty() {
+        return Err(SvgError::NoPaths);
+    }
+
+    Ok(lines)
+}
+
+fn path_attributes(source: &str) -> Vec<&str> {
+    let mut paths = vec![];
+    let mut rest = source;
+
+    while let Some(index) = rest.find("d=") {
+        let is_attribute = rest[..index]
+            .chars()
+            .next_back()
+            .is_some_and(char::is_whitespace);
+        rest = &rest[index + 2..];
+        if !is_attribute {
+            continue;
+        }
+
+        let Some(quote) = rest.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+            continue;
+        };
+        rest = &rest[1..];
+        let Some(end) = rest.find(quote) else {
+            break;
+        };
+        paths.push(&rest[..end]);
+        rest = &rest[end + 1..];
+    }
+
+    paths
+}
+
+/// Parses SVG path data (M, L, H, V, C, S, Q, T, A, Z in both absolute and relative forms).
+pub fn parse_path(data: &str) -> Result<Vec<Line2D<Point2D>>, SvgError> {
+    let tokens = tokenize(data)?;
+    let mut lines = vec![];
+
+    let mut current = (0.0, 0.0);
+    let mut start = (0.0, 0.0);
+    // Reflected control point for S and T
+    let mut last_control: Option<(char, (f64, f64))> = None;
+    let mut subpath: Vec<(f64, f64)> = vec![];
+
+    let mut index = 0;
+    let mut command: Option<char> = None;
+
+    while index < tokens.len() {
+        let letter = match tokens[index] {
+            Token::Command(c) => {
+                index += 1;
+                c
+            },
+            // Implicit repetition of the previous command
+            Token::Number(_) => match command {
+                Some('M') => 'L',
+                Some('m') => 'l',
+                // Z takes no numbers, so nothing can repeat it
+                Some(c @ ('Z' | 'z')) => return Err(SvgError::UnexpectedNumber(c)),
+                Some(c) => c,
+                None => return Err(SvgError::MissingMoveTo),
+            },
+        };
+        if command.is_none() && !letter.eq_ignore_ascii_case(&'M') {
+            return Err(SvgError::MissingMoveTo);
+        }
+        command = Some(letter);
+
+        let relative = letter.is_ascii_lowercase();
+        let origin = if relative { current } else { (0.0, 0.0) };
+        let mut numbers = |count: usize| -> Result<Vec<f64>, SvgError> {
+            let mut values = Vec::with_capacity(count);
+            for _ in 0..count {
+                match tokens.get(index) {
+                    Some(Token::Number(value)) => values.push(*value),
+                    _ => return Err(SvgError::ExpectedNumber(letter)),
+                }
+                index += 1;
+            }
+            Ok(values)
+        };
+        let point = |x: f64, y: f64| (origin.0 + x, origin.1 + y);
+
+        let mut control = None;
+        match letter.to_ascii_uppercase() {
+            'M' => {
+                let n = numbers(2)?;
+                flush(&mut subpath, &mut lines);
+                current = point(n[0], n[1]);
+                start = current;
+                subpath.push(current);
+            },
+            'L' => {
+                let n = numbers(2)?;
+                current = point(n[0], n[1]);
+                subpath.push(current);
+            },
+            'H' => {
+                let n = numbers(1)?;
+                current = (origin.0 + n[0], current.1);
+                subpath.push(current);
+            },
+            'V' => {
+                let n = numbers(1)?;
+                current = (current.0, origin.1 + n[0]);
+                subpath.push(current);
+            },
+            'C' | 'S' => {
+                let is_smooth = letter.eq_ignore_ascii_case(&'S');
+                let n = numbers(if is_smooth { 4 } else { 6 })?;
+                let (first, rest) = if is_smooth {
+                    (reflect(last_control, 'C', current), &n[..])
+                } else {
+                    (point(n[0], n[1]), &n[2..])
+                };
+                let second = point(rest[0], rest[1]);
+                let end = point(rest[2], rest[3]);
+
+                for k in 1..=CURVE_SEGMENTS {
+                    let t = k as f64 / CURVE_SEGMENTS as f64;
+                    subpath.push(cubic(current, first, second, end, t));
+                }
+                control = Some(('C', second));
+                current = end;
+            },
+            'Q' | 'T' => {
+                let is_smooth = letter.eq_ignore_ascii_case(&'T');
+                let n = numbers(if is_smooth { 2 } else { 4 })?;
+                let (middle, rest) = if is_smooth {
+                    (reflect(last_control, 'Q', current), &n[..])
+                } else {
+                    (point(n[0], n[1]), &n[2..])
+                };
+                let end = point(rest[0], rest[1]);
+
+                for k in 1..=CURVE_SEGMENTS {
+                    let t = k as f64 / CURVE_SEGMENTS as f64;
+                    subpath.push(quadratic(current, middle, end, t));
+                }
+                control = Some(('Q', middle));
+                current = end;
+            },
+            'A' => {
+                let n = numbers(7)?;
+                let end = point(n[5], n[6]);
+                subpath.extend(arc(
+                    current,
+                    end,
+                    (n[0], n[1]),
+                    n[2],
+                    n[3] != 0.0,
+                    n[4] != 0.0,
+                ));
+                current = end;
+            },
+            'Z' => {
+                subpath.push(start);
+                flush(&mut subpath, &mut lines);
+                current = start;
+                subpath.push(current);
+            },
+            other => return Err(SvgError::UnknownCommand(other)),
+        }
+        last_control = control;
+    }
+    flush(&mut subpath, &mut lines);
+
+    Ok(lines)
+}
+
+fn flush(subpath: &mut Vec<(f64, f64)>, lines: &mut Vec<Line2D<Point2D>>) {
+    let points: Vec<Point2D> = subpath
+        .drain(..)
+        .map(|(x, y)| Point2D::new(x, -y))
+        .collect();
+
+    lines.extend(polyline(&points));
+}
+
+fn reflect(
+    last_control: Option<(char, (f64, f64))>, kind: char, current: (f64, f64),
+) -> (f64, f64) {
+    match last_control {
+        Some((last_kind, control)) if last_kind == kind => {
+            (2.0 * current.0 - control.0, 2.0 * current.1 - control.1)
+        },
+        _ => current,
+    }
+}
+
+fn cubic(
+    p0: (f64, f64), p1: (f64, f64), p2: (f64, f64), p3: (f64, f64), t: f64,
+) -> (f64, f64) {
+    let s = 1.0 - t;
+    let (a, b, c, d) = (s * s * s, 3.0 * s * s * t, 3.0 * s * t * t, t * t * t);
+
+    (
+        a * p0.0 + b * p1.0 + c * p2.0 + d * p3.0,
+        a * p0.1 + b * p1.1 + c * p2.1 + d * p3.1,
+    )
+}
+
+fn quadratic(p0: (f64, f64), p1: (f64, f64), p2: (f64, f64), t: f64) -> (f64, f64) {
+    let s = 1.0 - t;
+    let (a, b, c) = (s * s, 2.0 * s * t, t * t);
+
+    (
+        a * p0.0 + b * p1.0 + c * p2.0,
+        a * p0.1 + b * p1.1 + c * p2.1,
+    )
+}
+
+/// Elliptical arc, converted from endpoint to center parameterization
+/// as described in the SVG specification, appendix B.2.4.
+fn arc(
+    from: (f64, f64), to: (f64, f64), radii: (f64, f64), rotation: f64, large_arc: bool,
+    sweep: bool,
+) -> Vec<(f64, f64)> {
+    let (mut rx, mut ry) = (radii.0.abs(), radii.1.abs());
+    if rx < 1e-12 || ry < 1e-12 || from == to {
+        return vec![to];
+    }
+
+    let phi = rotation.to_radians();
+    let (sin, cos) = phi.sin_cos();
+
+    let dx = (from.0 - to.0) / 2.0;
+    let dy = (from.1 - to.1) / 2.0;
+    let x1 = cos * dx + sin * dy;
+    let y1 = -sin * dx + cos * dy;
+
+    // Radii too small to reach the end point are scaled up
+    let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+
+    let numerator = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+    let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+    let mut factor = (numerator / denominator).max(0.0).sqrt();
+    if large_arc == sweep {
+        factor = -factor;
+    }
+
+    let cx1 = factor * rx * y1 / ry;
+    let cy1 = -factor * ry * x1 / rx;
+    let cx = cos * cx1 - sin * cy1 + (from.0 + to.0) / 2.0;
+    let cy = sin * cx1 + cos * cy1 + (from.1 + to.1) / 2.0;
+
+    let angle = |ux: f64, uy: f64| uy.atan2(ux);
+    let theta = angle((x1 - cx1) / rx, (y1 - cy1) / ry);
+    let mut delta = angle((-x1 - cx1) / rx, (-y1 - cy1) / ry) - theta;
+    if sweep && delta < 0.0 {
+        delta += 2.0 * PI;
+    } else if !sweep && delta > 0.0 {
+        delta -= 2.0 * PI;
+    }
+
+    (1..=CURVE_SEGMENTS)
+        .map(|k| {
+            let t = theta + delta * k as f64 / CURVE_SEGMENTS as f64;
+            let (x, y) = (rx * t.cos(), ry * t.sin());
+            (cos * x - sin * y + cx, sin * x + cos * y + cy)
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Token {
+    Command(char),
+    Number(f64),
+}
+
+fn tokenize(data: &str) -> Result<Vec<Token>, SvgError> {
+    let chars: Vec<char> = data.chars().collect();
+    let mut tokens = vec![];
+    let mut index = 0;
+
+    while index < chars.len() {
+        let c = chars[index];
+
+        if c.is_whitespace() || c == ',' {
+            index += 1;
+        } else if c.is_ascii_alphabetic() && c != 'e' && c != 'E' {
+            if !"MmLlHhVvCcSsQqTtAaZz".contains(c) {
+                return Err(SvgError::UnknownCommand(c));
+            }
+            tokens.push(Token::Command(c));
+            index += 1;
+        } else {
+            // Numbers may follow each other without separators: "1-2", "0.5.5"
+            let start = index;
+            let mut has_dot = false;
+            if chars[index] == '-' || chars[index] == '+' {
+                index += 1;
+            }
+            while index < chars.len() {
+                let d = chars[index];
+                if d.is_ascii_digit() {
+                    index += 1;
+                } else if d == '.' && !has_dot {
+                    has_dot = true;
+                    index += 1;
+                } else if (d == 'e' || d == 'E') && index > start {
+                    index += 1;
+                    if index < chars.len() && (chars[index] == '-' || chars[index] == '+')
+                    {
+                        index += 1;
+                    }
+                } else {
+                    break;
+                }
+            }
+
+            let text: String = chars[start..index].iter().collect();
+            let value = text
+                .parse::<f64>()
+                .map_err(|_| SvgError::ExpectedNumber(c))?;
+            tokens.push(Token::Number(value));
+        }
+    }
+
+    Ok(tokens)
+}
@@ -0,0 +1,42 @@
+use crate::primitives::line2d::Line2D;
+use crate::primitives::point2d::Point2D;
+use crate::textures::font::{self, ADVANCE, LINE_HEIGHT};
+use crate::textures::polyline;
+
+/// Text drawn with the built-in single-stroke font. Supports several lines.
+#[derive(Debug, Clone)]
+pub struct TextOutline {
+    pub text: String,
+}
+
+impl Default for TextOutline {
+    fn default() -> Self {
+        Self {
+            text: "LAB 6".to_string(),
+        }
+    }
+}
+
+impl TextOutline {
+    pub fn lines(&self) -> Vec<Line2D<Point2D>> {
+        let mut lines = vec![];
+
+        for (row, text_line) in self.text.lines().enumerate() {
+            let baseline = -(row as f64) * LINE_HEIGHT;
+
+            for (column, c) in text_line.chars().enumerate() {
+                let left = column as f64 * ADVANCE;
+
+                for stroke in font::glyph(c) {
+                    let points: Vec<Point2D> = stroke
+                        .iter()
+                        .map(|(x, y)| Point2D::new(left + x, baseline + y))
+                        .collect();
+                    lines.extend(polyline(&points));
+                }
+            }
+        }
+
+        lines
+    }
+}
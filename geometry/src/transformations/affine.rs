@@ -1,9 +1,17 @@
+use crate::math::angle::Angle;
 use crate::primitives::line2d::Line2D;
 use crate::primitives::point2d::Point2D;
+use crate::primitives::point3d::Point3D;
+use crate::transformations::correspondence::{CorrespondenceError, solve_affine};
 use crate::units::Centimeter;
-use nalgebra::Matrix3;
+use nalgebra::{Matrix3, Matrix4, RowVector3};
 
+pub mod reflection;
+pub mod reflection3d;
 pub mod scaling;
+pub mod scaling3d;
+pub mod shear;
+pub mod shear3d;
 pub mod symmetry;
 
 #[derive(Debug)]
@@ -34,6 +42,24 @@ impl Default for Affine {
 }
 
 impl Affine {
+    /// Enabled map that takes the three source points to the three targets.
+    pub fn from_correspondences(
+        source: &[Point2D; 3], target: &[Point2D; 3],
+    ) -> Result<Self, CorrespondenceError> {
+        let matrix = solve_affine(source, target)?;
+
+        Ok(Self {
+            is_enabled: true,
+
+            xx: Centimeter(matrix[(0, 0)]),
+            xy: Centimeter(matrix[(0, 1)]),
+            yx: Centimeter(matrix[(1, 0)]),
+            yy: Centimeter(matrix[(1, 1)]),
+            zero_x: Centimeter(matrix[(2, 0)]),
+            zero_y: Centimeter(matrix[(2, 1)]),
+        })
+    }
+
     pub fn handle(&self, lines: &mut [Line2D<Point2D>]) {
         if !self.is_enabled {
             return;
@@ -54,7 +80,7 @@ impl Affine {
         };
     }
 
-    fn matrix(&self) -> Matrix3<f64> {
+    pub fn matrix(&self) -> Matrix3<f64> {
         Matrix3::new(
             self.xx.value(),
             self.xy.value(),
@@ -72,3 +98,107 @@ impl Affine {
         *self = Self::default();
     }
 }
+
+/// Affine map split into the steps a student can see one by one: shear, then
+/// scale, then rotation, all about the center, then translation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AffineDecomposition {
+    pub center: Point2D,
+    pub translation_x: f64,
+    pub translation_y: f64,
+    pub rotation: Angle,
+    pub scale_x: f64,
+    pub scale_y: f64,
+    /// Shift of X per unit of Y before the scale.
+    pub shear: f64,
+}
+
+impl AffineDecomposition {
+    /// Decomposes a row-vector affine matrix. The linear part is split as a
+    /// rotation times an upper triangular matrix, so a reflection shows up as a
+    /// negative `scale_y`.
+    pub fn new(matrix: &Matrix3<f64>, center: Point2D) -> Self {
+        let (a, b) = (matrix[(0, 0)], matrix[(0, 1)]);
+        let (c, d) = (matrix[(1, 0)], matrix[(1, 1)]);
+
+        let scale_x = a.hypot(b);
+        let angle = if scale_x > 1e-12 { b.atan2(a) } else { 0.0 };
+        let (sin, cos) = angle.sin_cos();
+        let skew = cos * c + sin * d;
+        let scale_y = cos * d - sin * c;
+        let shear = if scale_x > 1e-12 { skew / scale_x } else { 0.0 };
+
+        // Where the center goes, less the center itself
+        let (x, y) = (center.x.value(), center.y.value());
+
+        Self {
+            center,
+            translation_x: a * x + c * y + matrix[(2, 0)] - x,
+            translation_y: b * x + d * y + matrix[(2, 1)] - y,
+            rotation: Angle::from_radian(angle),
+            scale_x,
+            scale_y,
+            shear,
+        }
+    }
+
+    /// Row-vector matrix of the map.
+    pub fn matrix(&self) -> Matrix3<f64> {
+        let (sin, cos) = self.rotation.radian().sin_cos();
+        let (sx, sy, h) = (self.scale_x, self.scale_y, self.shear);
+
+        let a = cos * sx;
+        let b = sin * sx;
+        let c = cos * sx * h - sin * sy;
+        let d = sin * sx * h + cos * sy;
+
+        let (x, y) = (self.center.x.value(), self.center.y.value());
+
+        Matrix3::new(
+            a,
+            b,
+            0.0,
+            c,
+            d,
+            0.0,
+            x - a * x - c * y + self.translation_x,
+            y - b * x - d * y + self.translation_y,
+            1.0,
+        )
+    }
+
+    /// Part of the way from the identity (0) to the map (1), every step taken
+    /// proportionally.
+    pub fn partial(&self, t: f64) -> Self {
+        Self {
+            center: self.center,
+            translation_x: self.translation_x * t,
+            translation_y: self.translation_y * t,
+            rotation: Angle::from_radian(self.rotation.radian() * t),
+            scale_x: 1.0 + (self.scale_x - 1.0) * t,
+            scale_y: 1.0 + (self.scale_y - 1.0) * t,
+            shear: self.shear * t,
+        }
+    }
+}
+
+/// Row-vector matrix of a linear map of the space that keeps the pivot in place.
+pub(crate) fn about_pivot3d(linear: &Matrix3<f64>, pivot: &Point3D) -> Matrix4<f64> {
+    let origin = RowVector3::new(pivot.x.value(), pivot.y.value(), pivot.z.value());
+    let translation = origin - origin * linear;
+
+    let mut matrix = Matrix4::identity();
+    matrix.fixed_view_mut::<3, 3>(0, 0).copy_from(linear);
+    matrix.fixed_view_mut::<1, 3>(3, 0).copy_from(&translation);
+    matrix
+}
+
+pub(crate) fn transform_point3d(matrix: &Matrix4<f64>, point: &mut Point3D) {
+    let result = point.to_vector() * matrix;
+
+    *point = Point3D {
+        x: Centimeter(result.x),
+        y: Centimeter(result.y),
+        z: Centimeter(result.z),
+    };
+}
@@ -0,0 +1,129 @@
+use crate::math::angle::Angle;
+use crate::pipeline::{Operation, Pipeline};
+use crate::primitives::line2d::Line2D;
+use crate::primitives::point2d::Point2D;
+use crate::shapes::dot::DotMetadata;
+use crate::units::Centimeter;
+use crate::viewport::Viewport;
+use egui::{Color32, Shape, Stroke};
+use nalgebra::Matrix3;
+
+/// Reflection across the line through the point at the angle to the X axis.
+#[derive(Debug, Default)]
+pub struct AffineLineReflection {
+    pub is_enabled: bool,
+
+    pub x: Centimeter,
+    pub y: Centimeter,
+    pub angle: f64,
+}
+
+impl AffineLineReflection {
+    /// Half the length of the drawn mirror line.
+    const MIRROR_REACH: f64 = 500.0;
+
+    pub fn handle(&mut self, operators: Vec<&mut Pipeline>) {
+        if !self.is_enabled {
+            return;
+        }
+
+        for pipeline in operators {
+            pipeline.add_operation(self.create_operation())
+        }
+
+        self.reset();
+    }
+
+    pub fn leading_point(&self) -> Option<Point2D> {
+        if self.x.0 == 0.0 && self.y.0 == 0.0 {
+            return None;
+        }
+
+        Some(Point2D {
+            x: self.x,
+            y: self.y,
+        })
+    }
+
+    pub fn leading_shape(point: Point2D, viewport: &Viewport) -> Shape {
+        point.to_pixels(viewport).to_dot(&DotMetadata {
+            radius: 5.0,
+            fill: Color32::GOLD,
+            stroke: Stroke::new(0.5, Color32::BLACK),
+        })
+    }
+
+    /// The mirror, once it differs from the X axis.
+    pub fn mirror(&self) -> Option<Line2D<Point2D>> {
+        if self.leading_point().is_none() && self.angle == 0.0 {
+            return None;
+        }
+
+        let radian = Angle::from_degree(self.angle).radian();
+        let (dx, dy) = (
+            Self::MIRROR_REACH * radian.cos(),
+            Self::MIRROR_REACH * radian.sin(),
+        );
+        let (x, y) = (self.x.value(), self.y.value());
+
+        Some(Line2D::new(
+            Point2D::new(x - dx, y - dy),
+            Point2D::new(x + dx, y + dy),
+            Stroke::new(1.0, Color32::GOLD),
+        ))
+    }
+
+    fn create_operation(&mut self) -> Operation {
+        Operation::LineReflection(LineReflectionOperation {
+            point: Point2D {
+                x: self.x,
+                y: self.y,
+            },
+            angle: Angle::from_degree(self.angle),
+        })
+    }
+
+    pub fn run(&mut self) {
+        self.is_enabled = true;
+    }
+
+    pub fn reset(&mut self) {
+        *self = Default::default();
+    }
+}
+
+#[derive(Debug)]
+pub struct LineReflectionOperation {
+    pub point: Point2D,
+    pub angle: Angle,
+}
+
+impl LineReflectionOperation {
+    pub fn go(&self, point: &mut Point2D) {
+        let vector = point.to_vector();
+        let result = vector * self.matrix();
+
+        *point = Point2D {
+            x: Centimeter(result.x),
+            y: Centimeter(result.y),
+        };
+    }
+
+    pub fn matrix(&self) -> Matrix3<f64> {
+        let double = 2.0 * self.angle.radian();
+        let (sin, cos) = (f64::sin(double), f64::cos(double));
+        let (x, y) = (self.point.x.value(), self.point.y.value());
+
+        Matrix3::new(
+            cos,
+            sin,
+            0.0,
+            sin,
+            -cos,
+            0.0,
+            x - (cos * x + sin * y),
+            y - (sin * x - cos * y),
+            1.0,
+        )
+    }
+}
@@ -0,0 +1,156 @@
+use crate::pipeline::{Operation3D, Pipeline3D};
+use crate::primitives::line3d::Line3D;
+use crate::primitives::point2d::Point2D;
+use crate::primitives::point3d::Point3D;
+use crate::shapes::dot::DotMetadata;
+use crate::transformations::affine::{about_pivot3d, transform_point3d};
+use crate::units::Centimeter;
+use crate::viewport::Viewport;
+use egui::{Color32, Shape, Stroke};
+use nalgebra::{Matrix3, Matrix4, Vector3};
+
+/// Reflection of a figure across a plane. The plane is perpendicular to the
+/// normal and lies the offset away from the pivot along it.
+#[derive(Debug)]
+pub struct AffinePlaneReflection3D {
+    pub is_enabled: bool,
+
+    pub normal_x: f64,
+    pub normal_y: f64,
+    pub normal_z: f64,
+    pub offset: Centimeter,
+}
+
+impl Default for AffinePlaneReflection3D {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+            normal_x: 0.0,
+            normal_y: 0.0,
+            normal_z: 1.0,
+            offset: Centimeter(0.0),
+        }
+    }
+}
+
+impl AffinePlaneReflection3D {
+    pub fn handle(&mut self, operators: Vec<&mut Pipeline3D>) {
+        if !self.is_enabled {
+            return;
+        }
+
+        for pipeline in operators {
+            pipeline.add_operation(self.create_operation())
+        }
+
+        self.reset();
+    }
+
+    /// Point of the plane closest to the pivot, while the plane is being edited.
+    pub fn leading_point(&self, pivot: Point3D) -> Option<Point3D> {
+        let default = Self::default();
+        if self.normal_x == default.normal_x
+            && self.normal_y == default.normal_y
+            && self.normal_z == default.normal_z
+            && self.offset == default.offset
+        {
+            return None;
+        }
+
+        Some(self.create_plane().origin(&pivot))
+    }
+
+    pub fn leading_shape(point: Point2D, viewport: &Viewport) -> Shape {
+        point.to_pixels(viewport).to_dot(&DotMetadata {
+            radius: 5.0,
+            fill: Color32::GOLD,
+            stroke: Stroke::new(0.5, Color32::BLACK),
+        })
+    }
+
+    /// Outline of the plane: a square of the given side around the leading
+    /// point, with its diagonals.
+    pub fn mirror(&self, pivot: Point3D, side: Centimeter) -> Vec<Line3D<Point3D>> {
+        let Some(origin) = self.leading_point(pivot) else {
+            return vec![];
+        };
+
+        let normal = self.create_plane().normal;
+        let helper = if normal.x.abs() < 0.9 {
+            Vector3::x()
+        } else {
+            Vector3::y()
+        };
+        let u = normal.cross(&helper).normalize() * (side.value() / 2.0);
+        let v = normal.cross(&u);
+
+        let center = Vector3::new(origin.x.value(), origin.y.value(), origin.z.value());
+        let corners = [u + v, v - u, -u - v, u - v]
+            .map(|corner| center + corner)
+            .map(|corner| Point3D::new(corner.x, corner.y, corner.z));
+
+        let stroke = Stroke::new(1.0, Color32::GOLD);
+        let mut lines = (0..corners.len())
+            .map(|index| {
+                Line3D::new(corners[index], corners[(index + 1) % corners.len()], stroke)
+            })
+            .collect::<Vec<_>>();
+        lines.push(Line3D::new(corners[0], corners[2], stroke));
+        lines.push(Line3D::new(corners[1], corners[3], stroke));
+
+        lines
+    }
+
+    fn create_plane(&self) -> PlaneReflectionOperation {
+        let normal = Vector3::new(self.normal_x, self.normal_y, self.normal_z);
+        let normal = normal.try_normalize(1e-9).unwrap_or_else(Vector3::z);
+
+        PlaneReflectionOperation {
+            normal,
+            offset: self.offset,
+        }
+    }
+
+    fn create_operation(&mut self) -> Operation3D {
+        Operation3D::Reflection(self.create_plane())
+    }
+
+    pub fn run(&mut self) {
+        self.is_enabled = true;
+    }
+
+    pub fn reset(&mut self) {
+        *self = Default::default();
+    }
+}
+
+#[derive(Debug)]
+pub struct PlaneReflectionOperation {
+    /// Unit normal of the plane.
+    pub normal: Vector3<f64>,
+    pub offset: Centimeter,
+}
+
+impl PlaneReflectionOperation {
+    pub fn go(&self, point: &mut Point3D, pivot: &Point3D) {
+        transform_point3d(&self.matrix(pivot), point);
+    }
+
+    /// Point of the plane closest to the pivot.
+    pub fn origin(&self, pivot: &Point3D) -> Point3D {
+        let shift = self.normal * self.offset.value();
+
+        Point3D::new(
+            pivot.x.value() + shift.x,
+            pivot.y.value() + shift.y,
+            pivot.z.value() + shift.z,
+        )
+    }
+
+    /// Householder reflection about the plane origin.
+    pub fn matrix(&self, pivot: &Point3D) -> Matrix4<f64> {
+        let linear: Matrix3<f64> =
+            Matrix3::identity() - self.normal * self.normal.transpose() * 2.0;
+        about_pivot3d(&linear, &self.origin(pivot))
+    }
+}
@@ -1,5 +1,11 @@
+use crate::pipeline::{Operation, Pipeline};
 use crate::primitives::line2d::Line2D;
 use crate::primitives::point2d::Point2D;
+use crate::shapes::dot::DotMetadata;
+use crate::units::Centimeter;
+use crate::viewport::Viewport;
+use egui::{Color32, Shape, Stroke};
+use nalgebra::Matrix3;
 
 #[derive(Debug)]
 pub struct AffineScaling {
@@ -40,7 +46,119 @@ impl AffineScaling {
         point.y *= self.my;
     }
 
+    pub fn matrix(&self) -> Matrix3<f64> {
+        Matrix3::new(self.mx, 0.0, 0.0, 0.0, self.my, 0.0, 0.0, 0.0, 1.0)
+    }
+
     pub fn reset(&mut self) {
         *self = Self::default();
     }
 }
+
+/// Non-uniform scaling about a pivot, saved to a pipeline.
+#[derive(Debug)]
+pub struct AffinePivotScaling {
+    pub is_enabled: bool,
+
+    pub x: Centimeter,
+    pub y: Centimeter,
+    pub mx: f64,
+    pub my: f64,
+}
+
+impl Default for AffinePivotScaling {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+            x: Centimeter(0.0),
+            y: Centimeter(0.0),
+            mx: 1.0,
+            my: 1.0,
+        }
+    }
+}
+
+impl AffinePivotScaling {
+    pub fn handle(&mut self, operators: Vec<&mut Pipeline>) {
+        if !self.is_enabled {
+            return;
+        }
+
+        for pipeline in operators {
+            pipeline.add_operation(self.create_operation())
+        }
+
+        self.reset();
+    }
+
+    pub fn leading_point(&self) -> Option<Point2D> {
+        if self.x.0 == 0.0 && self.y.0 == 0.0 {
+            return None;
+        }
+
+        Some(Point2D {
+            x: self.x,
+            y: self.y,
+        })
+    }
+
+    pub fn leading_shape(point: Point2D, viewport: &Viewport) -> Shape {
+        point.to_pixels(viewport).to_dot(&DotMetadata {
+            radius: 5.0,
+            fill: Color32::BLUE,
+            stroke: Stroke::new(0.5, Color32::BLACK),
+        })
+    }
+
+    fn create_operation(&mut self) -> Operation {
+        Operation::Scaling(ScalingOperation {
+            pivot: Point2D {
+                x: self.x,
+                y: self.y,
+            },
+            mx: self.mx,
+            my: self.my,
+        })
+    }
+
+    pub fn run(&mut self) {
+        self.is_enabled = true;
+    }
+
+    pub fn reset(&mut self) {
+        *self = Default::default();
+    }
+}
+
+#[derive(Debug)]
+pub struct ScalingOperation {
+    pub pivot: Point2D,
+    pub mx: f64,
+    pub my: f64,
+}
+
+impl ScalingOperation {
+    pub fn go(&self, point: &mut Point2D) {
+        let vector = point.to_vector();
+        let result = vector * self.matrix();
+
+        *point = Point2D {
+            x: Centimeter(result.x),
+            y: Centimeter(result.y),
+        };
+    }
+
+    pub fn matrix(&self) -> Matrix3<f64> {
+        Matrix3::new(
+            self.mx,
+            0.0,
+            0.0,
+            0.0,
+            self.my,
+            0.0,
+            self.pivot.x.value() * (1.0 - self.mx),
+            self.pivot.y.value() * (1.0 - self.my),
+            1.0,
+        )
+    }
+}
@@ -0,0 +1,94 @@
+use crate::pipeline::{Operation3D, Pipeline3D};
+use crate::primitives::point2d::Point2D;
+use crate::primitives::point3d::Point3D;
+use crate::shapes::dot::DotMetadata;
+use crate::transformations::affine::{about_pivot3d, transform_point3d};
+use crate::viewport::Viewport;
+use egui::{Color32, Shape, Stroke};
+use nalgebra::{Matrix3, Matrix4};
+
+/// Non-uniform scaling of a figure about its pivot.
+#[derive(Debug)]
+pub struct AffineScaling3D {
+    pub is_enabled: bool,
+
+    pub mx: f64,
+    pub my: f64,
+    pub mz: f64,
+}
+
+impl Default for AffineScaling3D {
+    fn default() -> Self {
+        Self {
+            is_enabled: false,
+            mx: 1.0,
+            my: 1.0,
+            mz: 1.0,
+        }
+    }
+}
+
+impl AffineScaling3D {
+    pub fn handle(&mut self, operators: Vec<&mut Pipeline3D>) {
+        if !self.is_enabled {
+            return;
+        }
+
+        for pipeline in operators {
+            pipeline.add_operation(self.create_operation())
+        }
+
+        self.reset();
+    }
+
+    /// The pivot, while the factors are being edited.
+    pub fn leading_point(&self, pivot: Point3D) -> Option<Point3D> {
+        if self.mx == 1.0 && self.my == 1.0 && self.mz == 1.0 {
+            return None;
+        }
+
+        Some(pivot)
+    }
+
+    pub fn leading_shape(point: Point2D, viewport: &Viewport) -> Shape {
+        point.to_pixels(viewport).to_dot(&DotMetadata {
+            radius: 5.0,
+            fill: Color32::BLUE,
+            stroke: Stroke::new(0.5, Color32::BLACK),
+        })
+    }
+
+    fn create_operation(&mut self) -> Operation3D {
+        Operation3D::Scaling(Scaling3DOperation {
+            mx: self.mx,
+            my: self.my,
+            mz: self.mz,
+        })
+    }
+
+    pub fn run(&mut self) {
+        self.is_enabled = true;
+    }
+
+    pub fn reset(&mut self) {
+        *self = Default::default();
+    }
+}
+
+#[derive(Debug)]
+pub struct Scaling3DOperation {
+    pub mx: f64,
+    pub my: f64,
+    pub mz: f64,
+}
+
+impl Scaling3DOperation {
+    pub fn go(&self, point: &mut Point3D, pivot: &Point3D) {
+        transform_point3d(&self.matrix(pivot), point);
+    }
+
+    pub fn matrix(&self, pivot: &Point3D) -> Matrix4<f64> {
+        let linear = Matrix3::from_diagonal(&[self.mx, self.my, self.mz].into());
+        about_pivot3d(&linear, pivot)
+    }
+}
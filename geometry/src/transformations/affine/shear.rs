@@ -0,0 +1,104 @@
+use crate::pipeline::{Operation, Pipeline};
+use crate::primitives::point2d::Point2D;
+use crate::shapes::dot::DotMetadata;
+use crate::units::Centimeter;
+use crate::viewport::Viewport;
+use egui::{Color32, Shape, Stroke};
+use nalgebra::Matrix3;
+
+#[derive(Debug, Default)]
+pub struct AffineShear {
+    pub is_enabled: bool,
+
+    pub x: Centimeter,
+    pub y: Centimeter,
+    /// Shift along X per centimeter of Y from the pivot.
+    pub shear_x: f64,
+    /// Shift along Y per centimeter of X from the pivot.
+    pub shear_y: f64,
+}
+
+impl AffineShear {
+    pub fn handle(&mut self, operators: Vec<&mut Pipeline>) {
+        if !self.is_enabled {
+            return;
+        }
+
+        for pipeline in operators {
+            pipeline.add_operation(self.create_operation())
+        }
+
+        self.reset();
+    }
+
+    pub fn leading_point(&self) -> Option<Point2D> {
+        if self.x.0 == 0.0 && self.y.0 == 0.0 {
+            return None;
+        }
+
+        Some(Point2D {
+            x: self.x,
+            y: self.y,
+        })
+    }
+
+    pub fn leading_shape(point: Point2D, viewport: &Viewport) -> Shape {
+        point.to_pixels(viewport).to_dot(&DotMetadata {
+            radius: 5.0,
+            fill: Color32::DARK_GREEN,
+            stroke: Stroke::new(0.5, Color32::BLACK),
+        })
+    }
+
+    fn create_operation(&mut self) -> Operation {
+        Operation::Shear(ShearOperation {
+            pivot: Point2D {
+                x: self.x,
+                y: self.y,
+            },
+            shear_x: self.shear_x,
+            shear_y: self.shear_y,
+        })
+    }
+
+    pub fn run(&mut self) {
+        self.is_enabled = true;
+    }
+
+    pub fn reset(&mut self) {
+        *self = Default::default();
+    }
+}
+
+#[derive(Debug)]
+pub struct ShearOperation {
+    pub pivot: Point2D,
+    pub shear_x: f64,
+    pub shear_y: f64,
+}
+
+impl ShearOperation {
+    pub fn go(&self, point: &mut Point2D) {
+        let vector = point.to_vector();
+        let result = vector * self.matrix();
+
+        *point = Point2D {
+            x: Centimeter(result.x),
+            y: Centimeter(result.y),
+        };
+    }
+
+    pub fn matrix(&self) -> Matrix3<f64> {
+        Matrix3::new(
+            1.0,
+            self.shear_y,
+            0.0,
+            self.shear_x,
+            1.0,
+            0.0,
+            -self.shear_x * self.pivot.y.value(),
+            -self.shear_y * self.pivot.x.value(),
+            1.0,
+        )
+    }
+}
@@ -0,0 +1,97 @@
+use crate::pipeline::{Operation3D, Pipeline3D};
+use crate::primitives::point2d::Point2D;
+use crate::primitives::point3d::Point3D;
+use crate::shapes::dot::DotMetadata;
+use crate::transformations::affine::{about_pivot3d, transform_point3d};
+use crate::viewport::Viewport;
+use egui::{Color32, Shape, Stroke};
+use nalgebra::{Matrix3, Matrix4};
+
+/// Shear of a figure about its pivot. `xy` is the shift along X per centimeter
+/// of Y from the pivot, and so on for the other factors.
+#[derive(Debug, Default)]
+pub struct AffineShear3D {
+    pub is_enabled: bool,
+
+    pub xy: f64,
+    pub xz: f64,
+    pub yx: f64,
+    pub yz: f64,
+    pub zx: f64,
+    pub zy: f64,
+}
+
+impl AffineShear3D {
+    pub fn handle(&mut self, operators: Vec<&mut Pipeline3D>) {
+        if !self.is_enabled {
+            return;
+        }
+
+        for pipeline in operators {
+            pipeline.add_operation(self.create_operation())
+        }
+
+        self.reset();
+    }
+
+    /// The pivot, while the factors are being edited.
+    pub fn leading_point(&self, pivot: Point3D) -> Option<Point3D> {
+        let factors = [self.xy, self.xz, self.yx, self.yz, self.zx, self.zy];
+        if factors.iter().all(|factor| *factor == 0.0) {
+            return None;
+        }
+
+        Some(pivot)
+    }
+
+    pub fn leading_shape(point: Point2D, viewport: &Viewport) -> Shape {
+        point.to_pixels(viewport).to_dot(&DotMetadata {
+            radius: 5.0,
+            fill: Color32::DARK_GREEN,
+            stroke: Stroke::new(0.5, Color32::BLACK),
+        })
+    }
+
+    fn create_operation(&mut self) -> Operation3D {
+        Operation3D::Shear(Shear3DOperation {
+            xy: self.xy,
+            xz: self.xz,
+            yx: self.yx,
+            yz: self.yz,
+            zx: self.zx,
+            zy: self.zy,
+        })
+    }
+
+    pub fn run(&mut self) {
+        self.is_enabled = true;
+    }
+
+    pub fn reset(&mut self) {
+        *self = Default::default();
+    }
+}
+
+#[derive(Debug)]
+pub struct Shear3DOperation {
+    pub xy: f64,
+    pub xz: f64,
+    pub yx: f64,
+    pub yz: f64,
+    pub zx: f64,
+    pub zy: f64,
+}
+
+impl Shear3DOperation {
+    pub fn go(&self, point: &mut Point3D, pivot: &Point3D) {
+        transform_point3d(&self.matrix(pivot), point);
+    }
+
+    pub fn matrix(&self, pivot: &Point3D) -> Matrix4<f64> {
+        // Row of a coordinate holds what it adds to every axis
+        let linear = Matrix3::new(
+            1.0, self.yx, self.zx, self.xy, 1.0, self.zy, self.xz, self.yz, 1.0,
+        );
+        about_pivot3d(&linear, pivot)
+    }
+}
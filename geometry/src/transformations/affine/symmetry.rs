@@ -46,6 +46,17 @@ impl AffinePointSymmetry {
         })
     }
 
+    /// Row-vector matrix of the reflection in the point.
+    pub fn matrix(&self) -> Matrix3<f64> {
+        PointSymmetryOperation {
+            point: Point2D {
+                x: self.x,
+                y: self.y,
+            },
+        }
+        .matrix()
+    }
+
     fn create_operation(&mut self) -> Operation {
         Operation::PointSymmetry(PointSymmetryOperation {
             point: Point2D {
@@ -76,7 +87,18 @@ impl PointSymmetryOperation {
 
     pub fn process_point(&self, point: &mut Point2D) {
         let vector = point.to_vector();
-        let matrix = Matrix3::new(
+        let result = vector * self.matrix();
+
+        let result = Point2D {
+            x: Centimeter(result.x),
+            y: Centimeter(result.y),
+        };
+
+        *point = result;
+    }
+
+    pub fn matrix(&self) -> Matrix3<f64> {
+        Matrix3::new(
             -1.0,
             0.0,
             0.0,
@@ -86,15 +108,6 @@ impl PointSymmetryOperation {
             2.0 * self.point.x.value(),
             2.0 * self.point.y.value(),
             1.0,
-        );
-
-        let result = vector * matrix;
-
-        let result = Point2D {
-            x: Centimeter(result.x),
-            y: Centimeter(result.y),
-        };
-
-        *point = result;
+        )
     }
 }
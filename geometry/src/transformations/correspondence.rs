@@ -0,0 +1,139 @@
+use crate::primitives::point2d::Point2D;
+use crate::transformations::affine::Affine;
+use derive_more::Display;
+use nalgebra::{Matrix3, RowVector3};
+
+/// Smaller doubled areas of a triangle are treated as a straight line.
+const COLLINEAR_EPSILON: f64 = 1e-9;
+
+#[derive(Debug, Display, Clone, Copy, PartialEq)]
+pub enum CorrespondenceError {
+    #[display("Source points {_0}, {_1} and {_2} lie on one line.")]
+    CollinearSource(usize, usize, usize),
+
+    #[display("Target points {_0}, {_1} and {_2} lie on one line.")]
+    CollinearTarget(usize, usize, usize),
+}
+
+impl std::error::Error for CorrespondenceError {}
+
+/// Row-vector matrix of the affine map that takes every source point to the
+/// target point of the same index. The targets may lie on one line, then the
+/// plane is flattened onto it.
+pub fn solve_affine(
+    source: &[Point2D; 3], target: &[Point2D; 3],
+) -> Result<Matrix3<f64>, CorrespondenceError> {
+    let from = rows(source);
+    let to = rows(target);
+
+    if from.determinant().abs() <= COLLINEAR_EPSILON {
+        return Err(CorrespondenceError::CollinearSource(1, 2, 3));
+    }
+    let inverse = from
+        .try_inverse()
+        .ok_or(CorrespondenceError::CollinearSource(1, 2, 3))?;
+
+    let mut matrix = inverse * to;
+    // Exact affine column, free of rounding
+    matrix[(0, 2)] = 0.0;
+    matrix[(1, 2)] = 0.0;
+    matrix[(2, 2)] = 1.0;
+
+    Ok(matrix)
+}
+
+/// Row-vector matrix of the homography that takes every source point to the
+/// target point of the same index, scaled so that its corner is 1 when it can
+/// be. No three points of either quad may lie on one line.
+pub fn solve_homography(
+    source: &[Point2D; 4], target: &[Point2D; 4],
+) -> Result<Matrix3<f64>, CorrespondenceError> {
+    let from = basis(source)
+        .map_err(|(a, b, c)| CorrespondenceError::CollinearSource(a, b, c))?;
+    let to = basis(target)
+        .map_err(|(a, b, c)| CorrespondenceError::CollinearTarget(a, b, c))?;
+
+    let inverse = from
+        .try_inverse()
+        .ok_or(CorrespondenceError::CollinearSource(1, 2, 3))?;
+    let matrix = inverse * to;
+
+    let corner = matrix[(2, 2)];
+    if corner.abs() > COLLINEAR_EPSILON {
+        Ok(matrix / corner)
+    } else {
+        Ok(matrix / matrix.norm())
+    }
+}
+
+/// Points as homogeneous rows.
+fn rows(points: &[Point2D; 3]) -> Matrix3<f64> {
+    Matrix3::from_rows(
+        &points.map(|point| RowVector3::new(point.x.value(), point.y.value(), 1.0)),
+    )
+}
+
+/// Matrix that takes the three unit points to the first three points and
+/// (1, 1, 1) to the fourth one. Fails with the (1-based) indices of three
+/// points on one line.
+fn basis(points: &[Point2D; 4]) -> Result<Matrix3<f64>, (usize, usize, usize)> {
+    let triangle = rows(&[points[0], points[1], points[2]]);
+    if triangle.determinant().abs() <= COLLINEAR_EPSILON {
+        return Err((1, 2, 3));
+    }
+
+    // Weights the fourth point is made of
+    let fourth = RowVector3::new(points[3].x.value(), points[3].y.value(), 1.0);
+    let weights = triangle
+        .transpose()
+        .lu()
+        .solve(&fourth.transpose())
+        .ok_or((1, 2, 3))?;
+
+    // A zero weight means the fourth point is on the line of the other two
+    let others = [(2, 3, 4), (1, 3, 4), (1, 2, 4)];
+    for (weight, indices) in weights.iter().zip(others) {
+        if weight.abs() * triangle.determinant().abs() <= COLLINEAR_EPSILON {
+            return Err(indices);
+        }
+    }
+
+    let mut basis = triangle;
+    for (index, weight) in weights.iter().enumerate() {
+        basis.row_mut(index).scale_mut(*weight);
+    }
+
+    Ok(basis)
+}
+
+/// Three pairs of points an affine map is solved from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AffineCorrespondence {
+    pub source: [Point2D; 3],
+    pub target: [Point2D; 3],
+}
+
+impl Default for AffineCorrespondence {
+    fn default() -> Self {
+        let triangle = [
+            Point2D::new(0.0, 0.0),
+            Point2D::new(10.0, 0.0),
+            Point2D::new(0.0, 10.0),
+        ];
+
+        Self {
+            source: triangle,
+            target: triangle,
+        }
+    }
+}
+
+impl AffineCorrespondence {
+    pub fn solve(&self) -> Result<Affine, CorrespondenceError> {
+        Affine::from_correspondences(&self.source, &self.target)
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
@@ -0,0 +1,64 @@
+use crate::math::quaternion::Quaternion;
+use crate::transformations::euclidean::rotation3d::Rotation3DOperation;
+use egui::{Pos2, Rect, Response};
+use nalgebra::Vector3;
+
+/// Rotation of a figure by dragging the canvas with the secondary button. The
+/// canvas is treated as a ball under the cursor: the point grabbed on it follows
+/// the pointer, and dragging outside the ball turns around the view direction.
+#[derive(Debug, Default)]
+pub struct Arcball {
+    pub orientation: Quaternion,
+    /// Grabbed point on the ball and the orientation when the drag started.
+    drag: Option<(Vector3<f64>, Quaternion)>,
+}
+
+impl Arcball {
+    /// Turns the orientation while the canvas is dragged with the secondary
+    /// button. Returns whether the drag was taken, so that it does not pan.
+    pub fn handle(&mut self, ui: &egui::Ui, response: &Response) -> bool {
+        if !response.dragged_by(egui::PointerButton::Secondary) {
+            self.drag = None;
+            return false;
+        }
+        let Some(position) = response.interact_pointer_pos() else {
+            return false;
+        };
+
+        let current = Self::project(response.rect, position);
+        let (start, orientation) = *self.drag.get_or_insert((current, self.orientation));
+        self.orientation =
+            (Quaternion::from_arc(start, current) * orientation).normalize();
+
+        ui.ctx().set_cursor_icon(egui::CursorIcon::AllScroll);
+        ui.ctx().request_repaint();
+
+        true
+    }
+
+    /// Rotation of the figure around its pivot.
+    pub fn operation(&self) -> Rotation3DOperation {
+        self.orientation.into()
+    }
+
+    pub fn reset(&mut self) {
+        *self = Default::default();
+    }
+
+    /// Point on the unit ball under the position. The ball fills the smaller
+    /// side of the canvas; outside of it the point lies on its rim.
+    fn project(rect: Rect, position: Pos2) -> Vector3<f64> {
+        let center = rect.center();
+        let radius = f64::from(rect.width().min(rect.height()) / 2.0).max(1.0);
+        // Canvas Y grows downwards, the world one upwards
+        let x = f64::from(position.x - center.x) / radius;
+        let y = f64::from(center.y - position.y) / radius;
+
+        let length = x * x + y * y;
+        if length <= 1.0 {
+            Vector3::new(x, y, (1.0 - length).sqrt())
+        } else {
+            Vector3::new(x, y, 0.0) / length.sqrt()
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::math::angle::Angle;
+use crate::math::quaternion::Quaternion;
 use crate::pipeline::{Operation3D, Pipeline3D};
 use crate::primitives::point3d::Point3D;
 use crate::units::Centimeter;
@@ -27,11 +28,11 @@ impl EuclideanRotation3D {
     }
 
     fn create_operation(&mut self) -> Operation3D {
-        Operation3D::Rotation(Rotation3DOperation {
-            angle_x: Angle::from_degree(self.angle_x),
-            angle_y: Angle::from_degree(self.angle_y),
-            angle_z: Angle::from_degree(self.angle_z),
-        })
+        Operation3D::Rotation(Rotation3DOperation::from_euler(
+            Angle::from_degree(self.angle_x),
+            Angle::from_degree(self.angle_y),
+            Angle::from_degree(self.angle_z),
+        ))
     }
 
     pub fn run(&mut self) {
@@ -43,33 +44,32 @@ impl EuclideanRotation3D {
     }
 }
 
-#[derive(Debug)]
+/// Rotation around a pivot. Kept as a quaternion rather than three angles, so
+/// that composed rotations do not run into the gimbal lock.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub struct Rotation3DOperation {
-    pub angle_x: Angle,
-    pub angle_y: Angle,
-    pub angle_z: Angle,
+    pub orientation: Quaternion,
 }
 
-impl Default for Rotation3DOperation {
-    fn default() -> Self {
-        Self {
-            angle_x: Angle::from_degree(0.0),
-            angle_y: Angle::from_degree(0.0),
-            angle_z: Angle::from_degree(0.0),
-        }
+impl From<Quaternion> for Rotation3DOperation {
+    fn from(orientation: Quaternion) -> Self {
+        Self { orientation }
     }
 }
 
 impl Rotation3DOperation {
+    /// Turns around OX, then OY, then OZ.
+    pub fn from_euler(angle_x: Angle, angle_y: Angle, angle_z: Angle) -> Self {
+        Quaternion::from_euler(angle_x, angle_y, angle_z).into()
+    }
+
     pub fn go(&self, point: &mut Point3D, pivot: &mut Point3D) {
         let matrix_to_origin = self.matrix_offset_to_origin(pivot);
         let matrix_from_origin = self.matrix_offset_to_point(pivot);
-        let matrix_ox = self.matrix_around_ox();
-        let matrix_oy = self.matrix_around_oy();
-        let matrix_oz = self.matrix_around_oz();
+        let matrix_rotation = self.orientation.to_matrix4();
 
         let result_matrix: Matrix4<f64> =
-            matrix_to_origin * matrix_ox * matrix_oy * matrix_oz * matrix_from_origin;
+            matrix_to_origin * matrix_rotation * matrix_from_origin;
         self.update_point(point, &result_matrix);
     }
 
@@ -85,75 +85,6 @@ impl Rotation3DOperation {
         };
     }
 
-    fn matrix_around_ox(&self) -> Matrix4<f64> {
-        let angle = self.angle_x.radian();
-
-        Matrix4::new(
-            1.0,
-            0.0,
-            0.0,
-            0.0,
-            0.0,
-            f64::cos(angle),
-            f64::sin(angle),
-            0.0,
-            0.0,
-            -f64::sin(angle),
-            f64::cos(angle),
-            0.0,
-            0.0,
-            0.0,
-            0.0,
-            1.0,
-        )
-    }
-
-    fn matrix_around_oy(&self) -> Matrix4<f64> {
-        let angle = self.angle_y.radian();
-
-        Matrix4::new(
-            f64::cos(angle),
-            0.0,
-            -f64::sin(angle),
-            0.0,
-            0.0,
-            1.0,
-            0.0,
-            0.0,
-            f64::sin(angle),
-            0.0,
-            f64::cos(angle),
-            0.0,
-            0.0,
-            0.0,
-            0.0,
-            1.0,
-        )
-    }
-
-    fn matrix_around_oz(&self) -> Matrix4<f64> {
-        let angle = self.angle_z.radian();
-
-        Matrix4::new(
-            f64::cos(angle),
-            f64::sin(angle),
-            0.0,
-            0.0,
-            -f64::sin(angle),
-            f64::cos(angle),
-            0.0,
-            0.0,
-            0.0,
-            0.0,
-            1.0,
-            0.0,
-            0.0,
-            0.0,
-            0.0,
-            1.0,
-        )
-    }
-
     fn matrix_offset_to_origin(&self, pivot: &Point3D) -> Matrix4<f64> {
         Matrix4::new(
             1.0,
@@ -1,6 +1,10 @@
 use crate::primitives::line2d::Line2D;
 use crate::primitives::point2d::Point2D;
-use crate::units::Centimeter;
+use crate::transformations::correspondence::{CorrespondenceError, solve_homography};
+use egui::{Color32, Stroke};
+use nalgebra::{Matrix3, Vector2};
+
+pub mod quad;
 
 #[derive(Debug)]
 pub struct Projective {
@@ -15,6 +19,10 @@ pub struct Projective {
     pub zero_x: f64,
     pub zero_y: f64,
     pub w_zero: f64,
+    /// Solved map that has no weighted form, used instead of the coefficients.
+    /// It keeps a direction of an axis at infinity, so its weight is zero.
+    pub homography: Option<Matrix3<f64>>,
+    pub is_horizon_visible: bool,
 }
 
 impl Default for Projective {
@@ -31,48 +39,199 @@ impl Default for Projective {
             zero_x: 0.0,
             zero_y: 0.0,
             w_zero: 500.0,
+            homography: None,
+            is_horizon_visible: true,
         }
     }
 }
 
 impl Projective {
-    pub fn handle(&self, lines: &mut [Line2D<Point2D>]) {
-        if !self.is_enabled {
+    /// Weight under which the coefficients of a solved map are not kept.
+    const WEIGHT_EPSILON: f64 = 1e-9;
+
+    /// Enabled map that takes the four source points to the four targets.
+    pub fn from_correspondences(
+        source: &[Point2D; 4], target: &[Point2D; 4],
+    ) -> Result<Self, CorrespondenceError> {
+        Ok(Self::from_matrix(solve_homography(source, target)?))
+    }
+
+    /// Enabled map of a row-vector homography, with the default settings.
+    pub fn from_matrix(matrix: Matrix3<f64>) -> Self {
+        let mut projective = Self::default();
+        projective.set_matrix(matrix);
+        projective
+    }
+
+    /// Enables the map of a row-vector homography, keeping the settings. Its
+    /// coefficients are filled in when all three weights are non-zero.
+    pub fn set_matrix(&mut self, matrix: Matrix3<f64>) {
+        let (wx, wy, w_zero) = (matrix[(0, 2)], matrix[(1, 2)], matrix[(2, 2)]);
+        self.is_enabled = true;
+
+        if [wx, wy, w_zero]
+            .iter()
+            .any(|weight| weight.abs() < Self::WEIGHT_EPSILON)
+        {
+            self.homography = Some(matrix);
             return;
         }
 
-        for line in lines.iter_mut() {
-            self.transform_point(&mut line.start);
-            self.transform_point(&mut line.end);
+        self.xx = matrix[(0, 0)] / wx;
+        self.xy = matrix[(1, 0)] / wy;
+        self.wx = wx;
+        self.yx = matrix[(0, 1)] / wx;
+        self.yy = matrix[(1, 1)] / wy;
+        self.wy = wy;
+        self.zero_x = matrix[(2, 0)] / w_zero;
+        self.zero_y = matrix[(2, 1)] / w_zero;
+        self.w_zero = w_zero;
+        self.homography = None;
+    }
+
+    /// Maps the lines, splitting the ones that cross the vanishing line.
+    pub fn handle(&self, lines: &mut Vec<Line2D<Point2D>>) {
+        if !self.is_enabled {
+            return;
         }
+
+        *lines = project_lines(&self.matrix(), lines);
     }
 
+    /// A point on the vanishing line has no image and is left in place.
     pub fn transform_point(&self, point: &mut Point2D) {
         if !self.is_enabled {
             return;
         }
 
-        *point = Point2D {
-            x: Centimeter(
-                (self.zero_x * self.w_zero
-                    + self.xx * self.wx * point.x.value()
-                    + self.xy * self.wy * point.y.value())
-                    / (self.w_zero
-                        + self.wx * point.x.value()
-                        + self.wy * point.y.value()),
-            ),
-            y: Centimeter(
-                (self.zero_y * self.w_zero
-                    + self.yx * self.wx * point.x.value()
-                    + self.yy * self.wy * point.y.value())
-                    / (self.w_zero
-                        + self.wx * point.x.value()
-                        + self.wy * point.y.value()),
-            ),
-        };
+        if let Some(image) = project_point(&self.matrix(), *point) {
+            *point = image;
+        }
+    }
+
+    /// Image of the line at infinity, if it is not at infinity itself.
+    pub fn horizon(&self) -> Option<Line2D<Point2D>> {
+        if !self.is_enabled || !self.is_horizon_visible {
+            return None;
+        }
+
+        horizon(&self.matrix())
+    }
+
+    /// Row-vector matrix of the map in homogeneous coordinates.
+    pub fn matrix(&self) -> Matrix3<f64> {
+        if let Some(homography) = self.homography {
+            return homography;
+        }
+
+        Matrix3::new(
+            self.xx * self.wx,
+            self.yx * self.wx,
+            self.wx,
+            self.xy * self.wy,
+            self.yy * self.wy,
+            self.wy,
+            self.zero_x * self.w_zero,
+            self.zero_y * self.w_zero,
+            self.w_zero,
+        )
     }
 
     pub fn reset(&mut self) {
         *self = Self::default();
     }
 }
+
+/// Smaller weights are treated as zero, the point as being at infinity.
+const INFINITY_EPSILON: f64 = 1e-9;
+/// How far lines going off to infinity and the horizon are drawn.
+const REACH: f64 = 10_000.0;
+
+/// Lines mapped by a row-vector homography. The weight of a point changes
+/// linearly along a line, so a line whose ends have weights of opposite signs
+/// crosses the vanishing line: its image is two rays that go off to infinity
+/// in opposite directions, each drawn up to the reach. Lines lying on the
+/// vanishing line have no image and are dropped.
+pub fn project_lines(
+    matrix: &Matrix3<f64>, lines: &[Line2D<Point2D>],
+) -> Vec<Line2D<Point2D>> {
+    let mut projected = Vec::with_capacity(lines.len());
+
+    for line in lines {
+        let start = line.start.to_vector() * matrix;
+        let end = line.end.to_vector() * matrix;
+        let (w_start, w_end) = (start.z, end.z);
+
+        let is_start_finite = w_start.abs() > INFINITY_EPSILON;
+        let is_end_finite = w_end.abs() > INFINITY_EPSILON;
+        if is_start_finite && is_end_finite && w_start.signum() == w_end.signum() {
+            projected.push(Line2D::new(
+                Point2D::new(start.x / w_start, start.y / w_start),
+                Point2D::new(end.x / w_end, end.y / w_end),
+                line.stroke,
+            ));
+            continue;
+        }
+        if !is_start_finite && !is_end_finite {
+            continue;
+        }
+
+        // The point at infinity the image runs to, as a direction
+        let t = w_start / (w_start - w_end);
+        let crossing = start + (end - start) * t;
+        let Some(direction) = Vector2::new(crossing.x, crossing.y).try_normalize(0.0)
+        else {
+            continue;
+        };
+
+        for (point, w) in [(start, w_start), (end, w_end)] {
+            if w.abs() <= INFINITY_EPSILON {
+                continue;
+            }
+
+            let from = Vector2::new(point.x / w, point.y / w);
+            let to = from + direction * (w.signum() * REACH);
+            projected.push(Line2D::new(
+                Point2D::new(from.x, from.y),
+                Point2D::new(to.x, to.y),
+                line.stroke,
+            ));
+        }
+    }
+
+    projected
+}
+
+/// Image of the point, `None` if it lies on the vanishing line.
+pub fn project_point(matrix: &Matrix3<f64>, point: Point2D) -> Option<Point2D> {
+    let vector = point.to_vector() * matrix;
+    if vector.z.abs() <= INFINITY_EPSILON {
+        return None;
+    }
+
+    Some(Point2D::new(vector.x / vector.z, vector.y / vector.z))
+}
+
+/// Image of the line at infinity under a row-vector homography: the points
+/// the inverse map takes to a zero weight. `None` for affine maps, which keep
+/// the line at infinity, and for singular ones.
+pub fn horizon(matrix: &Matrix3<f64>) -> Option<Line2D<Point2D>> {
+    let inverse = matrix.try_inverse()?;
+    let (a, b, c) = (inverse[(0, 2)], inverse[(1, 2)], inverse[(2, 2)]);
+
+    let normal = Vector2::new(a, b);
+    let length = normal.norm();
+    if length <= INFINITY_EPSILON * c.abs().max(1.0) {
+        return None;
+    }
+
+    // Point of the line closest to the origin, and the line direction
+    let foot = normal * (-c / (length * length));
+    let direction = Vector2::new(-b, a) / length * REACH;
+
+    Some(Line2D::new(
+        Point2D::new(foot.x - direction.x, foot.y - direction.y),
+        Point2D::new(foot.x + direction.x, foot.y + direction.y),
+        Stroke::new(1.5, Color32::from_rgb(200, 60, 160)),
+    ))
+}
@@ -0,0 +1,125 @@
+use crate::primitives::line2d::Line2D;
+use crate::primitives::point2d::{MoveablePoint, Point2D};
+use crate::shapes::dot::DotMetadata;
+use crate::transformations::correspondence::{CorrespondenceError, solve_homography};
+use crate::viewport::Viewport;
+use egui::{Color32, Response, Shape, Stroke};
+use nalgebra::Matrix3;
+
+/// Reference square and the quad its corners are dragged to. The projective
+/// map solved from them takes the square onto the quad.
+#[derive(Debug)]
+pub struct ProjectiveQuad {
+    pub is_enabled: bool,
+    pub source: [Point2D; 4],
+    pub corners: [MoveablePoint; 4],
+    /// Why the last placement of the corners has no map.
+    pub error: Option<CorrespondenceError>,
+}
+
+impl Default for ProjectiveQuad {
+    fn default() -> Self {
+        let source = Self::square();
+
+        Self {
+            is_enabled: false,
+            source,
+            corners: source.map(MoveablePoint::new),
+            error: None,
+        }
+    }
+}
+
+impl ProjectiveQuad {
+    /// Side of the reference square, large enough for the detail.
+    const SIDE: f64 = 120.0;
+
+    fn square() -> [Point2D; 4] {
+        [
+            Point2D::new(0.0, 0.0),
+            Point2D::new(Self::SIDE, 0.0),
+            Point2D::new(Self::SIDE, Self::SIDE),
+            Point2D::new(0.0, Self::SIDE),
+        ]
+    }
+
+    pub fn targets(&self) -> [Point2D; 4] {
+        self.corners.each_ref().map(|corner| corner.coordinates)
+    }
+
+    /// Drags the corners. Returns whether any of them moved.
+    pub fn update(
+        &mut self, ui: &egui::Ui, response: &Response, viewport: &Viewport,
+    ) -> bool {
+        if !self.is_enabled {
+            return false;
+        }
+
+        let before = self.targets();
+        for (index, corner) in self.corners.iter_mut().enumerate() {
+            corner.update_on_pan(ui, response, viewport);
+            corner.show_tooltip(index + 1, ui, response, viewport);
+        }
+
+        before != self.targets()
+    }
+
+    /// Homography of the square onto the quad. While three corners are on one
+    /// line there is none, and the reason is kept.
+    pub fn solve(&mut self) -> Option<Matrix3<f64>> {
+        match solve_homography(&self.source, &self.targets()) {
+            Ok(matrix) => {
+                self.error = None;
+                Some(matrix)
+            },
+            Err(error) => {
+                self.error = Some(error);
+                None
+            },
+        }
+    }
+
+    /// Outlines of the square and the quad, and the corners to drag.
+    pub fn shapes(&self, viewport: &Viewport) -> Vec<Shape> {
+        if !self.is_enabled {
+            return vec![];
+        }
+
+        let outline = |points: [Point2D; 4], stroke: Stroke| {
+            (0..points.len()).map(move |index| {
+                Line2D::new(points[index], points[(index + 1) % points.len()], stroke)
+                    .to_pixels(viewport)
+                    .to_shape()
+            })
+        };
+
+        let quad_color = if self.error.is_some() {
+            Color32::RED
+        } else {
+            Color32::from_rgb(0, 140, 200)
+        };
+
+        let mut shapes: Vec<Shape> =
+            outline(self.source, Stroke::new(1.0, Color32::GRAY))
+                .chain(outline(self.targets(), Stroke::new(1.5, quad_color)))
+                .collect();
+
+        shapes.extend(self.corners.iter().map(|corner| {
+            corner.coordinates.to_pixels(viewport).to_dot(&DotMetadata {
+                radius: corner.radius.value() as f32,
+                fill: quad_color,
+                stroke: Stroke::new(0.5, Color32::BLACK),
+            })
+        }));
+
+        shapes
+    }
+
+    /// Corners back on the square.
+    pub fn reset(&mut self) {
+        *self = Self {
+            is_enabled: self.is_enabled,
+            ..Default::default()
+        };
+    }
+}
@@ -0,0 +1,28 @@
+use egui::Color32;
+use geometry::transformations::euclidean::arcball::Arcball;
+
+/// Current axis and angle, and a button to return to the start.
+pub fn show(ui: &mut egui::Ui, arcball: &mut Arcball) {
+    let (axis, angle) = arcball.orientation.axis_angle();
+    ui.horizontal(|ui| {
+        ui.label("Orientation:");
+        ui.colored_label(
+            Color32::WHITE,
+            format!(
+                "{:.1}° around ({:.2}, {:.2}, {:.2})",
+                angle.degree(),
+                axis.x,
+                axis.y,
+                axis.z
+            ),
+        );
+    });
+
+    ui.label("Drag the canvas with the right mouse button to rotate.");
+
+    ui.vertical_centered_justified(|ui| {
+        if ui.button("Reset Orientation").clicked() {
+            arcball.reset();
+        }
+    });
+}
@@ -0,0 +1,31 @@
+use egui::{ComboBox, DragValue};
+use geometry::animations::clock::{Clock, ClockMode};
+use strum::IntoEnumIterator;
+
+/// Mode and steps per second. Returns whether any of them changed.
+pub fn show(ui: &mut egui::Ui, clock: &mut Clock) -> bool {
+    let mut is_changed = false;
+
+    ComboBox::from_id_salt("CLOCK_MODE")
+        .selected_text(clock.mode.name())
+        .show_ui(ui, |ui| {
+            for mode in ClockMode::iter() {
+                is_changed |= ui
+                    .selectable_value(&mut clock.mode, mode, mode.name())
+                    .changed();
+            }
+        });
+
+    if clock.mode != ClockMode::RealTime {
+        is_changed |= ui
+            .add(
+                DragValue::new(&mut clock.fps)
+                    .speed(0.5)
+                    .range(1.0..=120.0)
+                    .suffix(" fps"),
+            )
+            .changed();
+    }
+
+    is_changed
+}
@@ -0,0 +1,19 @@
+use egui::ComboBox;
+use geometry::animations::easing::Easing;
+use std::hash::Hash;
+use strum::IntoEnumIterator;
+
+/// Combo box of all curves. Returns whether the curve changed.
+pub fn show(ui: &mut egui::Ui, easing: &mut Easing, id_salt: impl Hash) -> bool {
+    let mut is_changed = false;
+
+    ComboBox::from_id_salt(id_salt)
+        .selected_text(easing.name())
+        .show_ui(ui, |ui| {
+            for value in Easing::iter() {
+                is_changed |= ui.selectable_value(easing, value, value.name()).changed();
+            }
+        });
+
+    is_changed
+}
@@ -0,0 +1,118 @@
+use egui::color_picker::{Alpha, color_edit_button_srgba};
+use egui::{ComboBox, DragValue, Grid, ProgressBar, Window};
+use geometry::animations::export::{
+    AnimationExport, ExportError, ExportFormat, ExportSettings,
+};
+use geometry::animations::timeline::Timeline;
+use strum::IntoEnumIterator;
+
+/// Window with the settings and the progress of the export. Returns the error
+/// of a recording that has just finished, if it failed.
+pub fn show(
+    ui: &mut egui::Ui, export: &mut AnimationExport, timeline: &mut Timeline,
+) -> Option<ExportError> {
+    let error = export.receive(ui.ctx()).and_then(Result::err);
+
+    let mut is_open = export.is_open;
+    Window::new("Export Animation")
+        .open(&mut is_open)
+        .resizable(false)
+        .collapsible(false)
+        .show(ui.ctx(), |ui| {
+            if export.is_recording() {
+                ui.add(ProgressBar::new(export.progress()).show_percentage());
+                ui.vertical_centered_justified(|ui| {
+                    if ui.button("Cancel").clicked() {
+                        export.cancel(timeline);
+                    }
+                });
+                return;
+            }
+
+            ui.add_enabled_ui(!export.is_writing(), |ui| {
+                settings(ui, &mut export.settings);
+
+                ui.add_space(5.0);
+
+                ui.vertical_centered_justified(|ui| {
+                    let format = export.settings.format;
+                    if ui.button("Export…").clicked()
+                        && let Some(path) = rfd::FileDialog::new()
+                            .add_filter(format.name(), &[format.extension()])
+                            .save_file()
+                    {
+                        export.start(path, timeline);
+                    }
+                });
+            });
+
+            if export.is_writing() {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label("Writing the file…");
+                });
+            }
+        });
+    export.is_open = is_open;
+
+    error
+}
+
+/// Format, resolution, frame rate, duration and background.
+pub fn settings(ui: &mut egui::Ui, settings: &mut ExportSettings) {
+    Grid::new("EXPORT_SETTINGS").num_columns(2).show(ui, |ui| {
+        ui.label("Format:");
+        ComboBox::from_id_salt("EXPORT_FORMAT")
+            .selected_text(settings.format.name())
+            .show_ui(ui, |ui| {
+                for format in ExportFormat::iter() {
+                    ui.selectable_value(&mut settings.format, format, format.name());
+                }
+            });
+        ui.end_row();
+
+        ui.label("Width:");
+        ui.add(
+            DragValue::new(&mut settings.width)
+                .speed(1)
+                .range(16..=4096)
+                .suffix(" px"),
+        );
+        ui.end_row();
+
+        ui.label("Height:");
+        ui.add(
+            DragValue::new(&mut settings.height)
+                .speed(1)
+                .range(16..=4096)
+                .suffix(" px"),
+        );
+        ui.end_row();
+
+        ui.label("Frame Rate:");
+        ui.add(
+            DragValue::new(&mut settings.fps)
+                .speed(0.5)
+                .range(1.0..=60.0)
+                .suffix(" fps"),
+        );
+        ui.end_row();
+
+        ui.label("Duration:");
+        ui.add(
+            DragValue::new(&mut settings.duration)
+                .speed(0.05)
+                .range(0.1..=600.0)
+                .suffix(" s"),
+        );
+        ui.end_row();
+
+        ui.label("Background:");
+        color_edit_button_srgba(ui, &mut settings.background, Alpha::Opaque);
+        ui.end_row();
+
+        ui.label("Frames:");
+        ui.label(settings.frame_count().to_string());
+        ui.end_row();
+    });
+}
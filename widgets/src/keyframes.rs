@@ -0,0 +1,121 @@
+use crate::{easing, playback};
+use egui::{Color32, ComboBox, DragValue, Grid, RichText};
+use geometry::animations::Animatable;
+use geometry::animations::keyframes::KeyframeAnimation;
+use geometry::animations::timeline::Timeline;
+
+/// Parameter picker, playback settings and the keyframes of every track.
+pub fn show(
+    ui: &mut egui::Ui, animation: &mut KeyframeAnimation, target: &mut dyn Animatable,
+    timeline: &Timeline,
+) {
+    ui.horizontal(|ui| {
+        ui.label("Status:");
+        if animation.playback.is_enabled {
+            ui.colored_label(Color32::LIGHT_GREEN, "Running");
+        } else {
+            ui.colored_label(Color32::RED, "Stopped");
+        }
+    });
+
+    playback::show(ui, &mut animation.playback, "KEYFRAMES");
+
+    ui.add_space(5.0);
+
+    let parameters = target.parameters();
+    if parameters.is_empty() {
+        ui.label("The figure has no parameters to animate.");
+        return;
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Parameter:");
+        ComboBox::from_id_salt("KEYFRAME_PARAMETER")
+            .selected_text(
+                parameters
+                    .get(animation.selected)
+                    .map_or("None", |parameter| parameter.name),
+            )
+            .show_ui(ui, |ui| {
+                for (index, parameter) in parameters.iter().enumerate() {
+                    ui.selectable_value(&mut animation.selected, index, parameter.name);
+                }
+            });
+    });
+
+    let local_time = animation.local_time(timeline.time());
+    ui.vertical_centered_justified(|ui| {
+        if let Some(parameter) = parameters.get(animation.selected)
+            && ui
+                .button(format!("Add Keyframe at {local_time:.2} s"))
+                .clicked()
+        {
+            animation.add_keyframe(parameter.name, *parameter.value, timeline);
+        }
+    });
+
+    let duration = animation.playback.duration;
+    let mut removed_track = None;
+    for (track_index, track) in animation.tracks.iter_mut().enumerate() {
+        ui.add_space(5.0);
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut track.is_enabled, "");
+            ui.label(RichText::new(track.parameter).color(Color32::WHITE));
+            if ui.small_button("🗑").on_hover_text("Remove track").clicked() {
+                removed_track = Some(track_index);
+            }
+        });
+
+        let parameter = track.parameter;
+        let mut is_moved = false;
+        let mut removed = None;
+        Grid::new(("KEYFRAMES", parameter))
+            .num_columns(4)
+            .show(ui, |ui| {
+                for (index, keyframe) in track.keyframes_mut().iter_mut().enumerate() {
+                    is_moved |= ui
+                        .add(
+                            DragValue::new(&mut keyframe.time)
+                                .speed(0.01)
+                                .range(0.0..=duration)
+                                .suffix(" s"),
+                        )
+                        .changed();
+                    ui.add(DragValue::new(&mut keyframe.value).speed(0.01));
+                    easing::show(
+                        ui,
+                        &mut keyframe.easing,
+                        ("KEYFRAME_EASING", parameter, index),
+                    );
+                    if ui.small_button("🗑").clicked() {
+                        removed = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+
+        if let Some(index) = removed {
+            track.remove(index);
+        }
+        if is_moved {
+            track.sort();
+        }
+    }
+    if let Some(index) = removed_track {
+        animation.tracks.remove(index);
+    }
+
+    ui.add_space(5.0);
+
+    ui.vertical_centered_justified(|ui| {
+        let text = if animation.playback.is_enabled {
+            "Stop"
+        } else {
+            "Start"
+        };
+        if ui.button(text).clicked() {
+            animation.playback.toggle();
+        }
+    });
+}
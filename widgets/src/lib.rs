@@ -0,0 +1,8 @@
+pub mod arcball;
+pub mod clock;
+pub mod easing;
+pub mod export;
+pub mod keyframes;
+pub mod orientation;
+pub mod playback;
+pub mod timeline;
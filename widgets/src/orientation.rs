@@ -0,0 +1,106 @@
+use crate::playback;
+use egui::{Color32, ComboBox, DragValue, Grid};
+use geometry::animations::orientation::{AnimationOrientation, OrientationInterpolation};
+use geometry::animations::timeline::Timeline;
+use geometry::math::quaternion::Quaternion;
+use strum::IntoEnumIterator;
+
+/// Playback settings, a button that records the current orientation and the
+/// list of keyframes.
+pub fn show(
+    ui: &mut egui::Ui, animation: &mut AnimationOrientation,
+    orientation: &mut Quaternion, timeline: &Timeline,
+) {
+    ui.horizontal(|ui| {
+        ui.label("Status:");
+        if animation.playback.is_enabled {
+            ui.colored_label(Color32::LIGHT_GREEN, "Running");
+        } else {
+            ui.colored_label(Color32::RED, "Stopped");
+        }
+    });
+
+    playback::show(ui, &mut animation.playback, "ORIENTATION");
+
+    ui.horizontal(|ui| {
+        ui.label("Interpolation:");
+        ComboBox::from_id_salt("ORIENTATION_INTERPOLATION")
+            .selected_text(animation.interpolation.name())
+            .show_ui(ui, |ui| {
+                for interpolation in OrientationInterpolation::iter() {
+                    ui.selectable_value(
+                        &mut animation.interpolation,
+                        interpolation,
+                        interpolation.name(),
+                    );
+                }
+            });
+    });
+
+    ui.add_space(5.0);
+
+    let local_time = animation.local_time(timeline.time());
+    ui.vertical_centered_justified(|ui| {
+        if ui
+            .button(format!("Add Orientation Keyframe at {local_time:.2} s"))
+            .clicked()
+        {
+            animation.add_keyframe(*orientation, timeline);
+        }
+    });
+
+    let duration = animation.playback.duration;
+    let mut is_moved = false;
+    let mut removed = None;
+    Grid::new("ORIENTATION_KEYFRAMES")
+        .num_columns(4)
+        .show(ui, |ui| {
+            for (index, keyframe) in animation.keyframes_mut().iter_mut().enumerate() {
+                is_moved |= ui
+                    .add(
+                        DragValue::new(&mut keyframe.time)
+                            .speed(0.01)
+                            .range(0.0..=duration)
+                            .suffix(" s"),
+                    )
+                    .changed();
+
+                let (axis, angle) = keyframe.orientation.axis_angle();
+                ui.label(format!(
+                    "{:.1}° ({:.2}, {:.2}, {:.2})",
+                    angle.degree(),
+                    axis.x,
+                    axis.y,
+                    axis.z
+                ));
+
+                if ui.small_button("👁").on_hover_text("Go to").clicked() {
+                    *orientation = keyframe.orientation;
+                }
+                if ui.small_button("🗑").clicked() {
+                    removed = Some(index);
+                }
+                ui.end_row();
+            }
+        });
+
+    if let Some(index) = removed {
+        animation.remove(index);
+    }
+    if is_moved {
+        animation.sort();
+    }
+
+    ui.add_space(5.0);
+
+    ui.vertical_centered_justified(|ui| {
+        let text = if animation.playback.is_enabled {
+            "Stop"
+        } else {
+            "Start"
+        };
+        if ui.button(text).clicked() {
+            animation.playback.toggle();
+        }
+    });
+}
@@ -0,0 +1,43 @@
+use crate::easing;
+use egui::{ComboBox, DragValue, Grid};
+use geometry::animations::{Playback, PlaybackMode};
+use strum::IntoEnumIterator;
+
+/// Duration, mode and easing. Returns whether any of them changed.
+pub fn show(ui: &mut egui::Ui, playback: &mut Playback, id_salt: &str) -> bool {
+    let mut is_changed = false;
+
+    Grid::new(("PLAYBACK", id_salt))
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Duration:");
+            is_changed |= ui
+                .add(
+                    DragValue::new(&mut playback.duration)
+                        .speed(0.05)
+                        .range(0.1..=600.0)
+                        .suffix(" s"),
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Mode:");
+            ComboBox::from_id_salt(("PLAYBACK_MODE", id_salt))
+                .selected_text(playback.mode.name())
+                .show_ui(ui, |ui| {
+                    for mode in PlaybackMode::iter() {
+                        is_changed |= ui
+                            .selectable_value(&mut playback.mode, mode, mode.name())
+                            .changed();
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Easing:");
+            is_changed |=
+                easing::show(ui, &mut playback.easing, ("PLAYBACK_EASING", id_salt));
+            ui.end_row();
+        });
+
+    is_changed
+}
@@ -0,0 +1,103 @@
+use crate::{clock, export};
+use egui::{DragValue, Slider, TopBottomPanel};
+use geometry::animations::clock::ClockMode;
+use geometry::animations::export::{AnimationExport, ExportError};
+use geometry::animations::timeline::Timeline;
+
+/// Play and pause buttons, scrubber, speed, length and clock.
+pub fn show(ui: &mut egui::Ui, timeline: &mut Timeline) {
+    ui.horizontal(|ui| {
+        if ui.button("⏮").on_hover_text("Rewind").clicked() {
+            timeline.rewind();
+        }
+
+        if timeline.clock.mode == ClockMode::Manual {
+            if ui.button("⏴").on_hover_text("Previous Step").clicked() {
+                timeline.step(false);
+            }
+            if ui.button("⏵").on_hover_text("Next Step").clicked() {
+                timeline.step(true);
+            }
+        } else {
+            let (icon, hint) = if timeline.is_playing {
+                ("⏸", "Pause")
+            } else {
+                ("▶", "Play")
+            };
+            if ui.button(icon).on_hover_text(hint).clicked() {
+                timeline.toggle();
+            }
+        }
+
+        // The controls on the right take about 520 points
+        ui.spacing_mut().slider_width = (ui.available_width() - 520.0).max(100.0);
+        let mut time = timeline.time();
+        let scrubber = ui.add(
+            Slider::new(&mut time, 0.0..=timeline.length)
+                .fixed_decimals(2)
+                .suffix(" s"),
+        );
+        if scrubber.changed() {
+            timeline.seek(time);
+        }
+
+        ui.separator();
+
+        if timeline.clock.mode == ClockMode::RealTime {
+            ui.label("Speed:");
+            ui.add(
+                DragValue::new(&mut timeline.speed)
+                    .speed(0.01)
+                    .range(0.05..=10.0)
+                    .suffix("×"),
+            );
+        }
+
+        ui.label("Length:");
+        ui.add(
+            DragValue::new(&mut timeline.length)
+                .speed(0.1)
+                .range(1.0..=3600.0)
+                .suffix(" s"),
+        );
+
+        ui.checkbox(&mut timeline.is_looping, "Loop");
+
+        ui.separator();
+
+        ui.label("Clock:");
+        clock::show(ui, &mut timeline.clock);
+        if timeline.clock.mode != ClockMode::RealTime {
+            ui.label(format!("Step {}", timeline.frame()));
+        }
+    });
+}
+
+/// Bottom panel with the timeline and, if the lab exports its animations, the
+/// export button and window. Returns the error of a recording that has just
+/// finished, if it failed.
+pub fn panel(
+    ui: &mut egui::Ui, timeline: &mut Timeline, mut export: Option<&mut AnimationExport>,
+) -> Option<ExportError> {
+    TopBottomPanel::bottom("TIMELINE_PANEL")
+        .resizable(false)
+        .show_inside(ui, |ui| {
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                let is_recording = match export.as_deref_mut() {
+                    Some(export) => {
+                        ui.toggle_value(&mut export.is_open, "🎞")
+                            .on_hover_text("Export Animation");
+                        export.is_recording()
+                    },
+                    None => false,
+                };
+                ui.add_enabled_ui(!is_recording, |ui| {
+                    show(ui, timeline);
+                });
+            });
+            ui.add_space(4.0);
+        });
+
+    export.and_then(|export| export::show(ui, export, timeline))
+}